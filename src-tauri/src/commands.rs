@@ -27,23 +27,47 @@ use crate::binaries::{Binaries, BinaryResolver};
 use crate::configs::config_core::{AirdropTokens, ConfigCore, ConfigCoreContent};
 use crate::configs::config_mining::{ConfigMining, ConfigMiningContent};
 use crate::configs::config_pools::{ConfigPools, ConfigPoolsContent};
+use crate::configs::config_schedule::{
+    ConfigSchedule, ConfigScheduleContent, MiningScheduleAction, MiningScheduleRule,
+};
 use crate::configs::config_ui::{ConfigUI, ConfigUIContent, DisplayMode};
 use crate::configs::config_wallet::{ConfigWallet, ConfigWalletContent, WalletId};
-use crate::configs::pools::BasePoolData;
 use crate::configs::pools::{cpu_pools::CpuPool, gpu_pools::GpuPool};
+use crate::configs::pools::{BasePoolData, CustomPoolData, PoolOrigin};
 use crate::configs::trait_config::ConfigImpl;
-use crate::events::ConnectionStatusPayload;
+use crate::events::{ConnectionStatusPayload, MiningPauseUpdatePayload};
 use crate::events_emitter::EventsEmitter;
 use crate::events_manager::EventsManager;
+use crate::hardware::hardware_status_monitor::{HardwareStatusMonitor, PublicDeviceGpuProperties};
+use crate::hardware::thermal_guard::{ThermalGuardManager, ThermalLimit};
 use crate::internal_wallet::{mnemonic_to_tari_cipher_seed, InternalWallet, PaperWalletConfig};
-use crate::mining::gpu::consts::{EngineType, GpuMinerType};
+use crate::mining::gpu::benchmark::{GpuBenchmark, GpuBenchmarkParams};
+use crate::mining::gpu::consts::{EngineType, GpuMinerType, GpuMiningAlgorithm};
 use crate::mining::gpu::manager::GpuManager;
+use crate::mining::idle_monitor::IdleMiningMonitor;
+use crate::mining::pause_manager::{MiningPauseManager, MiningPauseReason};
 use crate::mining::pools::cpu_pool_manager::CpuPoolManager;
+use crate::mining::pools::earnings_history::{
+    PoolEarningsAggregate, PoolEarningsHistory, PoolEarningsPeriod,
+};
 use crate::mining::pools::gpu_pool_manager::GpuPoolManager;
-use crate::mining::pools::PoolManagerInterfaceTrait;
+use crate::mining::pools::pool_failover::PoolHealth;
+use crate::mining::pools::{PoolAddressStatus, PoolManagerInterfaceTrait};
+use crate::mining::profit_switcher::{ProfitSwitchDecision, ProfitSwitcher};
+use crate::mining::scheduler::MiningScheduler;
+use crate::node::block_explorer::{ExplorerBlock, MempoolStats};
+use crate::node::external_node::{self, ExternalNodeConfig, ExternalNodeTestResult};
 use crate::node::node_adapter::BaseNodeStatus;
 use crate::node::node_manager::NodeType;
+use crate::node::node_snapshot::{self, SnapshotManifest};
+use crate::node::node_storage::{
+    self, load_storage_profile, node_network_dir, NodeDiskUsage, NodeStorageProfile,
+};
+use crate::node::remote_node_adapter::parse_grpc_address;
+use crate::node::remote_node_selector::{probe_remote_nodes, RemoteNodeProbe, RemoteNodeSelector};
 use crate::pin::PinManager;
+use crate::process_output_buffer::{ProcessOutputLine, ProcessOutputRegistry};
+use crate::process_resource_limits::ProcessResourceLimits;
 use crate::release_notes::ReleaseNotes;
 use crate::setup::setup_manager::{SetupManager, SetupPhase};
 use crate::system_dependencies::system_dependencies_manager::SystemDependenciesManager;
@@ -55,14 +79,17 @@ use crate::utils::address_utils::verify_send;
 use crate::utils::app_flow_utils::FrontendReadyChannel;
 use crate::wallet::wallet_manager::WalletManagerError;
 use crate::wallet::wallet_types::{TariAddressVariants, TransactionInfo};
+use crate::xmrig::config::XmrigConfig;
 use crate::{airdrop, UniverseAppState};
 
 use base64::prelude::*;
 use log::{debug, error, info, warn};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fs::{read_dir, remove_dir_all, remove_file, File};
+use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::atomic::Ordering;
 use std::thread::sleep;
@@ -83,6 +110,7 @@ use urlencoding::encode;
 const MAX_ACCEPTABLE_COMMAND_TIME: Duration = Duration::from_secs(1);
 const LOG_TARGET: &str = "tari::universe::commands";
 const LOG_TARGET_WEB: &str = "tari::universe::web";
+const PROCESS_OUTPUT_TAIL_DEFAULT_LINES: usize = 100;
 
 #[derive(Debug, Serialize)]
 pub struct ApplicationsInformation {
@@ -107,6 +135,7 @@ pub struct CpuMinerStatus {
     pub hash_rate: f64,
     pub estimated_earnings: u64,
     pub connection: CpuMinerConnectionStatus,
+    pub details: Option<CpuMinerDetails>,
 }
 
 impl Default for CpuMinerStatus {
@@ -118,6 +147,7 @@ impl Default for CpuMinerStatus {
             connection: CpuMinerConnectionStatus {
                 is_connected: false,
             },
+            details: None,
         }
     }
 }
@@ -127,6 +157,67 @@ pub struct CpuMinerConnectionStatus {
     // pub error: Option<String>,
 }
 
+/// Telemetry reported by xmrig, values it doesn't report are `None`
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct CpuMinerDetails {
+    pub algorithm: Option<String>,
+    pub is_paused: bool,
+    pub pool: Option<String>,
+    pub pool_ping_ms: Option<u64>,
+    pub difficulty: u64,
+    pub accepted_shares: u64,
+    pub rejected_shares: u64,
+    pub connection_failures: u64,
+    pub good_shares: Option<u64>,
+    pub total_shares: Option<u64>,
+    pub average_share_time_ms: Option<u64>,
+    pub total_hashes: Option<u64>,
+    pub highest_hash_rate: Option<f64>,
+    pub hugepages: CpuMinerHugepagesStatus,
+    pub cpu: Option<CpuMinerCpuInfo>,
+    pub threads: Vec<CpuMinerThreadStatus>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct CpuMinerHugepagesStatus {
+    pub is_enabled: bool,
+    pub allocated: Option<u64>,
+    pub total: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct CpuMinerCpuInfo {
+    pub brand: String,
+    pub architecture: Option<String>,
+    pub cores: u32,
+    pub threads: u32,
+    pub numa_nodes: u32,
+    pub aes: bool,
+    pub avx2: bool,
+    pub msr: Option<String>,
+    pub features: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct CpuMinerThreadStatus {
+    pub index: usize,
+    pub affinity: Option<u32>,
+    pub intensity: Option<u32>,
+    pub hash_rate: f64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PoolsHealth {
+    pub cpu_pools: HashMap<String, PoolHealth>,
+    pub gpu_pools: HashMap<String, PoolHealth>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PoolsAddressStatuses {
+    pub cpu_pools: Vec<PoolAddressStatus>,
+    pub gpu_pools: Vec<PoolAddressStatus>,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SignWsDataResponse {
@@ -710,6 +801,24 @@ pub fn open_log_dir(app: tauri::AppHandle) {
     }
 }
 
+/// Mmproxy and solo gpu miners only get the plain grpc address of the node, they would lose the connection
+async fn ensure_no_plain_grpc_consumers(
+    config: &ExternalNodeConfig,
+    state: &UniverseAppState,
+) -> Result<(), anyhow::Error> {
+    if !config.requires_secure_connection()? {
+        return Ok(());
+    }
+    let is_gpu_solo_mining =
+        GpuManager::read().await.is_mining() && !*ConfigPools::content().await.gpu_pool_enabled();
+    if state.mm_proxy_manager.is_running().await || is_gpu_solo_mining {
+        return Err(anyhow::anyhow!(
+            "Nodes requiring tls or authentication can't be used while merge mining or solo gpu mining"
+        ));
+    }
+    Ok(())
+}
+
 async fn reset_app_configs(
     app_handle: &tauri::AppHandle,
     reset_wallet: bool,
@@ -1024,260 +1133,997 @@ pub async fn toggle_device_exclusion(device_index: u32, excluded: bool) -> Resul
 }
 
 #[tauri::command]
-pub async fn set_gpu_mining_enabled(enabled: bool) -> Result<(), InvokeError> {
+pub async fn assign_gpu_device_miner(
+    device_index: u32,
+    miner: Option<GpuMinerType>,
+) -> Result<(), InvokeError> {
     let timer = Instant::now();
+    info!(target: LOG_TARGET, "[assign_gpu_device_miner] called with device_index: {device_index}, miner: {miner:?}");
 
-    ConfigMining::update_field(ConfigMiningContent::set_gpu_mining_enabled, enabled)
+    if let Some(miner) = &miner {
+        if !GpuManager::read().await.is_miner_available(miner) {
+            return Err(InvokeError::from_anyhow(anyhow::anyhow!(
+                "GPU miner {miner} is not available"
+            )));
+        }
+    }
+
+    ConfigMining::update_field(
+        ConfigMiningContent::assign_gpu_device_miner,
+        (device_index, miner),
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
+    EventsEmitter::emit_update_gpu_devices_settings(
+        ConfigMining::content().await.gpu_devices_settings().clone(),
+    )
+    .await;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "assign_gpu_device_miner took too long: {:?}", timer.elapsed());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn start_gpu_benchmark(
+    app: tauri::AppHandle,
+    duration_secs: Option<u64>,
+) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[start_gpu_benchmark] called with duration_secs: {duration_secs:?}");
+
+    if let Some(duration_secs) = duration_secs {
+        ConfigMining::update_field(
+            ConfigMiningContent::set_gpu_benchmark_duration_secs,
+            duration_secs,
+        )
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+    }
+
+    let params = GpuBenchmarkParams {
+        gpu_usage_percentage: ConfigMining::content()
+            .await
+            .get_selected_gpu_usage_percentage(),
+        base_path: app
+            .path()
+            .app_local_data_dir()
+            .map_err(|e| InvokeError::from_anyhow(e.into()))?,
+        config_path: app
+            .path()
+            .app_config_dir()
+            .map_err(|e| InvokeError::from_anyhow(e.into()))?,
+        log_path: app
+            .path()
+            .app_log_dir()
+            .map_err(|e| InvokeError::from_anyhow(e.into()))?,
+    };
+    GpuBenchmark::start(params)
         .await
         .map_err(InvokeError::from_anyhow)?;
 
     if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
-        warn!(target: LOG_TARGET,
-            "set_gpu_mining_enabled took too long: {:?}",
-            timer.elapsed()
-        );
+        warn!(target: LOG_TARGET, "start_gpu_benchmark took too long: {:?}", timer.elapsed());
     }
-
     Ok(())
 }
 
 #[tauri::command]
-pub async fn set_mine_on_app_start(mine_on_app_start: bool) -> Result<(), InvokeError> {
+pub async fn cancel_gpu_benchmark() -> Result<(), InvokeError> {
     let timer = Instant::now();
-    ConfigMining::update_field(
-        ConfigMiningContent::set_mine_on_app_start,
-        mine_on_app_start,
-    )
-    .await
-    .map_err(InvokeError::from_anyhow)?;
+    info!(target: LOG_TARGET, "[cancel_gpu_benchmark] called");
+
+    GpuBenchmark::cancel().await;
 
     if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
-        warn!(target: LOG_TARGET, "set_mine_on_app_start took too long: {:?}", timer.elapsed());
+        warn!(target: LOG_TARGET, "cancel_gpu_benchmark took too long: {:?}", timer.elapsed());
     }
-
     Ok(())
 }
 
 #[tauri::command]
-pub async fn select_mining_mode(mode: String) -> Result<(), InvokeError> {
+pub async fn set_profit_switching_enabled(enabled: bool) -> Result<(), InvokeError> {
     let timer = Instant::now();
-    info!(target: LOG_TARGET, "[select_mining_mode] called with mode: {mode:?}");
+    info!(target: LOG_TARGET, "[set_profit_switching_enabled] called with enabled: {enabled}");
 
-    ConfigMining::update_field(ConfigMiningContent::set_selected_mining_mode, mode)
+    ConfigMining::update_field(ConfigMiningContent::set_profit_switching_enabled, enabled)
         .await
         .map_err(InvokeError::from_anyhow)?;
 
     if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
-        warn!(target: LOG_TARGET, "select_mining_mode took too long: {:?}", timer.elapsed());
+        warn!(target: LOG_TARGET, "set_profit_switching_enabled took too long: {:?}", timer.elapsed());
     }
     Ok(())
 }
 
 #[tauri::command]
-pub async fn update_custom_mining_mode(
-    custom_cpu_usage: u32,
-    custom_gpu_usage: u32,
+pub async fn set_profit_switching_settings(
+    threshold_percentage: u32,
+    min_interval_secs: u64,
 ) -> Result<(), InvokeError> {
     let timer = Instant::now();
-    info!(target: LOG_TARGET, "[update_custom_mining_mode] called with custom_cpu_usage: {custom_cpu_usage:?}, custom_gpu_usage: {custom_gpu_usage:?}");
+    info!(target: LOG_TARGET, "[set_profit_switching_settings] called with threshold_percentage: {threshold_percentage}, min_interval_secs: {min_interval_secs}");
 
     ConfigMining::update_field(
-        ConfigMiningContent::update_custom_mode_cpu_usage,
-        custom_cpu_usage,
+        ConfigMiningContent::set_profit_switching_threshold_percentage,
+        threshold_percentage,
     )
     .await
     .map_err(InvokeError::from_anyhow)?;
-
     ConfigMining::update_field(
-        ConfigMiningContent::update_custom_mode_gpu_usage,
-        custom_gpu_usage,
+        ConfigMiningContent::set_profit_switching_min_interval_secs,
+        min_interval_secs,
     )
     .await
     .map_err(InvokeError::from_anyhow)?;
 
     if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
-        warn!(target: LOG_TARGET, "update_custom_mining_mode took too long: {:?}", timer.elapsed());
+        warn!(target: LOG_TARGET, "set_profit_switching_settings took too long: {:?}", timer.elapsed());
     }
     Ok(())
 }
 
 #[tauri::command]
-pub async fn set_monero_address(monero_address: String) -> Result<(), InvokeError> {
+pub async fn get_profit_switch_decisions() -> Result<Vec<ProfitSwitchDecision>, InvokeError> {
     let timer = Instant::now();
-    SetupManager::get_instance()
-        .add_phases_to_restart_queue(vec![SetupPhase::CpuMining])
-        .await;
 
-    InternalWallet::set_external_monero_address(monero_address)
+    let decisions = ProfitSwitcher::get_decisions().await;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "get_profit_switch_decisions took too long: {:?}", timer.elapsed());
+    }
+    Ok(decisions)
+}
+
+#[tauri::command]
+pub async fn pause_mining(app: tauri::AppHandle) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[pause_mining] called");
+
+    MiningPauseManager::current()
+        .pause(&app, MiningPauseReason::User)
         .await
         .map_err(InvokeError::from_anyhow)?;
 
-    SetupManager::get_instance()
-        .restart_phases_from_queue()
-        .await;
     if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
-        warn!(target: LOG_TARGET, "set_monero_address took too long: {:?}", timer.elapsed());
+        warn!(target: LOG_TARGET, "pause_mining took too long: {:?}", timer.elapsed());
     }
     Ok(())
 }
 
 #[tauri::command]
-pub async fn set_monerod_config(
-    use_monero_fail: bool,
-    monero_nodes: Vec<String>,
+pub async fn resume_mining(app: tauri::AppHandle) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[resume_mining] called");
+
+    MiningPauseManager::current()
+        .resume(&app, MiningPauseReason::User)
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "resume_mining took too long: {:?}", timer.elapsed());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_mining_pause_status(
+    app: tauri::AppHandle,
+) -> Result<MiningPauseUpdatePayload, InvokeError> {
+    let timer = Instant::now();
+
+    let status = MiningPauseManager::current().get_status(&app).await;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "get_mining_pause_status took too long: {:?}", timer.elapsed());
+    }
+    Ok(status)
+}
+
+#[tauri::command]
+pub async fn set_mining_schedule(
+    app: tauri::AppHandle,
+    is_enabled: bool,
+    default_action: MiningScheduleAction,
+    rules: Vec<MiningScheduleRule>,
 ) -> Result<(), InvokeError> {
     let timer = Instant::now();
-    info!(target: LOG_TARGET, "[set_monerod_config] called with use_monero_fail: {use_monero_fail:?}, monero_nodes: {monero_nodes:?}");
-    ConfigCore::update_field_requires_restart(
-        ConfigCoreContent::set_mmproxy_monero_nodes,
-        monero_nodes.clone(),
-        vec![SetupPhase::CpuMining],
-    )
-    .await
-    .map_err(InvokeError::from_anyhow)?;
+    info!(target: LOG_TARGET, "[set_mining_schedule] called with is_enabled: {is_enabled:?}, default_action: {default_action:?}, rules: {rules:?}");
 
-    ConfigCore::update_field_requires_restart(
-        ConfigCoreContent::set_mmproxy_use_monero_failover,
-        use_monero_fail,
-        vec![SetupPhase::CpuMining],
+    let mining_modes: Vec<String> = ConfigMining::content()
+        .await
+        .mining_modes()
+        .keys()
+        .cloned()
+        .collect();
+    default_action
+        .validate(&mining_modes)
+        .map_err(InvokeError::from_anyhow)?;
+    for rule in &rules {
+        rule.validate(&mining_modes)
+            .map_err(InvokeError::from_anyhow)?;
+    }
+
+    ConfigSchedule::update_field(
+        ConfigScheduleContent::set_schedule,
+        (is_enabled, default_action, rules),
     )
     .await
     .map_err(InvokeError::from_anyhow)?;
+    EventsEmitter::emit_schedule_config_loaded(&ConfigSchedule::content().await).await;
 
-    SetupManager::get_instance()
-        .restart_phases_from_queue()
-        .await;
+    MiningScheduler::evaluate(&app, true).await;
 
     if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
-        warn!(target: LOG_TARGET, "set_monerod_config took too long: {:?}", timer.elapsed());
+        warn!(target: LOG_TARGET, "set_mining_schedule took too long: {:?}", timer.elapsed());
     }
-
     Ok(())
 }
 
 #[tauri::command]
-pub async fn set_show_experimental_settings(
-    show_experimental_settings: bool,
+pub async fn set_idle_mining(
+    app: tauri::AppHandle,
+    enabled: bool,
+    mining_mode: String,
+    timeout_secs: u64,
 ) -> Result<(), InvokeError> {
-    ConfigUI::update_field(
-        ConfigUIContent::set_show_experimental_settings,
-        show_experimental_settings,
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[set_idle_mining] called with enabled: {enabled:?}, mining_mode: {mining_mode:?}, timeout_secs: {timeout_secs:?}");
+
+    if !ConfigMining::content()
+        .await
+        .mining_modes()
+        .contains_key(&mining_mode)
+    {
+        return Err(InvokeError::from_anyhow(anyhow::anyhow!(
+            "Unknown mining mode: {mining_mode}"
+        )));
+    }
+    if timeout_secs == 0 {
+        return Err(InvokeError::from_anyhow(anyhow::anyhow!(
+            "Idle timeout has to be greater than zero"
+        )));
+    }
+
+    ConfigMining::update_field(
+        ConfigMiningContent::set_idle_mining_settings,
+        (enabled, mining_mode, timeout_secs),
     )
     .await
     .map_err(InvokeError::from_anyhow)?;
+    IdleMiningMonitor::current()
+        .handle_settings_changed(&app)
+        .await;
 
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "set_idle_mining took too long: {:?}", timer.elapsed());
+    }
     Ok(())
 }
 
 #[tauri::command]
-pub async fn set_should_always_use_system_language(
-    should_always_use_system_language: bool,
+pub async fn set_thermal_guard_settings(
+    app: tauri::AppHandle,
+    enabled: bool,
+    cpu_limit: ThermalLimit,
+    gpu_limit: ThermalLimit,
+    gpu_devices_limits: HashMap<u32, ThermalLimit>,
 ) -> Result<(), InvokeError> {
-    ConfigUI::update_field(
-        ConfigUIContent::set_should_always_use_system_language,
-        should_always_use_system_language,
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[set_thermal_guard_settings] called with enabled: {enabled:?}, cpu_limit: {cpu_limit:?}, gpu_limit: {gpu_limit:?}, gpu_devices_limits: {gpu_devices_limits:?}");
+
+    for limit in [&cpu_limit, &gpu_limit]
+        .into_iter()
+        .chain(gpu_devices_limits.values())
+    {
+        limit.validate().map_err(InvokeError::from_anyhow)?;
+    }
+
+    ConfigMining::update_field(
+        ConfigMiningContent::set_thermal_guard_settings,
+        (enabled, cpu_limit, gpu_limit, gpu_devices_limits),
     )
     .await
     .map_err(InvokeError::from_anyhow)?;
+    ThermalGuardManager::evaluate(&app).await;
 
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "set_thermal_guard_settings took too long: {:?}", timer.elapsed());
+    }
     Ok(())
 }
 
 #[tauri::command]
-pub async fn set_should_auto_launch(should_auto_launch: bool) -> Result<(), InvokeError> {
-    ConfigCore::update_field(
-        ConfigCoreContent::set_should_auto_launch,
-        should_auto_launch,
-    )
-    .await
-    .map_err(InvokeError::from_anyhow)?;
+pub async fn get_gpu_devices_telemetry() -> Result<Vec<PublicDeviceGpuProperties>, InvokeError> {
+    let timer = Instant::now();
 
-    AutoLauncher::current()
-        .update_auto_launcher(should_auto_launch)
+    let devices = HardwareStatusMonitor::current()
+        .get_gpu_public_properties()
         .await
         .map_err(InvokeError::from_anyhow)?;
 
-    Ok(())
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "get_gpu_devices_telemetry took too long: {:?}", timer.elapsed());
+    }
+    Ok(devices)
 }
 
 #[tauri::command]
-pub async fn set_tor_config(
-    config: TorConfig,
-    _window: tauri::Window,
-    state: tauri::State<'_, UniverseAppState>,
-) -> Result<TorConfig, String> {
+pub async fn get_process_output_tail(
+    process_name: String,
+    lines: Option<usize>,
+) -> Result<Vec<ProcessOutputLine>, InvokeError> {
     let timer = Instant::now();
-    info!(target: LOG_TARGET, "[set_tor_config] called with config: {config:?}");
-    let tor_config = state
-        .tor_manager
-        .set_tor_config(config)
-        .await
-        .map_err(|e| e.to_string())?;
 
-    SetupManager::get_instance()
-        .restart_phases(vec![SetupPhase::Node, SetupPhase::Wallet])
-        .await;
+    let output_registry = ProcessOutputRegistry::current();
+    let output_buffer = output_registry.get(&process_name).ok_or_else(|| {
+        InvokeError::from_anyhow(anyhow::anyhow!(
+            "No output captured for process {process_name}, known processes: {}",
+            output_registry.process_names().join(", ")
+        ))
+    })?;
+    let tail = output_buffer.tail(lines.unwrap_or(PROCESS_OUTPUT_TAIL_DEFAULT_LINES));
 
     if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
-        warn!(target: LOG_TARGET, "set_tor_config took too long: {:?}", timer.elapsed());
+        warn!(target: LOG_TARGET, "get_process_output_tail took too long: {:?}", timer.elapsed());
     }
-    Ok(tor_config)
+    Ok(tail)
 }
 
+/// Latest probe results of the configured remote base nodes, probes them when it wasn't done yet
 #[tauri::command]
-pub async fn set_use_tor(use_tor: bool, app_handle: tauri::AppHandle) -> Result<(), InvokeError> {
+pub async fn get_remote_node_probes() -> Result<Vec<RemoteNodeProbe>, InvokeError> {
     let timer = Instant::now();
-    ConfigCore::update_field_requires_restart(
-        ConfigCoreContent::set_use_tor,
-        use_tor,
-        vec![SetupPhase::Node, SetupPhase::Wallet],
-    )
-    .await
-    .map_err(InvokeError::from_anyhow)?;
 
-    SetupManager::get_instance()
-        .restart_phases_from_queue()
-        .await;
+    let mut probes = RemoteNodeSelector::current().read().await.probes();
+    if probes.is_empty() {
+        probes = probe_remote_nodes(RemoteNodeSelector::configured_addresses().await).await;
+    }
 
-    let config_dir = app_handle
-        .path()
-        .app_config_dir()
-        .expect("Could not get config dir");
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "get_remote_node_probes took too long: {:?}", timer.elapsed());
+    }
+    Ok(probes)
+}
 
-    //TODO: Do we still need this?
-    if config_dir.exists() {
-        let tcp_tor_toggled_file = config_dir.join("tcp_tor_toggled");
-        File::create(tcp_tor_toggled_file).map_err(|e| e.to_string())?;
+/// Additional remote nodes are picked up by the next probe, the selected node is kept while it keeps up
+#[tauri::command]
+pub async fn set_remote_base_nodes(
+    addresses: Vec<String>,
+    failover_blocks_behind: u64,
+) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[set_remote_base_nodes] called with addresses: {addresses:?}, failover_blocks_behind: {failover_blocks_behind}");
+
+    for address in &addresses {
+        parse_grpc_address(address).map_err(InvokeError::from_anyhow)?;
     }
+    ConfigCore::update_field(
+        ConfigCoreContent::set_remote_base_nodes,
+        (addresses, failover_blocks_behind),
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
 
     if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
-        warn!(target: LOG_TARGET, "set_use_tor took too long: {:?}", timer.elapsed());
+        warn!(target: LOG_TARGET, "set_remote_base_nodes took too long: {:?}", timer.elapsed());
     }
-
     Ok(())
 }
 
+/// Checks the settings against the node before they are saved with `set_external_node`
 #[tauri::command]
-pub async fn set_visual_mode(enabled: bool) -> Result<(), InvokeError> {
+pub async fn test_external_node_connection(
+    config: ExternalNodeConfig,
+    password: Option<String>,
+) -> Result<ExternalNodeTestResult, InvokeError> {
     let timer = Instant::now();
-    ConfigUI::update_field(ConfigUIContent::set_visual_mode, enabled)
+    info!(target: LOG_TARGET, "[test_external_node_connection] called with config: {config:?}");
+
+    let result = external_node::test_connection(&config, password.as_deref())
         .await
         .map_err(InvokeError::from_anyhow)?;
 
     if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
-        warn!(target: LOG_TARGET,
-            "set_visual_mode took too long: {:?}",
-            timer.elapsed()
-        );
+        warn!(target: LOG_TARGET, "test_external_node_connection took too long: {:?}", timer.elapsed());
     }
-    Ok(())
+    Ok(result)
 }
 
-#[allow(clippy::too_many_lines)]
+/// Tls and authentication are used by the app itself, mmproxy and the gpu miners still need plain grpc access
 #[tauri::command]
-pub async fn set_airdrop_tokens(airdrop_tokens: Option<AirdropTokens>) -> Result<(), InvokeError> {
-    let old_id = ConfigCore::content()
-        .await
+pub async fn set_external_node(
+    config: ExternalNodeConfig,
+    password: Option<String>,
+    state: tauri::State<'_, UniverseAppState>,
+) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[set_external_node] called with config: {config:?}");
+
+    // Also checks the certificates can be read
+    config
+        .connection_options(password.as_deref())
+        .map_err(InvokeError::from_anyhow)?;
+    if state.node_manager.is_external().await {
+        ensure_no_plain_grpc_consumers(&config, &state)
+            .await
+            .map_err(InvokeError::from_anyhow)?;
+    }
+    let password = password.filter(|_| config.username.is_some());
+    external_node::save_password(password.as_deref()).map_err(InvokeError::from_anyhow)?;
+
+    if state.node_manager.is_external().await {
+        ConfigCore::update_field_requires_restart(
+            ConfigCoreContent::set_external_node,
+            config,
+            vec![
+                SetupPhase::Node,
+                SetupPhase::Wallet,
+                SetupPhase::CpuMining,
+                SetupPhase::GpuMining,
+            ],
+        )
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+        SetupManager::get_instance()
+            .restart_phases_from_queue()
+            .await;
+    } else {
+        ConfigCore::update_field(ConfigCoreContent::set_external_node, config)
+            .await
+            .map_err(InvokeError::from_anyhow)?;
+    }
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "set_external_node took too long: {:?}", timer.elapsed());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_node_disk_usage(
+    state: tauri::State<'_, UniverseAppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<NodeDiskUsage, InvokeError> {
+    let timer = Instant::now();
+    let data_dir = app_handle
+        .path()
+        .app_local_data_dir()
+        .map_err(|e| InvokeError::from_anyhow(e.into()))?;
+    let disk_usage = node_storage::get_node_disk_usage(&state.node_manager, &data_dir)
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "get_node_disk_usage took too long: {:?}", timer.elapsed());
+    }
+    Ok(disk_usage)
+}
+
+/// Switching a pruned database to archival deletes it, so it has to be allowed with `allow_resync`
+#[tauri::command]
+pub async fn set_node_storage(
+    storage_profile: NodeStorageProfile,
+    min_free_disk_space_bytes: u64,
+    allow_resync: bool,
+    state: tauri::State<'_, UniverseAppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[set_node_storage] called with storage_profile: {storage_profile:?}, min_free_disk_space_bytes: {min_free_disk_space_bytes}, allow_resync: {allow_resync}");
+
+    storage_profile
+        .validate()
+        .map_err(InvokeError::from_anyhow)?;
+    let data_dir = app_handle
+        .path()
+        .app_local_data_dir()
+        .map_err(|e| InvokeError::from_anyhow(e.into()))?;
+    let previous_profile =
+        load_storage_profile(&node_network_dir(&data_dir)).map_err(InvokeError::from_anyhow)?;
+    if previous_profile.is_some_and(|previous| storage_profile.requires_resync_from(&previous))
+        && !allow_resync
+    {
+        return Err(InvokeError::from_anyhow(anyhow::anyhow!(
+            "Switching a pruned node to archival requires syncing the blockchain again"
+        )));
+    }
+
+    let is_profile_changed = *ConfigCore::content().await.node_storage_profile() != storage_profile;
+    if is_profile_changed && state.node_manager.is_local().await {
+        ConfigCore::update_field_requires_restart(
+            ConfigCoreContent::set_node_storage,
+            (storage_profile, min_free_disk_space_bytes),
+            vec![SetupPhase::Node, SetupPhase::Wallet, SetupPhase::CpuMining],
+        )
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+        SetupManager::get_instance()
+            .restart_phases_from_queue()
+            .await;
+    } else {
+        // The disk watchdog reads the minimum on every check
+        ConfigCore::update_field(
+            ConfigCoreContent::set_node_storage,
+            (storage_profile, min_free_disk_space_bytes),
+        )
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+    }
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "set_node_storage took too long: {:?}", timer.elapsed());
+    }
+    Ok(())
+}
+
+/// The local node is stopped while its database is copied and restarted afterwards
+#[tauri::command]
+pub async fn export_node_snapshot(
+    destination_dir: PathBuf,
+    state: tauri::State<'_, UniverseAppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<SnapshotManifest, InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[export_node_snapshot] called with destination_dir: {destination_dir:?}");
+
+    if !state.node_manager.is_local_current().await {
+        return Err(InvokeError::from_anyhow(anyhow::anyhow!(
+            "Snapshots can only be exported while the local node is used"
+        )));
+    }
+    let data_dir = app_handle
+        .path()
+        .app_local_data_dir()
+        .map_err(|e| InvokeError::from_anyhow(e.into()))?;
+    let result =
+        node_snapshot::export_snapshot(&state.node_manager, &data_dir, &destination_dir).await;
+    SetupManager::get_instance()
+        .restart_phases(vec![
+            SetupPhase::Node,
+            SetupPhase::Wallet,
+            SetupPhase::CpuMining,
+        ])
+        .await;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "export_node_snapshot took too long: {:?}", timer.elapsed());
+    }
+    result.map_err(InvokeError::from_anyhow)
+}
+
+/// The previous database is restored when the snapshot can't be verified or swapped in
+#[tauri::command]
+pub async fn import_node_snapshot(
+    snapshot_path: PathBuf,
+    state: tauri::State<'_, UniverseAppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<SnapshotManifest, InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[import_node_snapshot] called with snapshot_path: {snapshot_path:?}");
+
+    let data_dir = app_handle
+        .path()
+        .app_local_data_dir()
+        .map_err(|e| InvokeError::from_anyhow(e.into()))?;
+    let is_local = state.node_manager.is_local().await;
+    if is_local {
+        state
+            .node_manager
+            .stop_local_node()
+            .await
+            .map_err(InvokeError::from_anyhow)?;
+    }
+    let imported = tokio::task::spawn_blocking(move || {
+        node_snapshot::import_snapshot(&data_dir, &snapshot_path)
+    })
+    .await
+    .map_err(anyhow::Error::from)
+    .and_then(|result| result);
+    // The stopped node is restarted whatever failed, the first error is returned
+    let result = match imported {
+        Ok(manifest) => node_snapshot::use_snapshot_storage_profile(&manifest)
+            .await
+            .map(|()| manifest),
+        Err(e) => Err(e),
+    };
+    if is_local {
+        SetupManager::get_instance()
+            .restart_phases(vec![
+                SetupPhase::Node,
+                SetupPhase::Wallet,
+                SetupPhase::CpuMining,
+            ])
+            .await;
+    }
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "import_node_snapshot took too long: {:?}", timer.elapsed());
+    }
+    result.map_err(InvokeError::from_anyhow)
+}
+
+/// At most `MAX_EXPLORER_BLOCKS_PER_REQUEST` blocks can be requested at once
+#[tauri::command]
+pub async fn get_explorer_blocks(
+    from_height: u64,
+    to_height: u64,
+    state: tauri::State<'_, UniverseAppState>,
+) -> Result<Vec<ExplorerBlock>, InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[get_explorer_blocks] called with from_height: {from_height}, to_height: {to_height}");
+
+    let blocks = state
+        .node_manager
+        .get_explorer_blocks(from_height, to_height)
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "get_explorer_blocks took too long: {:?}", timer.elapsed());
+    }
+    Ok(blocks)
+}
+
+#[tauri::command]
+pub async fn get_explorer_block_by_hash(
+    hash: String,
+    state: tauri::State<'_, UniverseAppState>,
+) -> Result<ExplorerBlock, InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[get_explorer_block_by_hash] called with hash: {hash}");
+
+    let block = state
+        .node_manager
+        .get_explorer_block_by_hash(&hash)
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "get_explorer_block_by_hash took too long: {:?}", timer.elapsed());
+    }
+    Ok(block)
+}
+
+#[tauri::command]
+pub async fn get_mempool_stats(
+    state: tauri::State<'_, UniverseAppState>,
+) -> Result<MempoolStats, InvokeError> {
+    let timer = Instant::now();
+
+    let stats = state
+        .node_manager
+        .get_mempool_stats()
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "get_mempool_stats took too long: {:?}", timer.elapsed());
+    }
+    Ok(stats)
+}
+
+/// Limits are applied the next time each process is started
+#[tauri::command]
+pub async fn set_process_resource_limits(
+    node_limits: ProcessResourceLimits,
+    wallet_limits: ProcessResourceLimits,
+    cpu_miner_limits: ProcessResourceLimits,
+) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[set_process_resource_limits] called with node_limits: {node_limits:?}, wallet_limits: {wallet_limits:?}, cpu_miner_limits: {cpu_miner_limits:?}");
+
+    for limits in [&node_limits, &wallet_limits, &cpu_miner_limits] {
+        limits.validate().map_err(InvokeError::from_anyhow)?;
+    }
+
+    ConfigCore::update_field(
+        ConfigCoreContent::set_process_resource_limits,
+        (node_limits, wallet_limits),
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
+    ConfigMining::update_field(
+        ConfigMiningContent::set_cpu_miner_resource_limits,
+        cpu_miner_limits,
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "set_process_resource_limits took too long: {:?}", timer.elapsed());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_xmrig_config(
+    state: tauri::State<'_, UniverseAppState>,
+    app: tauri::AppHandle,
+    xmrig_config: XmrigConfig,
+) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[set_xmrig_config] called with xmrig_config: {xmrig_config:?}");
+
+    ConfigMining::update_field(ConfigMiningContent::set_xmrig_config, xmrig_config.clone())
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+
+    let log_dir = app
+        .path()
+        .app_log_dir()
+        .map_err(|e| InvokeError::from_anyhow(e.into()))?;
+    state
+        .cpu_miner
+        .read()
+        .await
+        .reload_xmrig_config(xmrig_config, &log_dir)
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "set_xmrig_config took too long: {:?}", timer.elapsed());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_gpu_mining_enabled(enabled: bool) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+
+    ConfigMining::update_field(ConfigMiningContent::set_gpu_mining_enabled, enabled)
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET,
+            "set_gpu_mining_enabled took too long: {:?}",
+            timer.elapsed()
+        );
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_mine_on_app_start(mine_on_app_start: bool) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+    ConfigMining::update_field(
+        ConfigMiningContent::set_mine_on_app_start,
+        mine_on_app_start,
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "set_mine_on_app_start took too long: {:?}", timer.elapsed());
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn select_mining_mode(mode: String) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[select_mining_mode] called with mode: {mode:?}");
+
+    ConfigMining::update_field(ConfigMiningContent::set_selected_mining_mode, mode)
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "select_mining_mode took too long: {:?}", timer.elapsed());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn update_custom_mining_mode(
+    custom_cpu_usage: u32,
+    custom_gpu_usage: u32,
+) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[update_custom_mining_mode] called with custom_cpu_usage: {custom_cpu_usage:?}, custom_gpu_usage: {custom_gpu_usage:?}");
+
+    ConfigMining::update_field(
+        ConfigMiningContent::update_custom_mode_cpu_usage,
+        custom_cpu_usage,
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
+
+    ConfigMining::update_field(
+        ConfigMiningContent::update_custom_mode_gpu_usage,
+        custom_gpu_usage,
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "update_custom_mining_mode took too long: {:?}", timer.elapsed());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_monero_address(monero_address: String) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+    SetupManager::get_instance()
+        .add_phases_to_restart_queue(vec![SetupPhase::CpuMining])
+        .await;
+
+    InternalWallet::set_external_monero_address(monero_address)
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+
+    SetupManager::get_instance()
+        .restart_phases_from_queue()
+        .await;
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "set_monero_address took too long: {:?}", timer.elapsed());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_monerod_config(
+    use_monero_fail: bool,
+    monero_nodes: Vec<String>,
+) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[set_monerod_config] called with use_monero_fail: {use_monero_fail:?}, monero_nodes: {monero_nodes:?}");
+    ConfigCore::update_field_requires_restart(
+        ConfigCoreContent::set_mmproxy_monero_nodes,
+        monero_nodes.clone(),
+        vec![SetupPhase::CpuMining],
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
+
+    ConfigCore::update_field_requires_restart(
+        ConfigCoreContent::set_mmproxy_use_monero_failover,
+        use_monero_fail,
+        vec![SetupPhase::CpuMining],
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
+
+    SetupManager::get_instance()
+        .restart_phases_from_queue()
+        .await;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "set_monerod_config took too long: {:?}", timer.elapsed());
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_show_experimental_settings(
+    show_experimental_settings: bool,
+) -> Result<(), InvokeError> {
+    ConfigUI::update_field(
+        ConfigUIContent::set_show_experimental_settings,
+        show_experimental_settings,
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_should_always_use_system_language(
+    should_always_use_system_language: bool,
+) -> Result<(), InvokeError> {
+    ConfigUI::update_field(
+        ConfigUIContent::set_should_always_use_system_language,
+        should_always_use_system_language,
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_should_auto_launch(should_auto_launch: bool) -> Result<(), InvokeError> {
+    ConfigCore::update_field(
+        ConfigCoreContent::set_should_auto_launch,
+        should_auto_launch,
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
+
+    AutoLauncher::current()
+        .update_auto_launcher(should_auto_launch)
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_tor_config(
+    config: TorConfig,
+    _window: tauri::Window,
+    state: tauri::State<'_, UniverseAppState>,
+) -> Result<TorConfig, String> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[set_tor_config] called with config: {config:?}");
+    let tor_config = state
+        .tor_manager
+        .set_tor_config(config)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    SetupManager::get_instance()
+        .restart_phases(vec![SetupPhase::Node, SetupPhase::Wallet])
+        .await;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "set_tor_config took too long: {:?}", timer.elapsed());
+    }
+    Ok(tor_config)
+}
+
+#[tauri::command]
+pub async fn set_use_tor(use_tor: bool, app_handle: tauri::AppHandle) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+    ConfigCore::update_field_requires_restart(
+        ConfigCoreContent::set_use_tor,
+        use_tor,
+        vec![SetupPhase::Node, SetupPhase::Wallet],
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
+
+    SetupManager::get_instance()
+        .restart_phases_from_queue()
+        .await;
+
+    let config_dir = app_handle
+        .path()
+        .app_config_dir()
+        .expect("Could not get config dir");
+
+    //TODO: Do we still need this?
+    if config_dir.exists() {
+        let tcp_tor_toggled_file = config_dir.join("tcp_tor_toggled");
+        File::create(tcp_tor_toggled_file).map_err(|e| e.to_string())?;
+    }
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "set_use_tor took too long: {:?}", timer.elapsed());
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_visual_mode(enabled: bool) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+    ConfigUI::update_field(ConfigUIContent::set_visual_mode, enabled)
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET,
+            "set_visual_mode took too long: {:?}",
+            timer.elapsed()
+        );
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_lines)]
+#[tauri::command]
+pub async fn set_airdrop_tokens(airdrop_tokens: Option<AirdropTokens>) -> Result<(), InvokeError> {
+    let old_id = ConfigCore::content()
+        .await
         .airdrop_tokens()
         .clone()
         .and_then(|tokens| {
@@ -1310,16 +2156,26 @@ pub async fn start_cpu_mining(
 ) -> Result<(), String> {
     let timer = Instant::now();
     let cpu_mining_enabled = *ConfigMining::content().await.cpu_mining_enabled();
-    let cpu_usage_percentage = ConfigMining::content()
-        .await
-        .get_selected_cpu_usage_percentage();
+    let config_mining = ConfigMining::content().await;
+    let cpu_usage_percentage = config_mining
+        .get_cpu_usage_percentage(&IdleMiningMonitor::current().active_mining_mode(&config_mining));
+    let Some(cpu_usage_percentage) = ThermalGuardManager::current()
+        .limit_cpu_usage(cpu_usage_percentage)
+        .await
+    else {
+        warn!(target: LOG_TARGET, "CPU is too hot, cpu mining will start once it cools down");
+        return Ok(());
+    };
 
     let cpu_miner = state.cpu_miner.read().await;
     let cpu_miner_running = cpu_miner.is_running().await;
 
     let mut cpu_config = state.cpu_miner_config.write().await;
     let tari_address = InternalWallet::tari_address().await;
-    cpu_config.load_from_config_pools(ConfigPools::content().await.clone(), &tari_address);
+    cpu_config
+        .load_from_config_pools(ConfigPools::content().await.clone(), &tari_address)
+        .await;
+    cpu_config.load_from_config_mining(&ConfigMining::content().await);
     drop(cpu_config);
 
     drop(cpu_miner);
@@ -1363,6 +2219,10 @@ pub async fn start_cpu_mining(
                 .ok();
             return Err(e.to_string());
         }
+        drop(cpu_miner);
+        MiningPauseManager::current()
+            .handle_mining_started(&app)
+            .await;
     }
     if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
         warn!(target: LOG_TARGET, "start_cpu_mining took too long: {:?}", timer.elapsed());
@@ -1401,10 +2261,24 @@ pub async fn start_gpu_mining(
 
     let tari_address = InternalWallet::tari_address().await;
 
-    let gpu_usage_percentage = ConfigMining::content()
+    let config_mining = ConfigMining::content().await;
+    let gpu_usage_percentage = config_mining
+        .get_gpu_usage_percentage(&IdleMiningMonitor::current().active_mining_mode(&config_mining));
+    let Some(gpu_usage_percentage) = ThermalGuardManager::current()
+        .limit_gpu_usage(gpu_usage_percentage)
         .await
-        .get_selected_gpu_usage_percentage();
+    else {
+        warn!(target: LOG_TARGET, "All mining GPUs are too hot, gpu mining will start once they cool down");
+        return Ok(());
+    };
 
+    if !*ConfigPools::content().await.gpu_pool_enabled() {
+        state
+            .node_manager
+            .ensure_plain_grpc_access()
+            .await
+            .map_err(|e| e.to_string())?;
+    }
     let grpc_address = state
         .node_manager
         .get_grpc_address()
@@ -1439,6 +2313,9 @@ pub async fn start_gpu_mining(
 
         return Err(e.to_string());
     }
+    MiningPauseManager::current()
+        .handle_mining_started(&app)
+        .await;
 
     if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
         warn!(target: LOG_TARGET, "start_gpu_mining took too long: {:?}", timer.elapsed());
@@ -1448,7 +2325,10 @@ pub async fn start_gpu_mining(
 }
 
 #[tauri::command]
-pub async fn stop_cpu_mining(state: tauri::State<'_, UniverseAppState>) -> Result<(), String> {
+pub async fn stop_cpu_mining(
+    state: tauri::State<'_, UniverseAppState>,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
     let timer = Instant::now();
     state
         .cpu_miner
@@ -1458,6 +2338,10 @@ pub async fn stop_cpu_mining(state: tauri::State<'_, UniverseAppState>) -> Resul
         .await
         .map_err(|e| e.to_string())?;
     info!(target:LOG_TARGET, "cpu miner stopped");
+    ThermalGuardManager::current().handle_cpu_mining_stopped();
+    MiningPauseManager::current()
+        .handle_mining_stopped(&app)
+        .await;
 
     if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
         warn!(target: LOG_TARGET, "stop_cpu_mining took too long: {:?}", timer.elapsed());
@@ -1466,7 +2350,7 @@ pub async fn stop_cpu_mining(state: tauri::State<'_, UniverseAppState>) -> Resul
     Ok(())
 }
 #[tauri::command]
-pub async fn stop_gpu_mining() -> Result<(), String> {
+pub async fn stop_gpu_mining(app: tauri::AppHandle) -> Result<(), String> {
     let timer = Instant::now();
 
     GpuManager::write()
@@ -1474,6 +2358,10 @@ pub async fn stop_gpu_mining() -> Result<(), String> {
         .stop_mining()
         .await
         .map_err(|e| e.to_string())?;
+    ThermalGuardManager::current().handle_gpu_mining_stopped();
+    MiningPauseManager::current()
+        .handle_mining_stopped(&app)
+        .await;
 
     if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
         warn!(target: LOG_TARGET, "stop_cpu_mining took too long: {:?}", timer.elapsed());
@@ -1735,15 +2623,27 @@ pub async fn set_node_type(
     if node_type != NodeType::Local
         && node_type != NodeType::RemoteUntilLocal
         && node_type != NodeType::Remote
+        && node_type != NodeType::External
     {
         node_type = NodeType::Local;
     }
+    if node_type == NodeType::External {
+        let external_node = ConfigCore::content().await.external_node().clone();
+        if external_node.grpc_address.is_empty() {
+            return Err(InvokeError::from_anyhow(anyhow::anyhow!(
+                "External node has to be set before switching to it"
+            )));
+        }
+        ensure_no_plain_grpc_consumers(&external_node, &state)
+            .await
+            .map_err(InvokeError::from_anyhow)?;
+    }
 
     let prev_node_type = state.node_manager.get_node_type().await;
     info!(target: LOG_TARGET, "[set_node_type] from {prev_node_type:?} to: {node_type:?}");
 
     let is_current_local = matches!(prev_node_type, NodeType::Local | NodeType::LocalAfterRemote);
-    if is_current_local && node_type != NodeType::Remote {
+    if is_current_local && !matches!(node_type, NodeType::Remote | NodeType::External) {
         info!(target: LOG_TARGET, "[set_node_type] Local node is already running, no restart needed for node_type: {node_type:?}");
         ConfigCore::update_field(ConfigCoreContent::set_node_type, node_type.clone())
             .await
@@ -1867,14 +2767,20 @@ pub async fn reset_gpu_pool_config(gpu_pool_name: String) -> Result<(), InvokeEr
 
     let gpu_pool = GpuPool::from_string(&gpu_pool_name).map_err(InvokeError::from_anyhow)?;
 
+    let default_content = gpu_pool.default_content().ok_or_else(|| {
+        InvokeError::from_anyhow(anyhow::anyhow!(
+            "Custom GPU pool {gpu_pool_name} has no default config to reset to"
+        ))
+    })?;
+
     ConfigPools::update_field(
         ConfigPoolsContent::update_current_gpu_config,
-        gpu_pool.default_content(),
+        default_content.clone(),
     )
     .await
     .map_err(InvokeError::from_anyhow)?;
     EventsEmitter::emit_pools_config_loaded(&ConfigPools::content().await.clone()).await;
-    GpuPoolManager::handle_new_selected_pool(gpu_pool.default_content()).await;
+    GpuPoolManager::handle_new_selected_pool(default_content).await;
 
     if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
         warn!(target: LOG_TARGET, "reset_pool_gpu_pool_config took too long: {:?}", timer.elapsed());
@@ -1889,14 +2795,20 @@ pub async fn reset_cpu_pool_config(cpu_pool_name: String) -> Result<(), InvokeEr
 
     let cpu_pool = CpuPool::from_string(&cpu_pool_name).map_err(InvokeError::from_anyhow)?;
 
+    let default_content = cpu_pool.default_content().ok_or_else(|| {
+        InvokeError::from_anyhow(anyhow::anyhow!(
+            "Custom CPU pool {cpu_pool_name} has no default config to reset to"
+        ))
+    })?;
+
     ConfigPools::update_field(
         ConfigPoolsContent::update_current_cpu_config,
-        cpu_pool.default_content(),
+        default_content.clone(),
     )
     .await
     .map_err(InvokeError::from_anyhow)?;
     EventsEmitter::emit_pools_config_loaded(&ConfigPools::content().await.clone()).await;
-    CpuPoolManager::handle_new_selected_pool(cpu_pool.default_content()).await;
+    CpuPoolManager::handle_new_selected_pool(default_content).await;
 
     if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
         warn!(target: LOG_TARGET, "reset_pool_cpu_pool_config took too long: {:?}", timer.elapsed());
@@ -1904,6 +2816,401 @@ pub async fn reset_cpu_pool_config(cpu_pool_name: String) -> Result<(), InvokeEr
     Ok(())
 }
 
+#[tauri::command]
+pub async fn add_custom_cpu_pool(
+    pool_name: String,
+    pool_url: String,
+    stats_url: String,
+    custom: CustomPoolData,
+) -> Result<String, InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[add_custom_cpu_pool] called with pool_name: {pool_name:?}, pool_url: {pool_url:?}");
+
+    let pool_data = BasePoolData {
+        pool_name,
+        pool_url,
+        stats_url,
+        pool_type: CpuPool::new_custom(),
+        pool_origin: PoolOrigin::Custom,
+        custom: Some(custom),
+    };
+    pool_data.validate().map_err(InvokeError::from_anyhow)?;
+    let pool_key = pool_data.pool_type.key_string();
+
+    ConfigPools::update_field(ConfigPoolsContent::upsert_custom_cpu_pool, pool_data)
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+    EventsEmitter::emit_pools_config_loaded(&ConfigPools::content().await.clone()).await;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "add_custom_cpu_pool took too long: {:?}", timer.elapsed());
+    }
+    Ok(pool_key)
+}
+
+#[tauri::command]
+pub async fn update_custom_cpu_pool(
+    updated_config: BasePoolData<CpuPool>,
+) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[update_custom_cpu_pool] called with updated_config: {updated_config:?}");
+
+    let config_content = ConfigPools::content().await;
+    if !updated_config.pool_type.is_custom()
+        || !config_content
+            .cpu_pools()
+            .contains_key(&updated_config.pool_type)
+    {
+        return Err(InvokeError::from_anyhow(anyhow::anyhow!(
+            "Custom CPU pool {} does not exist",
+            updated_config.pool_type
+        )));
+    }
+    updated_config
+        .validate()
+        .map_err(InvokeError::from_anyhow)?;
+
+    let updated_config = BasePoolData {
+        pool_origin: PoolOrigin::Custom,
+        custom: Some(updated_config.custom.unwrap_or_default()),
+        ..updated_config
+    };
+    let is_current_pool = config_content.current_cpu_pool().pool_type == updated_config.pool_type;
+
+    ConfigPools::update_field(
+        ConfigPoolsContent::upsert_custom_cpu_pool,
+        updated_config.clone(),
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
+    EventsEmitter::emit_pools_config_loaded(&ConfigPools::content().await.clone()).await;
+
+    if is_current_pool {
+        CpuPoolManager::handle_new_selected_pool(updated_config).await;
+    }
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "update_custom_cpu_pool took too long: {:?}", timer.elapsed());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn remove_custom_cpu_pool(cpu_pool_name: String) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[remove_custom_cpu_pool] called with cpu_pool_name: {cpu_pool_name:?}");
+
+    let cpu_pool = CpuPool::from_string(&cpu_pool_name).map_err(InvokeError::from_anyhow)?;
+    if !cpu_pool.is_custom() {
+        return Err(InvokeError::from_anyhow(anyhow::anyhow!(
+            "Built-in CPU pool {cpu_pool_name} can't be removed"
+        )));
+    }
+    let was_current_pool = ConfigPools::content().await.current_cpu_pool().pool_type == cpu_pool;
+
+    ConfigPools::update_field(ConfigPoolsContent::remove_custom_cpu_pool, cpu_pool)
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+    EventsEmitter::emit_pools_config_loaded(&ConfigPools::content().await.clone()).await;
+
+    if was_current_pool {
+        let cpu_pool_content = ConfigPools::content().await.current_cpu_pool();
+        CpuPoolManager::handle_new_selected_pool(cpu_pool_content).await;
+    }
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "remove_custom_cpu_pool took too long: {:?}", timer.elapsed());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn add_custom_gpu_pool(
+    pool_name: String,
+    pool_url: String,
+    stats_url: String,
+    custom: CustomPoolData,
+) -> Result<String, InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[add_custom_gpu_pool] called with pool_name: {pool_name:?}, pool_url: {pool_url:?}");
+
+    let pool_data = BasePoolData {
+        pool_name,
+        pool_url,
+        stats_url,
+        pool_type: GpuPool::new_custom(),
+        pool_origin: PoolOrigin::Custom,
+        custom: Some(custom),
+    };
+    pool_data
+        .validate_gpu_pool()
+        .map_err(InvokeError::from_anyhow)?;
+    let pool_key = pool_data.pool_type.key_string();
+
+    ConfigPools::update_field(ConfigPoolsContent::upsert_custom_gpu_pool, pool_data)
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+    EventsEmitter::emit_pools_config_loaded(&ConfigPools::content().await.clone()).await;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "add_custom_gpu_pool took too long: {:?}", timer.elapsed());
+    }
+    Ok(pool_key)
+}
+
+#[tauri::command]
+pub async fn update_custom_gpu_pool(
+    updated_config: BasePoolData<GpuPool>,
+) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[update_custom_gpu_pool] called with updated_config: {updated_config:?}");
+
+    let config_content = ConfigPools::content().await;
+    if !updated_config.pool_type.is_custom()
+        || !config_content
+            .gpu_pools()
+            .contains_key(&updated_config.pool_type)
+    {
+        return Err(InvokeError::from_anyhow(anyhow::anyhow!(
+            "Custom GPU pool {} does not exist",
+            updated_config.pool_type
+        )));
+    }
+    updated_config
+        .validate_gpu_pool()
+        .map_err(InvokeError::from_anyhow)?;
+
+    let updated_config = BasePoolData {
+        pool_origin: PoolOrigin::Custom,
+        custom: Some(updated_config.custom.unwrap_or_default()),
+        ..updated_config
+    };
+    let is_current_pool = config_content.current_gpu_pool().pool_type == updated_config.pool_type;
+
+    ConfigPools::update_field(
+        ConfigPoolsContent::upsert_custom_gpu_pool,
+        updated_config.clone(),
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
+    EventsEmitter::emit_pools_config_loaded(&ConfigPools::content().await.clone()).await;
+
+    if is_current_pool {
+        GpuPoolManager::handle_new_selected_pool(updated_config).await;
+    }
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "update_custom_gpu_pool took too long: {:?}", timer.elapsed());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn remove_custom_gpu_pool(gpu_pool_name: String) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[remove_custom_gpu_pool] called with gpu_pool_name: {gpu_pool_name:?}");
+
+    let gpu_pool = GpuPool::from_string(&gpu_pool_name).map_err(InvokeError::from_anyhow)?;
+    if !gpu_pool.is_custom() {
+        return Err(InvokeError::from_anyhow(anyhow::anyhow!(
+            "Built-in GPU pool {gpu_pool_name} can't be removed"
+        )));
+    }
+    let was_current_pool = ConfigPools::content().await.current_gpu_pool().pool_type == gpu_pool;
+
+    ConfigPools::update_field(ConfigPoolsContent::remove_custom_gpu_pool, gpu_pool)
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+    EventsEmitter::emit_pools_config_loaded(&ConfigPools::content().await.clone()).await;
+
+    if was_current_pool {
+        let gpu_pool_content = ConfigPools::content().await.current_gpu_pool();
+        GpuPoolManager::handle_new_selected_pool(gpu_pool_content).await;
+    }
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "remove_custom_gpu_pool took too long: {:?}", timer.elapsed());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_cpu_pools_failover(cpu_pools: Vec<String>) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[set_cpu_pools_failover] called with cpu_pools: {cpu_pools:?}");
+
+    let config_content = ConfigPools::content().await;
+    let mut failover_pools = Vec::with_capacity(cpu_pools.len());
+    for pool_name in cpu_pools {
+        let cpu_pool = CpuPool::from_string(&pool_name).map_err(InvokeError::from_anyhow)?;
+        if !config_content.cpu_pools().contains_key(&cpu_pool) {
+            return Err(InvokeError::from_anyhow(anyhow::anyhow!(
+                "CPU pool {pool_name} does not exist"
+            )));
+        }
+        failover_pools.push(cpu_pool);
+    }
+
+    ConfigPools::update_field(ConfigPoolsContent::set_cpu_pools_failover, failover_pools)
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+    EventsEmitter::emit_pools_config_loaded(&ConfigPools::content().await.clone()).await;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "set_cpu_pools_failover took too long: {:?}", timer.elapsed());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_gpu_pools_failover(
+    algorithm: GpuMiningAlgorithm,
+    gpu_pools: Vec<String>,
+) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[set_gpu_pools_failover] called with algorithm: {algorithm:?}, gpu_pools: {gpu_pools:?}");
+
+    let config_content = ConfigPools::content().await;
+    let mut failover_pools = Vec::with_capacity(gpu_pools.len());
+    for pool_name in gpu_pools {
+        let gpu_pool = GpuPool::from_string(&pool_name).map_err(InvokeError::from_anyhow)?;
+        let pool_algorithm = config_content
+            .gpu_pools()
+            .get(&gpu_pool)
+            .ok_or_else(|| {
+                InvokeError::from_anyhow(anyhow::anyhow!("GPU pool {pool_name} does not exist"))
+            })?
+            .algorithm();
+        if pool_algorithm.as_ref() != Some(&algorithm) {
+            return Err(InvokeError::from_anyhow(anyhow::anyhow!(
+                "GPU pool {pool_name} does not mine {algorithm:?}"
+            )));
+        }
+        failover_pools.push(gpu_pool);
+    }
+
+    let mut gpu_pools_failover = config_content.gpu_pools_failover().clone();
+    gpu_pools_failover.insert(algorithm, failover_pools);
+    ConfigPools::update_field(
+        ConfigPoolsContent::set_gpu_pools_failover,
+        gpu_pools_failover,
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
+    EventsEmitter::emit_pools_config_loaded(&ConfigPools::content().await.clone()).await;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "set_gpu_pools_failover took too long: {:?}", timer.elapsed());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_gpu_miner_pool(miner: GpuMinerType, gpu_pool: String) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[set_gpu_miner_pool] called with miner: {miner}, gpu_pool: {gpu_pool}");
+
+    let config_content = ConfigPools::content().await;
+    let pool = GpuPool::from_string(&gpu_pool).map_err(InvokeError::from_anyhow)?;
+    let pool_data = config_content.gpu_pools().get(&pool).ok_or_else(|| {
+        InvokeError::from_anyhow(anyhow::anyhow!("GPU pool {gpu_pool} does not exist"))
+    })?;
+    if !miner.is_pool_supported(pool_data) {
+        return Err(InvokeError::from_anyhow(anyhow::anyhow!(
+            "GPU pool {gpu_pool} is not supported by {miner}"
+        )));
+    }
+
+    let mut gpu_miner_pools = config_content.gpu_miner_pools().clone();
+    gpu_miner_pools.insert(miner, pool);
+    ConfigPools::update_field(ConfigPoolsContent::set_gpu_miner_pools, gpu_miner_pools)
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+    EventsEmitter::emit_pools_config_loaded(&ConfigPools::content().await.clone()).await;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "set_gpu_miner_pool took too long: {:?}", timer.elapsed());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn toggle_pool_failover(enabled: bool) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+
+    // When disabled while mining on a fallback pool, the failover watchers switch back to the selected pool
+    ConfigPools::update_field(ConfigPoolsContent::set_pool_failover_enabled, enabled)
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+    EventsEmitter::emit_pools_config_loaded(&ConfigPools::content().await.clone()).await;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "toggle_pool_failover took too long: {:?}", timer.elapsed());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn toggle_switch_back_to_primary_pool(enabled: bool) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+
+    ConfigPools::update_field(ConfigPoolsContent::set_switch_back_to_primary_pool, enabled)
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+    EventsEmitter::emit_pools_config_loaded(&ConfigPools::content().await.clone()).await;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "toggle_switch_back_to_primary_pool took too long: {:?}", timer.elapsed());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_pools_health() -> Result<PoolsHealth, InvokeError> {
+    let timer = Instant::now();
+
+    let pools_health = PoolsHealth {
+        cpu_pools: CpuPoolManager::get_pools_health().await,
+        gpu_pools: GpuPoolManager::get_pools_health().await,
+    };
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "get_pools_health took too long: {:?}", timer.elapsed());
+    }
+    Ok(pools_health)
+}
+
+#[tauri::command]
+pub async fn get_pools_address_statuses() -> Result<PoolsAddressStatuses, InvokeError> {
+    let timer = Instant::now();
+
+    let statuses = PoolsAddressStatuses {
+        cpu_pools: CpuPoolManager::get_address_pool_statuses().await,
+        gpu_pools: GpuPoolManager::get_address_pool_statuses().await,
+    };
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "get_pools_address_statuses took too long: {:?}", timer.elapsed());
+    }
+    Ok(statuses)
+}
+
+#[tauri::command]
+pub async fn get_pool_earnings_history(
+    period: PoolEarningsPeriod,
+) -> Result<Vec<PoolEarningsAggregate>, InvokeError> {
+    let timer = Instant::now();
+
+    let aggregates = PoolEarningsHistory::get_aggregates(period)
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "get_pool_earnings_history took too long: {:?}", timer.elapsed());
+    }
+    Ok(aggregates)
+}
+
 #[tauri::command]
 pub async fn create_pin(app_handle: tauri::AppHandle) -> Result<(), String> {
     InternalWallet::create_pin(&app_handle)
@@ -30,7 +30,10 @@ use tokio::sync::RwLock;
 
 use crate::ab_test_selector::ABTestSelector;
 use crate::app_in_memory_config::{MinerType, DEFAULT_EXCHANGE_ID};
+use crate::node::external_node::ExternalNodeConfig;
 use crate::node::node_manager::NodeType;
+use crate::node::node_storage::NodeStorageProfile;
+use crate::process_resource_limits::ProcessResourceLimits;
 use crate::utils::rand_utils;
 
 use super::trait_config::{ConfigContentImpl, ConfigImpl};
@@ -66,8 +69,20 @@ pub struct ConfigCoreContent {
     last_changelog_version: Version,
     airdrop_tokens: Option<AirdropTokens>,
     remote_base_node_address: String,
+    // Probed next to `remote_base_node_address`, the fastest node at the tip is used
+    remote_base_node_addresses: Vec<String>,
+    // Blocks the selected remote node may lag behind the others before failing over
+    remote_node_failover_blocks_behind: u64,
     node_type: NodeType,
+    // Used when `node_type` is `External`
+    external_node: ExternalNodeConfig,
     exchange_id: String,
+    // Keep the node and wallet from starving the desktop, applied on their next start
+    node_resource_limits: ProcessResourceLimits,
+    wallet_resource_limits: ProcessResourceLimits,
+    node_storage_profile: NodeStorageProfile,
+    // The local node is paused below this much free disk space, 0 disables the check
+    node_min_free_disk_space_bytes: u64,
 }
 
 fn default_monero_nodes() -> Vec<String> {
@@ -118,8 +133,15 @@ impl Default for ConfigCoreContent {
             last_changelog_version: Version::new(0, 0, 0),
             airdrop_tokens: None,
             remote_base_node_address,
+            remote_base_node_addresses: vec![],
+            remote_node_failover_blocks_behind: 5,
             node_type: NodeType::default(),
+            external_node: ExternalNodeConfig::default(),
             exchange_id: DEFAULT_EXCHANGE_ID.to_string(),
+            node_resource_limits: ProcessResourceLimits::default(),
+            wallet_resource_limits: ProcessResourceLimits::default(),
+            node_storage_profile: NodeStorageProfile::default(),
+            node_min_free_disk_space_bytes: 3 * 1024 * 1024 * 1024,
         }
     }
 }
@@ -128,6 +150,33 @@ impl ConfigCoreContent {
     pub fn is_on_exchange_specific_variant(&self) -> bool {
         MinerType::from_str(&self.exchange_id).is_exchange_mode()
     }
+
+    pub fn set_remote_base_nodes(
+        &mut self,
+        (addresses, failover_blocks_behind): (Vec<String>, u64),
+    ) -> &mut Self {
+        self.remote_base_node_addresses = addresses;
+        self.remote_node_failover_blocks_behind = failover_blocks_behind;
+        self
+    }
+
+    pub fn set_node_storage(
+        &mut self,
+        (storage_profile, min_free_disk_space_bytes): (NodeStorageProfile, u64),
+    ) -> &mut Self {
+        self.node_storage_profile = storage_profile;
+        self.node_min_free_disk_space_bytes = min_free_disk_space_bytes;
+        self
+    }
+
+    pub fn set_process_resource_limits(
+        &mut self,
+        (node_limits, wallet_limits): (ProcessResourceLimits, ProcessResourceLimits),
+    ) -> &mut Self {
+        self.node_resource_limits = node_limits;
+        self.wallet_resource_limits = wallet_limits;
+        self
+    }
 }
 
 pub struct ConfigCore {
@@ -20,7 +20,10 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use crate::mining::gpu::consts::{EngineType, GpuMinerType};
+use crate::hardware::thermal_guard::ThermalLimit;
+use crate::mining::gpu::consts::{EngineType, GpuDeviceMinerRecommendation, GpuMinerType};
+use crate::process_resource_limits::ProcessResourceLimits;
+use crate::xmrig::config::XmrigConfig;
 use getset::{Getters, Setters};
 use log::{info, warn};
 use serde::{Deserialize, Serialize};
@@ -54,6 +57,9 @@ pub struct MiningMode {
 pub struct GpuDeviceSettings {
     device_id: u32,
     is_excluded: bool,
+    // Miner which should mine on this device instead of the selected one
+    #[serde(default)]
+    assigned_miner: Option<GpuMinerType>,
 }
 #[derive(Serialize, Deserialize, Clone)]
 pub struct GpuDevicesSettings(HashMap<u32, GpuDeviceSettings>);
@@ -67,6 +73,7 @@ impl GpuDevicesSettings {
         self.0.entry(device_id).or_insert(GpuDeviceSettings {
             device_id,
             is_excluded: false,
+            assigned_miner: None,
         });
     }
     pub fn set_excluded(&mut self, device_id: u32, is_excluded: bool) {
@@ -74,6 +81,16 @@ impl GpuDevicesSettings {
             settings.is_excluded = is_excluded;
         }
     }
+    pub fn get_assigned_miner(&self, device_id: u32) -> Option<GpuMinerType> {
+        self.0
+            .get(&device_id)
+            .and_then(|settings| settings.assigned_miner.clone())
+    }
+    pub fn set_assigned_miner(&mut self, device_id: u32, assigned_miner: Option<GpuMinerType>) {
+        if let Some(settings) = self.0.get_mut(&device_id) {
+            settings.assigned_miner = assigned_miner;
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -96,6 +113,29 @@ pub struct ConfigMiningContent {
     gpu_miner_type: GpuMinerType,
     is_lolminer_tested: bool,
     is_gpu_mining_recommended: bool,
+    // How long each miner is measured during the gpu benchmark
+    gpu_benchmark_duration_secs: u64,
+    gpu_devices_recommendations: HashMap<u32, GpuDeviceMinerRecommendation>,
+    // Every measurement of the last benchmark, used by the profit switcher to compare algorithms
+    gpu_benchmark_results: Vec<GpuDeviceMinerRecommendation>,
+    gpu_benchmark_completed_at: Option<SystemTime>,
+    profit_switching_enabled: bool,
+    // Minimal earnings gain in percent over the current algorithm required to switch
+    profit_switching_threshold_percentage: u32,
+    // Minimal time between two switches of the same cpu or gpu device
+    profit_switching_min_interval_secs: u64,
+    xmrig_config: XmrigConfig,
+    idle_mining_enabled: bool,
+    // Mode used while there is no keyboard or mouse input, the selected mode is kept untouched
+    idle_mining_mode: String,
+    idle_mining_timeout_secs: u64,
+    thermal_guard_enabled: bool,
+    cpu_thermal_limit: ThermalLimit,
+    gpu_thermal_limit: ThermalLimit,
+    // Overrides the gpu limit for single devices
+    gpu_devices_thermal_limits: HashMap<u32, ThermalLimit>,
+    // Process wide limits of xmrig, mining threads are pinned within them by `xmrig_config.cpu_affinity`
+    cpu_miner_resource_limits: ProcessResourceLimits,
 }
 
 impl Default for ConfigMiningContent {
@@ -155,6 +195,22 @@ impl Default for ConfigMiningContent {
             squad_override: None,
             is_lolminer_tested: false,
             is_gpu_mining_recommended: true,
+            gpu_benchmark_duration_secs: 60,
+            gpu_devices_recommendations: HashMap::new(),
+            gpu_benchmark_results: vec![],
+            gpu_benchmark_completed_at: None,
+            profit_switching_enabled: false,
+            profit_switching_threshold_percentage: 10,
+            profit_switching_min_interval_secs: 30 * 60,
+            xmrig_config: XmrigConfig::default(),
+            idle_mining_enabled: false,
+            idle_mining_mode: "Ludicrous".to_string(),
+            idle_mining_timeout_secs: 5 * 60,
+            thermal_guard_enabled: false,
+            cpu_thermal_limit: ThermalLimit::new(90.0, 10.0),
+            gpu_thermal_limit: ThermalLimit::new(85.0, 10.0),
+            gpu_devices_thermal_limits: HashMap::new(),
+            cpu_miner_resource_limits: ProcessResourceLimits::default(),
         }
     }
 }
@@ -194,22 +250,83 @@ impl ConfigMiningContent {
         self
     }
 
-    pub fn get_selected_cpu_usage_percentage(&self) -> u32 {
-        match self.mining_modes.get(&self.selected_mining_mode) {
+    pub fn get_cpu_usage_percentage(&self, mining_mode: &str) -> u32 {
+        match self.mining_modes.get(mining_mode) {
             Some(mode) => mode.cpu_usage_percentage,
             None => {
-                warn!("Mining mode '{}' not found", self.selected_mining_mode);
+                warn!("Mining mode '{mining_mode}' not found");
                 0
             }
         }
     }
 
-    pub fn get_excluded_devices(&self) -> Vec<u32> {
+    pub fn set_idle_mining_settings(
+        &mut self,
+        (enabled, mining_mode, timeout_secs): (bool, String, u64),
+    ) -> &mut Self {
+        self.idle_mining_enabled = enabled;
+        self.idle_mining_mode = mining_mode;
+        self.idle_mining_timeout_secs = timeout_secs;
+        self
+    }
+
+    pub fn assign_gpu_device_miner(
+        &mut self,
+        (device_id, assigned_miner): (u32, Option<GpuMinerType>),
+    ) -> &mut Self {
+        self.gpu_devices_settings
+            .set_assigned_miner(device_id, assigned_miner);
+        self
+    }
+
+    /// Miners other than the primary one that have at least one mining device assigned
+    pub fn get_secondary_gpu_miners(&self, primary_miner: &GpuMinerType) -> Vec<GpuMinerType> {
+        let mut miners: Vec<GpuMinerType> = vec![];
+        for settings in self.gpu_devices_settings.0.values() {
+            if settings.is_excluded {
+                continue;
+            }
+            if let Some(miner) = &settings.assigned_miner {
+                if miner != primary_miner && !miners.contains(miner) {
+                    miners.push(miner.clone());
+                }
+            }
+        }
+        miners
+    }
+
+    /// Devices which are not excluded from mining
+    pub fn get_mining_gpu_devices(&self) -> Vec<u32> {
+        let mut devices: Vec<u32> = self
+            .gpu_devices_settings
+            .0
+            .values()
+            .filter(|settings| !settings.is_excluded)
+            .map(|settings| settings.device_id)
+            .collect();
+        devices.sort_unstable();
+        devices
+    }
+
+    /// Devices the given miner should not mine on
+    /// Devices without assignment are mined by the primary miner
+    /// Assignments to miners which are not running are ignored so their devices are not left idle
+    pub fn get_excluded_devices_for_miner(
+        &self,
+        miner: &GpuMinerType,
+        primary_miner: &GpuMinerType,
+        running_miners: &[GpuMinerType],
+    ) -> Vec<u32> {
         self.gpu_devices_settings
             .0
             .iter()
             .filter_map(|(&device_id, settings)| {
-                if settings.is_excluded {
+                let device_miner = settings
+                    .assigned_miner
+                    .as_ref()
+                    .filter(|assigned_miner| running_miners.contains(assigned_miner))
+                    .unwrap_or(primary_miner);
+                if settings.is_excluded || device_miner != miner {
                     Some(device_id)
                 } else {
                     None
@@ -218,11 +335,37 @@ impl ConfigMiningContent {
             .collect()
     }
 
+    pub fn set_thermal_guard_settings(
+        &mut self,
+        (enabled, cpu_limit, gpu_limit, gpu_devices_limits): (
+            bool,
+            ThermalLimit,
+            ThermalLimit,
+            HashMap<u32, ThermalLimit>,
+        ),
+    ) -> &mut Self {
+        self.thermal_guard_enabled = enabled;
+        self.cpu_thermal_limit = cpu_limit;
+        self.gpu_thermal_limit = gpu_limit;
+        self.gpu_devices_thermal_limits = gpu_devices_limits;
+        self
+    }
+
+    pub fn get_gpu_thermal_limit(&self, device_id: u32) -> &ThermalLimit {
+        self.gpu_devices_thermal_limits
+            .get(&device_id)
+            .unwrap_or(&self.gpu_thermal_limit)
+    }
+
     pub fn get_selected_gpu_usage_percentage(&self) -> u32 {
-        match self.mining_modes.get(&self.selected_mining_mode) {
+        self.get_gpu_usage_percentage(&self.selected_mining_mode)
+    }
+
+    pub fn get_gpu_usage_percentage(&self, mining_mode: &str) -> u32 {
+        match self.mining_modes.get(mining_mode) {
             Some(mode) => mode.gpu_usage_percentage,
             None => {
-                warn!("Mining mode '{}' not found", self.selected_mining_mode);
+                warn!("Mining mode '{mining_mode}' not found");
                 0
             }
         }
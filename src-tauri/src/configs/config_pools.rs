@@ -28,12 +28,16 @@ use tokio::sync::RwLock;
 
 use crate::{
     configs::pools::{cpu_pools::CpuPool, gpu_pools::GpuPool, BasePoolData},
-    mining::pools::{cpu_pool_manager::CpuPoolManager, gpu_pool_manager::GpuPoolManager},
+    mining::{
+        gpu::consts::{GpuMinerType, GpuMiningAlgorithm},
+        pools::{cpu_pool_manager::CpuPoolManager, gpu_pool_manager::GpuPoolManager},
+    },
 };
 
 use super::trait_config::{ConfigContentImpl, ConfigImpl};
 
 pub const POOLS_CONFIG_VERSION: u32 = 0;
+const MAX_USED_MINING_ADDRESSES: usize = 20;
 static INSTANCE: LazyLock<RwLock<ConfigPools>> = LazyLock::new(|| RwLock::new(ConfigPools::new()));
 
 #[allow(clippy::struct_excessive_bools)]
@@ -54,6 +58,9 @@ pub struct ConfigPoolsContent {
     current_gpu_pool: GpuPool,
     #[getset(get = "pub", set = "pub")]
     gpu_pools: HashMap<GpuPool, BasePoolData<GpuPool>>,
+    // Pools used by miners running next to the selected one on their assigned devices
+    #[getset(get = "pub", set = "pub")]
+    gpu_miner_pools: HashMap<GpuMinerType, GpuPool>,
     // ======= Cpu Pool =======
     #[getset(get = "pub", set = "pub")]
     cpu_pool_enabled: bool,
@@ -61,6 +68,19 @@ pub struct ConfigPoolsContent {
     current_cpu_pool: CpuPool,
     #[getset(get = "pub", set = "pub")]
     cpu_pools: HashMap<CpuPool, BasePoolData<CpuPool>>,
+    // ======= Pool failover =======
+    #[getset(get = "pub", set = "pub")]
+    pool_failover_enabled: bool,
+    #[getset(get = "pub", set = "pub")]
+    switch_back_to_primary_pool: bool,
+    #[getset(get = "pub", set = "pub")]
+    cpu_pools_failover: Vec<CpuPool>,
+    #[getset(get = "pub", set = "pub")]
+    gpu_pools_failover: HashMap<GpuMiningAlgorithm, Vec<GpuPool>>,
+    // ======= Pool stats =======
+    // Most recent first, pool stats are still fetched for these so unpaid balances are not forgotten
+    #[getset(get = "pub")]
+    used_mining_addresses: Vec<String>,
 }
 
 impl Default for ConfigPoolsContent {
@@ -73,10 +93,18 @@ impl Default for ConfigPoolsContent {
             gpu_pool_enabled: true,
             current_gpu_pool: GpuPool::default(),
             gpu_pools: GpuPool::load_default_pools_data(),
+            gpu_miner_pools: HashMap::new(),
             // ======= Cpu Pool =======
             cpu_pool_enabled: true,
             current_cpu_pool: CpuPool::default(),
             cpu_pools: CpuPool::load_default_pools_data(),
+            // ======= Pool failover =======
+            pool_failover_enabled: true,
+            switch_back_to_primary_pool: true,
+            cpu_pools_failover: CpuPool::load_default_failover_pools(),
+            gpu_pools_failover: GpuPool::load_default_failover_pools(),
+            // ======= Pool stats =======
+            used_mining_addresses: Vec::new(),
         }
     }
 }
@@ -86,14 +114,70 @@ impl ConfigPoolsContent {
         self.gpu_pools
             .get(&self.current_gpu_pool)
             .cloned()
-            .unwrap_or_else(|| GpuPool::default().default_content())
+            .unwrap_or_else(GpuPool::default_pool_data)
     }
 
     pub fn current_cpu_pool(&self) -> BasePoolData<CpuPool> {
         self.cpu_pools
             .get(&self.current_cpu_pool)
             .cloned()
-            .unwrap_or_else(|| CpuPool::default().default_content())
+            .unwrap_or_else(CpuPool::default_pool_data)
+    }
+
+    /// Pool for a miner running on its assigned devices next to the selected miner
+    /// Falls back to the selected pool and then to the miner's default pool when the configured one doesn't fit
+    pub fn gpu_pool_for_miner(&self, miner: &GpuMinerType) -> Option<BasePoolData<GpuPool>> {
+        let configured_pool = self
+            .gpu_miner_pools
+            .get(miner)
+            .and_then(|pool| self.gpu_pools.get(pool));
+        let current_pool = self.current_gpu_pool();
+        let default_pool = miner
+            .default_pool()
+            .and_then(|pool| self.gpu_pools.get(&pool));
+
+        configured_pool
+            .into_iter()
+            .chain(Some(&current_pool))
+            .chain(default_pool)
+            .find(|pool_data| miner.is_pool_supported(pool_data))
+            .cloned()
+    }
+
+    /// Ordered list of CPU pools to try when the selected one is unhealthy
+    /// The selected pool is always first, pools missing from the config are skipped
+    pub fn cpu_failover_candidates(&self) -> Vec<CpuPool> {
+        let mut candidates = vec![self.current_cpu_pool.clone()];
+        for pool in &self.cpu_pools_failover {
+            if self.cpu_pools.contains_key(pool) && !candidates.contains(pool) {
+                candidates.push(pool.clone());
+            }
+        }
+        candidates
+    }
+
+    /// Ordered list of GPU pools to try when the selected one is unhealthy
+    /// Only pools mining the same algorithm as the selected pool are considered
+    pub fn gpu_failover_candidates(&self) -> Vec<GpuPool> {
+        let mut candidates = vec![self.current_gpu_pool.clone()];
+        let Some(algorithm) = self.current_gpu_pool().algorithm() else {
+            return candidates;
+        };
+        for pool in self
+            .gpu_pools_failover
+            .get(&algorithm)
+            .into_iter()
+            .flatten()
+        {
+            let same_algorithm = self
+                .gpu_pools
+                .get(pool)
+                .is_some_and(|pool_data| pool_data.algorithm().as_ref() == Some(&algorithm));
+            if same_algorithm && !candidates.contains(pool) {
+                candidates.push(pool.clone());
+            }
+        }
+        candidates
     }
 
     pub fn update_current_cpu_config(
@@ -115,7 +199,66 @@ impl ConfigPoolsContent {
         }
         self
     }
+
+    /// Inserts a user-defined CPU pool or replaces an existing one with the same key
+    pub fn upsert_custom_cpu_pool(&mut self, pool_data: BasePoolData<CpuPool>) -> &mut Self {
+        if pool_data.pool_type.is_custom() {
+            self.cpu_pools
+                .insert(pool_data.pool_type.clone(), pool_data);
+        }
+        self
+    }
+
+    /// Inserts a user-defined GPU pool or replaces an existing one with the same key
+    pub fn upsert_custom_gpu_pool(&mut self, pool_data: BasePoolData<GpuPool>) -> &mut Self {
+        if pool_data.pool_type.is_custom() {
+            self.gpu_pools
+                .insert(pool_data.pool_type.clone(), pool_data);
+        }
+        self
+    }
+
+    /// Removes a user-defined CPU pool, built-in pools can't be removed
+    /// If the removed pool was selected, the default pool gets selected instead
+    pub fn remove_custom_cpu_pool(&mut self, pool: CpuPool) -> &mut Self {
+        if pool.is_custom() {
+            self.cpu_pools.remove(&pool);
+            self.cpu_pools_failover
+                .retain(|failover_pool| *failover_pool != pool);
+            if self.current_cpu_pool == pool {
+                self.current_cpu_pool = CpuPool::default();
+            }
+        }
+        self
+    }
+
+    /// Removes a user-defined GPU pool, built-in pools can't be removed
+    /// If the removed pool was selected, the default pool gets selected instead
+    pub fn remove_custom_gpu_pool(&mut self, pool: GpuPool) -> &mut Self {
+        if pool.is_custom() {
+            self.gpu_pools.remove(&pool);
+            for failover_pools in self.gpu_pools_failover.values_mut() {
+                failover_pools.retain(|failover_pool| *failover_pool != pool);
+            }
+            self.gpu_miner_pools
+                .retain(|_, miner_pool| *miner_pool != pool);
+            if self.current_gpu_pool == pool {
+                self.current_gpu_pool = GpuPool::default();
+            }
+        }
+        self
+    }
+
+    pub fn add_used_mining_address(&mut self, address: String) -> &mut Self {
+        self.used_mining_addresses
+            .retain(|used_address| *used_address != address);
+        self.used_mining_addresses.insert(0, address);
+        self.used_mining_addresses
+            .truncate(MAX_USED_MINING_ADDRESSES);
+        self
+    }
 }
+
 pub struct ConfigPools {
     content: ConfigPoolsContent,
     app_handle: RwLock<Option<AppHandle>>,
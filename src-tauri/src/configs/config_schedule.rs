@@ -0,0 +1,195 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use chrono::Weekday;
+use getset::{Getters, Setters};
+use serde::{Deserialize, Serialize};
+use std::{sync::LazyLock, time::SystemTime};
+use tauri::AppHandle;
+use tokio::sync::RwLock;
+
+use super::trait_config::{ConfigContentImpl, ConfigImpl};
+
+pub const SCHEDULE_CONFIG_VERSION: u32 = 0;
+pub const MINUTES_PER_DAY: u16 = 24 * 60;
+static INSTANCE: LazyLock<RwLock<ConfigSchedule>> =
+    LazyLock::new(|| RwLock::new(ConfigSchedule::new()));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScheduleDay {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl From<Weekday> for ScheduleDay {
+    fn from(weekday: Weekday) -> Self {
+        match weekday {
+            Weekday::Mon => ScheduleDay::Monday,
+            Weekday::Tue => ScheduleDay::Tuesday,
+            Weekday::Wed => ScheduleDay::Wednesday,
+            Weekday::Thu => ScheduleDay::Thursday,
+            Weekday::Fri => ScheduleDay::Friday,
+            Weekday::Sat => ScheduleDay::Saturday,
+            Weekday::Sun => ScheduleDay::Sunday,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MiningScheduleAction {
+    Mine { mode: String },
+    Off,
+}
+
+impl MiningScheduleAction {
+    pub fn validate(&self, mining_modes: &[String]) -> Result<(), anyhow::Error> {
+        match self {
+            MiningScheduleAction::Mine { mode } if !mining_modes.contains(mode) => {
+                Err(anyhow::anyhow!("Unknown mining mode '{mode}'"))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Weekly time window in local time
+/// A window ending before it starts runs past midnight into the next day, equal start and end cover the whole day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MiningScheduleRule {
+    pub name: String,
+    pub days: Vec<ScheduleDay>,
+    // Minutes since midnight
+    pub start_minute: u16,
+    pub end_minute: u16,
+    pub action: MiningScheduleAction,
+}
+
+impl MiningScheduleRule {
+    pub fn validate(&self, mining_modes: &[String]) -> Result<(), anyhow::Error> {
+        if self.days.is_empty() {
+            return Err(anyhow::anyhow!("Schedule rule '{}' has no days", self.name));
+        }
+        if self.start_minute >= MINUTES_PER_DAY || self.end_minute >= MINUTES_PER_DAY {
+            return Err(anyhow::anyhow!(
+                "Schedule rule '{}' has a time outside of the day",
+                self.name
+            ));
+        }
+        self.action
+            .validate(mining_modes)
+            .map_err(|e| anyhow::anyhow!("Schedule rule '{}': {e}", self.name))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "snake_case")]
+#[serde(default)]
+#[derive(Getters, Setters)]
+#[getset(get = "pub", set = "pub")]
+pub struct ConfigScheduleContent {
+    version_counter: u32,
+    created_at: SystemTime,
+    is_enabled: bool,
+    // Applied when none of the rules matches
+    default_action: MiningScheduleAction,
+    // Earlier rules take precedence over later ones
+    rules: Vec<MiningScheduleRule>,
+}
+
+impl Default for ConfigScheduleContent {
+    fn default() -> Self {
+        Self {
+            version_counter: SCHEDULE_CONFIG_VERSION,
+            created_at: SystemTime::now(),
+            is_enabled: false,
+            default_action: MiningScheduleAction::Mine {
+                mode: "Eco".to_string(),
+            },
+            rules: Vec::new(),
+        }
+    }
+}
+impl ConfigContentImpl for ConfigScheduleContent {}
+
+impl ConfigScheduleContent {
+    pub fn set_schedule(
+        &mut self,
+        (is_enabled, default_action, rules): (bool, MiningScheduleAction, Vec<MiningScheduleRule>),
+    ) -> &mut Self {
+        self.is_enabled = is_enabled;
+        self.default_action = default_action;
+        self.rules = rules;
+        self
+    }
+}
+
+pub struct ConfigSchedule {
+    content: ConfigScheduleContent,
+    app_handle: RwLock<Option<AppHandle>>,
+}
+
+impl ConfigSchedule {
+    pub async fn initialize(app_handle: AppHandle) {
+        let mut config = Self::current().write().await;
+        config.load_app_handle(app_handle.clone()).await;
+    }
+}
+
+impl ConfigImpl for ConfigSchedule {
+    type Config = ConfigScheduleContent;
+
+    fn current() -> &'static RwLock<Self> {
+        &INSTANCE
+    }
+
+    fn new() -> Self {
+        Self {
+            content: ConfigSchedule::_load_or_create(),
+            app_handle: RwLock::new(None),
+        }
+    }
+
+    async fn _get_app_handle(&self) -> Option<AppHandle> {
+        self.app_handle.read().await.clone()
+    }
+
+    async fn load_app_handle(&mut self, app_handle: AppHandle) {
+        *self.app_handle.write().await = Some(app_handle);
+    }
+
+    fn _get_name() -> String {
+        "config_schedule".to_string()
+    }
+
+    fn _get_content(&self) -> &Self::Config {
+        &self.content
+    }
+
+    fn _get_content_mut(&mut self) -> &mut Self::Config {
+        &mut self.content
+    }
+}
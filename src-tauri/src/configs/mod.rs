@@ -23,6 +23,7 @@
 pub mod config_core;
 pub mod config_mining;
 pub mod config_pools;
+pub mod config_schedule;
 pub mod config_ui;
 pub mod config_wallet;
 pub mod pools;
@@ -25,7 +25,10 @@ use std::{collections::HashMap, fmt::Display, sync::LazyLock};
 use serde::{Deserialize, Serialize};
 use tari_common::configuration::Network;
 
-use crate::configs::pools::{BasePoolData, PoolOrigin};
+use crate::{
+    configs::pools::{BasePoolData, PoolOrigin, CUSTOM_POOL_ID_LENGTH, CUSTOM_POOL_KEY_PREFIX},
+    utils::rand_utils::get_rand_string,
+};
 
 fn global_tari_cpu_mining_pool_url() -> String {
     match Network::get_current_or_user_setting_or_default() {
@@ -56,6 +59,7 @@ static DEFAULT_CPU_SUPPORTXTM_RANDOMX: LazyLock<BasePoolData<CpuPool>> =
         stats_url: global_tari_cpu_mining_pool_status_url(),
         pool_type: CpuPool::SupportXTMPoolRANDOMX,
         pool_origin: PoolOrigin::SupportXTM,
+        custom: None,
     });
 
 static DEFAULT_CPU_LUCKYPOOL_RANDOMX: LazyLock<BasePoolData<CpuPool>> =
@@ -66,6 +70,7 @@ static DEFAULT_CPU_LUCKYPOOL_RANDOMX: LazyLock<BasePoolData<CpuPool>> =
             .to_string(),
         pool_type: CpuPool::LuckyPoolRANDOMX,
         pool_origin: PoolOrigin::LuckyPool,
+        custom: None,
     });
 
 static DEFAULT_CPU_KRYPTEX_RANDOMX: LazyLock<BasePoolData<CpuPool>> =
@@ -76,24 +81,40 @@ static DEFAULT_CPU_KRYPTEX_RANDOMX: LazyLock<BasePoolData<CpuPool>> =
             .to_string(),
         pool_type: CpuPool::KryptexPoolRANDOMX,
         pool_origin: PoolOrigin::Kryptex,
+        custom: None,
     });
 
+/// Built-in pools are serialized as their variant name, e.g. `LuckyPoolRANDOMX`,
+/// user-defined pools as `Custom:<id>` so they can be used as keys in the pools config
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[serde(into = "String", try_from = "String")]
 pub enum CpuPool {
     SupportXTMPoolRANDOMX,
     #[default]
     LuckyPoolRANDOMX,
     KryptexPoolRANDOMX,
+    Custom {
+        id: String,
+    },
 }
 
 impl Display for CpuPool {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let name = match self {
-            CpuPool::SupportXTMPoolRANDOMX => "SupportXTMPoolRANDOMX",
-            CpuPool::LuckyPoolRANDOMX => "LuckyPoolRANDOMX",
-            CpuPool::KryptexPoolRANDOMX => "KryptexPoolRANDOMX",
-        };
-        write!(f, "{name}")
+        write!(f, "{}", self.key_string())
+    }
+}
+
+impl From<CpuPool> for String {
+    fn from(pool: CpuPool) -> Self {
+        pool.key_string()
+    }
+}
+
+impl TryFrom<String> for CpuPool {
+    type Error = anyhow::Error;
+
+    fn try_from(pool_name: String) -> Result<Self, Self::Error> {
+        CpuPool::from_string(&pool_name)
     }
 }
 
@@ -103,7 +124,10 @@ impl CpuPool {
             "SupportXTMPoolRANDOMX" => Ok(CpuPool::SupportXTMPoolRANDOMX),
             "LuckyPoolRANDOMX" => Ok(CpuPool::LuckyPoolRANDOMX),
             "KryptexPoolRANDOMX" => Ok(CpuPool::KryptexPoolRANDOMX),
-            _ => Err(anyhow::anyhow!("Invalid CPU pool name")),
+            _ => match pool_name.strip_prefix(CUSTOM_POOL_KEY_PREFIX) {
+                Some(id) if !id.is_empty() => Ok(CpuPool::Custom { id: id.to_string() }),
+                _ => Err(anyhow::anyhow!("Invalid CPU pool name")),
+            },
         }
     }
 
@@ -112,17 +136,35 @@ impl CpuPool {
             CpuPool::SupportXTMPoolRANDOMX => "SupportXTMPoolRANDOMX".to_string(),
             CpuPool::LuckyPoolRANDOMX => "LuckyPoolRANDOMX".to_string(),
             CpuPool::KryptexPoolRANDOMX => "KryptexPoolRANDOMX".to_string(),
+            CpuPool::Custom { id } => format!("{CUSTOM_POOL_KEY_PREFIX}{id}"),
         }
     }
 
-    pub fn default_content(&self) -> BasePoolData<CpuPool> {
+    pub fn new_custom() -> Self {
+        CpuPool::Custom {
+            id: get_rand_string(CUSTOM_POOL_ID_LENGTH),
+        }
+    }
+
+    pub fn is_custom(&self) -> bool {
+        matches!(self, CpuPool::Custom { .. })
+    }
+
+    /// Returns the built-in pool data, user-defined pools have no defaults
+    pub fn default_content(&self) -> Option<BasePoolData<CpuPool>> {
         match self {
-            CpuPool::SupportXTMPoolRANDOMX => DEFAULT_CPU_SUPPORTXTM_RANDOMX.clone(),
-            CpuPool::LuckyPoolRANDOMX => DEFAULT_CPU_LUCKYPOOL_RANDOMX.clone(),
-            CpuPool::KryptexPoolRANDOMX => DEFAULT_CPU_KRYPTEX_RANDOMX.clone(),
+            CpuPool::SupportXTMPoolRANDOMX => Some(DEFAULT_CPU_SUPPORTXTM_RANDOMX.clone()),
+            CpuPool::LuckyPoolRANDOMX => Some(DEFAULT_CPU_LUCKYPOOL_RANDOMX.clone()),
+            CpuPool::KryptexPoolRANDOMX => Some(DEFAULT_CPU_KRYPTEX_RANDOMX.clone()),
+            CpuPool::Custom { .. } => None,
         }
     }
 
+    /// Returns data of the default built-in pool
+    pub fn default_pool_data() -> BasePoolData<CpuPool> {
+        DEFAULT_CPU_LUCKYPOOL_RANDOMX.clone()
+    }
+
     pub fn load_default_pools_data() -> HashMap<Self, BasePoolData<CpuPool>> {
         use CpuPool::*;
         let mut cpu_pools = HashMap::new();
@@ -134,4 +176,10 @@ impl CpuPool {
         cpu_pools.insert(KryptexPoolRANDOMX, DEFAULT_CPU_KRYPTEX_RANDOMX.clone());
         cpu_pools
     }
+
+    /// Default order in which built-in pools are tried when the selected pool becomes unhealthy
+    pub fn load_default_failover_pools() -> Vec<Self> {
+        use CpuPool::*;
+        vec![LuckyPoolRANDOMX, KryptexPoolRANDOMX, SupportXTMPoolRANDOMX]
+    }
 }
@@ -24,7 +24,11 @@ use std::{collections::HashMap, fmt::Display, sync::LazyLock};
 
 use serde::{Deserialize, Serialize};
 
-use crate::configs::pools::{BasePoolData, PoolOrigin};
+use crate::{
+    configs::pools::{BasePoolData, PoolOrigin, CUSTOM_POOL_ID_LENGTH, CUSTOM_POOL_KEY_PREFIX},
+    mining::gpu::consts::GpuMiningAlgorithm,
+    utils::rand_utils::get_rand_string,
+};
 
 static DEFAULT_GPU_LUCKYPOOL_SHA3X: LazyLock<BasePoolData<GpuPool>> =
     LazyLock::new(|| BasePoolData {
@@ -33,6 +37,7 @@ static DEFAULT_GPU_LUCKYPOOL_SHA3X: LazyLock<BasePoolData<GpuPool>> =
         stats_url: "https://api-tari.luckypool.io/stats_address?address=%TARI_ADDRESS%".to_string(),
         pool_type: GpuPool::LuckyPoolSHA3X,
         pool_origin: PoolOrigin::LuckyPool,
+        custom: None,
     });
 
 static DEFAULT_GPU_LUCKYPOOL_C29: LazyLock<BasePoolData<GpuPool>> =
@@ -43,6 +48,7 @@ static DEFAULT_GPU_LUCKYPOOL_C29: LazyLock<BasePoolData<GpuPool>> =
             .to_string(),
         pool_type: GpuPool::LuckyPoolC29,
         pool_origin: PoolOrigin::LuckyPool,
+        custom: None,
     });
 
 static DEFAULT_GPU_SUPPORTXTM_SHA3X: LazyLock<BasePoolData<GpuPool>> =
@@ -53,6 +59,7 @@ static DEFAULT_GPU_SUPPORTXTM_SHA3X: LazyLock<BasePoolData<GpuPool>> =
             .to_string(),
         pool_type: GpuPool::SupportXTMPoolSHA3X,
         pool_origin: PoolOrigin::SupportXTM,
+        custom: None,
     });
 
 static DEFAULT_GPU_KRYPTEX_SHA3X: LazyLock<BasePoolData<GpuPool>> =
@@ -63,6 +70,7 @@ static DEFAULT_GPU_KRYPTEX_SHA3X: LazyLock<BasePoolData<GpuPool>> =
             .to_string(),
         pool_type: GpuPool::KryptexPoolSHA3X,
         pool_origin: PoolOrigin::Kryptex,
+        custom: None,
     });
 
 static DEFAULT_GPU_KRYPTEX_C29: LazyLock<BasePoolData<GpuPool>> = LazyLock::new(|| BasePoolData {
@@ -71,9 +79,13 @@ static DEFAULT_GPU_KRYPTEX_C29: LazyLock<BasePoolData<GpuPool>> = LazyLock::new(
     stats_url: "https://pool.kryptex.com/xtm-c29/api/v1/miner/balance/%TARI_ADDRESS%".to_string(),
     pool_type: GpuPool::KryptexPoolC29,
     pool_origin: PoolOrigin::Kryptex,
+    custom: None,
 });
 
+/// Built-in pools are serialized as their variant name, e.g. `LuckyPoolSHA3X`,
+/// user-defined pools as `Custom:<id>` so they can be used as keys in the pools config
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[serde(into = "String", try_from = "String")]
 pub enum GpuPool {
     #[default]
     LuckyPoolSHA3X,
@@ -81,18 +93,28 @@ pub enum GpuPool {
     SupportXTMPoolSHA3X,
     KryptexPoolSHA3X,
     KryptexPoolC29,
+    Custom {
+        id: String,
+    },
 }
 
 impl Display for GpuPool {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let name = match self {
-            GpuPool::LuckyPoolSHA3X => "LuckyPoolSHA3X",
-            GpuPool::LuckyPoolC29 => "LuckyPoolC29",
-            GpuPool::SupportXTMPoolSHA3X => "SupportXTMPoolSHA3X",
-            GpuPool::KryptexPoolSHA3X => "KryptexPoolSHA3X",
-            GpuPool::KryptexPoolC29 => "KryptexPoolC29",
-        };
-        write!(f, "{name}")
+        write!(f, "{}", self.key_string())
+    }
+}
+
+impl From<GpuPool> for String {
+    fn from(pool: GpuPool) -> Self {
+        pool.key_string()
+    }
+}
+
+impl TryFrom<String> for GpuPool {
+    type Error = anyhow::Error;
+
+    fn try_from(pool_name: String) -> Result<Self, Self::Error> {
+        GpuPool::from_string(&pool_name)
     }
 }
 
@@ -104,7 +126,10 @@ impl GpuPool {
             "SupportXTMPoolSHA3X" => Ok(GpuPool::SupportXTMPoolSHA3X),
             "KryptexPoolSHA3X" => Ok(GpuPool::KryptexPoolSHA3X),
             "KryptexPoolC29" => Ok(GpuPool::KryptexPoolC29),
-            _ => Err(anyhow::anyhow!("Invalid GPU pool name")),
+            _ => match pool_name.strip_prefix(CUSTOM_POOL_KEY_PREFIX) {
+                Some(id) if !id.is_empty() => Ok(GpuPool::Custom { id: id.to_string() }),
+                _ => Err(anyhow::anyhow!("Invalid GPU pool name")),
+            },
         }
     }
 
@@ -115,19 +140,37 @@ impl GpuPool {
             GpuPool::SupportXTMPoolSHA3X => "SupportXTMPoolSHA3X".to_string(),
             GpuPool::KryptexPoolSHA3X => "KryptexPoolSHA3X".to_string(),
             GpuPool::KryptexPoolC29 => "KryptexPoolC29".to_string(),
+            GpuPool::Custom { id } => format!("{CUSTOM_POOL_KEY_PREFIX}{id}"),
+        }
+    }
+
+    pub fn new_custom() -> Self {
+        GpuPool::Custom {
+            id: get_rand_string(CUSTOM_POOL_ID_LENGTH),
         }
     }
 
-    pub fn default_content(&self) -> BasePoolData<GpuPool> {
+    pub fn is_custom(&self) -> bool {
+        matches!(self, GpuPool::Custom { .. })
+    }
+
+    /// Returns the built-in pool data, user-defined pools have no defaults
+    pub fn default_content(&self) -> Option<BasePoolData<GpuPool>> {
         match self {
-            GpuPool::LuckyPoolSHA3X => DEFAULT_GPU_LUCKYPOOL_SHA3X.clone(),
-            GpuPool::LuckyPoolC29 => DEFAULT_GPU_LUCKYPOOL_C29.clone(),
-            GpuPool::SupportXTMPoolSHA3X => DEFAULT_GPU_SUPPORTXTM_SHA3X.clone(),
-            GpuPool::KryptexPoolSHA3X => DEFAULT_GPU_KRYPTEX_SHA3X.clone(),
-            GpuPool::KryptexPoolC29 => DEFAULT_GPU_KRYPTEX_C29.clone(),
+            GpuPool::LuckyPoolSHA3X => Some(DEFAULT_GPU_LUCKYPOOL_SHA3X.clone()),
+            GpuPool::LuckyPoolC29 => Some(DEFAULT_GPU_LUCKYPOOL_C29.clone()),
+            GpuPool::SupportXTMPoolSHA3X => Some(DEFAULT_GPU_SUPPORTXTM_SHA3X.clone()),
+            GpuPool::KryptexPoolSHA3X => Some(DEFAULT_GPU_KRYPTEX_SHA3X.clone()),
+            GpuPool::KryptexPoolC29 => Some(DEFAULT_GPU_KRYPTEX_C29.clone()),
+            GpuPool::Custom { .. } => None,
         }
     }
 
+    /// Returns data of the default built-in pool
+    pub fn default_pool_data() -> BasePoolData<GpuPool> {
+        DEFAULT_GPU_LUCKYPOOL_SHA3X.clone()
+    }
+
     pub fn load_default_pools_data() -> HashMap<Self, BasePoolData<GpuPool>> {
         use GpuPool::*;
         let mut gpu_pools = HashMap::new();
@@ -138,4 +181,49 @@ impl GpuPool {
         gpu_pools.insert(KryptexPoolC29, DEFAULT_GPU_KRYPTEX_C29.clone());
         gpu_pools
     }
+
+    /// Default order in which built-in pools are tried when the selected pool becomes unhealthy
+    pub fn load_default_failover_pools() -> HashMap<GpuMiningAlgorithm, Vec<Self>> {
+        use GpuPool::*;
+        let mut failover_pools = HashMap::new();
+        failover_pools.insert(
+            GpuMiningAlgorithm::SHA3X,
+            vec![LuckyPoolSHA3X, KryptexPoolSHA3X, SupportXTMPoolSHA3X],
+        );
+        failover_pools.insert(GpuMiningAlgorithm::C29, vec![LuckyPoolC29, KryptexPoolC29]);
+        failover_pools
+    }
+}
+
+impl BasePoolData<GpuPool> {
+    /// Algorithm mined on the pool, user-defined pools without an algorithm return None
+    pub fn algorithm(&self) -> Option<GpuMiningAlgorithm> {
+        match self.pool_type {
+            GpuPool::LuckyPoolSHA3X | GpuPool::SupportXTMPoolSHA3X | GpuPool::KryptexPoolSHA3X => {
+                Some(GpuMiningAlgorithm::SHA3X)
+            }
+            GpuPool::LuckyPoolC29 | GpuPool::KryptexPoolC29 => Some(GpuMiningAlgorithm::C29),
+            GpuPool::Custom { .. } => self
+                .custom
+                .as_ref()
+                .and_then(|custom| custom.algorithm.clone()),
+        }
+    }
+
+    /// Besides the common validation, user-defined GPU pools need an algorithm
+    /// as it decides which miners can connect to them
+    pub fn validate_gpu_pool(&self) -> Result<(), anyhow::Error> {
+        self.validate()?;
+        let has_algorithm = self
+            .custom
+            .as_ref()
+            .is_some_and(|custom| custom.algorithm.is_some());
+        if self.pool_type.is_custom() && !has_algorithm {
+            return Err(anyhow::anyhow!(
+                "Custom GPU pool {} has no mining algorithm selected",
+                self.pool_name
+            ));
+        }
+        Ok(())
+    }
 }
@@ -20,17 +20,70 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use anyhow::anyhow;
+use reqwest::Url;
 use serde::{Deserialize, Serialize};
 
+use crate::mining::gpu::consts::GpuMiningAlgorithm;
+
 pub mod cpu_pools;
 pub mod gpu_pools;
 
+const TARI_ADDRESS_PLACEHOLDER: &str = "%TARI_ADDRESS%";
+pub const CUSTOM_POOL_KEY_PREFIX: &str = "Custom:";
+pub const CUSTOM_POOL_ID_LENGTH: usize = 8;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PoolOrigin {
     SupportXTM,
     LuckyPool,
     Kryptex,
+    Custom,
+}
+
+/// JSON pointer paths ( RFC 6901, e.g. `/stats/unpaid` ) used by the generic pool adapter
+/// to read values from the pool stats response. Fields without a path are reported as zero.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(default)]
+pub struct GenericPoolStatsPaths {
+    pub accepted_shares: Option<String>,
+    pub unpaid: Option<String>,
+    pub balance: Option<String>,
+    pub min_payout: Option<String>,
+}
+
+impl GenericPoolStatsPaths {
+    pub fn validate(&self) -> Result<(), anyhow::Error> {
+        for path in [
+            &self.accepted_shares,
+            &self.unpaid,
+            &self.balance,
+            &self.min_payout,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            if !path.is_empty() && !path.starts_with('/') {
+                return Err(anyhow!(
+                    "Invalid JSON pointer path: {path}, it must be empty or start with '/'"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Additional data stored only for user-defined pools
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct CustomPoolData {
+    /// Algorithm mined on the pool, used to match GPU pools with compatible miners
+    pub algorithm: Option<GpuMiningAlgorithm>,
+    /// Suffix appended to the mining address, e.g. `.worker` or `/worker`
+    pub worker_name: Option<String>,
+    pub stats_paths: GenericPoolStatsPaths,
 }
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BasePoolData<T> {
     pub pool_name: String,
@@ -38,4 +91,60 @@ pub struct BasePoolData<T> {
     pub stats_url: String,
     pub pool_type: T,
     pub pool_origin: PoolOrigin,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom: Option<CustomPoolData>,
+}
+
+impl<T> BasePoolData<T> {
+    /// Validates user provided pool data before it is persisted in the pools config
+    /// * `pool_url` has to be in `host:port` format as expected by the miners
+    /// * `stats_url` has to be empty or a valid http(s) url, it can contain `%TARI_ADDRESS%` placeholder
+    pub fn validate(&self) -> Result<(), anyhow::Error> {
+        if self.pool_name.trim().is_empty() {
+            return Err(anyhow!("Pool name cannot be empty"));
+        }
+
+        let (host, port) = self
+            .pool_url
+            .rsplit_once(':')
+            .ok_or_else(|| anyhow!("Invalid pool URL: {}, expected host:port", self.pool_url))?;
+        if host.is_empty() || host.contains(':') || host.contains('/') {
+            return Err(anyhow!(
+                "Invalid pool host: {host}, expected host name without scheme"
+            ));
+        }
+        port.parse::<u16>()
+            .map_err(|e| anyhow!("Invalid pool port: {port} | error: {e}"))?;
+
+        if !self.stats_url.is_empty() {
+            let stats_url =
+                Url::parse(&self.stats_url.replace(TARI_ADDRESS_PLACEHOLDER, "address"))
+                    .map_err(|e| anyhow!("Invalid stats URL: {} | error: {e}", self.stats_url))?;
+            if !matches!(stats_url.scheme(), "http" | "https") {
+                return Err(anyhow!(
+                    "Invalid stats URL scheme: {}, expected http or https",
+                    stats_url.scheme()
+                ));
+            }
+        }
+
+        if let Some(custom) = &self.custom {
+            custom.stats_paths.validate()?;
+        }
+
+        Ok(())
+    }
+
+    pub fn worker_name(&self) -> Option<String> {
+        match self.pool_origin {
+            PoolOrigin::LuckyPool => Some(".Tari-universe".to_string()),
+            PoolOrigin::Kryptex => Some("/Tari-universe".to_string()),
+            PoolOrigin::SupportXTM => None,
+            PoolOrigin::Custom => self
+                .custom
+                .as_ref()
+                .and_then(|custom| custom.worker_name.clone())
+                .filter(|worker_name| !worker_name.is_empty()),
+        }
+    }
 }
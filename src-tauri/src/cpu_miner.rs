@@ -21,23 +21,30 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use crate::binaries::Binaries;
-use crate::commands::{CpuMinerConnectionStatus, CpuMinerStatus};
+use crate::commands::{
+    CpuMinerConnectionStatus, CpuMinerCpuInfo, CpuMinerDetails, CpuMinerHugepagesStatus,
+    CpuMinerStatus, CpuMinerThreadStatus,
+};
+use crate::configs::config_mining::ConfigMiningContent;
 use crate::configs::config_pools::ConfigPoolsContent;
 use crate::configs::config_wallet::ConfigWalletContent;
 use crate::configs::pools::cpu_pools::CpuPool;
 use crate::configs::pools::BasePoolData;
 use crate::mining::cpu::CpuMinerConnection;
 use crate::mining::pools::cpu_pool_manager::CpuPoolManager;
+use crate::mining::pools::earnings_history::{PoolEarningsHistory, PoolEarningsMiner};
 use crate::mining::pools::PoolManagerInterfaceTrait;
+use crate::process_resource_limits::ProcessResourceLimits;
 use crate::process_stats_collector::ProcessStatsCollectorBuilder;
 use crate::process_watcher::ProcessWatcher;
 use crate::tasks_tracker::TasksTrackers;
 use crate::utils::math_utils::estimate_earning;
-use crate::xmrig::http_api::models::Summary;
+use crate::xmrig::config::XmrigConfig;
+use crate::xmrig::http_api::models::{Backend, Summary};
 use crate::xmrig_adapter::{XmrigAdapter, XmrigNodeConnection};
 use crate::{mm_proxy_manager, BaseNodeStatus};
 use log::{debug, error, info, warn};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::thread;
 use tari_common_types::tari_address::TariAddress;
@@ -55,6 +62,8 @@ pub struct CpuMinerConfig {
     pub pool_port: Option<u16>,
     pub pool_status_url: Option<String>,
     pub worker_name: Option<String>,
+    pub xmrig_config: XmrigConfig,
+    pub resource_limits: ProcessResourceLimits,
 }
 
 impl CpuMinerConfig {
@@ -86,48 +95,34 @@ impl CpuMinerConfig {
                 .stats_url
                 .replace("%TARI_ADDRESS%", &tari_address.to_base58()),
         );
-        let pool_url = pool_data.pool_url;
+        let pool_url = &pool_data.pool_url;
 
-        if let Ok((host_name, port)) = Self::split_url_to_hostname_port(&pool_url) {
+        if let Ok((host_name, port)) = Self::split_url_to_hostname_port(pool_url) {
             self.pool_host_name = Some(host_name);
             self.pool_port = Some(port);
         } else {
             error!(target: LOG_TARGET, "Invalid pool URL format: {pool_url}");
         }
 
-        match pool_data.pool_type {
-            CpuPool::SupportXTMPoolRANDOMX => {
-                self.worker_name = None;
-            }
-            CpuPool::LuckyPoolRANDOMX => {
-                self.worker_name = Some(".Tari-universe".to_string());
-            }
-            CpuPool::KryptexPoolRANDOMX => {
-                self.worker_name = Some("/Tari-universe".to_string());
-            }
-        }
+        // Worker name format depends on the pool
+        // LuckyPool: .Tari-universe
+        // Kryptex: /Tari-universe
+        // SupportXTM: Not specified so we use None
+        // Custom: Defined by the user
+        self.worker_name = pool_data.worker_name();
 
         self.node_connection = CpuMinerConnection::Pool;
     }
-    pub fn load_from_config_pools(
+    pub async fn load_from_config_pools(
         &mut self,
         config_pools_content: ConfigPoolsContent,
         tari_address: &TariAddress,
     ) {
-        let selected_cpu_pool_data = config_pools_content.current_cpu_pool().clone();
-
         if *config_pools_content.cpu_pool_enabled() {
-            match selected_cpu_pool_data.pool_type {
-                CpuPool::SupportXTMPoolRANDOMX => {
-                    self.resolve_pool_connection(selected_cpu_pool_data, tari_address)
-                }
-                CpuPool::LuckyPoolRANDOMX => {
-                    self.resolve_pool_connection(selected_cpu_pool_data, tari_address)
-                }
-                CpuPool::KryptexPoolRANDOMX => {
-                    self.resolve_pool_connection(selected_cpu_pool_data, tari_address)
-                }
-            }
+            // Selected pool or the fallback one if the failover switched away from it
+            let active_cpu_pool_data =
+                CpuPoolManager::active_pool_data(&config_pools_content).await;
+            self.resolve_pool_connection(active_cpu_pool_data, tari_address);
         } else {
             self.pool_status_url = None;
             self.pool_host_name = None;
@@ -136,6 +131,11 @@ impl CpuMinerConfig {
         }
     }
 
+    pub fn load_from_config_mining(&mut self, config_mining_content: &ConfigMiningContent) {
+        self.xmrig_config = config_mining_content.xmrig_config().clone();
+        self.resource_limits = config_mining_content.cpu_miner_resource_limits().clone();
+    }
+
     pub fn load_from_config_wallet(&mut self, config_wallet_content: &ConfigWalletContent) {
         self.monero_address = config_wallet_content.monero_address().to_string();
     }
@@ -145,6 +145,7 @@ pub(crate) struct CpuMiner {
     watcher: Arc<RwLock<ProcessWatcher<XmrigAdapter>>>,
     cpu_miner_status_watch_tx: watch::Sender<CpuMinerStatus>,
     summary_watch_rx: watch::Receiver<Option<Summary>>,
+    backends_watch_rx: watch::Receiver<Option<Vec<Backend>>>,
     node_status_watch_rx: watch::Receiver<BaseNodeStatus>,
     pub pool_status_shutdown_signal: Shutdown,
 }
@@ -156,12 +157,14 @@ impl CpuMiner {
         node_status_watch_rx: watch::Receiver<BaseNodeStatus>,
     ) -> Self {
         let (summary_watch_tx, summary_watch_rx) = watch::channel::<Option<Summary>>(None);
-        let xmrig_adapter = XmrigAdapter::new(summary_watch_tx);
+        let (backends_watch_tx, backends_watch_rx) = watch::channel::<Option<Vec<Backend>>>(None);
+        let xmrig_adapter = XmrigAdapter::new(summary_watch_tx, backends_watch_tx);
         let process_watcher = ProcessWatcher::new(xmrig_adapter, stats_collector.take_cpu_miner());
         Self {
             watcher: Arc::new(RwLock::new(process_watcher)),
             cpu_miner_status_watch_tx,
             summary_watch_rx,
+            backends_watch_rx,
             node_status_watch_rx,
             pool_status_shutdown_signal: Shutdown::new(),
         }
@@ -218,23 +221,7 @@ impl CpuMiner {
                 }
             }
         };
-        let max_cpu_available = thread::available_parallelism();
-        let max_cpu_available = match max_cpu_available {
-            Ok(available_cpus) => {
-                debug!(target:LOG_TARGET, "Available CPUs: {available_cpus}");
-                u32::try_from(available_cpus.get()).unwrap_or(1)
-            }
-            Err(err) => {
-                error!("Available CPUs: Unknown, error: {err}");
-                1
-            }
-        };
-
-        let cpu_cores_to_use = max_cpu_available
-            .saturating_mul(cpu_usage_percentage)
-            .saturating_div(100)
-            .clamp(1, max_cpu_available);
-
+        let cpu_cores_to_use = cpu_cores_for_usage(cpu_usage_percentage);
         info!(target: LOG_TARGET, "Using {cpu_cores_to_use} CPU cores for mining");
 
         {
@@ -242,6 +229,8 @@ impl CpuMiner {
 
             lock.adapter.node_connection = Some(xmrig_node_connection);
             lock.adapter.cpu_threads = Some(cpu_cores_to_use);
+            lock.adapter.xmrig_config = cpu_miner_config.xmrig_config.clone();
+            lock.adapter.resource_limits = cpu_miner_config.resource_limits.clone();
 
             let shutdown_signal = TasksTrackers::current().cpu_mining_phase.get_signal().await;
             let task_tracker = TasksTrackers::current()
@@ -289,6 +278,47 @@ impl CpuMiner {
         Ok(())
     }
 
+    /// Pauses hashing without stopping xmrig, so resuming doesn't have to reconnect
+    pub async fn pause(&self) -> Result<(), anyhow::Error> {
+        let lock = self.watcher.read().await;
+        if !lock.is_running() {
+            return Err(anyhow::anyhow!("Cpu miner is not running"));
+        }
+        if lock.is_paused() {
+            return Ok(());
+        }
+        lock.set_paused(true);
+        if let Err(e) = lock.adapter.http_api_client().pause().await {
+            lock.set_paused(false);
+            return Err(e);
+        }
+        info!(target: LOG_TARGET, "Cpu miner paused");
+        self.cpu_miner_status_watch_tx.send_modify(|status| {
+            status.hash_rate = 0.0;
+            status.estimated_earnings = 0;
+            if let Some(details) = status.details.as_mut() {
+                details.is_paused = true;
+            }
+        });
+        Ok(())
+    }
+
+    pub async fn resume(&self) -> Result<(), anyhow::Error> {
+        let lock = self.watcher.read().await;
+        if !lock.is_paused() {
+            return Ok(());
+        }
+        lock.adapter.http_api_client().resume().await?;
+        lock.set_paused(false);
+        info!(target: LOG_TARGET, "Cpu miner resumed");
+        Ok(())
+    }
+
+    pub async fn is_paused(&self) -> bool {
+        let lock = self.watcher.read().await;
+        lock.is_running() && lock.is_paused()
+    }
+
     pub async fn stop_status_updates(&mut self) -> Result<(), anyhow::Error> {
         info!(target: LOG_TARGET, "Stopping status updates");
         self.pool_status_shutdown_signal.trigger();
@@ -300,6 +330,50 @@ impl CpuMiner {
         lock.is_running()
     }
 
+    /// Sends the new config to the running xmrig through its http api so it doesn't have to be restarted
+    /// The config is only stored in the adapter when xmrig is not running, it's used on the next start
+    pub async fn reload_xmrig_config(
+        &self,
+        xmrig_config: XmrigConfig,
+        log_dir: &Path,
+    ) -> Result<(), anyhow::Error> {
+        let mut lock = self.watcher.write().await;
+        lock.adapter.xmrig_config = xmrig_config;
+        if !lock.is_running() {
+            return Ok(());
+        }
+
+        let config = lock.adapter.generate_config(log_dir)?;
+        lock.adapter
+            .http_api_client()
+            .update_config(&config)
+            .await?;
+        info!(target: LOG_TARGET, "Xmrig config reloaded");
+        Ok(())
+    }
+
+    /// Changes the number of mining threads of the running xmrig without restarting it
+    pub async fn set_cpu_usage_percentage(
+        &self,
+        cpu_usage_percentage: u32,
+        log_dir: &Path,
+    ) -> Result<(), anyhow::Error> {
+        let cpu_cores_to_use = cpu_cores_for_usage(cpu_usage_percentage);
+        let mut lock = self.watcher.write().await;
+        lock.adapter.cpu_threads = Some(cpu_cores_to_use);
+        if !lock.is_running() {
+            return Ok(());
+        }
+
+        let config = lock.adapter.generate_config(log_dir)?;
+        lock.adapter
+            .http_api_client()
+            .update_config(&config)
+            .await?;
+        info!(target: LOG_TARGET, "Using {cpu_cores_to_use} CPU cores for mining");
+        Ok(())
+    }
+
     pub async fn get_port(&self) -> u16 {
         let lock = self.watcher.read().await;
         lock.adapter.http_api_port
@@ -313,6 +387,7 @@ impl CpuMiner {
     async fn initialize_status_updates(&self, mut app_shutdown: ShutdownSignal) {
         let cpu_miner_status_watch_tx = self.cpu_miner_status_watch_tx.clone();
         let mut summary_watch_rx = self.summary_watch_rx.clone();
+        let backends_watch_rx = self.backends_watch_rx.clone();
         let node_status_watch_rx = self.node_status_watch_rx.clone();
         let mut inner_shutdown_signal = self.pool_status_shutdown_signal.to_signal();
 
@@ -322,6 +397,7 @@ impl CpuMiner {
                     _ = summary_watch_rx.changed() => {
                         let node_status = *node_status_watch_rx.borrow();
                         let xmrig_summary = summary_watch_rx.borrow().clone();
+                        let xmrig_backends = backends_watch_rx.borrow().clone();
 
                         let cpu_status = match xmrig_summary {
                             Some(xmrig_status) => {
@@ -338,12 +414,21 @@ impl CpuMiner {
                                 let is_connected = xmrig_status.connection.uptime > 0;
                                 // dbg!(&last_pool_status);
 
+                                CpuPoolManager::handle_miner_shares_update(
+                                    xmrig_status.connection.accepted,
+                                    xmrig_status.connection.rejected,
+                                )
+                                .await;
+
+
+                                let details = miner_details(&xmrig_status, xmrig_backends.as_deref());
 
                                 CpuMinerStatus {
-                                    is_mining: true,
+                                    is_mining: !xmrig_status.paused,
                                     hash_rate,
                                     estimated_earnings: MicroMinotari(estimated_earnings).as_u64(),
                                     connection: CpuMinerConnectionStatus { is_connected },
+                                    details: Some(details),
                                 }
                             }
                             None => {
@@ -352,6 +437,7 @@ impl CpuMiner {
                             }
                         };
 
+                        PoolEarningsHistory::set_estimated_daily_earnings(PoolEarningsMiner::Cpu, cpu_status.estimated_earnings);
                         let _result = cpu_miner_status_watch_tx.send(cpu_status);
                     },
                     _ = inner_shutdown_signal.wait() => {
@@ -367,3 +453,110 @@ impl CpuMiner {
         });
     }
 }
+
+fn miner_details(summary: &Summary, backends: Option<&[Backend]>) -> CpuMinerDetails {
+    let cpu_backend = backends
+        .unwrap_or_default()
+        .iter()
+        .find(|backend| backend.kind == "cpu" && backend.enabled);
+
+    // The cpu backend knows thread affinity and intensity, the summary only the hashrate
+    let threads = match cpu_backend {
+        Some(backend) if !backend.threads.is_empty() => backend
+            .threads
+            .iter()
+            .enumerate()
+            .map(|(index, thread)| CpuMinerThreadStatus {
+                index,
+                affinity: u32::try_from(thread.affinity).ok(),
+                intensity: Some(thread.intensity),
+                hash_rate: current_hash_rate(&thread.hashrate),
+            })
+            .collect(),
+        _ => summary
+            .hashrate
+            .threads
+            .iter()
+            .enumerate()
+            .map(|(index, hashrate)| CpuMinerThreadStatus {
+                index,
+                affinity: None,
+                intensity: None,
+                hash_rate: current_hash_rate(hashrate),
+            })
+            .collect(),
+    };
+
+    let hugepages = cpu_backend
+        .and_then(|backend| backend.hugepages)
+        .or(summary.hugepages)
+        .map(|hugepages| {
+            let pages = hugepages.pages();
+            CpuMinerHugepagesStatus {
+                is_enabled: hugepages.is_fully_allocated(),
+                allocated: pages.map(|(allocated, _)| allocated),
+                total: pages.map(|(_, total)| total),
+            }
+        })
+        .unwrap_or_default();
+
+    CpuMinerDetails {
+        algorithm: summary
+            .algo
+            .clone()
+            .or_else(|| cpu_backend.and_then(|backend| backend.algo.clone())),
+        is_paused: summary.paused,
+        pool: summary.connection.pool.clone(),
+        pool_ping_ms: Some(summary.connection.ping).filter(|ping| *ping > 0),
+        difficulty: summary.connection.diff,
+        accepted_shares: summary.connection.accepted,
+        rejected_shares: summary.connection.rejected,
+        connection_failures: summary.connection.failures,
+        good_shares: summary.results.as_ref().map(|results| results.shares_good),
+        total_shares: summary.results.as_ref().map(|results| results.shares_total),
+        average_share_time_ms: summary.results.as_ref().map(|results| results.avg_time_ms),
+        total_hashes: summary.results.as_ref().map(|results| results.hashes_total),
+        highest_hash_rate: summary.hashrate.highest,
+        hugepages,
+        cpu: summary.cpu.as_ref().map(|cpu| CpuMinerCpuInfo {
+            brand: cpu.brand.clone(),
+            architecture: cpu.arch.clone(),
+            cores: cpu.cores,
+            threads: cpu.threads,
+            numa_nodes: cpu.nodes,
+            aes: cpu.aes,
+            avx2: cpu.avx2,
+            msr: cpu.msr.clone(),
+            features: cpu.flags.clone(),
+        }),
+        threads,
+    }
+}
+
+/// Xmrig reports 10s, 60s and 15m averages, the first one is missing right after start
+fn current_hash_rate(hashrate: &[Option<f64>]) -> f64 {
+    hashrate
+        .iter()
+        .flatten()
+        .next()
+        .copied()
+        .unwrap_or_default()
+}
+
+fn cpu_cores_for_usage(cpu_usage_percentage: u32) -> u32 {
+    let max_cpu_available = match thread::available_parallelism() {
+        Ok(available_cpus) => {
+            debug!(target:LOG_TARGET, "Available CPUs: {available_cpus}");
+            u32::try_from(available_cpus.get()).unwrap_or(1)
+        }
+        Err(err) => {
+            error!("Available CPUs: Unknown, error: {err}");
+            1
+        }
+    };
+
+    max_cpu_available
+        .saturating_mul(cpu_usage_percentage)
+        .saturating_div(100)
+        .clamp(1, max_cpu_available)
+}
@@ -28,7 +28,14 @@ use std::{
 
 use crate::{
     internal_wallet::TariAddressType,
-    mining::gpu::miners::GpuCommonInformation,
+    mining::{
+        gpu::{
+            consts::{EngineType, GpuMinerType},
+            miners::GpuCommonInformation,
+        },
+        pause_manager::MiningPauseReason,
+        pools::pool_failover::{PoolHealth, PoolSwitchReason},
+    },
     node::{node_adapter::NodeIdentity, node_manager::NodeType},
     setup::{listeners::AppModule, setup_manager::SetupPhase},
     wallet::wallet_types::{TransactionInfo, WalletBalance},
@@ -41,8 +48,18 @@ pub enum EventType {
     GpuDevicesUpdate,
     CpuPoolsStatsUpdate,
     GpuPoolsStatsUpdate,
+    CpuPoolFailover,
+    GpuPoolFailover,
     CpuMiningUpdate,
     GpuMiningUpdate,
+    GpuDevicesMiningUpdate,
+    GpuBenchmarkProgress,
+    ProfitSwitchDecision,
+    MiningPauseUpdate,
+    MiningScheduleUpdate,
+    IdleMiningUpdate,
+    ThermalGuardUpdate,
+    NodeDiskUsageUpdate,
     NewBlockHeight,
     CloseSplashscreen,
     DetectedDevices,
@@ -61,6 +78,7 @@ pub enum EventType {
     ConfigWalletLoaded,
     ConfigMiningLoaded,
     ConfigPoolsLoaded,
+    ConfigScheduleLoaded,
     BackgroundNodeSyncUpdate,
     InitWalletScanningProgress,
     ConnectionStatus,
@@ -119,6 +137,31 @@ pub struct NetworkStatusPayload {
     pub is_too_low: bool,
 }
 
+#[derive(Clone, Debug, Serialize)]
+pub struct PoolFailoverPayload {
+    pub from_pool: String,
+    pub to_pool: String,
+    pub primary_pool: String,
+    pub reason: PoolSwitchReason,
+    pub pools_health: HashMap<String, PoolHealth>,
+}
+
+#[derive(Clone, Debug, Serialize, Default)]
+pub struct GpuBenchmarkProgressPayload {
+    pub is_running: bool,
+    pub current_miner: Option<GpuMinerType>,
+    pub current_engine: Option<EngineType>,
+    pub completed_runs: usize,
+    pub total_runs: usize,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct MiningPauseUpdatePayload {
+    pub is_cpu_paused: bool,
+    pub is_gpu_paused: bool,
+    pub reasons: Vec<MiningPauseReason>,
+}
+
 #[derive(Clone, Debug, Serialize)]
 pub struct DetectedAvailableGpuEnginesPayload {
     pub engines: Vec<String>,
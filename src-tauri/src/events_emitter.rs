@@ -23,12 +23,18 @@ use crate::configs::config_mining::GpuDevicesSettings;
 use crate::configs::config_ui::WalletUIMode;
 use crate::events::{
     ConnectionStatusPayload, CriticalProblemPayload, DisabledPhasesPayload,
-    InitWalletScanningProgressPayload, UpdateAppModuleStatusPayload, WalletStatusUpdatePayload,
+    GpuBenchmarkProgressPayload, InitWalletScanningProgressPayload, MiningPauseUpdatePayload,
+    PoolFailoverPayload, UpdateAppModuleStatusPayload, WalletStatusUpdatePayload,
 };
+use crate::hardware::thermal_guard::ThermalGuardStatus;
 use crate::internal_wallet::TariAddressType;
-use crate::mining::gpu::consts::{GpuMiner, GpuMinerStatus, GpuMinerType};
+use crate::mining::gpu::consts::{GpuDeviceMiningStatus, GpuMiner, GpuMinerStatus, GpuMinerType};
 use crate::mining::gpu::miners::GpuCommonInformation;
+use crate::mining::idle_monitor::IdleMiningStatus;
 use crate::mining::pools::PoolStatus;
+use crate::mining::profit_switcher::ProfitSwitchDecision;
+use crate::mining::scheduler::MiningScheduleStatus;
+use crate::node::node_storage::NodeDiskUsage;
 #[cfg(target_os = "windows")]
 use crate::system_dependencies::UniversalSystemDependency;
 use crate::wallet::wallet_types::{TransactionInfo, WalletBalance};
@@ -56,6 +62,7 @@ use tauri::{AppHandle, Emitter};
 use tokio::sync::RwLock;
 
 use crate::configs::config_pools::ConfigPoolsContent;
+use crate::configs::config_schedule::ConfigScheduleContent;
 
 const LOG_TARGET: &str = "tari::universe::events_emitter";
 const BACKEND_STATE_UPDATE: &str = "backend_state_update";
@@ -369,6 +376,20 @@ impl EventsEmitter {
         }
     }
 
+    pub async fn emit_schedule_config_loaded(payload: &ConfigScheduleContent) {
+        let _unused = FrontendReadyChannel::current().wait_for_ready().await;
+        let event = Event {
+            event_type: EventType::ConfigScheduleLoaded,
+            payload,
+        };
+        if let Err(e) = Self::get_app_handle()
+            .await
+            .emit(BACKEND_STATE_UPDATE, event)
+        {
+            error!(target: LOG_TARGET, "Failed to emit ScheduleConfigLoaded event: {e:?}");
+        }
+    }
+
     pub async fn emit_wallet_balance_update(balance: WalletBalance) {
         let _unused = FrontendReadyChannel::current().wait_for_ready().await;
         let event = Event {
@@ -427,6 +448,34 @@ impl EventsEmitter {
         }
     }
 
+    pub async fn emit_cpu_pool_failover(payload: PoolFailoverPayload) {
+        let _unused = FrontendReadyChannel::current().wait_for_ready().await;
+        let event = Event {
+            event_type: EventType::CpuPoolFailover,
+            payload,
+        };
+        if let Err(e) = Self::get_app_handle()
+            .await
+            .emit(BACKEND_STATE_UPDATE, event)
+        {
+            error!(target: LOG_TARGET, "Failed to emit CpuPoolFailover event: {e:?}");
+        }
+    }
+
+    pub async fn emit_gpu_pool_failover(payload: PoolFailoverPayload) {
+        let _unused = FrontendReadyChannel::current().wait_for_ready().await;
+        let event = Event {
+            event_type: EventType::GpuPoolFailover,
+            payload,
+        };
+        if let Err(e) = Self::get_app_handle()
+            .await
+            .emit(BACKEND_STATE_UPDATE, event)
+        {
+            error!(target: LOG_TARGET, "Failed to emit GpuPoolFailover event: {e:?}");
+        }
+    }
+
     pub async fn emit_gpu_pools_status_update(pool_status: HashMap<String, PoolStatus>) {
         let _unused = FrontendReadyChannel::current().wait_for_ready().await;
         let event = Event {
@@ -469,6 +518,118 @@ impl EventsEmitter {
         }
     }
 
+    pub async fn emit_gpu_devices_mining_update(devices: Vec<GpuDeviceMiningStatus>) {
+        let _unused = FrontendReadyChannel::current().wait_for_ready().await;
+        let event = Event {
+            event_type: EventType::GpuDevicesMiningUpdate,
+            payload: devices,
+        };
+        if let Err(e) = Self::get_app_handle()
+            .await
+            .emit(BACKEND_STATE_UPDATE, event)
+        {
+            error!(target: LOG_TARGET, "Failed to emit GpuDevicesMiningUpdate event: {e:?}");
+        }
+    }
+
+    pub async fn emit_gpu_benchmark_progress(payload: GpuBenchmarkProgressPayload) {
+        let _unused = FrontendReadyChannel::current().wait_for_ready().await;
+        let event = Event {
+            event_type: EventType::GpuBenchmarkProgress,
+            payload,
+        };
+        if let Err(e) = Self::get_app_handle()
+            .await
+            .emit(BACKEND_STATE_UPDATE, event)
+        {
+            error!(target: LOG_TARGET, "Failed to emit GpuBenchmarkProgress event: {e:?}");
+        }
+    }
+
+    pub async fn emit_profit_switch_decision(payload: ProfitSwitchDecision) {
+        let _unused = FrontendReadyChannel::current().wait_for_ready().await;
+        let event = Event {
+            event_type: EventType::ProfitSwitchDecision,
+            payload,
+        };
+        if let Err(e) = Self::get_app_handle()
+            .await
+            .emit(BACKEND_STATE_UPDATE, event)
+        {
+            error!(target: LOG_TARGET, "Failed to emit ProfitSwitchDecision event: {e:?}");
+        }
+    }
+
+    pub async fn emit_mining_pause_update(payload: MiningPauseUpdatePayload) {
+        let _unused = FrontendReadyChannel::current().wait_for_ready().await;
+        let event = Event {
+            event_type: EventType::MiningPauseUpdate,
+            payload,
+        };
+        if let Err(e) = Self::get_app_handle()
+            .await
+            .emit(BACKEND_STATE_UPDATE, event)
+        {
+            error!(target: LOG_TARGET, "Failed to emit MiningPauseUpdate event: {e:?}");
+        }
+    }
+
+    pub async fn emit_mining_schedule_update(payload: MiningScheduleStatus) {
+        let _unused = FrontendReadyChannel::current().wait_for_ready().await;
+        let event = Event {
+            event_type: EventType::MiningScheduleUpdate,
+            payload,
+        };
+        if let Err(e) = Self::get_app_handle()
+            .await
+            .emit(BACKEND_STATE_UPDATE, event)
+        {
+            error!(target: LOG_TARGET, "Failed to emit MiningScheduleUpdate event: {e:?}");
+        }
+    }
+
+    pub async fn emit_idle_mining_update(payload: IdleMiningStatus) {
+        let _unused = FrontendReadyChannel::current().wait_for_ready().await;
+        let event = Event {
+            event_type: EventType::IdleMiningUpdate,
+            payload,
+        };
+        if let Err(e) = Self::get_app_handle()
+            .await
+            .emit(BACKEND_STATE_UPDATE, event)
+        {
+            error!(target: LOG_TARGET, "Failed to emit IdleMiningUpdate event: {e:?}");
+        }
+    }
+
+    pub async fn emit_thermal_guard_update(payload: ThermalGuardStatus) {
+        let _unused = FrontendReadyChannel::current().wait_for_ready().await;
+        let event = Event {
+            event_type: EventType::ThermalGuardUpdate,
+            payload,
+        };
+        if let Err(e) = Self::get_app_handle()
+            .await
+            .emit(BACKEND_STATE_UPDATE, event)
+        {
+            error!(target: LOG_TARGET, "Failed to emit ThermalGuardUpdate event: {e:?}");
+        }
+    }
+
+    pub async fn emit_node_disk_usage_update(payload: NodeDiskUsage) {
+        let _unused = FrontendReadyChannel::current().wait_for_ready().await;
+        let event = Event {
+            event_type: EventType::NodeDiskUsageUpdate,
+            payload,
+        };
+        if let Err(e) = Self::get_app_handle()
+            .await
+            .emit(BACKEND_STATE_UPDATE, event)
+        {
+            error!(target: LOG_TARGET, "Failed to emit NodeDiskUsageUpdate event: {e:?}");
+        }
+    }
+
     pub async fn emit_new_block_mined(
         block_height: u64,
         coinbase_transaction: Option<TransactionInfo>,
@@ -36,6 +36,7 @@ use zip::ZipWriter;
 use crate::app_in_memory_config::AppInMemoryConfig;
 use crate::configs::config_core::ConfigCore;
 use crate::configs::trait_config::ConfigImpl;
+use crate::process_output_buffer::ProcessOutputRegistry;
 use crate::utils::file_utils::{make_relative_path, path_as_string};
 
 const LOG_TARGET: &str = "tari::universe::feedback";
@@ -95,6 +96,16 @@ impl Feedback {
             }
         }
 
+        // Output of the child processes which is not written to their log files
+        let output_registry = ProcessOutputRegistry::current();
+        for process_name in output_registry.process_names() {
+            if let Some(output_buffer) = output_registry.get(&process_name) {
+                let lines = output_buffer.tail_formatted(usize::MAX);
+                zip.start_file(format!("process_output/{process_name}.log"), file_options)?;
+                zip.write_all(lines.join("\n").as_bytes())?;
+            }
+        }
+
         Ok(zip.finish())
     }
 
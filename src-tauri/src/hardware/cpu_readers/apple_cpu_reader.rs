@@ -78,10 +78,11 @@ impl CpuParametersReader for AppleCpuParametersReader {
 
         let device_parameters = DeviceParameters {
             usage_percentage: usage,
-            current_temperature: avarage_temperature,
+            current_temperature: Some(avarage_temperature),
             max_temperature: old_device_parameters.map_or(avarage_temperature, |old| {
                 old.max_temperature.max(avarage_temperature)
             }),
+            ..Default::default()
         };
 
         Ok(device_parameters)
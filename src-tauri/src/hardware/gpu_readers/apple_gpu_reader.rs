@@ -54,8 +54,9 @@ impl GpuParametersReader for AppleGpuReader {
     ) -> Result<DeviceParameters, Error> {
         let device_parameters = DeviceParameters {
             usage_percentage: 0.0,
-            current_temperature: 0.0,
+            current_temperature: None,
             max_temperature: 0.0,
+            ..Default::default()
         };
         Ok(device_parameters)
     }
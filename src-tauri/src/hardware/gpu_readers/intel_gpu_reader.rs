@@ -20,7 +20,7 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use anyhow::{Error, Ok};
+use anyhow::Error;
 use async_trait::async_trait;
 
 use crate::{
@@ -28,14 +28,25 @@ use crate::{
     utils::platform_utils::{CurrentOperatingSystem, PlatformUtils},
 };
 
-use super::GpuParametersReader;
+use super::{
+    sysfs_gpu_reader::{SysfsGpuDriver, SysfsGpuReader},
+    GpuParametersReader,
+};
 
 #[derive(Clone)]
-pub struct IntelGpuReader {}
+pub struct IntelGpuReader {
+    sysfs_reader: SysfsGpuReader,
+}
 
 impl IntelGpuReader {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(pci_bus_id: Option<String>, intel_device_position: u32) -> Self {
+        Self {
+            sysfs_reader: SysfsGpuReader::new(
+                &[SysfsGpuDriver::I915, SysfsGpuDriver::Xe],
+                pci_bus_id,
+                intel_device_position,
+            ),
+        }
     }
 }
 
@@ -44,18 +55,26 @@ impl GpuParametersReader for IntelGpuReader {
     fn get_is_reader_implemented(&self) -> bool {
         match PlatformUtils::detect_current_os() {
             CurrentOperatingSystem::Windows => false,
-            CurrentOperatingSystem::Linux => false,
+            CurrentOperatingSystem::Linux => self.sysfs_reader.is_available(),
             CurrentOperatingSystem::MacOS => false,
         }
     }
+
     async fn get_device_parameters(
         &self,
-        _old_device_parameters: Option<DeviceParameters>,
+        old_device_parameters: Option<DeviceParameters>,
     ) -> Result<DeviceParameters, Error> {
+        if PlatformUtils::detect_current_os() == CurrentOperatingSystem::Linux {
+            return self
+                .sysfs_reader
+                .get_device_parameters(old_device_parameters);
+        }
+
         let device_parameters = DeviceParameters {
             usage_percentage: 0.0,
-            current_temperature: 0.0,
+            current_temperature: None,
             max_temperature: 0.0,
+            ..Default::default()
         };
         Ok(device_parameters)
     }
@@ -24,6 +24,9 @@ pub mod amd_gpu_reader;
 pub mod apple_gpu_reader;
 pub mod intel_gpu_reader;
 pub mod nvidia_gpu_reader;
+pub mod sysfs_gpu_reader;
+
+use std::fmt;
 
 use anyhow::Error;
 use async_trait::async_trait;
@@ -64,11 +67,115 @@ impl GpuParametersReader for DefaultGpuParametersReader {
     ) -> Result<DeviceParameters, Error> {
         let device_parameters = DeviceParameters {
             usage_percentage: 0.0,
-            current_temperature: 0.0,
+            current_temperature: None,
             max_temperature: 0.0,
+            ..Default::default()
         };
         Ok(device_parameters)
     }
 }
 
 dyn_clone::clone_trait_object!(GpuParametersReader);
+
+/// PCI location of a device. Miners and nvml format it differently
+/// (`01:00.0`, sysfs `0000:01:00.0` or nvml's `00000000:01:00.0`), so ids are compared after parsing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PciBusId {
+    domain: u32,
+    bus: u8,
+    device: u8,
+    function: u8,
+}
+
+impl PciBusId {
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.trim().rsplitn(3, ':');
+        let (device, function) = parts.next()?.split_once('.')?;
+        let bus = parts.next()?;
+        let domain = parts.next().unwrap_or("0");
+
+        Some(Self {
+            domain: u32::from_str_radix(domain, 16).ok()?,
+            bus: u8::from_str_radix(bus, 16).ok()?,
+            device: u8::from_str_radix(device, 16).ok()?,
+            function: u8::from_str_radix(function, 16).ok()?,
+        })
+    }
+}
+
+impl fmt::Display for PciBusId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:04x}:{:02x}:{:02x}.{:x}",
+            self.domain, self.bus, self.device, self.function
+        )
+    }
+}
+
+/// Picks the index of the card described by the miner among the cards found by a driver.
+/// Drivers enumerate cards in PCI bus order, the same order OpenCL uses within a vendor platform,
+/// so the position among the vendor cards is used when the miner did not report a bus id
+pub fn select_device_index(
+    bus_ids: &[Option<PciBusId>],
+    pci_bus_id: Option<PciBusId>,
+    vendor_device_position: u32,
+) -> Option<u32> {
+    let index = match pci_bus_id {
+        Some(pci_bus_id) => bus_ids
+            .iter()
+            .position(|bus_id| *bus_id == Some(pci_bus_id))?,
+        None => vendor_device_position as usize,
+    };
+    if index < bus_ids.len() {
+        u32::try_from(index).ok()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn parses_pci_bus_id_formats() {
+        let expected = PciBusId {
+            domain: 0,
+            bus: 0x1a,
+            device: 0,
+            function: 0,
+        };
+        assert_eq!(PciBusId::parse("1a:00.0"), Some(expected));
+        assert_eq!(PciBusId::parse("0000:1A:00.0"), Some(expected));
+        assert_eq!(PciBusId::parse("00000000:1a:00.0"), Some(expected));
+        assert_eq!(PciBusId::parse("1a:00"), None);
+        assert_eq!(PciBusId::parse("gpu0"), None);
+        assert_eq!(expected.to_string(), "0000:1a:00.0");
+    }
+
+    #[test]
+    fn selects_device_by_pci_bus_id() {
+        let bus_ids = vec![
+            PciBusId::parse("00000000:01:00.0"),
+            None,
+            PciBusId::parse("00000000:02:00.0"),
+        ];
+        assert_eq!(
+            select_device_index(&bus_ids, PciBusId::parse("02:00.0"), 0),
+            Some(2)
+        );
+        assert_eq!(
+            select_device_index(&bus_ids, PciBusId::parse("03:00.0"), 0),
+            None
+        );
+    }
+
+    #[test]
+    fn falls_back_to_device_position() {
+        let bus_ids = vec![PciBusId::parse("01:00.0"), PciBusId::parse("02:00.0")];
+        assert_eq!(select_device_index(&bus_ids, None, 1), Some(1));
+        assert_eq!(select_device_index(&bus_ids, None, 2), None);
+    }
+}
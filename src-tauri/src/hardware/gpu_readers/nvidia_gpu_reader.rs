@@ -20,36 +20,78 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use std::sync::LazyLock;
+
 use anyhow::{anyhow, Error};
 use async_trait::async_trait;
-use log::{debug, error};
-use nvml_wrapper::{enum_wrappers::device::TemperatureSensor, Nvml};
+use log::{debug, error, warn};
+use nvml_wrapper::{
+    enum_wrappers::device::{Clock, TemperatureSensor},
+    Device, Nvml,
+};
 
 use crate::{
     hardware::hardware_status_monitor::DeviceParameters,
     utils::platform_utils::{CurrentOperatingSystem, PlatformUtils},
 };
 
-use super::GpuParametersReader;
+use super::{select_device_index, GpuParametersReader, PciBusId};
+
+const LOG_TARGET: &str = "tari::universe::hardware::nvidia_gpu_reader";
+
+// Initializing nvml loads the driver library, so it is done once and shared between all cards
+static NVML: LazyLock<Option<Nvml>> = LazyLock::new(|| match Nvml::init() {
+    Ok(nvml) => {
+        debug!(target: LOG_TARGET, "Nvidia GPU reader initialized");
+        Some(nvml)
+    }
+    Err(e) => {
+        error!(target: LOG_TARGET, "Failed to initialize Nvidia GPU reader: {e}");
+        None
+    }
+});
 
 #[derive(Clone)]
-pub struct NvidiaGpuReader {}
+pub struct NvidiaGpuReader {
+    pci_bus_id: Option<PciBusId>,
+    nvidia_device_position: u32,
+}
 impl NvidiaGpuReader {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(pci_bus_id: Option<String>, nvidia_device_position: u32) -> Self {
+        let parsed_pci_bus_id = pci_bus_id.as_deref().and_then(PciBusId::parse);
+        if pci_bus_id.is_some() && parsed_pci_bus_id.is_none() {
+            warn!(target: LOG_TARGET, "Invalid PCI bus id: {pci_bus_id:?}, falling back to device position");
+        }
+        Self {
+            pci_bus_id: parsed_pci_bus_id,
+            nvidia_device_position,
+        }
     }
 
-    pub fn init_nvml(&self) -> Option<Nvml> {
-        match Nvml::init() {
-            Ok(nvml) => {
-                debug!("Nvidia GPU reader initialized");
-                Some(nvml)
-            }
-            Err(e) => {
-                error!("Failed to initialize Nvidia GPU reader: {e}");
-                None
-            }
-        }
+    fn find_device<'a>(&self, nvml: &'a Nvml) -> Result<Device<'a>, Error> {
+        let device_count = nvml
+            .device_count()
+            .map_err(|e| anyhow!("Failed to get Nvidia GPU device count: {}", e))?;
+        let nvml_bus_ids: Vec<Option<PciBusId>> = (0..device_count)
+            .map(|index| {
+                nvml.device_by_index(index)
+                    .and_then(|device| device.pci_info())
+                    .ok()
+                    .and_then(|pci_info| PciBusId::parse(&pci_info.bus_id))
+            })
+            .collect();
+
+        let index =
+            select_device_index(&nvml_bus_ids, self.pci_bus_id, self.nvidia_device_position)
+                .ok_or_else(|| {
+                    anyhow!(
+                        "Nvidia GPU device not found, pci bus id: {:?}, position: {}",
+                        self.pci_bus_id.map(|id| id.to_string()),
+                        self.nvidia_device_position
+                    )
+                })?;
+        nvml.device_by_index(index)
+            .map_err(|e| anyhow!("Failed to get Nvidia GPU device: {}", e))
     }
 }
 
@@ -57,8 +99,8 @@ impl NvidiaGpuReader {
 impl GpuParametersReader for NvidiaGpuReader {
     fn get_is_reader_implemented(&self) -> bool {
         match PlatformUtils::detect_current_os() {
-            CurrentOperatingSystem::Windows => self.init_nvml().is_some(),
-            CurrentOperatingSystem::Linux => self.init_nvml().is_some(),
+            CurrentOperatingSystem::Windows => NVML.is_some(),
+            CurrentOperatingSystem::Linux => NVML.is_some(),
             CurrentOperatingSystem::MacOS => false,
         }
     }
@@ -66,28 +108,36 @@ impl GpuParametersReader for NvidiaGpuReader {
         &self,
         old_device_parameters: Option<DeviceParameters>,
     ) -> Result<DeviceParameters, Error> {
-        let nvml = self
-            .init_nvml()
+        let nvml = NVML
+            .as_ref()
             .ok_or(anyhow!("Failed to initialize Nvidia GPU reader"))?;
-        // TODO ADD support for multiple GPUs
-        let main_device = nvml
-            .device_by_index(0)
-            .map_err(|e| anyhow!("Failed to get Nvidia GPU device: {}", e))?;
-        let usage_percentage = main_device
+        let device = self.find_device(nvml)?;
+        let usage_percentage = device
             .utilization_rates()
             .map_err(|e| anyhow!("Failed to get Nvidia GPU utilization rates: {}", e))?
             .gpu as f32;
-        let current_temperature = main_device
+        let current_temperature = device
             .temperature(TemperatureSensor::Gpu)
             .map_err(|e| anyhow!("Failed to get Nvidia GPU temperature: {}", e))?
             as f32;
+        // Not every card exposes these sensors, e.g. passively cooled cards have no fan
+        let memory_info = device.memory_info().ok();
 
         let device_parameters = DeviceParameters {
             usage_percentage,
-            current_temperature,
+            current_temperature: Some(current_temperature),
             max_temperature: old_device_parameters.map_or(current_temperature, |old| {
                 old.max_temperature.max(current_temperature)
             }),
+            power_draw_watts: device
+                .power_usage()
+                .ok()
+                .map(|milliwatts| milliwatts as f32 / 1000.0),
+            fan_speed_percentage: device.fan_speed(0).ok(),
+            graphics_clock_mhz: device.clock_info(Clock::Graphics).ok(),
+            memory_clock_mhz: device.clock_info(Clock::Memory).ok(),
+            memory_used_bytes: memory_info.as_ref().map(|info| info.used),
+            memory_total_bytes: memory_info.as_ref().map(|info| info.total),
         };
         Ok(device_parameters)
     }
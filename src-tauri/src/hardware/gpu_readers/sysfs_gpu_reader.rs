@@ -0,0 +1,452 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, Error};
+
+use crate::hardware::hardware_status_monitor::DeviceParameters;
+
+use super::{select_device_index, PciBusId};
+
+const DRM_CLASS_PATH: &str = "/sys/class/drm";
+const MAX_TEMPERATURE_SENSORS: u32 = 8;
+// hwmon reports temperatures in millidegrees, power in microwatts and fan duty cycle as 0-255 pwm
+const MILLIDEGREES_PER_DEGREE: f32 = 1_000.0;
+const MICROWATTS_PER_WATT: f32 = 1_000_000.0;
+const MAX_PWM: u32 = 255;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SysfsGpuDriver {
+    Amdgpu,
+    I915,
+    Xe,
+}
+
+impl SysfsGpuDriver {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "amdgpu" => Some(Self::Amdgpu),
+            "i915" => Some(Self::I915),
+            "xe" => Some(Self::Xe),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SysfsGpuCard {
+    card_path: PathBuf,
+    driver: SysfsGpuDriver,
+    pci_bus_id: Option<PciBusId>,
+}
+
+impl SysfsGpuCard {
+    fn device_path(&self) -> PathBuf {
+        self.card_path.join("device")
+    }
+
+    fn hwmon_path(&self) -> Option<PathBuf> {
+        let mut hwmon_paths: Vec<PathBuf> = fs::read_dir(self.device_path().join("hwmon"))
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with("hwmon"))
+            })
+            .collect();
+        hwmon_paths.sort();
+        hwmon_paths.into_iter().next()
+    }
+
+    /// Actual and maximum (RP0) frequency of the render engine
+    fn intel_frequency_paths(&self) -> (PathBuf, PathBuf) {
+        match self.driver {
+            SysfsGpuDriver::Xe => {
+                let freq_path = self.device_path().join("tile0").join("gt0").join("freq0");
+                (freq_path.join("act_freq"), freq_path.join("rp0_freq"))
+            }
+            _ => (
+                self.card_path.join("gt_act_freq_mhz"),
+                self.card_path.join("gt_RP0_freq_mhz"),
+            ),
+        }
+    }
+
+    fn read_parameters(
+        &self,
+        old_device_parameters: Option<DeviceParameters>,
+    ) -> Result<DeviceParameters, Error> {
+        let device_path = self.device_path();
+        let hwmon_path = self.hwmon_path();
+
+        let (usage_percentage, graphics_clock_mhz, memory_clock_mhz) = match self.driver {
+            SysfsGpuDriver::Amdgpu => (
+                read_value::<f32>(&device_path.join("gpu_busy_percent")),
+                read_active_dpm_clock(&device_path.join("pp_dpm_sclk")),
+                read_active_dpm_clock(&device_path.join("pp_dpm_mclk")),
+            ),
+            SysfsGpuDriver::I915 | SysfsGpuDriver::Xe => {
+                // Intel drivers do not expose a busy counter in sysfs,
+                // so the load is estimated from the actual to maximum frequency ratio
+                let (actual_frequency_path, max_frequency_path) = self.intel_frequency_paths();
+                let actual_frequency = read_value::<u32>(&actual_frequency_path);
+                let usage_percentage = actual_frequency
+                    .zip(read_value::<u32>(&max_frequency_path))
+                    .filter(|(_, max_frequency)| *max_frequency > 0)
+                    .map(|(actual_frequency, max_frequency)| {
+                        (actual_frequency as f32 / max_frequency as f32 * 100.0).min(100.0)
+                    });
+                (usage_percentage, actual_frequency, None)
+            }
+        };
+        let current_temperature = hwmon_path.as_deref().and_then(read_temperature);
+
+        if usage_percentage.is_none() && current_temperature.is_none() {
+            return Err(anyhow!(
+                "No gpu sensors found in: {}",
+                self.card_path.display()
+            ));
+        }
+        // Devices without a temperature sensor must not look cool to the thermal guard
+        let max_temperature = match (old_device_parameters, current_temperature) {
+            (Some(old), Some(temperature)) => old.max_temperature.max(temperature),
+            (Some(old), None) => old.max_temperature,
+            (None, temperature) => temperature.unwrap_or_default(),
+        };
+
+        Ok(DeviceParameters {
+            usage_percentage: usage_percentage.unwrap_or_default(),
+            current_temperature,
+            max_temperature,
+            power_draw_watts: hwmon_path.as_deref().and_then(read_power_draw),
+            fan_speed_percentage: hwmon_path
+                .as_deref()
+                .and_then(|path| read_value::<u32>(&path.join("pwm1")))
+                .map(|pwm| pwm.min(MAX_PWM) * 100 / MAX_PWM),
+            graphics_clock_mhz,
+            memory_clock_mhz,
+            memory_used_bytes: read_value::<u64>(&device_path.join("mem_info_vram_used")),
+            memory_total_bytes: read_value::<u64>(&device_path.join("mem_info_vram_total")),
+        })
+    }
+}
+
+fn read_value<T: FromStr>(path: &Path) -> Option<T> {
+    fs::read_to_string(path).ok()?.trim().parse::<T>().ok()
+}
+
+fn read_temperature(hwmon_path: &Path) -> Option<f32> {
+    let sensors: Vec<(Option<String>, f32)> = (1..=MAX_TEMPERATURE_SENSORS)
+        .filter_map(|index| {
+            let temperature = read_value::<f32>(&hwmon_path.join(format!("temp{index}_input")))?;
+            let label = read_value::<String>(&hwmon_path.join(format!("temp{index}_label")));
+            Some((label, temperature))
+        })
+        .collect();
+
+    // amdgpu labels the die sensor "edge" and xe the package sensor "pkg",
+    // hotter sensors like "junction" or "mem" are only used when they are the only ones
+    sensors
+        .iter()
+        .find(|(label, _)| matches!(label.as_deref(), Some("edge") | Some("pkg")))
+        .or_else(|| sensors.first())
+        .map(|(_, temperature)| temperature / MILLIDEGREES_PER_DEGREE)
+}
+
+fn read_power_draw(hwmon_path: &Path) -> Option<f32> {
+    read_value::<f32>(&hwmon_path.join("power1_average"))
+        .or_else(|| read_value::<f32>(&hwmon_path.join("power1_input")))
+        .map(|microwatts| microwatts / MICROWATTS_PER_WATT)
+}
+
+/// amdgpu lists the dpm clock levels as `1: 1800Mhz *`, with the active one marked by a star
+fn read_active_dpm_clock(path: &Path) -> Option<u32> {
+    fs::read_to_string(path)
+        .ok()?
+        .lines()
+        .find(|line| line.trim_end().ends_with('*'))?
+        .split_whitespace()
+        .nth(1)?
+        .to_lowercase()
+        .strip_suffix("mhz")?
+        .parse()
+        .ok()
+}
+
+fn parse_uevent(content: &str) -> (Option<SysfsGpuDriver>, Option<PciBusId>) {
+    let mut driver = None;
+    let mut pci_bus_id = None;
+    for (key, value) in content.lines().filter_map(|line| line.split_once('=')) {
+        match key {
+            "DRIVER" => driver = SysfsGpuDriver::from_name(value.trim()),
+            "PCI_SLOT_NAME" => pci_bus_id = PciBusId::parse(value),
+            _ => {}
+        }
+    }
+    (driver, pci_bus_id)
+}
+
+/// Cards bound to one of the drivers, in PCI bus order
+fn list_cards(drm_path: &Path, drivers: &[SysfsGpuDriver]) -> Vec<SysfsGpuCard> {
+    let Ok(entries) = fs::read_dir(drm_path) else {
+        return vec![];
+    };
+    let mut cards: Vec<SysfsGpuCard> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            // Connectors are listed next to the cards, e.g. card0-DP-1
+            let name = entry.file_name().into_string().ok()?;
+            name.strip_prefix("card")?.parse::<u32>().ok()?;
+
+            let card_path = entry.path();
+            let uevent = fs::read_to_string(card_path.join("device").join("uevent")).ok()?;
+            let (driver, pci_bus_id) = parse_uevent(&uevent);
+            let driver = driver.filter(|driver| drivers.contains(driver))?;
+            Some(SysfsGpuCard {
+                card_path,
+                driver,
+                pci_bus_id,
+            })
+        })
+        .collect();
+    cards.sort_by_key(|card| (card.pci_bus_id, card.card_path.clone()));
+    cards
+}
+
+/// Reads gpu telemetry of a single card from the linux drm sysfs tree
+#[derive(Clone)]
+pub struct SysfsGpuReader {
+    drm_path: PathBuf,
+    drivers: &'static [SysfsGpuDriver],
+    pci_bus_id: Option<PciBusId>,
+    vendor_device_position: u32,
+}
+
+impl SysfsGpuReader {
+    pub fn new(
+        drivers: &'static [SysfsGpuDriver],
+        pci_bus_id: Option<String>,
+        vendor_device_position: u32,
+    ) -> Self {
+        Self {
+            drm_path: PathBuf::from(DRM_CLASS_PATH),
+            drivers,
+            pci_bus_id: pci_bus_id.as_deref().and_then(PciBusId::parse),
+            vendor_device_position,
+        }
+    }
+
+    fn find_card(&self) -> Result<SysfsGpuCard, Error> {
+        let cards = list_cards(&self.drm_path, self.drivers);
+        let bus_ids: Vec<Option<PciBusId>> = cards.iter().map(|card| card.pci_bus_id).collect();
+        let index = select_device_index(&bus_ids, self.pci_bus_id, self.vendor_device_position)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Gpu card not found in {}, pci bus id: {:?}, position: {}",
+                    self.drm_path.display(),
+                    self.pci_bus_id.map(|id| id.to_string()),
+                    self.vendor_device_position
+                )
+            })?;
+        cards
+            .into_iter()
+            .nth(index as usize)
+            .ok_or_else(|| anyhow!("Gpu card index out of range: {index}"))
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.find_card().is_ok()
+    }
+
+    pub fn get_device_parameters(
+        &self,
+        old_device_parameters: Option<DeviceParameters>,
+    ) -> Result<DeviceParameters, Error> {
+        self.find_card()?.read_parameters(old_device_parameters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    const AMD: &[SysfsGpuDriver] = &[SysfsGpuDriver::Amdgpu];
+    const INTEL: &[SysfsGpuDriver] = &[SysfsGpuDriver::I915, SysfsGpuDriver::Xe];
+
+    struct FakeSysfs {
+        drm_path: PathBuf,
+    }
+
+    impl FakeSysfs {
+        fn new(name: &str) -> Self {
+            let drm_path = std::env::temp_dir()
+                .join(format!("tari-sysfs-gpu-{name}-{}", std::process::id()))
+                .join("drm");
+            let _unused = fs::remove_dir_all(&drm_path);
+            fs::create_dir_all(&drm_path).unwrap();
+            Self { drm_path }
+        }
+
+        fn write(&self, path: &str, content: &str) {
+            let path = self.drm_path.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+
+        fn add_card(&self, card: &str, driver: &str, pci_slot_name: &str) {
+            self.write(
+                &format!("{card}/device/uevent"),
+                &format!("DRIVER={driver}\nPCI_CLASS=30000\nPCI_SLOT_NAME={pci_slot_name}\n"),
+            );
+        }
+
+        fn reader(
+            &self,
+            drivers: &'static [SysfsGpuDriver],
+            pci_bus_id: Option<&str>,
+            position: u32,
+        ) -> SysfsGpuReader {
+            SysfsGpuReader {
+                drm_path: self.drm_path.clone(),
+                ..SysfsGpuReader::new(drivers, pci_bus_id.map(str::to_string), position)
+            }
+        }
+    }
+
+    impl Drop for FakeSysfs {
+        fn drop(&mut self) {
+            let _unused = fs::remove_dir_all(self.drm_path.parent().unwrap());
+        }
+    }
+
+    #[test]
+    fn reads_amdgpu_sensors() {
+        let sysfs = FakeSysfs::new("amdgpu");
+        sysfs.add_card("card0", "amdgpu", "0000:03:00.0");
+        sysfs.write("card0-DP-1/status", "connected\n");
+        sysfs.write("card0/device/gpu_busy_percent", "87\n");
+        sysfs.write("card0/device/pp_dpm_sclk", "0: 500Mhz\n1: 2100Mhz *\n");
+        sysfs.write("card0/device/pp_dpm_mclk", "0: 96Mhz\n1: 1000Mhz *\n");
+        sysfs.write("card0/device/mem_info_vram_used", "1073741824\n");
+        sysfs.write("card0/device/mem_info_vram_total", "8589934592\n");
+        sysfs.write("card0/device/hwmon/hwmon4/temp1_input", "65000\n");
+        sysfs.write("card0/device/hwmon/hwmon4/temp1_label", "edge\n");
+        sysfs.write("card0/device/hwmon/hwmon4/temp2_input", "80000\n");
+        sysfs.write("card0/device/hwmon/hwmon4/temp2_label", "junction\n");
+        sysfs.write("card0/device/hwmon/hwmon4/power1_average", "150000000\n");
+        sysfs.write("card0/device/hwmon/hwmon4/pwm1", "128\n");
+
+        let reader = sysfs.reader(AMD, None, 0);
+        assert!(reader.is_available());
+        let old_parameters = DeviceParameters {
+            max_temperature: 70.0,
+            ..Default::default()
+        };
+        let parameters = reader.get_device_parameters(Some(old_parameters)).unwrap();
+
+        assert_eq!(parameters.usage_percentage, 87.0);
+        assert_eq!(parameters.current_temperature, Some(65.0));
+        assert_eq!(parameters.max_temperature, 70.0);
+        assert_eq!(parameters.power_draw_watts, Some(150.0));
+        assert_eq!(parameters.fan_speed_percentage, Some(50));
+        assert_eq!(parameters.graphics_clock_mhz, Some(2100));
+        assert_eq!(parameters.memory_clock_mhz, Some(1000));
+        assert_eq!(parameters.memory_used_bytes, Some(1_073_741_824));
+        assert_eq!(parameters.memory_total_bytes, Some(8_589_934_592));
+    }
+
+    #[test]
+    fn reads_intel_sensors() {
+        let sysfs = FakeSysfs::new("intel");
+        sysfs.add_card("card0", "i915", "0000:00:02.0");
+        sysfs.write("card0/gt_act_freq_mhz", "600\n");
+        sysfs.write("card0/gt_RP0_freq_mhz", "1200\n");
+        sysfs.add_card("card1", "xe", "0000:03:00.0");
+        sysfs.write("card1/device/tile0/gt0/freq0/act_freq", "2400\n");
+        sysfs.write("card1/device/tile0/gt0/freq0/rp0_freq", "2400\n");
+        sysfs.write("card1/device/hwmon/hwmon2/temp2_input", "71000\n");
+        sysfs.write("card1/device/hwmon/hwmon2/temp2_label", "pkg\n");
+        sysfs.write("card1/device/hwmon/hwmon2/temp3_input", "78000\n");
+        sysfs.write("card1/device/hwmon/hwmon2/temp3_label", "vram\n");
+        sysfs.write("card1/device/hwmon/hwmon2/power1_input", "95500000\n");
+
+        let integrated = sysfs
+            .reader(INTEL, None, 0)
+            .get_device_parameters(None)
+            .unwrap();
+        assert_eq!(integrated.usage_percentage, 50.0);
+        assert_eq!(integrated.current_temperature, None);
+        assert_eq!(integrated.graphics_clock_mhz, Some(600));
+        assert_eq!(integrated.power_draw_watts, None);
+
+        let dedicated = sysfs
+            .reader(INTEL, None, 1)
+            .get_device_parameters(None)
+            .unwrap();
+        assert_eq!(dedicated.usage_percentage, 100.0);
+        assert_eq!(dedicated.current_temperature, Some(71.0));
+        assert_eq!(dedicated.max_temperature, 71.0);
+        assert_eq!(dedicated.power_draw_watts, Some(95.5));
+    }
+
+    #[test]
+    fn matches_cards_by_pci_bus_id_or_position() {
+        let sysfs = FakeSysfs::new("matching");
+        sysfs.add_card("card0", "amdgpu", "0000:0b:00.0");
+        sysfs.write("card0/device/gpu_busy_percent", "10\n");
+        sysfs.add_card("card1", "amdgpu", "0000:03:00.0");
+        sysfs.write("card1/device/gpu_busy_percent", "30\n");
+        sysfs.add_card("card2", "i915", "0000:00:02.0");
+
+        let usage = |pci_bus_id: Option<&str>, position: u32| {
+            sysfs
+                .reader(AMD, pci_bus_id, position)
+                .get_device_parameters(None)
+                .map(|parameters| parameters.usage_percentage)
+        };
+        assert_eq!(usage(Some("0b:00.0"), 0).unwrap(), 10.0);
+        assert_eq!(usage(Some("0000:03:00.0"), 0).unwrap(), 30.0);
+        assert_eq!(usage(None, 0).unwrap(), 30.0);
+        assert_eq!(usage(None, 1).unwrap(), 10.0);
+        assert!(usage(None, 2).is_err());
+        assert!(usage(Some("00:02.0"), 0).is_err());
+    }
+
+    #[test]
+    fn card_without_sensors_is_an_error() {
+        let sysfs = FakeSysfs::new("no-sensors");
+        sysfs.add_card("card0", "amdgpu", "0000:03:00.0");
+
+        let reader = sysfs.reader(AMD, None, 0);
+        assert!(reader.is_available());
+        assert!(reader.get_device_parameters(None).is_err());
+        assert!(!sysfs.reader(INTEL, None, 0).is_available());
+    }
+}
@@ -112,13 +112,23 @@ struct GpuStatusFileEntry {
     max_work_group_size: u32,
     max_compute_units: u32,
     global_mem_size: u64,
+    #[serde(default)]
+    pci_bus_id: Option<String>,
 }
 
 #[derive(Debug, Serialize, Clone, Default)]
 pub struct DeviceParameters {
     pub usage_percentage: f32,
-    pub current_temperature: f32,
+    // None when the device has no readable temperature sensor
+    pub current_temperature: Option<f32>,
     pub max_temperature: f32,
+    // Extended telemetry, only filled by readers whose driver exposes it
+    pub power_draw_watts: Option<f32>,
+    pub fan_speed_percentage: Option<u32>,
+    pub graphics_clock_mhz: Option<u32>,
+    pub memory_clock_mhz: Option<u32>,
+    pub memory_used_bytes: Option<u64>,
+    pub memory_total_bytes: Option<u64>,
 }
 #[derive(Debug, Serialize, Clone, Default)]
 pub struct DeviceStatus {
@@ -135,6 +145,8 @@ pub struct PublicDeviceCpuProperties {
 }
 #[derive(Debug, Serialize, Clone, Default)]
 pub struct PublicDeviceGpuProperties {
+    pub device_id: u32,
+    pub pci_bus_id: Option<String>,
     pub vendor: HardwareVendor,
     pub name: String,
     pub status: DeviceStatus,
@@ -148,6 +160,7 @@ pub struct PrivateCpuDeviceProperties {
 }
 #[derive(Clone)]
 pub struct PrivateGpuDeviceProperties {
+    pub device_id: u32,
     pub device_reader: Box<dyn GpuParametersReader>,
 }
 
@@ -196,11 +209,17 @@ impl HardwareStatusMonitor {
     async fn select_reader_for_gpu_device(
         &self,
         vendor: HardwareVendor,
+        pci_bus_id: Option<String>,
+        vendor_device_position: u32,
     ) -> Box<dyn GpuParametersReader> {
         match vendor {
-            HardwareVendor::Nvidia => Box::new(NvidiaGpuReader::new()),
-            HardwareVendor::Amd => Box::new(AmdGpuReader::new()),
-            HardwareVendor::Intel => Box::new(IntelGpuReader::new()),
+            HardwareVendor::Nvidia => {
+                Box::new(NvidiaGpuReader::new(pci_bus_id, vendor_device_position))
+            }
+            HardwareVendor::Amd => Box::new(AmdGpuReader::new(pci_bus_id, vendor_device_position)),
+            HardwareVendor::Intel => {
+                Box::new(IntelGpuReader::new(pci_bus_id, vendor_device_position))
+            }
             HardwareVendor::Apple => Box::new(AppleGpuReader::new()),
             _ => {
                 warn!("Unsupported GPU vendor: {vendor:?}");
@@ -214,17 +233,31 @@ impl HardwareStatusMonitor {
             .expect("Could not get config dir")
             .join(APPLICATION_FOLDER_ID);
         let gpu_status_file_content = self.load_gpu_devices_from_status_file(config_dir).await?;
-        let mut platform_devices = Vec::new();
+        let mut platform_devices: Vec<GpuDeviceProperties> = Vec::new();
 
         for gpu_device in &gpu_status_file_content.devices {
-            debug!(target: LOG_TARGET, "GPU device name: {:?}", gpu_device.name);
+            debug!(target: LOG_TARGET, "GPU device name: {:?}, pci bus id: {:?}", gpu_device.name, gpu_device.pci_bus_id);
             let vendor = HardwareVendor::from_string(&gpu_device.name);
-            let device_reader = self.select_reader_for_gpu_device(vendor.clone()).await;
+            #[allow(clippy::cast_possible_truncation)]
+            let vendor_device_position = platform_devices
+                .iter()
+                .filter(|device| device.public_properties.vendor == vendor)
+                .count() as u32;
+            let device_reader = self
+                .select_reader_for_gpu_device(
+                    vendor.clone(),
+                    gpu_device.pci_bus_id.clone(),
+                    vendor_device_position,
+                )
+                .await;
             let platform_device = GpuDeviceProperties {
                 private_properties: PrivateGpuDeviceProperties {
+                    device_id: gpu_device.device_id,
                     device_reader: device_reader.clone(),
                 },
                 public_properties: PublicDeviceGpuProperties {
+                    device_id: gpu_device.device_id,
+                    pci_bus_id: gpu_device.pci_bus_id.clone(),
                     vendor: vendor.clone(),
                     name: gpu_device.name.clone(),
                     status: DeviceStatus {
@@ -318,6 +351,31 @@ impl HardwareStatusMonitor {
         Ok(cpu_devices.clone())
     }
 
+    /// Every cpu entry reads the same package sensors, so one reader covers the whole cpu
+    pub async fn get_cpu_parameters_reader(&self) -> Option<Box<dyn CpuParametersReader>> {
+        self.cpu_devices
+            .read()
+            .await
+            .iter()
+            .find(|device| device.public_properties.status.is_reader_implemented)
+            .map(|device| device.private_properties.device_reader.clone())
+    }
+
+    pub async fn get_gpu_parameters_readers(&self) -> Vec<(u32, Box<dyn GpuParametersReader>)> {
+        self.gpu_devices
+            .read()
+            .await
+            .iter()
+            .filter(|device| device.public_properties.status.is_reader_implemented)
+            .map(|device| {
+                (
+                    device.private_properties.device_id,
+                    device.private_properties.device_reader.clone(),
+                )
+            })
+            .collect()
+    }
+
     pub async fn get_gpu_public_properties(&self) -> Result<Vec<PublicDeviceGpuProperties>, Error> {
         let gpu_devices = self.gpu_devices.read().await;
 
@@ -325,6 +383,8 @@ impl HardwareStatusMonitor {
 
         for device in gpu_devices.iter() {
             platform_devices.push(PublicDeviceGpuProperties {
+                device_id: device.public_properties.device_id,
+                pci_bus_id: device.public_properties.pci_bus_id.clone(),
                 device_type: device.public_properties.device_type.clone(),
                 vendor: device.public_properties.vendor.clone(),
                 name: device.public_properties.name.clone(),
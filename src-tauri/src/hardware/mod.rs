@@ -20,7 +20,8 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-mod cpu_readers;
-mod gpu_readers;
+pub mod cpu_readers;
+pub mod gpu_readers;
 
 pub mod hardware_status_monitor;
+pub mod thermal_guard;
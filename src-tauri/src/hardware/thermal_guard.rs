@@ -0,0 +1,785 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        LazyLock,
+    },
+    time::Duration,
+};
+
+use anyhow::Error;
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tokio::{select, sync::Mutex, time::interval};
+
+use crate::{
+    commands::{start_cpu_mining, start_gpu_mining, stop_cpu_mining, stop_gpu_mining},
+    configs::{
+        config_mining::{ConfigMining, ConfigMiningContent},
+        trait_config::ConfigImpl,
+    },
+    events_emitter::EventsEmitter,
+    mining::{
+        gpu::manager::GpuManager, idle_monitor::IdleMiningMonitor,
+        pause_manager::MiningPauseManager,
+    },
+    tasks_tracker::TasksTrackers,
+    UniverseAppState,
+};
+
+use super::{
+    cpu_readers::CpuParametersReader,
+    gpu_readers::GpuParametersReader,
+    hardware_status_monitor::{DeviceParameters, HardwareStatusMonitor},
+};
+
+static LOG_TARGET: &str = "tari::universe::thermal_guard";
+static INSTANCE: LazyLock<ThermalGuardManager> = LazyLock::new(ThermalGuardManager::new);
+
+const EVALUATION_INTERVAL: Duration = Duration::from_secs(15);
+const THROTTLE_STEP_PERCENTAGE: u32 = 25;
+const MIN_THROTTLED_USAGE_PERCENTAGE: u32 = 25;
+// Device this much above its max temperature is stopped right away instead of being throttled step by step
+const CRITICAL_MARGIN_CELSIUS: f32 = 10.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ThermalLimit {
+    pub max_temperature: f32,
+    // Throttling is lifted step by step only once the device cools this much below max temperature
+    pub hysteresis: f32,
+}
+
+impl ThermalLimit {
+    pub fn new(max_temperature: f32, hysteresis: f32) -> Self {
+        Self {
+            max_temperature,
+            hysteresis,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        if !(1.0..=150.0).contains(&self.max_temperature) {
+            return Err(anyhow::anyhow!(
+                "Max temperature has to be between 1 and 150 °C, got {}",
+                self.max_temperature
+            ));
+        }
+        if self.hysteresis <= 0.0 || self.hysteresis >= self.max_temperature {
+            return Err(anyhow::anyhow!(
+                "Hysteresis has to be greater than zero and lower than max temperature, got {}",
+                self.hysteresis
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ThermalThrottleLevel {
+    Normal,
+    // Percentage of the mining mode usage the device is allowed to use
+    Throttled { usage_percentage: u32 },
+    Stopped,
+}
+
+impl ThermalThrottleLevel {
+    /// Moves at most one step per reading, except for critical temperatures which stop the device right away
+    fn next(self, temperature: f32, limit: ThermalLimit) -> Self {
+        if temperature >= limit.max_temperature + CRITICAL_MARGIN_CELSIUS {
+            return Self::Stopped;
+        }
+        if temperature >= limit.max_temperature {
+            return match self {
+                Self::Normal => Self::Throttled {
+                    usage_percentage: 100 - THROTTLE_STEP_PERCENTAGE,
+                },
+                Self::Throttled { usage_percentage }
+                    if usage_percentage > MIN_THROTTLED_USAGE_PERCENTAGE =>
+                {
+                    Self::Throttled {
+                        usage_percentage: usage_percentage
+                            .saturating_sub(THROTTLE_STEP_PERCENTAGE)
+                            .max(MIN_THROTTLED_USAGE_PERCENTAGE),
+                    }
+                }
+                Self::Throttled { .. } | Self::Stopped => Self::Stopped,
+            };
+        }
+        if temperature <= limit.max_temperature - limit.hysteresis {
+            return match self {
+                Self::Stopped => Self::Throttled {
+                    usage_percentage: MIN_THROTTLED_USAGE_PERCENTAGE,
+                },
+                Self::Throttled { usage_percentage }
+                    if usage_percentage + THROTTLE_STEP_PERCENTAGE < 100 =>
+                {
+                    Self::Throttled {
+                        usage_percentage: usage_percentage + THROTTLE_STEP_PERCENTAGE,
+                    }
+                }
+                Self::Throttled { .. } | Self::Normal => Self::Normal,
+            };
+        }
+        self
+    }
+
+    fn usage_percentage(self) -> u32 {
+        match self {
+            Self::Normal => 100,
+            Self::Throttled { usage_percentage } => usage_percentage,
+            Self::Stopped => 0,
+        }
+    }
+
+    /// Usage of the mining mode reduced by throttling, `None` when the miner has to stay stopped
+    pub fn limit_usage(self, usage_percentage: u32) -> Option<u32> {
+        match self {
+            Self::Normal => Some(usage_percentage),
+            Self::Throttled {
+                usage_percentage: allowed_percentage,
+            } => Some(
+                usage_percentage
+                    .saturating_mul(allowed_percentage)
+                    .saturating_div(100)
+                    .max(1),
+            ),
+            Self::Stopped => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DeviceThermalStatus {
+    // Missing for the cpu
+    pub device_id: Option<u32>,
+    pub temperature: Option<f32>,
+    pub max_temperature: f32,
+    pub level: ThermalThrottleLevel,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct ThermalGuardStatus {
+    pub is_enabled: bool,
+    pub cpu: Option<DeviceThermalStatus>,
+    pub gpus: Vec<DeviceThermalStatus>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ThermalGuardChanges {
+    pub is_cpu_changed: bool,
+    pub is_gpu_changed: bool,
+}
+
+#[derive(Clone)]
+struct DeviceThermalState {
+    level: ThermalThrottleLevel,
+    parameters: Option<DeviceParameters>,
+    max_temperature: f32,
+}
+
+impl DeviceThermalState {
+    fn new() -> Self {
+        Self {
+            level: ThermalThrottleLevel::Normal,
+            parameters: None,
+            max_temperature: 0.0,
+        }
+    }
+
+    /// Returns whether the throttle level changed, failed readings keep the current level
+    fn update(
+        &mut self,
+        reading: Result<DeviceParameters, Error>,
+        limit: ThermalLimit,
+        device_name: &str,
+    ) -> bool {
+        self.max_temperature = limit.max_temperature;
+        let parameters = match reading {
+            Ok(parameters) => parameters,
+            Err(e) => {
+                warn!(target: LOG_TARGET, "Failed to read {device_name} temperature, keeping {:?}: {e}", self.level);
+                return false;
+            }
+        };
+        let Some(temperature) = parameters.current_temperature else {
+            warn!(target: LOG_TARGET, "{device_name} has no readable temperature sensor, keeping {:?}", self.level);
+            self.parameters = Some(parameters);
+            return false;
+        };
+        let next_level = self.level.next(temperature, limit);
+        let is_changed = next_level != self.level;
+        if is_changed {
+            info!(target: LOG_TARGET, "{device_name} at {temperature:.1} °C | max: {:.1} °C | {:?} => {next_level:?}", limit.max_temperature, self.level);
+        }
+        self.level = next_level;
+        self.parameters = Some(parameters);
+        is_changed
+    }
+
+    fn status(&self, device_id: Option<u32>) -> DeviceThermalStatus {
+        DeviceThermalStatus {
+            device_id,
+            temperature: self
+                .parameters
+                .as_ref()
+                .and_then(|parameters| parameters.current_temperature),
+            max_temperature: self.max_temperature,
+            level: self.level,
+        }
+    }
+}
+
+/// Throttle levels of the cpu and of every gpu driven by temperature readings
+#[derive(Clone, Default)]
+pub struct ThermalGuard {
+    cpu: Option<DeviceThermalState>,
+    gpus: BTreeMap<u32, DeviceThermalState>,
+}
+
+impl ThermalGuard {
+    pub async fn evaluate(
+        &mut self,
+        config: &ConfigMiningContent,
+        cpu_reader: Option<&dyn CpuParametersReader>,
+        gpu_readers: &[(u32, Box<dyn GpuParametersReader>)],
+    ) -> ThermalGuardChanges {
+        let mut changes = ThermalGuardChanges::default();
+        if let Some(cpu_reader) = cpu_reader {
+            let state = self.cpu.get_or_insert_with(DeviceThermalState::new);
+            let reading = cpu_reader
+                .get_device_parameters(state.parameters.clone())
+                .await;
+            changes.is_cpu_changed = state.update(reading, *config.cpu_thermal_limit(), "CPU");
+        }
+        for (device_id, gpu_reader) in gpu_readers {
+            let state = self
+                .gpus
+                .entry(*device_id)
+                .or_insert_with(DeviceThermalState::new);
+            let reading = gpu_reader
+                .get_device_parameters(state.parameters.clone())
+                .await;
+            changes.is_gpu_changed |= state.update(
+                reading,
+                *config.get_gpu_thermal_limit(*device_id),
+                &format!("GPU {device_id}"),
+            );
+        }
+        changes
+    }
+
+    /// Lifts all throttling at once, used when the guard gets disabled
+    pub fn reset(&mut self) -> ThermalGuardChanges {
+        let mut changes = ThermalGuardChanges::default();
+        if let Some(state) = self.cpu.as_mut() {
+            changes.is_cpu_changed = state.level != ThermalThrottleLevel::Normal;
+            state.level = ThermalThrottleLevel::Normal;
+        }
+        for state in self.gpus.values_mut() {
+            changes.is_gpu_changed |= state.level != ThermalThrottleLevel::Normal;
+            state.level = ThermalThrottleLevel::Normal;
+        }
+        changes
+    }
+
+    pub fn cpu_level(&self) -> ThermalThrottleLevel {
+        self.cpu
+            .as_ref()
+            .map_or(ThermalThrottleLevel::Normal, |state| state.level)
+    }
+
+    /// Gpu miners take one intensity for all their devices, so the most throttled mining device decides
+    /// Stopped devices are excluded instead, the gpu is stopped only when none of the mining devices is left
+    pub fn gpu_level(&self, mining_devices: &[u32]) -> ThermalThrottleLevel {
+        let levels: Vec<ThermalThrottleLevel> = mining_devices
+            .iter()
+            .filter_map(|device_id| self.gpus.get(device_id).map(|state| state.level))
+            .collect();
+        if !levels.is_empty()
+            && levels
+                .iter()
+                .all(|level| *level == ThermalThrottleLevel::Stopped)
+        {
+            return ThermalThrottleLevel::Stopped;
+        }
+        levels
+            .into_iter()
+            .filter(|level| *level != ThermalThrottleLevel::Stopped)
+            .min_by_key(|level| level.usage_percentage())
+            .unwrap_or(ThermalThrottleLevel::Normal)
+    }
+
+    pub fn stopped_gpu_devices(&self) -> Vec<u32> {
+        self.gpus
+            .iter()
+            .filter(|(_, state)| state.level == ThermalThrottleLevel::Stopped)
+            .map(|(device_id, _)| *device_id)
+            .collect()
+    }
+
+    pub fn status(&self, is_enabled: bool) -> ThermalGuardStatus {
+        ThermalGuardStatus {
+            is_enabled,
+            cpu: self.cpu.as_ref().map(|state| state.status(None)),
+            gpus: self
+                .gpus
+                .iter()
+                .map(|(device_id, state)| state.status(Some(*device_id)))
+                .collect(),
+        }
+    }
+}
+
+/// Throttles and stops miners of devices which run too hot
+/// Miners stopped by the guard are started again once their devices cool down,
+/// unless mining was stopped in the meantime
+pub struct ThermalGuardManager {
+    guard: Mutex<ThermalGuard>,
+    is_cpu_resume_pending: AtomicBool,
+    is_gpu_resume_pending: AtomicBool,
+}
+
+impl ThermalGuardManager {
+    fn new() -> Self {
+        Self {
+            guard: Mutex::new(ThermalGuard::default()),
+            is_cpu_resume_pending: AtomicBool::new(false),
+            is_gpu_resume_pending: AtomicBool::new(false),
+        }
+    }
+
+    pub fn current() -> &'static ThermalGuardManager {
+        &INSTANCE
+    }
+
+    pub async fn spawn_monitoring_loop(app_handle: AppHandle) {
+        info!(target: LOG_TARGET, "Spawning thermal guard");
+        let mut shutdown_signal = TasksTrackers::current().common.get_signal().await;
+        if shutdown_signal.is_triggered() {
+            info!(target: LOG_TARGET, "Shutdown signal already triggered, exiting thermal guard");
+            return;
+        }
+
+        TasksTrackers::current()
+            .common
+            .get_task_tracker()
+            .await
+            .spawn(async move {
+                let mut evaluation_interval = interval(EVALUATION_INTERVAL);
+                loop {
+                    select! {
+                        _ = evaluation_interval.tick() => {
+                            Self::evaluate(&app_handle).await;
+                        }
+                        _ = shutdown_signal.wait() => {
+                            info!(target: LOG_TARGET, "Shutdown signal received, stopping thermal guard");
+                            break;
+                        }
+                    }
+                }
+            });
+    }
+
+    pub async fn evaluate(app_handle: &AppHandle) {
+        // Paused miners don't heat up, applying a level would resume them
+        if MiningPauseManager::current().is_paused().await {
+            return;
+        }
+
+        let config = ConfigMining::content().await;
+        let is_enabled = *config.thermal_guard_enabled();
+        let mut guard = INSTANCE.guard.lock().await;
+        let changes = if is_enabled {
+            let hardware_monitor = HardwareStatusMonitor::current();
+            let cpu_reader = hardware_monitor.get_cpu_parameters_reader().await;
+            let gpu_readers = hardware_monitor.get_gpu_parameters_readers().await;
+            guard
+                .evaluate(&config, cpu_reader.as_deref(), &gpu_readers)
+                .await
+        } else {
+            guard.reset()
+        };
+        let cpu_level = guard.cpu_level();
+        let gpu_level = guard.gpu_level(&config.get_mining_gpu_devices());
+        let status = guard.status(is_enabled);
+        drop(guard);
+
+        if changes.is_cpu_changed {
+            if let Err(e) = Self::apply_cpu_level(app_handle, &config, cpu_level).await {
+                error!(target: LOG_TARGET, "Failed to apply cpu throttle level {cpu_level:?}: {e}");
+            }
+        }
+        if changes.is_gpu_changed {
+            if let Err(e) = Self::apply_gpu_level(app_handle, gpu_level).await {
+                error!(target: LOG_TARGET, "Failed to apply gpu throttle level {gpu_level:?}: {e}");
+            }
+        }
+        Self::update_tray(app_handle, &status).await;
+        EventsEmitter::emit_thermal_guard_update(status).await;
+    }
+
+    /// Cpu usage to start xmrig with, `None` when the cpu is too hot to mine
+    pub async fn limit_cpu_usage(&self, usage_percentage: u32) -> Option<u32> {
+        let limited = self
+            .guard
+            .lock()
+            .await
+            .cpu_level()
+            .limit_usage(usage_percentage);
+        if limited.is_none() {
+            self.is_cpu_resume_pending.store(true, Ordering::SeqCst);
+        }
+        limited
+    }
+
+    /// Gpu usage to start the gpu miners with, `None` when all mining devices are too hot to mine
+    pub async fn limit_gpu_usage(&self, usage_percentage: u32) -> Option<u32> {
+        let mining_devices = ConfigMining::content().await.get_mining_gpu_devices();
+        let limited = self
+            .guard
+            .lock()
+            .await
+            .gpu_level(&mining_devices)
+            .limit_usage(usage_percentage);
+        if limited.is_none() {
+            self.is_gpu_resume_pending.store(true, Ordering::SeqCst);
+        }
+        limited
+    }
+
+    pub async fn stopped_gpu_devices(&self) -> Vec<u32> {
+        self.guard.lock().await.stopped_gpu_devices()
+    }
+
+    pub fn handle_cpu_mining_stopped(&self) {
+        self.is_cpu_resume_pending.store(false, Ordering::SeqCst);
+    }
+
+    pub fn handle_gpu_mining_stopped(&self) {
+        self.is_gpu_resume_pending.store(false, Ordering::SeqCst);
+    }
+
+    async fn apply_cpu_level(
+        app_handle: &AppHandle,
+        config: &ConfigMiningContent,
+        level: ThermalThrottleLevel,
+    ) -> Result<(), Error> {
+        let state = app_handle.state::<UniverseAppState>();
+        let is_running = state.cpu_miner.read().await.is_running().await;
+        let Some(usage_percentage) = level
+            .limit_usage(config.get_cpu_usage_percentage(
+                &IdleMiningMonitor::current().active_mining_mode(config),
+            ))
+        else {
+            if is_running {
+                warn!(target: LOG_TARGET, "CPU is too hot, stopping cpu mining until it cools down");
+                stop_cpu_mining(state, app_handle.clone())
+                    .await
+                    .map_err(Error::msg)?;
+                INSTANCE.is_cpu_resume_pending.store(true, Ordering::SeqCst);
+            }
+            return Ok(());
+        };
+
+        if INSTANCE.is_cpu_resume_pending.swap(false, Ordering::SeqCst) {
+            info!(target: LOG_TARGET, "CPU cooled down, starting cpu mining");
+            start_cpu_mining(state, app_handle.clone())
+                .await
+                .map_err(Error::msg)?;
+        } else if is_running {
+            let log_dir = app_handle.path().app_log_dir()?;
+            state
+                .cpu_miner
+                .read()
+                .await
+                .set_cpu_usage_percentage(usage_percentage, &log_dir)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn apply_gpu_level(
+        app_handle: &AppHandle,
+        level: ThermalThrottleLevel,
+    ) -> Result<(), Error> {
+        let is_mining = GpuManager::read().await.is_mining();
+        if level == ThermalThrottleLevel::Stopped {
+            if is_mining {
+                warn!(target: LOG_TARGET, "All mining GPUs are too hot, stopping gpu mining until they cool down");
+                stop_gpu_mining(app_handle.clone())
+                    .await
+                    .map_err(Error::msg)?;
+                INSTANCE.is_gpu_resume_pending.store(true, Ordering::SeqCst);
+            }
+            return Ok(());
+        }
+
+        let is_resume_pending = INSTANCE.is_gpu_resume_pending.swap(false, Ordering::SeqCst);
+        if !is_resume_pending && !is_mining {
+            return Ok(());
+        }
+        // Gpu miners pick up intensity and excluded devices only when started
+        if is_mining {
+            stop_gpu_mining(app_handle.clone())
+                .await
+                .map_err(Error::msg)?;
+        }
+        start_gpu_mining(app_handle.state::<UniverseAppState>(), app_handle.clone())
+            .await
+            .map_err(Error::msg)
+    }
+
+    async fn update_tray(app_handle: &AppHandle, status: &ThermalGuardStatus) {
+        let state = app_handle.state::<UniverseAppState>();
+        state
+            .systemtray_manager
+            .write()
+            .await
+            .update_tray_with_thermal_status(thermal_status_text(status));
+    }
+}
+
+fn thermal_status_text(status: &ThermalGuardStatus) -> String {
+    if !status.is_enabled {
+        return "Thermal guard: Off".to_string();
+    }
+    let devices: Vec<String> = status
+        .cpu
+        .iter()
+        .map(|cpu| ("CPU".to_string(), cpu))
+        .chain(
+            status
+                .gpus
+                .iter()
+                .map(|gpu| (format!("GPU {}", gpu.device_id.unwrap_or_default()), gpu)),
+        )
+        .filter_map(|(name, device)| match device.level {
+            ThermalThrottleLevel::Normal => None,
+            ThermalThrottleLevel::Throttled { usage_percentage } => {
+                Some(format!("{name} throttled to {usage_percentage}%"))
+            }
+            ThermalThrottleLevel::Stopped => Some(format!("{name} stopped")),
+        })
+        .collect();
+    if devices.is_empty() {
+        "Thermal guard: OK".to_string()
+    } else {
+        format!("Thermal guard: {}", devices.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use std::{
+        collections::{HashMap, VecDeque},
+        sync::{Arc, Mutex as StdMutex},
+    };
+
+    use async_trait::async_trait;
+
+    use super::*;
+
+    /// Returns the queued temperatures one by one, `None` simulates a failed reading
+    #[derive(Clone)]
+    struct MockReader {
+        temperatures: Arc<StdMutex<VecDeque<Option<f32>>>>,
+    }
+
+    impl MockReader {
+        fn new(temperatures: &[Option<f32>]) -> Self {
+            Self {
+                temperatures: Arc::new(StdMutex::new(temperatures.iter().copied().collect())),
+            }
+        }
+
+        fn read(&self) -> Result<DeviceParameters, Error> {
+            let temperature = self
+                .temperatures
+                .lock()
+                .unwrap()
+                .pop_front()
+                .flatten()
+                .ok_or_else(|| anyhow::anyhow!("No reading"))?;
+            Ok(DeviceParameters {
+                usage_percentage: 100.0,
+                current_temperature: Some(temperature),
+                max_temperature: temperature,
+                ..Default::default()
+            })
+        }
+    }
+
+    #[async_trait]
+    impl CpuParametersReader for MockReader {
+        async fn get_device_parameters(
+            &self,
+            _old_device_parameters: Option<DeviceParameters>,
+        ) -> Result<DeviceParameters, Error> {
+            self.read()
+        }
+        fn get_is_reader_implemented(&self) -> bool {
+            true
+        }
+    }
+
+    #[async_trait]
+    impl GpuParametersReader for MockReader {
+        async fn get_device_parameters(
+            &self,
+            _old_device_parameters: Option<DeviceParameters>,
+        ) -> Result<DeviceParameters, Error> {
+            self.read()
+        }
+        fn get_is_reader_implemented(&self) -> bool {
+            true
+        }
+    }
+
+    fn config() -> ConfigMiningContent {
+        let mut config = ConfigMiningContent::default();
+        config
+            .set_thermal_guard_enabled(true)
+            .set_cpu_thermal_limit(ThermalLimit::new(80.0, 10.0))
+            .set_gpu_thermal_limit(ThermalLimit::new(80.0, 10.0))
+            .set_gpu_devices_thermal_limits(HashMap::from([(1, ThermalLimit::new(70.0, 5.0))]));
+        config
+    }
+
+    async fn feed_cpu(temperatures: &[Option<f32>]) -> Vec<ThermalThrottleLevel> {
+        let config = config();
+        let reader = MockReader::new(temperatures);
+        let mut guard = ThermalGuard::default();
+        let mut levels = vec![];
+        for _ in temperatures {
+            guard.evaluate(&config, Some(&reader), &[]).await;
+            levels.push(guard.cpu_level());
+        }
+        levels
+    }
+
+    fn throttled(usage_percentage: u32) -> ThermalThrottleLevel {
+        ThermalThrottleLevel::Throttled { usage_percentage }
+    }
+
+    #[tokio::test]
+    async fn cpu_is_throttled_step_by_step_and_stopped() {
+        let levels = feed_cpu(&[Some(60.0), Some(80.0), Some(82.0), Some(85.0), Some(85.0)]).await;
+        assert_eq!(
+            levels,
+            vec![
+                ThermalThrottleLevel::Normal,
+                throttled(75),
+                throttled(50),
+                throttled(25),
+                ThermalThrottleLevel::Stopped,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn cpu_recovers_only_below_hysteresis() {
+        let levels = feed_cpu(&[
+            Some(81.0),
+            Some(81.0),
+            Some(75.0),
+            Some(71.0),
+            Some(70.0),
+            Some(65.0),
+            Some(60.0),
+        ])
+        .await;
+        assert_eq!(
+            levels,
+            vec![
+                throttled(75),
+                throttled(50),
+                throttled(50),
+                throttled(50),
+                throttled(75),
+                ThermalThrottleLevel::Normal,
+                ThermalThrottleLevel::Normal,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn critical_temperature_stops_right_away_and_failed_readings_keep_level() {
+        let levels = feed_cpu(&[Some(95.0), None, Some(60.0), None]).await;
+        assert_eq!(
+            levels,
+            vec![
+                ThermalThrottleLevel::Stopped,
+                ThermalThrottleLevel::Stopped,
+                throttled(25),
+                throttled(25),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn gpus_use_own_limits_and_stop_only_hot_devices() {
+        let config = config();
+        let gpu_readers: Vec<(u32, Box<dyn GpuParametersReader>)> = vec![
+            (0, Box::new(MockReader::new(&[Some(75.0), Some(75.0)]))),
+            (1, Box::new(MockReader::new(&[Some(75.0), Some(81.0)]))),
+        ];
+        let mut guard = ThermalGuard::default();
+
+        let changes = guard.evaluate(&config, None, &gpu_readers).await;
+        assert_eq!(
+            changes,
+            ThermalGuardChanges {
+                is_cpu_changed: false,
+                is_gpu_changed: true,
+            }
+        );
+        assert_eq!(guard.gpu_level(&[0, 1]), throttled(75));
+        assert_eq!(guard.gpu_level(&[0]), ThermalThrottleLevel::Normal);
+
+        guard.evaluate(&config, None, &gpu_readers).await;
+        assert_eq!(guard.stopped_gpu_devices(), vec![1]);
+        assert_eq!(guard.gpu_level(&[0, 1]), ThermalThrottleLevel::Normal);
+        assert_eq!(guard.gpu_level(&[1]), ThermalThrottleLevel::Stopped);
+
+        assert_eq!(
+            guard.reset(),
+            ThermalGuardChanges {
+                is_cpu_changed: false,
+                is_gpu_changed: true,
+            }
+        );
+        assert!(guard.stopped_gpu_devices().is_empty());
+    }
+
+    #[test]
+    fn throttling_limits_mining_mode_usage() {
+        assert_eq!(ThermalThrottleLevel::Normal.limit_usage(85), Some(85));
+        assert_eq!(throttled(50).limit_usage(85), Some(42));
+        assert_eq!(throttled(25).limit_usage(1), Some(1));
+        assert_eq!(ThermalThrottleLevel::Stopped.limit_usage(85), None);
+    }
+}
@@ -48,6 +48,7 @@ use tokio::sync::{OnceCell, RwLock};
 
 use tari_utilities::hex::Hex;
 
+use crate::configs::config_pools::{ConfigPools, ConfigPoolsContent};
 use crate::configs::config_ui::ConfigUI;
 use crate::configs::config_wallet::{ConfigWallet, ConfigWalletContent, WalletId, WALLET_VERSION};
 use crate::configs::trait_config::ConfigImpl;
@@ -59,7 +60,7 @@ use crate::events::CriticalProblemPayload;
 use crate::events_emitter::EventsEmitter;
 use crate::mining::pools::cpu_pool_manager::CpuPoolManager;
 use crate::mining::pools::gpu_pool_manager::GpuPoolManager;
-use crate::mining::pools::PoolManagerInterfaceTrait;
+use crate::mining::pools::{refresh_tracked_mining_addresses, PoolManagerInterfaceTrait};
 use crate::pin::PinManager;
 use crate::utils::{cryptography, rand_utils};
 use crate::UniverseAppState;
@@ -129,6 +130,8 @@ impl InternalWallet {
                 external_tari_address.clone(),
             )
             .await?;
+            // The address book entry is replaced
+            refresh_tracked_mining_addresses().await;
         }
 
         let wallet_config = ConfigWallet::content().await;
@@ -309,6 +312,16 @@ impl InternalWallet {
         CpuPoolManager::handle_wallet_address_change(self.extract_tari_address()).await;
         GpuPoolManager::handle_wallet_address_change(self.extract_tari_address()).await;
 
+        if let Err(e) = ConfigPools::update_field(
+            ConfigPoolsContent::add_used_mining_address,
+            self.extract_tari_address().to_base58(),
+        )
+        .await
+        {
+            log::warn!(target: LOG_TARGET, "Failed to store used mining address: {e}");
+        }
+        refresh_tracked_mining_addresses().await;
+
         log::info!(
             "Wallet with {} address initialized successfully",
             self.tari_address_type.clone()
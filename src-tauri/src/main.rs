@@ -32,13 +32,14 @@ use mining_status_manager::MiningStatusManager;
 use node::local_node_adapter::LocalNodeAdapter;
 use node::node_adapter::BaseNodeStatus;
 use node::node_manager::NodeType;
+use node::node_snapshot;
 use process_stats_collector::ProcessStatsCollectorBuilder;
 
 use node::remote_node_adapter::RemoteNodeAdapter;
 
 use setup::setup_manager::SetupManager;
 use std::fs::{remove_dir_all, remove_file};
-use std::path::Path;
+use std::path::PathBuf;
 use systemtray_manager::SystemTrayManager;
 use tasks_tracker::TasksTrackers;
 use tauri_plugin_cli::CliExt;
@@ -51,7 +52,6 @@ use websocket_events_manager::WebsocketEventsManager;
 use websocket_manager::{WebsocketManager, WebsocketManagerStatusMessage, WebsocketMessage};
 
 use log4rs::config::RawConfig;
-use std::fs;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tari_common::configuration::Network;
@@ -69,14 +69,20 @@ use telemetry_manager::TelemetryManager;
 use crate::cpu_miner::CpuMiner;
 
 use crate::feedback::Feedback;
+use crate::hardware::thermal_guard::ThermalGuardManager;
 use crate::mining::cpu::CpuMinerConnection;
 use crate::mining::gpu::consts::GpuMinerStatus;
 use crate::mining::gpu::manager::GpuManager;
+use crate::mining::idle_monitor::IdleMiningMonitor;
+use crate::mining::profit_switcher::ProfitSwitcher;
+use crate::mining::scheduler::MiningScheduler;
 use crate::mm_proxy_manager::MmProxyManager;
 use crate::node::node_manager::NodeManager;
+use crate::process_resource_limits::ProcessResourceLimits;
 use crate::tor_manager::TorManager;
 use crate::wallet::wallet_manager::WalletManager;
 use crate::wallet::wallet_types::WalletState;
+use crate::xmrig::config::XmrigConfig;
 
 mod ab_test_selector;
 mod airdrop;
@@ -106,6 +112,9 @@ mod port_allocator;
 mod process_adapter;
 mod process_adapter_utils;
 mod process_killer;
+mod process_output_buffer;
+mod process_resource_limits;
+mod process_restart_policy;
 mod process_stats_collector;
 mod process_utils;
 mod process_watcher;
@@ -264,6 +273,8 @@ fn main() {
         monero_address: "".to_string(),
         pool_status_url: None,
         worker_name: None,
+        xmrig_config: XmrigConfig::default(),
+        resource_limits: ProcessResourceLimits::default(),
     }));
 
     let app_in_memory_config = Arc::new(RwLock::new(AppInMemoryConfig::default()));
@@ -409,54 +420,30 @@ fn main() {
             app.manage(app_state_clone);
             match app.cli().matches() {
                 Ok(matches) => {
-                    if let Some(backup_path) = matches.args.get("import-backup") {
-                        if let Some(backup_path) = backup_path.value.as_str() {
-                            info!(
-                                target: LOG_TARGET,
-                                "Trying to copy backup to existing db: {backup_path:?}"
-                            );
-                            let backup_path = Path::new(backup_path);
-                            if backup_path.exists() {
-                                let existing_db = app
-                                    .path()
-                                    .app_local_data_dir()
-                                    .map_err(Box::new)?
-                                    .join("node")
-                                    .join(
-                                        Network::get_current_or_user_setting_or_default()
-                                            .to_string(),
-                                    )
-                                    .join("data")
-                                    .join("base_node")
-                                    .join("db");
-
-                                info!(target: LOG_TARGET, "Existing db path: {existing_db:?}");
-                                let _unused = fs::remove_dir_all(&existing_db).inspect_err(|e| {
-                                    warn!(
-                                        target: LOG_TARGET,
-                                        "Could not remove existing db when importing backup: {e:?}"
-                                    )
-                                });
-                                let _unused = fs::create_dir_all(&existing_db).inspect_err(|e| {
-                                    error!(
-                                        target: LOG_TARGET,
-                                        "Could not create existing db when importing backup: {e:?}"
-                                    )
-                                });
-                                let _unused = fs::copy(backup_path, existing_db.join("data.mdb"))
-                                    .inspect_err(|e| {
-                                        error!(
-                                            target: LOG_TARGET,
-                                            "Could not copy backup to existing db: {e:?}"
-                                        )
-                                    });
-                            } else {
-                                warn!(
-                                    target: LOG_TARGET,
-                                    "Backup file does not exist: {backup_path:?}"
-                                );
-                            }
-                        }
+                    if let Some(snapshot_path) = matches
+                        .args
+                        .get("import-snapshot")
+                        .and_then(|arg| arg.value.as_str())
+                    {
+                        info!(target: LOG_TARGET, "Node snapshot import requested: {snapshot_path:?}");
+                        node_snapshot::request_import(PathBuf::from(snapshot_path));
+                    }
+                    // Kept for existing scripts, it takes a bare data.mdb without a manifest
+                    if let Some(backup_path) = matches
+                        .args
+                        .get("import-backup")
+                        .and_then(|arg| arg.value.as_str())
+                    {
+                        info!(target: LOG_TARGET, "Node database import requested: {backup_path:?}");
+                        node_snapshot::request_database_import(PathBuf::from(backup_path));
+                    }
+                    if let Some(destination_dir) = matches
+                        .args
+                        .get("export-snapshot")
+                        .and_then(|arg| arg.value.as_str())
+                    {
+                        info!(target: LOG_TARGET, "Node snapshot export requested: {destination_dir:?}");
+                        node_snapshot::request_export(PathBuf::from(destination_dir));
                     }
                 }
                 Err(e) => {
@@ -553,6 +540,33 @@ fn main() {
             commands::proceed_with_update,
             commands::set_pre_release,
             commands::toggle_device_exclusion,
+            commands::assign_gpu_device_miner,
+            commands::start_gpu_benchmark,
+            commands::cancel_gpu_benchmark,
+            commands::set_profit_switching_enabled,
+            commands::set_profit_switching_settings,
+            commands::get_profit_switch_decisions,
+            commands::pause_mining,
+            commands::resume_mining,
+            commands::get_mining_pause_status,
+            commands::set_mining_schedule,
+            commands::set_idle_mining,
+            commands::set_thermal_guard_settings,
+            commands::get_gpu_devices_telemetry,
+            commands::get_process_output_tail,
+            commands::set_process_resource_limits,
+            commands::get_remote_node_probes,
+            commands::set_remote_base_nodes,
+            commands::test_external_node_connection,
+            commands::set_external_node,
+            commands::get_node_disk_usage,
+            commands::set_node_storage,
+            commands::export_node_snapshot,
+            commands::import_node_snapshot,
+            commands::get_explorer_blocks,
+            commands::get_explorer_block_by_hash,
+            commands::get_mempool_stats,
+            commands::set_xmrig_config,
             commands::set_airdrop_tokens,
             commands::get_airdrop_tokens,
             commands::set_selected_engine,
@@ -586,6 +600,20 @@ fn main() {
             commands::update_selected_cpu_pool_config,
             commands::reset_gpu_pool_config,
             commands::reset_cpu_pool_config,
+            commands::add_custom_cpu_pool,
+            commands::update_custom_cpu_pool,
+            commands::remove_custom_cpu_pool,
+            commands::add_custom_gpu_pool,
+            commands::update_custom_gpu_pool,
+            commands::remove_custom_gpu_pool,
+            commands::set_cpu_pools_failover,
+            commands::set_gpu_pools_failover,
+            commands::set_gpu_miner_pool,
+            commands::toggle_pool_failover,
+            commands::toggle_switch_back_to_primary_pool,
+            commands::get_pools_health,
+            commands::get_pool_earnings_history,
+            commands::get_pools_address_statuses,
             commands::restart_phases,
             commands::list_connected_peers,
             commands::switch_gpu_miner,
@@ -630,6 +658,10 @@ fn main() {
                         .start_setup(handle_clone.clone())
                         .await;
                     SetupManager::spawn_sleep_mode_handler().await;
+                    ProfitSwitcher::spawn_evaluation_loop(handle_clone.clone()).await;
+                    MiningScheduler::spawn_evaluation_loop(handle_clone.clone()).await;
+                    IdleMiningMonitor::spawn_idle_detection_loop(handle_clone.clone()).await;
+                    ThermalGuardManager::spawn_monitoring_loop(handle_clone.clone()).await;
                 });
             }
             tauri::RunEvent::ExitRequested { api: _, code, .. } => {
@@ -0,0 +1,533 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        LazyLock,
+    },
+    time::{Duration, SystemTime},
+};
+
+use log::{error, info, warn};
+use tari_shutdown::{Shutdown, ShutdownSignal};
+use tokio::{
+    select,
+    sync::{
+        watch::{Receiver, Sender},
+        Mutex,
+    },
+    time::{interval_at, sleep, Instant},
+};
+
+use crate::{
+    configs::{
+        config_mining::{ConfigMining, ConfigMiningContent},
+        trait_config::ConfigImpl,
+    },
+    events::GpuBenchmarkProgressPayload,
+    events_emitter::EventsEmitter,
+    mining::gpu::{
+        consts::{
+            EngineType, GpuConnectionType, GpuDeviceMinerRecommendation, GpuMinerFeature,
+            GpuMinerStatus, GpuMinerType,
+        },
+        interface::GpuMinerInterfaceTrait,
+        manager::GpuManager,
+    },
+    node::node_adapter::BaseNodeStatus,
+    process_watcher::{ProcessWatcher, ProcessWatcherStats},
+    tasks_tracker::TasksTrackers,
+    utils::math_utils::estimate_earning,
+};
+
+static LOG_TARGET: &str = "tari::mining::gpu::benchmark";
+static INSTANCE: LazyLock<GpuBenchmark> = LazyLock::new(GpuBenchmark::new);
+
+/// Miners need some time to prepare kernels and connect before their hashrate is stable
+const WARMUP_DURATION: Duration = Duration::from_secs(20);
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
+const MIN_RUN_DURATION: Duration = Duration::from_secs(15);
+/// Miners without hashrate for 3 minutes are marked as unhealthy, runs have to stay well below that
+const MAX_RUN_DURATION: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Clone)]
+struct BenchmarkRun {
+    miner: GpuMinerType,
+    engine: Option<EngineType>,
+}
+
+pub struct GpuBenchmarkParams {
+    pub gpu_usage_percentage: u32,
+    pub base_path: PathBuf,
+    pub config_path: PathBuf,
+    pub log_path: PathBuf,
+}
+
+/// Runs every available miner ( and every engine for miners with engine selection ) one after another
+/// Miners run in their benchmark mode, so nothing is submitted to pools or the node
+/// Hashrate is measured per device and converted to expected earnings, the best miner for each device
+/// is stored in `ConfigMining` as a recommendation
+pub struct GpuBenchmark {
+    is_running: AtomicBool,
+    cancel: Mutex<Shutdown>,
+}
+
+impl GpuBenchmark {
+    fn new() -> Self {
+        Self {
+            is_running: AtomicBool::new(false),
+            cancel: Mutex::new(Shutdown::new()),
+        }
+    }
+
+    pub fn is_running() -> bool {
+        INSTANCE.is_running.load(Ordering::SeqCst)
+    }
+
+    pub async fn start(params: GpuBenchmarkParams) -> Result<(), anyhow::Error> {
+        if GpuManager::read().await.is_mining() {
+            return Err(anyhow::anyhow!(
+                "Gpu benchmark can't run while gpu mining is active"
+            ));
+        }
+        if INSTANCE.is_running.swap(true, Ordering::SeqCst) {
+            return Err(anyhow::anyhow!("Gpu benchmark is already running"));
+        }
+
+        let runs = Self::resolve_runs().await;
+        if runs.is_empty() {
+            INSTANCE.is_running.store(false, Ordering::SeqCst);
+            return Err(anyhow::anyhow!("No gpu miners available to benchmark"));
+        }
+
+        let mut cancel_signal = {
+            let mut cancel = INSTANCE.cancel.lock().await;
+            *cancel = Shutdown::new();
+            cancel.to_signal()
+        };
+        let run_duration =
+            Duration::from_secs(*ConfigMining::content().await.gpu_benchmark_duration_secs())
+                .clamp(MIN_RUN_DURATION, MAX_RUN_DURATION);
+        info!(target: LOG_TARGET, "Starting gpu benchmark with {} runs of {}s", runs.len(), run_duration.as_secs());
+
+        TasksTrackers::current()
+            .gpu_mining_phase
+            .get_task_tracker()
+            .await
+            .spawn(async move {
+                let measurements =
+                    Self::run_all(&runs, &params, run_duration, &mut cancel_signal).await;
+                if cancel_signal.is_triggered() {
+                    info!(target: LOG_TARGET, "Gpu benchmark cancelled, recommendations are not updated");
+                } else if let Err(e) = Self::save_recommendations(measurements).await {
+                    error!(target: LOG_TARGET, "Failed to save gpu benchmark results: {e}");
+                }
+                INSTANCE.is_running.store(false, Ordering::SeqCst);
+                EventsEmitter::emit_gpu_benchmark_progress(GpuBenchmarkProgressPayload::default())
+                    .await;
+            });
+
+        Ok(())
+    }
+
+    pub async fn cancel() {
+        if Self::is_running() {
+            info!(target: LOG_TARGET, "Cancelling gpu benchmark");
+            INSTANCE.cancel.lock().await.trigger();
+        }
+    }
+
+    async fn resolve_runs() -> Vec<BenchmarkRun> {
+        let mut runs = vec![];
+        for miner in GpuManager::read().await.get_healthy_miners() {
+            if miner
+                .get_expected_features()
+                .contains(&GpuMinerFeature::EngineSelection)
+            {
+                runs.extend(EngineType::supported_on_current_platform().into_iter().map(
+                    |engine| BenchmarkRun {
+                        miner: miner.clone(),
+                        engine: Some(engine),
+                    },
+                ));
+            } else {
+                runs.push(BenchmarkRun {
+                    miner,
+                    engine: None,
+                });
+            }
+        }
+        runs
+    }
+
+    async fn run_all(
+        runs: &[BenchmarkRun],
+        params: &GpuBenchmarkParams,
+        run_duration: Duration,
+        cancel_signal: &mut ShutdownSignal,
+    ) -> Vec<GpuDeviceMinerRecommendation> {
+        let mut measurements = vec![];
+        for (completed_runs, run) in runs.iter().enumerate() {
+            if cancel_signal.is_triggered() {
+                break;
+            }
+            EventsEmitter::emit_gpu_benchmark_progress(GpuBenchmarkProgressPayload {
+                is_running: true,
+                current_miner: Some(run.miner.clone()),
+                current_engine: run.engine.clone(),
+                completed_runs,
+                total_runs: runs.len(),
+            })
+            .await;
+
+            info!(target: LOG_TARGET, "Benchmarking gpu miner {} with engine {:?}", run.miner, run.engine);
+            match Self::run(run, params, run_duration, cancel_signal).await {
+                Ok(run_measurements) => measurements.extend(run_measurements),
+                Err(e) => {
+                    warn!(target: LOG_TARGET, "Gpu benchmark of {} with engine {:?} failed: {e}", run.miner, run.engine);
+                }
+            }
+        }
+        measurements
+    }
+
+    async fn run(
+        run: &BenchmarkRun,
+        params: &GpuBenchmarkParams,
+        run_duration: Duration,
+        cancel_signal: &mut ShutdownSignal,
+    ) -> Result<Vec<GpuDeviceMinerRecommendation>, anyhow::Error> {
+        let config_mining = ConfigMining::content().await;
+        let status_channel = Sender::new(GpuMinerStatus::default());
+        let status_reciever = status_channel.subscribe();
+
+        let mut adapter = GpuManager::create_miner_interface(&run.miner, status_channel);
+        if let Some(engine) = &run.engine {
+            adapter.load_gpu_engine(engine.clone()).await?;
+        }
+        adapter.detect_devices().await?;
+        adapter
+            .load_connection_type(GpuConnectionType::Benchmark)
+            .await?;
+        adapter
+            .load_intensity_percentage(params.gpu_usage_percentage)
+            .await?;
+        // Benchmarked miner acts as the primary one so only devices excluded by the user are skipped
+        adapter
+            .load_excluded_devices(config_mining.get_excluded_devices_for_miner(
+                &run.miner,
+                &run.miner,
+                std::slice::from_ref(&run.miner),
+            ))
+            .await?;
+
+        let mut shutdown_signal = TasksTrackers::current().gpu_mining_phase.get_signal().await;
+        let mut process_watcher =
+            ProcessWatcher::new(adapter, Sender::new(ProcessWatcherStats::default()));
+        process_watcher
+            .start(
+                params.base_path.clone(),
+                params.config_path.clone(),
+                params.log_path.clone(),
+                GpuManager::resolve_miner_binary(&run.miner),
+                shutdown_signal.clone(),
+                TasksTrackers::current()
+                    .gpu_mining_phase
+                    .get_task_tracker()
+                    .await,
+            )
+            .await?;
+
+        let samples = Self::collect_samples(
+            status_reciever,
+            run_duration,
+            cancel_signal,
+            &mut shutdown_signal,
+        )
+        .await;
+
+        process_watcher.status_monitor = None;
+        process_watcher.stop().await?;
+
+        let node_status = GpuManager::read().await.get_node_status();
+        Ok(Self::measure_devices(
+            run,
+            &samples,
+            &config_mining.get_mining_gpu_devices(),
+            node_status.as_ref(),
+        ))
+    }
+
+    async fn collect_samples(
+        status_reciever: Receiver<GpuMinerStatus>,
+        run_duration: Duration,
+        cancel_signal: &mut ShutdownSignal,
+        shutdown_signal: &mut ShutdownSignal,
+    ) -> Vec<GpuMinerStatus> {
+        let mut samples = vec![];
+        let mut sample_interval = interval_at(Instant::now() + WARMUP_DURATION, SAMPLE_INTERVAL);
+        let run_end = sleep(WARMUP_DURATION + run_duration);
+        tokio::pin!(run_end);
+
+        loop {
+            select! {
+                _ = cancel_signal.wait() => break,
+                _ = shutdown_signal.wait() => break,
+                _ = &mut run_end => break,
+                _ = sample_interval.tick() => {
+                    let status = status_reciever.borrow().clone();
+                    if status.is_mining && status.hash_rate > 0.0 {
+                        samples.push(status);
+                    }
+                }
+            }
+        }
+        samples
+    }
+
+    /// Averages the hashrate of every device over the collected samples
+    /// Miners without per device stats have their total hashrate split evenly between the mining devices
+    fn measure_devices(
+        run: &BenchmarkRun,
+        samples: &[GpuMinerStatus],
+        mining_devices: &[u32],
+        node_status: Option<&BaseNodeStatus>,
+    ) -> Vec<GpuDeviceMinerRecommendation> {
+        let Some(algorithm) = run.miner.supported_algorithms().into_iter().next() else {
+            return vec![];
+        };
+        if samples.is_empty() {
+            return vec![];
+        }
+
+        let mut device_hashrates: BTreeMap<u32, f64> = BTreeMap::new();
+        for sample in samples {
+            if sample.devices.is_empty() {
+                let device_share = sample.hash_rate / mining_devices.len().max(1) as f64;
+                for device_id in mining_devices {
+                    *device_hashrates.entry(*device_id).or_default() += device_share;
+                }
+            } else {
+                for device in &sample.devices {
+                    *device_hashrates.entry(device.device_id).or_default() += device.hash_rate;
+                }
+            }
+        }
+
+        let network_hashrate = node_status.and_then(|status| algorithm.network_hashrate(status));
+        device_hashrates
+            .into_iter()
+            .map(|(device_id, total_hash_rate)| {
+                let hash_rate = total_hash_rate / samples.len() as f64;
+                GpuDeviceMinerRecommendation {
+                    device_id,
+                    miner: run.miner.clone(),
+                    algorithm: algorithm.clone(),
+                    engine: run.engine.clone(),
+                    hash_rate,
+                    estimated_daily_earnings: node_status.zip(network_hashrate).map(
+                        |(status, network_hashrate)| {
+                            estimate_earning(network_hashrate, hash_rate, status.block_reward)
+                        },
+                    ),
+                }
+            })
+            .collect()
+    }
+
+    /// Picks the measurement with the highest estimated earnings for every device
+    /// Without both estimates hashrates can only be compared within the same algorithm,
+    /// otherwise the earlier run wins as runs follow `MINERS_PRIORITY`
+    fn recommend_miners(
+        measurements: &[GpuDeviceMinerRecommendation],
+    ) -> HashMap<u32, GpuDeviceMinerRecommendation> {
+        let mut recommendations: HashMap<u32, GpuDeviceMinerRecommendation> = HashMap::new();
+        for measurement in measurements.iter().filter(|m| m.hash_rate > 0.0) {
+            let is_better = match recommendations.get(&measurement.device_id) {
+                None => true,
+                Some(current) => Self::is_better_measurement(measurement, current),
+            };
+            if is_better {
+                recommendations.insert(measurement.device_id, measurement.clone());
+            }
+        }
+        recommendations
+    }
+
+    fn is_better_measurement(
+        measurement: &GpuDeviceMinerRecommendation,
+        current: &GpuDeviceMinerRecommendation,
+    ) -> bool {
+        match (
+            measurement.estimated_daily_earnings,
+            current.estimated_daily_earnings,
+        ) {
+            (Some(earnings), Some(current_earnings)) => earnings > current_earnings,
+            // Hashrates of different algorithms aren't comparable
+            _ => {
+                measurement.algorithm == current.algorithm
+                    && measurement.hash_rate > current.hash_rate
+            }
+        }
+    }
+
+    async fn save_recommendations(
+        measurements: Vec<GpuDeviceMinerRecommendation>,
+    ) -> Result<(), anyhow::Error> {
+        if measurements.is_empty() {
+            return Err(anyhow::anyhow!("No gpu miner reported any hashrate"));
+        }
+
+        let recommendations = Self::recommend_miners(&measurements);
+        for recommendation in recommendations.values() {
+            info!(target: LOG_TARGET, "Recommended gpu miner for device {}: {} ( engine: {:?}, hashrate: {:.2}, estimated daily earnings: {:?} )",
+                recommendation.device_id,
+                recommendation.miner,
+                recommendation.engine,
+                recommendation.hash_rate,
+                recommendation.estimated_daily_earnings
+            );
+        }
+
+        if measurements
+            .iter()
+            .any(|measurement| measurement.miner == GpuMinerType::LolMiner)
+        {
+            ConfigMining::update_field(ConfigMiningContent::set_is_lolminer_tested, true).await?;
+        }
+        ConfigMining::update_field(ConfigMiningContent::set_gpu_benchmark_results, measurements)
+            .await?;
+        ConfigMining::update_field(
+            ConfigMiningContent::set_gpu_devices_recommendations,
+            recommendations,
+        )
+        .await?;
+        ConfigMining::update_field(
+            ConfigMiningContent::set_gpu_benchmark_completed_at,
+            Some(SystemTime::now()),
+        )
+        .await?;
+        EventsEmitter::emit_mining_config_loaded(&ConfigMining::content().await).await;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn measurement(
+        device_id: u32,
+        miner: GpuMinerType,
+        hash_rate: f64,
+        estimated_daily_earnings: Option<u64>,
+    ) -> GpuDeviceMinerRecommendation {
+        GpuDeviceMinerRecommendation {
+            device_id,
+            algorithm: miner.supported_algorithms().first().cloned().unwrap(),
+            miner,
+            engine: None,
+            hash_rate,
+            estimated_daily_earnings,
+        }
+    }
+
+    #[test]
+    fn recommends_highest_earnings_per_device() {
+        let measurements = vec![
+            measurement(0, GpuMinerType::Graxil, 100.0, Some(10)),
+            measurement(1, GpuMinerType::Graxil, 100.0, Some(10)),
+            measurement(0, GpuMinerType::Glytex, 150.0, Some(15)),
+            measurement(1, GpuMinerType::Glytex, 50.0, Some(5)),
+        ];
+
+        let recommendations = GpuBenchmark::recommend_miners(&measurements);
+
+        assert_eq!(recommendations[&0].miner, GpuMinerType::Glytex);
+        assert_eq!(recommendations[&1].miner, GpuMinerType::Graxil);
+    }
+
+    #[test]
+    fn unknown_earnings_only_compare_hashrate_of_same_algorithm() {
+        let measurements = vec![
+            measurement(0, GpuMinerType::LolMiner, 5.0, None),
+            measurement(0, GpuMinerType::Graxil, 1000.0, None),
+            measurement(1, GpuMinerType::Graxil, 0.0, Some(10)),
+            measurement(1, GpuMinerType::LolMiner, 5.0, None),
+        ];
+
+        let recommendations = GpuBenchmark::recommend_miners(&measurements);
+
+        assert_eq!(recommendations[&0].miner, GpuMinerType::LolMiner);
+        assert_eq!(recommendations[&1].miner, GpuMinerType::LolMiner);
+    }
+
+    #[test]
+    fn estimate_of_one_algorithm_does_not_beat_unknown_estimate_of_another() {
+        let measurements = vec![
+            measurement(0, GpuMinerType::LolMiner, 5.0, None),
+            measurement(0, GpuMinerType::Graxil, 1000.0, Some(10)),
+            measurement(1, GpuMinerType::Graxil, 1000.0, Some(10)),
+            measurement(1, GpuMinerType::LolMiner, 5.0, None),
+        ];
+
+        let recommendations = GpuBenchmark::recommend_miners(&measurements);
+
+        assert_eq!(recommendations[&0].miner, GpuMinerType::LolMiner);
+        assert_eq!(recommendations[&1].miner, GpuMinerType::Graxil);
+    }
+
+    #[test]
+    fn miners_without_device_stats_split_hashrate_evenly() {
+        let run = BenchmarkRun {
+            miner: GpuMinerType::Glytex,
+            engine: Some(EngineType::OpenCL),
+        };
+        let samples = vec![
+            GpuMinerStatus {
+                is_mining: true,
+                hash_rate: 100.0,
+                ..Default::default()
+            },
+            GpuMinerStatus {
+                is_mining: true,
+                hash_rate: 300.0,
+                ..Default::default()
+            },
+        ];
+
+        let measurements = GpuBenchmark::measure_devices(&run, &samples, &[0, 1], None);
+
+        assert_eq!(measurements.len(), 2);
+        assert!(measurements
+            .iter()
+            .all(|m| (m.hash_rate - 100.0).abs() < f64::EPSILON));
+        assert!(measurements
+            .iter()
+            .all(|m| m.estimated_daily_earnings.is_none()));
+    }
+}
@@ -25,7 +25,8 @@ use std::fmt::Display;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    configs::pools::gpu_pools::GpuPool,
+    configs::pools::{gpu_pools::GpuPool, BasePoolData},
+    node::node_adapter::BaseNodeStatus,
     utils::platform_utils::{CurrentOperatingSystem, PlatformUtils},
 };
 
@@ -56,6 +57,14 @@ impl EngineType {
             _ => Err(anyhow::anyhow!("Invalid engine type")),
         }
     }
+
+    /// Engines glytex can be started with on the current platform
+    pub fn supported_on_current_platform() -> Vec<EngineType> {
+        match PlatformUtils::detect_current_os() {
+            CurrentOperatingSystem::MacOS => vec![EngineType::OpenCL, EngineType::Metal],
+            _ => vec![EngineType::OpenCL, EngineType::Cuda],
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Clone, Default)]
@@ -63,12 +72,81 @@ pub(crate) struct GpuMinerStatus {
     pub is_mining: bool,
     pub hash_rate: f64,
     pub estimated_earnings: u64,
+    pub devices: Vec<GpuDeviceMiningStatus>,
+    // Hashrates of different algorithms aren't comparable, so they are reported separately
+    pub algorithms: Vec<GpuAlgorithmMiningStatus>,
+}
+
+/// Combined stats of all miners mining the same algorithm
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct GpuAlgorithmMiningStatus {
+    pub algorithm: GpuMiningAlgorithm,
+    pub hash_rate: f64,
+    pub estimated_earnings: u64,
+}
+
+/// Mining stats of a single device, values the miner doesn't report are left empty
+#[derive(Debug, Serialize, Clone, Default)]
+pub(crate) struct GpuDeviceMiningStatus {
+    pub device_id: u32,
+    pub name: String,
+    pub hash_rate: f64,
+    pub accepted_shares: Option<u64>,
+    pub rejected_shares: Option<u64>,
+    pub temperature: Option<f32>,
+    pub power_usage: Option<f32>,
+    pub fan_speed: Option<f32>,
+}
+
+impl GpuMinerStatus {
+    /// Combines statuses of miners running side by side on different devices
+    /// Hashrates are only added up per algorithm, `hash_rate` is the one of the first miner's algorithm
+    pub fn aggregate(statuses: &[(GpuMiningAlgorithm, GpuMinerStatus)]) -> Self {
+        let mut aggregated = GpuMinerStatus::default();
+        for (algorithm, status) in statuses {
+            aggregated.is_mining |= status.is_mining;
+            aggregated.estimated_earnings = aggregated
+                .estimated_earnings
+                .saturating_add(status.estimated_earnings);
+            // Miners run on disjoint devices, so the device lists never overlap
+            aggregated.devices.extend(status.devices.clone());
+            match aggregated
+                .algorithms
+                .iter_mut()
+                .find(|algorithm_status| algorithm_status.algorithm == *algorithm)
+            {
+                Some(algorithm_status) => {
+                    algorithm_status.hash_rate += status.hash_rate;
+                    algorithm_status.estimated_earnings = algorithm_status
+                        .estimated_earnings
+                        .saturating_add(status.estimated_earnings);
+                }
+                None => aggregated.algorithms.push(GpuAlgorithmMiningStatus {
+                    algorithm: algorithm.clone(),
+                    hash_rate: status.hash_rate,
+                    estimated_earnings: status.estimated_earnings,
+                }),
+            }
+        }
+        aggregated.hash_rate = aggregated
+            .algorithms
+            .first()
+            .map(|algorithm_status| algorithm_status.hash_rate)
+            .unwrap_or_default();
+        aggregated
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub enum GpuConnectionType {
-    Node { node_grpc_address: String },
-    Pool { pool_url: String },
+    Node {
+        node_grpc_address: String,
+    },
+    Pool {
+        pool_url: String,
+    },
+    /// Mines locally generated work without connecting anywhere, used by the gpu benchmark
+    Benchmark,
 }
 
 impl Default for GpuConnectionType {
@@ -100,7 +178,10 @@ impl GpuMinerType {
                 GpuMinerFeature::DeviceExclusion,
                 GpuMinerFeature::MiningIntensity,
             ],
-            GpuMinerType::LolMiner => vec![GpuMinerFeature::PoolMining],
+            GpuMinerType::LolMiner => vec![
+                GpuMinerFeature::PoolMining,
+                GpuMinerFeature::DeviceExclusion,
+            ],
         }
     }
 
@@ -142,8 +223,17 @@ impl GpuMinerType {
         }
     }
 
-    pub fn is_pool_supported(&self, pool: &GpuPool) -> bool {
-        self.supported_pools().contains(pool)
+    /// Built-in pools are matched against `supported_pools`,
+    /// user-defined pools are matched by the algorithm they were configured with
+    pub fn is_pool_supported(&self, pool: &BasePoolData<GpuPool>) -> bool {
+        match &pool.pool_type {
+            GpuPool::Custom { .. } => pool
+                .custom
+                .as_ref()
+                .and_then(|custom| custom.algorithm.as_ref())
+                .is_some_and(|algorithm| self.supported_algorithms().contains(algorithm)),
+            pool_type => self.supported_pools().contains(pool_type),
+        }
     }
 
     pub fn default_pool(&self) -> Option<GpuPool> {
@@ -185,6 +275,30 @@ pub enum GpuMiningAlgorithm {
     C29,
 }
 
+impl GpuMiningAlgorithm {
+    /// Network hashrate reported by the node, `None` when it's not known
+    pub fn network_hashrate(&self, node_status: &BaseNodeStatus) -> Option<u64> {
+        match self {
+            GpuMiningAlgorithm::SHA3X => Some(node_status.sha_network_hashrate),
+            GpuMiningAlgorithm::C29 => Some(node_status.c29_network_hashrate),
+        }
+        .filter(|network_hashrate| *network_hashrate > 0)
+    }
+}
+
+/// Best miner for a device found by the gpu benchmark
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuDeviceMinerRecommendation {
+    pub device_id: u32,
+    pub miner: GpuMinerType,
+    pub algorithm: GpuMiningAlgorithm,
+    // Only set for miners with engine selection
+    pub engine: Option<EngineType>,
+    pub hash_rate: f64,
+    // Empty when the network hashrate of the algorithm is not known
+    pub estimated_daily_earnings: Option<u64>,
+}
+
 #[derive(Eq, Hash, PartialEq, Clone, Serialize)]
 pub enum GpuMinerFeature {
     /// Support for solo mining
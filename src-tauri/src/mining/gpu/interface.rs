@@ -20,6 +20,7 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use axum::async_trait;
@@ -234,4 +235,11 @@ impl ProcessAdapter for GpuMinerInterface {
             GpuMinerInterface::Glytex(miner) => miner.pid_file_name(),
         }
     }
+    fn log_file_path(&self, log_folder: &Path) -> Option<PathBuf> {
+        match self {
+            GpuMinerInterface::LolMiner(miner) => miner.log_file_path(log_folder),
+            GpuMinerInterface::Graxil(miner) => miner.log_file_path(log_folder),
+            GpuMinerInterface::Glytex(miner) => miner.log_file_path(log_folder),
+        }
+    }
 }
@@ -20,7 +20,8 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use log::{error, info};
+use futures::future::select_all;
+use log::{error, info, warn};
 use std::{
     collections::HashMap,
     path::PathBuf,
@@ -33,7 +34,7 @@ use tari_common_types::tari_address::TariAddress;
 use tokio::{
     select,
     sync::{
-        watch::{Receiver, Sender},
+        watch::{error::RecvError, Receiver, Sender},
         RwLock,
     },
 };
@@ -44,20 +45,26 @@ use crate::{
     configs::{
         config_mining::{ConfigMining, ConfigMiningContent},
         config_pools::ConfigPools,
-        pools::{gpu_pools::GpuPool, PoolOrigin},
+        pools::gpu_pools::GpuPool,
         trait_config::ConfigImpl,
     },
     events_emitter::EventsEmitter,
+    hardware::thermal_guard::ThermalGuardManager,
     mining::{
         gpu::{
+            benchmark::GpuBenchmark,
             consts::{
                 EngineType, GpuConnectionType, GpuMiner, GpuMinerStatus, GpuMinerType,
-                MINERS_PRIORITY,
+                GpuMiningAlgorithm, MINERS_PRIORITY,
             },
             interface::{GpuMinerInterface, GpuMinerInterfaceTrait},
             miners::{glytex::GlytexGpuMiner, graxil::GraxilGpuMiner, lolminer::LolMinerGpuMiner},
         },
-        pools::{gpu_pool_manager::GpuPoolManager, PoolManagerInterfaceTrait},
+        pools::{
+            earnings_history::{PoolEarningsHistory, PoolEarningsMiner},
+            gpu_pool_manager::GpuPoolManager,
+            PoolManagerInterfaceTrait,
+        },
     },
     node::node_adapter::BaseNodeStatus,
     process_adapter::ProcessAdapter,
@@ -79,10 +86,13 @@ pub struct GpuManager {
     available_miners: HashMap<GpuMinerType, GpuMiner>,
     // ======= Process watcher =======
     process_watcher: ProcessWatcher<GpuMinerInterface>,
+    // Miners running next to the selected one on the devices assigned to them
+    secondary_process_watchers: HashMap<GpuMinerType, ProcessWatcher<GpuMinerInterface>>,
     // ======= Parameters tracking =======
     status_thread_shutdown: Shutdown,
     process_stats_collector: Sender<ProcessWatcherStats>,
     gpu_internal_status_channel: Sender<GpuMinerStatus>,
+    secondary_status_channels: HashMap<GpuMinerType, Sender<GpuMinerStatus>>,
     gpu_external_status_channel: Sender<GpuMinerStatus>,
     node_status_channel: Option<Receiver<BaseNodeStatus>>, // Optional, only if connected to a node
     // ======= Cached config =======
@@ -112,11 +122,13 @@ impl GpuManager {
                 GpuMinerInterface::LolMiner(LolMinerGpuMiner::default()),
                 Sender::new(ProcessWatcherStats::default()),
             ),
+            secondary_process_watchers: HashMap::new(),
             // ======= Parameters tracking =======
             status_thread_shutdown: Shutdown::new(),
             process_stats_collector: Sender::new(ProcessWatcherStats::default()),
             gpu_external_status_channel: Sender::new(GpuMinerStatus::default()),
             gpu_internal_status_channel: Sender::new(GpuMinerStatus::default()),
+            secondary_status_channels: HashMap::new(),
             node_status_channel: None,
             // ======= Cached config =======
             connection_type: GpuConnectionType::default(),
@@ -202,11 +214,10 @@ impl GpuManager {
     /// If the selected miner does not support pool mining, it attempts to switch to a fallback miner that does.
     /// If no suitable miner is found, an error is returned.
     async fn handle_pool_connection_load(&mut self) -> Result<(), anyhow::Error> {
-        let current_pool_data = ConfigPools::content().await.current_gpu_pool().clone();
+        let current_pool_data =
+            GpuPoolManager::active_pool_data(&ConfigPools::content().await).await;
         if self.selected_miner.is_pool_mining_supported()
-            && self
-                .selected_miner
-                .is_pool_supported(&current_pool_data.pool_type)
+            && self.selected_miner.is_pool_supported(&current_pool_data)
         {
             self.process_watcher
                 .adapter
@@ -228,14 +239,15 @@ impl GpuManager {
                     is_healthy
                         && *miner_type != &self.selected_miner
                         && miner_type.is_pool_mining_supported()
-                        && miner_type.is_pool_supported(&current_pool_data.pool_type)
+                        && miner_type.is_pool_supported(&current_pool_data)
                 })
                 .cloned();
 
             if let Some(fallback_miner) = fallback_miner {
                 info!(target: LOG_TARGET, "Selected gpu miner does not support pool mining, switching to fallback miner: {fallback_miner}");
                 self.switch_miner(fallback_miner).await?;
-                let current_pool_data = ConfigPools::content().await.current_gpu_pool().clone();
+                let current_pool_data =
+                    GpuPoolManager::active_pool_data(&ConfigPools::content().await).await;
 
                 self.process_watcher
                     .adapter
@@ -310,6 +322,11 @@ impl GpuManager {
         log_path: PathBuf,
         grpc_node_address: String,
     ) -> Result<(), anyhow::Error> {
+        if GpuBenchmark::is_running() {
+            return Err(anyhow::anyhow!(
+                "Gpu benchmark is running, mining can be started after it finishes"
+            ));
+        }
         info!(target: LOG_TARGET, "Starting gpu miner: {}", self.selected_miner);
         info!(target: LOG_TARGET, "Adapter miner type: {}", self.process_watcher.adapter.name());
         let global_shutdown_signal = TasksTrackers::current().gpu_mining_phase.get_signal().await;
@@ -319,29 +336,47 @@ impl GpuManager {
             .get_task_tracker()
             .await;
 
-        if *ConfigPools::content().await.gpu_pool_enabled() {
+        let is_pool_mining = *ConfigPools::content().await.gpu_pool_enabled();
+        if is_pool_mining {
             self.handle_pool_connection_load().await?;
         } else {
             self.handle_node_connection_load(grpc_node_address).await?;
         }
 
-        let binary = match self.selected_miner {
-            GpuMinerType::Graxil => Binaries::GpuMinerSHA3X,
-            GpuMinerType::LolMiner => Binaries::LolMiner,
-            GpuMinerType::Glytex => Binaries::GpuMiner,
+        let binary = Self::resolve_miner_binary(&self.selected_miner);
+
+        // Secondary miners are started first so devices of the ones that fail to start are mined by the selected miner
+        let running_miners = if is_pool_mining {
+            self.start_secondary_miners(
+                &tari_address,
+                gpu_usage_percentage,
+                selected_engine.clone(),
+                base_path.clone(),
+                config_path.clone(),
+                log_path.clone(),
+            )
+            .await
+        } else {
+            vec![self.selected_miner.clone()]
         };
 
         // Worker name format depends on the pool
         // LuckyPool: .Tari-Universe
         // Kryptex: /Tari-Universe
         // SupportXTM: Not specified so we use None
-        let worker_name = match ConfigPools::content().await.current_gpu_pool().pool_origin {
-            PoolOrigin::LuckyPool => Some(".Tari-universe"),
-            PoolOrigin::SupportXTM => None,
-            PoolOrigin::Kryptex => Some("/Tari-universe"),
-        };
-
-        let excluded_devices = ConfigMining::content().await.get_excluded_devices();
+        // Custom: Defined by the user
+        let worker_name = GpuPoolManager::active_pool_data(&ConfigPools::content().await)
+            .await
+            .worker_name();
+
+        let mut excluded_devices = ConfigMining::content()
+            .await
+            .get_excluded_devices_for_miner(
+                &self.selected_miner,
+                &self.selected_miner,
+                &running_miners,
+            );
+        Self::exclude_overheated_devices(&mut excluded_devices).await;
 
         self.process_watcher
             .adapter
@@ -349,7 +384,7 @@ impl GpuManager {
             .await?;
         self.process_watcher
             .adapter
-            .load_worker_name(worker_name)
+            .load_worker_name(worker_name.as_deref())
             .await?;
         self.process_watcher
             .adapter
@@ -377,6 +412,11 @@ impl GpuManager {
 
         self.initialize_status_updates().await;
 
+        // Start pool status watcher if mining to pool
+        if is_pool_mining {
+            GpuPoolManager::start_stats_watcher().await;
+        }
+
         Ok(())
     }
 
@@ -385,6 +425,7 @@ impl GpuManager {
         {
             self.process_watcher.status_monitor = None;
             self.process_watcher.stop().await?;
+            self.stop_secondary_miners().await;
             self.status_thread_shutdown.trigger();
             let _res = self
                 .gpu_external_status_channel
@@ -397,6 +438,155 @@ impl GpuManager {
         Ok(())
     }
 
+    /// Devices stopped by the thermal guard are not mined until they cool down
+    async fn exclude_overheated_devices(excluded_devices: &mut Vec<u32>) {
+        for device_id in ThermalGuardManager::current().stopped_gpu_devices().await {
+            if !excluded_devices.contains(&device_id) {
+                excluded_devices.push(device_id);
+            }
+        }
+    }
+
+    /// Miners with devices assigned to them which can run next to the selected miner
+    /// They are pool mining only, the pool is picked from the ones supported by each miner
+    async fn resolve_secondary_miners(&self) -> Vec<GpuMinerType> {
+        let config_pools = ConfigPools::content().await;
+        ConfigMining::content()
+            .await
+            .get_secondary_gpu_miners(&self.selected_miner)
+            .into_iter()
+            .filter(|miner_type| {
+                let is_healthy = self
+                    .available_miners
+                    .get(miner_type)
+                    .is_some_and(|m| m.is_healthy);
+                let can_mine = is_healthy
+                    && miner_type.is_supported_on_current_platform()
+                    && miner_type.is_pool_mining_supported()
+                    && config_pools.gpu_pool_for_miner(miner_type).is_some();
+                if !can_mine {
+                    warn!(target: LOG_TARGET, "Gpu miner {miner_type} can't mine on its assigned devices, they will be mined by {}", self.selected_miner);
+                }
+                can_mine
+            })
+            .collect()
+    }
+
+    /// Starts miners on the devices assigned to them
+    /// Returns all miners that are going to mine, including the selected one
+    async fn start_secondary_miners(
+        &mut self,
+        tari_address: &TariAddress,
+        gpu_usage_percentage: u32,
+        selected_engine: EngineType,
+        base_path: PathBuf,
+        config_path: PathBuf,
+        log_path: PathBuf,
+    ) -> Vec<GpuMinerType> {
+        self.stop_secondary_miners().await;
+
+        let secondary_miners = self.resolve_secondary_miners().await;
+        let mut planned_miners = vec![self.selected_miner.clone()];
+        planned_miners.extend(secondary_miners.iter().cloned());
+
+        let mut running_miners = vec![self.selected_miner.clone()];
+        for miner_type in secondary_miners {
+            match self
+                .start_secondary_miner(
+                    miner_type.clone(),
+                    &planned_miners,
+                    tari_address,
+                    gpu_usage_percentage,
+                    selected_engine.clone(),
+                    base_path.clone(),
+                    config_path.clone(),
+                    log_path.clone(),
+                )
+                .await
+            {
+                Ok(()) => running_miners.push(miner_type),
+                Err(e) => {
+                    warn!(target: LOG_TARGET, "Failed to start gpu miner {miner_type} on its assigned devices, they will be mined by {}: {e}", self.selected_miner);
+                }
+            }
+        }
+        running_miners
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn start_secondary_miner(
+        &mut self,
+        miner_type: GpuMinerType,
+        planned_miners: &[GpuMinerType],
+        tari_address: &TariAddress,
+        gpu_usage_percentage: u32,
+        selected_engine: EngineType,
+        base_path: PathBuf,
+        config_path: PathBuf,
+        log_path: PathBuf,
+    ) -> Result<(), anyhow::Error> {
+        let pool_data = ConfigPools::content()
+            .await
+            .gpu_pool_for_miner(&miner_type)
+            .ok_or_else(|| anyhow::anyhow!("No pool is supported by gpu miner {miner_type}"))?;
+        let mut excluded_devices = ConfigMining::content()
+            .await
+            .get_excluded_devices_for_miner(&miner_type, &self.selected_miner, planned_miners);
+        Self::exclude_overheated_devices(&mut excluded_devices).await;
+
+        let status_channel = Sender::new(GpuMinerStatus::default());
+        let mut adapter = Self::create_miner_interface(&miner_type, status_channel.clone());
+        adapter.detect_devices().await?;
+        adapter
+            .load_connection_type(GpuConnectionType::Pool {
+                pool_url: pool_data.pool_url.clone(),
+            })
+            .await?;
+        adapter.load_tari_address(&tari_address.to_base58()).await?;
+        adapter
+            .load_worker_name(pool_data.worker_name().as_deref())
+            .await?;
+        adapter
+            .load_intensity_percentage(gpu_usage_percentage)
+            .await?;
+        adapter.load_gpu_engine(selected_engine).await?;
+        adapter.load_excluded_devices(excluded_devices).await?;
+
+        let mut process_watcher =
+            ProcessWatcher::new(adapter, Sender::new(ProcessWatcherStats::default()));
+        process_watcher
+            .start(
+                base_path,
+                config_path,
+                log_path,
+                Self::resolve_miner_binary(&miner_type),
+                TasksTrackers::current().gpu_mining_phase.get_signal().await,
+                TasksTrackers::current()
+                    .gpu_mining_phase
+                    .get_task_tracker()
+                    .await,
+            )
+            .await?;
+        info!(target: LOG_TARGET, "Started gpu miner {miner_type} on its assigned devices with pool: {}", pool_data.pool_name);
+
+        self.secondary_status_channels
+            .insert(miner_type.clone(), status_channel);
+        self.secondary_process_watchers
+            .insert(miner_type, process_watcher);
+        Ok(())
+    }
+
+    async fn stop_secondary_miners(&mut self) {
+        for (miner_type, mut process_watcher) in self.secondary_process_watchers.drain() {
+            info!(target: LOG_TARGET, "Stopping gpu miner {miner_type} running on its assigned devices");
+            process_watcher.status_monitor = None;
+            if let Err(e) = process_watcher.stop().await {
+                warn!(target: LOG_TARGET, "Failed to stop gpu miner {miner_type}: {e}");
+            }
+        }
+        self.secondary_status_channels.clear();
+    }
+
     pub async fn switch_miner(&mut self, new_miner: GpuMinerType) -> Result<(), anyhow::Error> {
         info!(target: LOG_TARGET, "Switching gpu miner to: {new_miner}");
         if let Some(miner) = self.available_miners.get(&new_miner) {
@@ -422,16 +612,25 @@ impl GpuManager {
 
     /// Will need to mark current seleceted miner as unhealthy and switch to another one based on priority
     /// If no other miners are available, we will just mark the current one as unhealthy and emit the status
-    pub async fn handle_unhealthy_miner(&mut self) -> Result<(), anyhow::Error> {
-        info!(target: LOG_TARGET, "Handling unhealthy gpu miner");
+    /// Miners running on their assigned devices are only marked as unhealthy, the selected miner takes their devices on the next start
+    pub async fn handle_unhealthy_miner(
+        &mut self,
+        miner: &GpuMinerType,
+    ) -> Result<(), anyhow::Error> {
+        info!(target: LOG_TARGET, "Handling unhealthy gpu miner: {miner}");
 
-        // mark current miner as unhealthy
-        if let Some(current_miner) = self.available_miners.get_mut(&self.selected_miner) {
-            current_miner.is_healthy = false;
-            current_miner.last_error =
+        // mark miner as unhealthy
+        if let Some(unhealthy_miner) = self.available_miners.get_mut(miner) {
+            unhealthy_miner.is_healthy = false;
+            unhealthy_miner.last_error =
                 Some("Miner process crashed or became unresponsive".to_string());
         }
 
+        if !self.is_selected_miner(miner) {
+            EventsEmitter::emit_available_gpu_miners(self.available_miners.clone()).await;
+            return Ok(());
+        }
+
         // app handle is required to start mining on new miner
         if let Some(app_handle) = self.app_handle.clone() {
             let fallback_miner = MINERS_PRIORITY
@@ -475,24 +674,119 @@ impl GpuManager {
         Ok(())
     }
 
-    pub async fn is_current_miner_healthy(&self) -> bool {
+    pub fn is_miner_healthy(&self, miner: &GpuMinerType) -> bool {
         self.available_miners
-            .get(&self.selected_miner)
+            .get(miner)
             .map(|m| m.is_healthy)
             .unwrap_or(false)
     }
 
-    /// Will need to mark current seleceted miner as healthy if it was unhealthy before
+    pub fn is_selected_miner(&self, miner: &GpuMinerType) -> bool {
+        self.selected_miner == *miner
+    }
+
+    pub fn is_miner_available(&self, miner: &GpuMinerType) -> bool {
+        self.available_miners.contains_key(miner) && miner.is_supported_on_current_platform()
+    }
+
+    pub fn is_mining(&self) -> bool {
+        self.process_watcher.is_running() || !self.secondary_process_watchers.is_empty()
+    }
+
+    /// Suspends running miners without stopping them, neither graxil nor lolMiner can pause on their own
+    pub fn pause_mining(&self) -> Result<(), anyhow::Error> {
+        let running_watchers: Vec<&ProcessWatcher<GpuMinerInterface>> =
+            std::iter::once(&self.process_watcher)
+                .chain(self.secondary_process_watchers.values())
+                .filter(|watcher| watcher.is_running() && !watcher.is_paused())
+                .collect();
+        if running_watchers.is_empty() {
+            return Ok(());
+        }
+
+        for (index, watcher) in running_watchers.iter().enumerate() {
+            if let Err(e) = watcher.suspend() {
+                // Miners are paused all together or not at all
+                for suspended_watcher in &running_watchers[..index] {
+                    let _unused = suspended_watcher.resume().inspect_err(|resume_error| {
+                        error!(target: LOG_TARGET, "Failed to resume {} gpu miner: {resume_error}", suspended_watcher.adapter.name());
+                    });
+                }
+                return Err(e);
+            }
+        }
+        info!(target: LOG_TARGET, "Gpu mining paused");
+
+        for status_channel in std::iter::once(&self.gpu_internal_status_channel)
+            .chain(self.secondary_status_channels.values())
+        {
+            status_channel.send_modify(|status| {
+                status.hash_rate = 0.0;
+                status.estimated_earnings = 0;
+                for device in &mut status.devices {
+                    device.hash_rate = 0.0;
+                }
+            });
+        }
+        Ok(())
+    }
+
+    pub fn resume_mining(&self) -> Result<(), anyhow::Error> {
+        let mut result = Ok(());
+        for watcher in std::iter::once(&self.process_watcher)
+            .chain(self.secondary_process_watchers.values())
+            .filter(|watcher| watcher.is_paused())
+        {
+            if let Err(e) = watcher.resume() {
+                error!(target: LOG_TARGET, "Failed to resume {} gpu miner: {e}", watcher.adapter.name());
+                result = Err(e);
+            }
+        }
+        info!(target: LOG_TARGET, "Gpu mining resumed");
+        result
+    }
+
+    pub fn is_paused(&self) -> bool {
+        std::iter::once(&self.process_watcher)
+            .chain(self.secondary_process_watchers.values())
+            .any(|watcher| watcher.is_running() && watcher.is_paused())
+    }
+
+    /// Available and healthy miners ordered by `MINERS_PRIORITY`
+    pub fn get_healthy_miners(&self) -> Vec<GpuMinerType> {
+        MINERS_PRIORITY
+            .iter()
+            .filter(|miner_type| {
+                self.is_miner_available(miner_type) && self.is_miner_healthy(miner_type)
+            })
+            .cloned()
+            .collect()
+    }
+
+    pub fn get_node_status(&self) -> Option<BaseNodeStatus> {
+        self.node_status_channel
+            .as_ref()
+            .map(|node_status_reciever| *node_status_reciever.borrow())
+    }
+
+    /// Will need to mark the miner as healthy if it was unhealthy before
     /// If the miner was healthy before, we do nothing
     /// Mainly for cases when the miner was unhealthy and user want to try again and this time it works
-    pub async fn handle_healthy_miner(&mut self) -> Result<(), anyhow::Error> {
-        // mark current miner as healthy
-        if let Some(current_miner) = self.available_miners.get_mut(&self.selected_miner) {
-            if !current_miner.is_healthy {
-                current_miner.is_healthy = true;
-                current_miner.last_error = None;
+    pub async fn handle_healthy_miner(
+        &mut self,
+        miner: &GpuMinerType,
+    ) -> Result<(), anyhow::Error> {
+        let is_selected_miner = self.is_selected_miner(miner);
+        // mark miner as healthy
+        if let Some(healthy_miner) = self.available_miners.get_mut(miner) {
+            if !healthy_miner.is_healthy {
+                healthy_miner.is_healthy = true;
+                healthy_miner.last_error = None;
+            }
+            if is_selected_miner {
+                EventsEmitter::emit_update_selected_gpu_miner(healthy_miner.miner_type.clone())
+                    .await;
             }
-            EventsEmitter::emit_update_selected_gpu_miner(current_miner.miner_type.clone()).await;
         }
 
         EventsEmitter::emit_available_gpu_miners(self.available_miners.clone()).await;
@@ -540,21 +834,57 @@ impl GpuManager {
     }
 
     fn resolve_miner_interface(&self, miner_type: &GpuMinerType) -> GpuMinerInterface {
+        Self::create_miner_interface(miner_type, self.gpu_internal_status_channel.clone())
+    }
+
+    pub fn create_miner_interface(
+        miner_type: &GpuMinerType,
+        status_channel: Sender<GpuMinerStatus>,
+    ) -> GpuMinerInterface {
         match miner_type {
-            GpuMinerType::Graxil => GpuMinerInterface::Graxil(GraxilGpuMiner::new(
-                self.gpu_internal_status_channel.clone(),
-            )),
-            GpuMinerType::LolMiner => GpuMinerInterface::LolMiner(LolMinerGpuMiner::new(
-                self.gpu_internal_status_channel.clone(),
-            )),
-            GpuMinerType::Glytex => GpuMinerInterface::Glytex(GlytexGpuMiner::new(
-                self.gpu_internal_status_channel.clone(),
-            )),
+            GpuMinerType::Graxil => GpuMinerInterface::Graxil(GraxilGpuMiner::new(status_channel)),
+            GpuMinerType::LolMiner => {
+                GpuMinerInterface::LolMiner(LolMinerGpuMiner::new(status_channel))
+            }
+            GpuMinerType::Glytex => GpuMinerInterface::Glytex(GlytexGpuMiner::new(status_channel)),
         }
     }
 
+    pub fn resolve_miner_binary(miner_type: &GpuMinerType) -> Binaries {
+        match miner_type {
+            GpuMinerType::Graxil => Binaries::GpuMinerSHA3X,
+            GpuMinerType::LolMiner => Binaries::LolMiner,
+            GpuMinerType::Glytex => Binaries::GpuMiner,
+        }
+    }
+
+    /// Resolves once any of the running miners reports a new status
+    async fn wait_for_status_change(
+        status_recievers: &mut [Receiver<GpuMinerStatus>],
+    ) -> Result<(), RecvError> {
+        if status_recievers.is_empty() {
+            return std::future::pending().await;
+        }
+        let status_changes = status_recievers
+            .iter_mut()
+            .map(|status_reciever| Box::pin(status_reciever.changed()));
+        let (result, _, _) = select_all(status_changes).await;
+        result
+    }
+
     pub async fn initialize_status_updates(&mut self) {
-        let mut gpu_internal_status_reciever = self.gpu_internal_status_channel.subscribe();
+        // The selected miner comes first, its status is the one the pool estimate is based on
+        let (miner_algorithms, mut gpu_internal_status_recievers): (
+            Vec<GpuMiningAlgorithm>,
+            Vec<Receiver<GpuMinerStatus>>,
+        ) = std::iter::once((&self.selected_miner, &self.gpu_internal_status_channel))
+            .chain(self.secondary_status_channels.iter())
+            .filter_map(|(miner_type, status_channel)| {
+                // Every miner mines a single algorithm
+                let algorithm = miner_type.supported_algorithms().into_iter().next()?;
+                Some((algorithm, status_channel.subscribe()))
+            })
+            .unzip();
         let gpu_external_status_channel = self.gpu_external_status_channel.clone();
         let node_status_channel = self.node_status_channel.clone();
         let connection_type = self.connection_type.clone();
@@ -574,21 +904,33 @@ impl GpuManager {
                     _ = internal_shutdown_signal.wait() => {
                         info!(target: LOG_TARGET, "Shutting down gpu miner status updates");
                         EventsEmitter::emit_gpu_mining_update(GpuMinerStatus::default()).await;
+                        EventsEmitter::emit_gpu_devices_mining_update(vec![]).await;
                         break;
                     },
                     _ = global_shutdown_signal.wait() => {
                         info!(target: LOG_TARGET, "Shutting down gpu miner status updates");
                         break;
                     },
-                    updated_status = gpu_internal_status_reciever.changed() => {
+                    updated_status = Self::wait_for_status_change(&mut gpu_internal_status_recievers) => {
                         if updated_status.is_ok() {
-                            let status = gpu_internal_status_reciever.borrow().clone();
+                            let statuses: Vec<(GpuMiningAlgorithm, GpuMinerStatus)> = miner_algorithms
+                                .iter()
+                                .cloned()
+                                .zip(gpu_internal_status_recievers.iter().map(|status_reciever| status_reciever.borrow().clone()))
+                                .collect();
+                            let selected_miner_status = GpuMinerStatus::aggregate(statuses.first().map(std::slice::from_ref).unwrap_or_default());
+                            let status = GpuMinerStatus::aggregate(&statuses);
                             let paresd_status = match connection_type {
                                 GpuConnectionType::Node { .. } => Self::handle_node_connection_type_status_change(status.clone(), node_status_channel.clone()).await,
-                                GpuConnectionType::Pool { .. } => Self::handle_pool_connection_type_status_change(status.clone()).await,
+                                GpuConnectionType::Pool { .. } | GpuConnectionType::Benchmark => Self::handle_pool_connection_type_status_change(status.clone()).await,
                             };
+                            // Pool mining status doesn't carry an estimate, it's still needed to reconcile pool payouts
+                            // Only the selected miner mines to the tracked pool
+                            let estimated_daily_earnings = Self::handle_node_connection_type_status_change(selected_miner_status, node_status_channel.clone()).await.estimated_earnings;
+                            PoolEarningsHistory::set_estimated_daily_earnings(PoolEarningsMiner::Gpu, estimated_daily_earnings);
                             let _res = gpu_external_status_channel.send(paresd_status.clone());
                             EventsEmitter::emit_gpu_mining_update(paresd_status.clone()).await;
+                            EventsEmitter::emit_gpu_devices_mining_update(paresd_status.devices.clone()).await;
 
                             if let Some(systray_manager) = systray_manager.clone() {
                                 let gpu_systemtray_data = SystemTrayGpuData {
@@ -621,17 +963,30 @@ impl GpuManager {
         node_status_reciever: Option<Receiver<BaseNodeStatus>>,
     ) -> GpuMinerStatus {
         if let Some(node_status_reciever) = node_status_reciever {
-            let node_status = node_status_reciever.borrow();
-            let estimated_earnings = estimate_earning(
-                node_status.sha_network_hashrate,
-                gpu_status.hash_rate,
-                node_status.block_reward,
-            );
-
-            GpuMinerStatus {
-                estimated_earnings,
-                ..gpu_status
+            let node_status = *node_status_reciever.borrow();
+            let mut gpu_status = gpu_status;
+            // Every algorithm has its own network hashrate, only the earnings can be added up
+            for algorithm_status in &mut gpu_status.algorithms {
+                algorithm_status.estimated_earnings = algorithm_status
+                    .algorithm
+                    .network_hashrate(&node_status)
+                    .map(|network_hashrate| {
+                        estimate_earning(
+                            network_hashrate,
+                            algorithm_status.hash_rate,
+                            node_status.block_reward,
+                        )
+                    })
+                    .unwrap_or_default();
             }
+            gpu_status.estimated_earnings =
+                gpu_status
+                    .algorithms
+                    .iter()
+                    .fold(0, |total: u64, algorithm_status| {
+                        total.saturating_add(algorithm_status.estimated_earnings)
+                    });
+            gpu_status
         } else {
             gpu_status
         }
@@ -41,7 +41,7 @@ use crate::{
     configs::{config_mining::ConfigMining, trait_config::ConfigImpl},
     events_emitter::EventsEmitter,
     mining::gpu::{
-        consts::{EngineType, GpuConnectionType, GpuMinerStatus},
+        consts::{EngineType, GpuConnectionType, GpuMinerStatus, GpuMinerType},
         interface::{GpuMinerInterfaceTrait, GpuMinerStatusInterface},
         manager::GpuManager,
         miners::{load_file_content, save_file_content, GpuCommonInformation},
@@ -50,6 +50,8 @@ use crate::{
         HandleUnhealthyResult, HealthStatus, ProcessAdapter, ProcessInstance, ProcessStartupSpec,
         StatusMonitor,
     },
+    process_output_buffer::ProcessOutputRegistry,
+    process_resource_limits::ProcessResourceLimits,
     process_utils, APPLICATION_FOLDER_ID,
 };
 
@@ -83,6 +85,8 @@ pub struct GlytexGpuDevice {
     pub device_index: u32,
     pub status: GlytexGpuStatus,
     pub settings: GlytexGpuSettings,
+    #[serde(default)]
+    pub pci_bus_id: Option<String>,
 }
 #[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
 pub struct GlytexGpuDevices {
@@ -195,8 +199,14 @@ impl GpuMinerInterfaceTrait for GlytexGpuMiner {
             .await?;
 
         crate::download_utils::set_permissions(&gpuminer_bin).await?;
-        let child =
-            process_utils::launch_child_process(&gpuminer_bin, &config_dir, None, &args, false)?;
+        let child = process_utils::launch_child_process(
+            &gpuminer_bin,
+            &config_dir,
+            None,
+            &args,
+            false,
+            None,
+        )?;
         let output = child.wait_with_output().await?;
 
         match output.status.code() {
@@ -284,8 +294,10 @@ impl ProcessAdapter for GlytexGpuMiner {
         std::fs::create_dir_all(&working_dir)?;
         std::fs::create_dir_all(config_folder.join("gpuminer"))?;
 
+        // Benchmark mode mines locally generated templates, so neither the node nor the address is needed
         let tari_node_address = match &self.connection_type {
-            Some(GpuConnectionType::Node { node_grpc_address }) => node_grpc_address,
+            Some(GpuConnectionType::Node { node_grpc_address }) => Some(node_grpc_address),
+            Some(GpuConnectionType::Benchmark) => None,
             Some(GpuConnectionType::Pool { pool_url: _ }) => {
                 return Err(anyhow::anyhow!("Glytex does not support pool mining"));
             }
@@ -296,15 +308,6 @@ impl ProcessAdapter for GlytexGpuMiner {
             }
         };
 
-        let tari_address = match &self.tari_address {
-            Some(addr) => addr.clone(),
-            None => {
-                return Err(anyhow::anyhow!(
-                    "Tari address must be set before starting the GpuMinerShaAdapter"
-                ));
-            }
-        };
-
         let gpu_engine_statuses = config_folder
             .join("gpuminer")
             .join("engine_statuses")
@@ -317,10 +320,6 @@ impl ProcessAdapter for GlytexGpuMiner {
         let selected_engine = self.selected_engine.clone().unwrap_or_default();
 
         let mut args: Vec<String> = vec![
-            "--tari-address".to_string(),
-            tari_address,
-            "--tari-node-url".to_string(),
-            tari_node_address.to_string(),
             "--config".to_string(),
             config_folder
                 .join("gpuminer")
@@ -347,6 +346,23 @@ impl ProcessAdapter for GlytexGpuMiner {
             selected_engine.to_string(),
         ];
 
+        if let Some(tari_node_address) = tari_node_address {
+            let tari_address = match &self.tari_address {
+                Some(addr) => addr.clone(),
+                None => {
+                    return Err(anyhow::anyhow!(
+                        "Tari address must be set before starting the GpuMinerShaAdapter"
+                    ));
+                }
+            };
+            args.push("--tari-address".to_string());
+            args.push(tari_address);
+            args.push("--tari-node-url".to_string());
+            args.push(tari_node_address.to_string());
+        } else {
+            args.push("--benchmark".to_string());
+        }
+
         if let Some(worker_name) = &self.worker_name {
             // Only available after 0.1.8-pre.2
             args.push("--coinbase-extra".to_string());
@@ -389,8 +405,10 @@ impl ProcessAdapter for GlytexGpuMiner {
                     data_dir: base_folder,
                     pid_file_name: self.pid_file_name().to_string(),
                     name: self.name().to_string(),
+                    resource_limits: ProcessResourceLimits::default(),
                 },
                 handle: None,
+                output_buffer: ProcessOutputRegistry::buffer(self.name()),
             },
             GpuMinerStatusInterface::Glytex(GlytexGpuMinerStatusMonitor {
                 http_api_port,
@@ -428,7 +446,11 @@ impl StatusMonitor for GlytexGpuMinerStatusMonitor {
         if duration_since_last_healthy_status.as_secs().gt(&(60 * 3)) // Fallback after 3 minutes of unhealthiness
             && !WAS_FALLBACK_TO_OTHER_MINER_TRIGGERED.load(Ordering::SeqCst)
         {
-            match GpuManager::write().await.handle_unhealthy_miner().await {
+            match GpuManager::write()
+                .await
+                .handle_unhealthy_miner(&GpuMinerType::Glytex)
+                .await
+            {
                 Ok(_) => {
                     info!(target: LOG_TARGET, "GpuMinerShaAdapter: GPU Pool feature turned off due to prolonged unhealthiness.");
                     WAS_FALLBACK_TO_OTHER_MINER_TRIGGERED.store(true, Ordering::SeqCst);
@@ -458,9 +480,15 @@ impl StatusMonitor for GlytexGpuMinerStatusMonitor {
             Ok(status) => {
                 let _ = self.gpu_status_sender.send(status.clone());
                 if status.hash_rate > 0.0 {
-                    if !GpuManager::read().await.is_current_miner_healthy().await {
+                    if !GpuManager::read()
+                        .await
+                        .is_miner_healthy(&GpuMinerType::Glytex)
+                    {
                         info!(target: LOG_TARGET, "Marking current miner as healthy again");
-                        let _unused = GpuManager::write().await.handle_healthy_miner().await;
+                        let _unused = GpuManager::write()
+                            .await
+                            .handle_healthy_miner(&GpuMinerType::Glytex)
+                            .await;
                     }
                     HealthStatus::Healthy
                 } else {
@@ -492,12 +520,14 @@ impl GlytexGpuMinerStatusMonitor {
                         is_mining: false,
                         hash_rate: 0.0,
                         estimated_earnings: 0,
+                        devices: vec![],
                     });
                 }
                 return Ok(GpuMinerStatus {
                     is_mining: false,
                     hash_rate: 0.0,
                     estimated_earnings: 0,
+                    devices: vec![],
                 });
             }
         };
@@ -510,6 +540,7 @@ impl GlytexGpuMinerStatusMonitor {
                     is_mining: false,
                     hash_rate: 0.0,
                     estimated_earnings: 0,
+                    devices: vec![],
                 });
             }
         };
@@ -518,6 +549,7 @@ impl GlytexGpuMinerStatusMonitor {
             is_mining: true,
             estimated_earnings: 0,
             hash_rate: body.total_hashrate.ten_seconds.unwrap_or(0.0),
+            devices: vec![],
         })
     }
 }
@@ -41,18 +41,23 @@ use crate::{
         trait_config::ConfigImpl,
     },
     events_emitter::EventsEmitter,
-    mining::gpu::{
-        consts::{GpuConnectionType, GpuMinerStatus},
-        interface::{GpuMinerInterfaceTrait, GpuMinerStatusInterface},
-        manager::GpuManager,
-        miners::{load_file_content, GpuCommonInformation, GpuDeviceType, GpuVendor},
-        utils::gpu_miner_sha_websocket::GpuMinerShaWebSocket,
+    mining::{
+        gpu::{
+            consts::{GpuConnectionType, GpuDeviceMiningStatus, GpuMinerStatus, GpuMinerType},
+            interface::{GpuMinerInterfaceTrait, GpuMinerStatusInterface},
+            manager::GpuManager,
+            miners::{load_file_content, GpuCommonInformation, GpuDeviceType, GpuVendor},
+            utils::gpu_miner_sha_websocket::{GpuMinerShaWebSocket, WebSocketGpuMinerResponse},
+        },
+        pools::gpu_pool_manager::GpuPoolManager,
     },
     port_allocator::PortAllocator,
     process_adapter::{
         HandleUnhealthyResult, HealthStatus, ProcessAdapter, ProcessInstance, ProcessStartupSpec,
         StatusMonitor,
     },
+    process_output_buffer::ProcessOutputRegistry,
+    process_resource_limits::ProcessResourceLimits,
     process_utils, APPLICATION_FOLDER_ID,
 };
 
@@ -68,6 +73,8 @@ pub struct GraxilGpuDeviceInformation {
     pub max_compute_units: u32,
     pub global_mem_size: u64,
     pub device_type: GpuDeviceType,
+    #[serde(default)]
+    pub pci_bus_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -167,6 +174,7 @@ impl GpuMinerInterfaceTrait for GraxilGpuMiner {
             None,
             &args,
             false,
+            None,
         )?;
         let output = child.wait_with_output().await?;
         info!(target: LOG_TARGET, "Gpu detect exit code: {:?}", output.status.code().unwrap_or_default());
@@ -244,6 +252,21 @@ impl ProcessAdapter for GraxilGpuMiner {
                 GpuConnectionType::Pool { pool_url } => {
                     args.push("--pool".to_string());
                     args.push(pool_url.clone());
+
+                    let Some(tari_address) = &self.tari_address else {
+                        return Err(anyhow::anyhow!(
+                            "Tari address must be set before starting the GraxilMiner"
+                        ));
+                    };
+                    let mut address = tari_address.clone();
+                    if let Some(worker_name) = &self.worker_name {
+                        address = format!("{}{}", tari_address, worker_name);
+                    }
+                    args.push("--wallet".to_string());
+                    args.push(address);
+                }
+                GpuConnectionType::Benchmark => {
+                    args.push("--benchmark".to_string());
                 }
             }
         } else {
@@ -252,20 +275,6 @@ impl ProcessAdapter for GraxilGpuMiner {
             ));
         }
 
-        if let Some(tari_address) = &self.tari_address {
-            let mut address = tari_address.clone();
-            if let Some(worker_name) = &self.worker_name {
-                address = format!("{}{}", tari_address, worker_name);
-            }
-
-            args.push("--wallet".to_string());
-            args.push(address.clone());
-        } else {
-            return Err(anyhow::anyhow!(
-                "Tari address must be set before starting the GraxilMiner"
-            ));
-        }
-
         if let Some(intensity) = self.intensity_percentage {
             args.push("--gpu-intensity".to_string());
             args.push(intensity.to_string());
@@ -296,12 +305,20 @@ impl ProcessAdapter for GraxilGpuMiner {
                     data_dir: base_folder,
                     pid_file_name: self.pid_file_name().to_string(),
                     name: self.name().to_string(),
+                    resource_limits: ProcessResourceLimits::default(),
                 },
                 handle: None,
+                output_buffer: ProcessOutputRegistry::buffer(self.name()),
             },
             GpuMinerStatusInterface::Graxil(GraxilGpuMinerStatusMonitor {
                 gpu_status_sender: self.gpu_status_sender.clone(),
                 websocket_listener: GpuMinerShaWebSocket::new(ws_port),
+                mining_devices: self
+                    .gpu_devices
+                    .iter()
+                    .filter(|device| !self.excluded_devices.contains(&device.device_id))
+                    .cloned()
+                    .collect(),
             }),
         ))
     }
@@ -323,6 +340,7 @@ static WAS_FALLBACK_TO_OTHER_MINER_TRIGGERED: AtomicBool = AtomicBool::new(false
 pub struct GraxilGpuMinerStatusMonitor {
     gpu_status_sender: Sender<GpuMinerStatus>,
     websocket_listener: GpuMinerShaWebSocket,
+    mining_devices: Vec<GpuCommonInformation>,
 }
 
 #[async_trait]
@@ -335,7 +353,11 @@ impl StatusMonitor for GraxilGpuMinerStatusMonitor {
         if duration_since_last_healthy_status.as_secs().gt(&(60 * 3)) // Fallback after 3 minutes of unhealthiness
             && !WAS_FALLBACK_TO_OTHER_MINER_TRIGGERED.load(Ordering::SeqCst)
         {
-            match GpuManager::write().await.handle_unhealthy_miner().await {
+            match GpuManager::write()
+                .await
+                .handle_unhealthy_miner(&GpuMinerType::Graxil)
+                .await
+            {
                 Ok(_) => {
                     info!(target: LOG_TARGET, "GpuMinerShaAdapter: GPU Pool feature turned off due to prolonged unhealthiness.");
                     WAS_FALLBACK_TO_OTHER_MINER_TRIGGERED.store(true, Ordering::SeqCst);
@@ -366,9 +388,15 @@ impl StatusMonitor for GraxilGpuMinerStatusMonitor {
                 info!(target: LOG_TARGET, "ShaMiner status: {status:?}");
                 let _ = self.gpu_status_sender.send(status.clone());
                 if status.hash_rate > 0.0 {
-                    if !GpuManager::read().await.is_current_miner_healthy().await {
+                    if !GpuManager::read()
+                        .await
+                        .is_miner_healthy(&GpuMinerType::Graxil)
+                    {
                         info!(target: LOG_TARGET, "Marking current miner as healthy again");
-                        let _unused = GpuManager::write().await.handle_healthy_miner().await;
+                        let _unused = GpuManager::write()
+                            .await
+                            .handle_healthy_miner(&GpuMinerType::Graxil)
+                            .await;
                     }
                     HealthStatus::Healthy
                 } else {
@@ -389,10 +417,17 @@ impl GraxilGpuMinerStatusMonitor {
         let last_status = self.websocket_listener.get_last_message().await;
 
         if let Some(status) = last_status {
+            GpuPoolManager::handle_gpu_miner_shares_update(
+                &GpuMinerType::Graxil,
+                status.accepted_shares,
+                status.rejected_shares,
+            )
+            .await;
             return Ok(GpuMinerStatus {
                 is_mining: true,
                 estimated_earnings: 0,
                 hash_rate: status.current_hashrate as f64,
+                devices: self.devices_status(&status),
             });
         }
 
@@ -400,6 +435,38 @@ impl GraxilGpuMinerStatusMonitor {
             is_mining: false,
             estimated_earnings: 0,
             hash_rate: 0.0,
+            devices: vec![],
         })
     }
+
+    /// Graxil reports hashrate per mining thread and hardware info of a single GPU
+    /// Threads are matched with devices only when there is one thread per device
+    fn devices_status(&self, status: &WebSocketGpuMinerResponse) -> Vec<GpuDeviceMiningStatus> {
+        if let [device] = self.mining_devices.as_slice() {
+            return vec![GpuDeviceMiningStatus {
+                device_id: device.device_id,
+                name: device.name.clone(),
+                hash_rate: status.current_hashrate as f64,
+                accepted_shares: Some(status.accepted_shares),
+                rejected_shares: Some(status.rejected_shares),
+                temperature: status.gpu_info.temperature,
+                power_usage: status.gpu_info.power_usage,
+                fan_speed: None,
+            }];
+        }
+
+        if status.thread_hashrates.len() != self.mining_devices.len() {
+            return vec![];
+        }
+        self.mining_devices
+            .iter()
+            .zip(&status.thread_hashrates)
+            .map(|(device, hash_rate)| GpuDeviceMiningStatus {
+                device_id: device.device_id,
+                name: device.name.clone(),
+                hash_rate: *hash_rate as f64,
+                ..Default::default()
+            })
+            .collect()
+    }
 }
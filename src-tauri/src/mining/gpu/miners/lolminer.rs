@@ -20,6 +20,7 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 use std::{
+    path::{Path, PathBuf},
     sync::atomic::{AtomicBool, Ordering},
     time::Duration,
 };
@@ -41,17 +42,22 @@ use crate::{
         trait_config::ConfigImpl,
     },
     events_emitter::EventsEmitter,
-    mining::gpu::{
-        consts::{GpuConnectionType, GpuMinerStatus},
-        interface::{GpuMinerInterfaceTrait, GpuMinerStatusInterface},
-        manager::GpuManager,
-        miners::GpuCommonInformation,
+    mining::{
+        gpu::{
+            consts::{GpuConnectionType, GpuDeviceMiningStatus, GpuMinerStatus, GpuMinerType},
+            interface::{GpuMinerInterfaceTrait, GpuMinerStatusInterface},
+            manager::GpuManager,
+            miners::GpuCommonInformation,
+        },
+        pools::gpu_pool_manager::GpuPoolManager,
     },
     port_allocator::PortAllocator,
     process_adapter::{
         HandleUnhealthyResult, HealthStatus, ProcessAdapter, ProcessInstance, ProcessStartupSpec,
         StatusMonitor,
     },
+    process_output_buffer::ProcessOutputRegistry,
+    process_resource_limits::ProcessResourceLimits,
     process_utils::launch_child_process,
     APPLICATION_FOLDER_ID,
 };
@@ -66,6 +72,7 @@ pub struct LolMinerGpuMiner {
     pub connection_type: Option<GpuConnectionType>,
     pub gpu_status_sender: Sender<GpuMinerStatus>,
     pub gpu_devices: Vec<GpuCommonInformation>,
+    pub excluded_devices: Vec<u32>,
 }
 
 impl LolMinerGpuMiner {
@@ -77,6 +84,7 @@ impl LolMinerGpuMiner {
             connection_type: None,
             gpu_status_sender,
             gpu_devices: vec![],
+            excluded_devices: vec![],
         }
     }
 }
@@ -104,6 +112,13 @@ impl GpuMinerInterfaceTrait for LolMinerGpuMiner {
         self.connection_type = Some(connection_type);
         Ok(())
     }
+    async fn load_excluded_devices(
+        &mut self,
+        excluded_devices: Vec<u32>,
+    ) -> Result<(), anyhow::Error> {
+        self.excluded_devices = excluded_devices;
+        Ok(())
+    }
 
     async fn detect_devices(&mut self) -> Result<(), anyhow::Error> {
         let config_path =
@@ -118,7 +133,7 @@ impl GpuMinerInterfaceTrait for LolMinerGpuMiner {
         let args = vec!["--list-devices".to_string()];
 
         crate::download_utils::set_permissions(&gpu_miner_binary).await?;
-        let result = launch_child_process(&gpu_miner_binary, &config_dir, None, &args, true)?;
+        let result = launch_child_process(&gpu_miner_binary, &config_dir, None, &args, true, None)?;
 
         let mut gpu_devices: Vec<GpuCommonInformation> = vec![];
 
@@ -137,6 +152,7 @@ impl GpuMinerInterfaceTrait for LolMinerGpuMiner {
             gpu_devices.push(GpuCommonInformation {
                 name: device_name.trim().to_string(),
                 device_id,
+                pci_bus_id: None,
             });
         }
 
@@ -190,6 +206,23 @@ impl ProcessAdapter for LolMinerGpuMiner {
                 GpuConnectionType::Pool { pool_url } => {
                     args.push("--pool".to_string());
                     args.push(pool_url.clone());
+
+                    let Some(tari_address) = &self.tari_address else {
+                        return Err(anyhow::anyhow!(
+                            "Tari address must be set before starting the LolminerGpuMiner"
+                        ));
+                    };
+                    let mut address = tari_address.clone();
+                    if let Some(worker_name) = &self.worker_name {
+                        address = format!("{}{}", tari_address, worker_name);
+                    }
+                    args.push("--user".to_string());
+                    args.push(address);
+                }
+                GpuConnectionType::Benchmark => {
+                    // Mines lolminer's internal benchmark job instead of pool jobs
+                    args.push("--benchmark".to_string());
+                    args.push("CR29".to_string());
                 }
             }
         } else {
@@ -198,17 +231,21 @@ impl ProcessAdapter for LolMinerGpuMiner {
             ));
         }
 
-        if let Some(tari_address) = &self.tari_address {
-            let mut address = tari_address.clone();
-            if let Some(worker_name) = &self.worker_name {
-                address = format!("{}{}", tari_address, worker_name);
+        // Lolminer takes the list of devices to mine on, by default it mines on all of them
+        if !self.excluded_devices.is_empty() {
+            let devices: Vec<String> = self
+                .gpu_devices
+                .iter()
+                .filter(|device| !self.excluded_devices.contains(&device.device_id))
+                .map(|device| device.device_id.to_string())
+                .collect();
+            if devices.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "All devices are excluded from mining with the LolminerGpuMiner"
+                ));
             }
-            args.push("--user".to_string());
-            args.push(address);
-        } else {
-            return Err(anyhow::anyhow!(
-                "Tari address must be set before starting the LolminerGpuMiner"
-            ));
+            args.push("--devices".to_string());
+            args.push(devices.join(","));
         }
 
         info!(
@@ -241,8 +278,10 @@ impl ProcessAdapter for LolMinerGpuMiner {
                     data_dir: base_folder,
                     pid_file_name: self.pid_file_name().to_string(),
                     name: self.name().to_string(),
+                    resource_limits: ProcessResourceLimits::default(),
                 },
                 handle: None,
+                output_buffer: ProcessOutputRegistry::buffer(self.name()),
             },
             GpuMinerStatusInterface::LolMiner(LolMinerGpuMinerStatusMonitor {
                 http_api_port: api_port,
@@ -258,6 +297,10 @@ impl ProcessAdapter for LolMinerGpuMiner {
     fn pid_file_name(&self) -> &str {
         "lolminer_pid"
     }
+
+    fn log_file_path(&self, log_folder: &Path) -> Option<PathBuf> {
+        Some(log_folder.join("lolminer.txt"))
+    }
 }
 
 #[derive(Clone)]
@@ -280,7 +323,11 @@ impl StatusMonitor for LolMinerGpuMinerStatusMonitor {
         if duration_since_last_healthy_status.as_secs().gt(&(60 * 3)) // Fallback after 3 minutes of unhealthiness
             && !WAS_FALLBACK_TO_OTHER_MINER_TRIGGERED.load(Ordering::SeqCst)
         {
-            match GpuManager::write().await.handle_unhealthy_miner().await {
+            match GpuManager::write()
+                .await
+                .handle_unhealthy_miner(&GpuMinerType::LolMiner)
+                .await
+            {
                 Ok(_) => {
                     info!(target: LOG_TARGET, "GpuMinerShaAdapter: GPU Pool feature turned off due to prolonged unhealthiness.");
                     WAS_FALLBACK_TO_OTHER_MINER_TRIGGERED.store(true, Ordering::SeqCst);
@@ -310,9 +357,15 @@ impl StatusMonitor for LolMinerGpuMinerStatusMonitor {
             Ok(status) => {
                 let _ = self.gpu_status_sender.send(status.clone());
                 if status.hash_rate > 0.0 {
-                    if !GpuManager::read().await.is_current_miner_healthy().await {
+                    if !GpuManager::read()
+                        .await
+                        .is_miner_healthy(&GpuMinerType::LolMiner)
+                    {
                         info!(target: LOG_TARGET, "Marking current miner as healthy again");
-                        let _unused = GpuManager::write().await.handle_healthy_miner().await;
+                        let _unused = GpuManager::write()
+                            .await
+                            .handle_healthy_miner(&GpuMinerType::LolMiner)
+                            .await;
                     }
                     HealthStatus::Healthy
                 } else {
@@ -341,12 +394,14 @@ impl LolMinerGpuMinerStatusMonitor {
                         is_mining: false,
                         hash_rate: 0.0,
                         estimated_earnings: 0,
+                        devices: vec![],
                     });
                 }
                 return Ok(GpuMinerStatus {
                     is_mining: false,
                     hash_rate: 0.0,
                     estimated_earnings: 0,
+                    devices: vec![],
                 });
             }
         };
@@ -359,10 +414,18 @@ impl LolMinerGpuMinerStatusMonitor {
                     is_mining: false,
                     hash_rate: 0.0,
                     estimated_earnings: 0,
+                    devices: vec![],
                 });
             }
         };
 
+        GpuPoolManager::handle_gpu_miner_shares_update(
+            &GpuMinerType::LolMiner,
+            body.algorithms.iter().map(|a| a.total_accepted).sum(),
+            body.algorithms.iter().map(|a| a.total_rejected).sum(),
+        )
+        .await;
+
         Ok(GpuMinerStatus {
             is_mining: true,
             estimated_earnings: 0,
@@ -374,20 +437,81 @@ impl LolMinerGpuMinerStatusMonitor {
                 * 100.0)
                 .round()
                 / 100.0),
+            devices: body.devices_status(),
         })
     }
 }
 
 #[derive(Debug, Deserialize)]
 struct LolMinerHttpApiStatus {
+    #[serde(rename = "Workers", default)]
+    workers: Vec<Worker>,
     #[serde(rename = "Algorithms")]
     algorithms: Vec<Algorithm>,
 }
 
+impl LolMinerHttpApiStatus {
+    /// Per worker values of algorithms are listed in the same order as workers
+    fn devices_status(&self) -> Vec<GpuDeviceMiningStatus> {
+        self.workers
+            .iter()
+            .enumerate()
+            .map(|(position, worker)| GpuDeviceMiningStatus {
+                device_id: worker.index,
+                name: worker.name.clone(),
+                hash_rate: self
+                    .algorithms
+                    .iter()
+                    .filter_map(|a| a.worker_performance.get(position))
+                    .sum(),
+                accepted_shares: Some(
+                    self.algorithms
+                        .iter()
+                        .filter_map(|a| a.worker_accepted.get(position))
+                        .sum(),
+                ),
+                rejected_shares: Some(
+                    self.algorithms
+                        .iter()
+                        .filter_map(|a| a.worker_rejected.get(position))
+                        .sum(),
+                ),
+                temperature: worker.core_temperature,
+                power_usage: worker.power,
+                fan_speed: worker.fan_speed,
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Worker {
+    #[serde(rename = "Index")]
+    index: u32,
+    #[serde(rename = "Name", default)]
+    name: String,
+    #[serde(rename = "Power", default)]
+    power: Option<f32>,
+    #[serde(rename = "Core_Temp", default)]
+    core_temperature: Option<f32>,
+    #[serde(rename = "Fan_Speed", default)]
+    fan_speed: Option<f32>,
+}
+
 #[derive(Debug, Deserialize)]
 struct Algorithm {
     #[serde(rename = "Total_Performance")]
     total_performance: f64,
+    #[serde(rename = "Total_Accepted", default)]
+    total_accepted: u64,
+    #[serde(rename = "Total_Rejected", default)]
+    total_rejected: u64,
+    #[serde(rename = "Worker_Performance", default)]
+    worker_performance: Vec<f64>,
+    #[serde(rename = "Worker_Accepted", default)]
+    worker_accepted: Vec<u64>,
+    #[serde(rename = "Worker_Rejected", default)]
+    worker_rejected: Vec<u64>,
 }
 
 fn extract_device_names(output_str: &str) -> Vec<String> {
@@ -420,3 +544,45 @@ fn extract_device_names(output_str: &str) -> Vec<String> {
     }
     device_names
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn devices_status_matches_worker_values_by_position() {
+        let body: LolMinerHttpApiStatus = serde_json::from_str(
+            r#"{
+                "Workers": [
+                    { "Index": 0, "Name": "NVIDIA GeForce RTX 3080", "Power": 220.5, "Core_Temp": 61, "Fan_Speed": 70 },
+                    { "Index": 2, "Name": "NVIDIA GeForce RTX 3070" }
+                ],
+                "Algorithms": [
+                    {
+                        "Total_Performance": 7.5,
+                        "Total_Accepted": 12,
+                        "Total_Rejected": 1,
+                        "Worker_Performance": [4.5, 3.0],
+                        "Worker_Accepted": [10, 2],
+                        "Worker_Rejected": [0, 1]
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let devices = body.devices_status();
+
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[0].device_id, 0);
+        assert_eq!(devices[0].hash_rate, 4.5);
+        assert_eq!(devices[0].accepted_shares, Some(10));
+        assert_eq!(devices[0].temperature, Some(61.0));
+        assert_eq!(devices[0].power_usage, Some(220.5));
+        assert_eq!(devices[1].device_id, 2);
+        assert_eq!(devices[1].name, "NVIDIA GeForce RTX 3070");
+        assert_eq!(devices[1].rejected_shares, Some(1));
+        assert_eq!(devices[1].fan_speed, None);
+    }
+}
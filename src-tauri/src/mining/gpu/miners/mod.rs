@@ -38,6 +38,9 @@ pub mod lolminer;
 pub struct GpuCommonInformation {
     name: String,
     device_id: u32,
+    // Used to match the miner device with its hardware telemetry reader
+    #[serde(default)]
+    pci_bus_id: Option<String>,
 }
 
 impl GpuCommonInformation {
@@ -45,12 +48,14 @@ impl GpuCommonInformation {
         Self {
             name: device.device_name,
             device_id: device.device_index,
+            pci_bus_id: device.pci_bus_id,
         }
     }
     pub fn from_graxil_devices(devices: GraxilGpuDeviceInformation) -> Self {
         Self {
             name: devices.name,
             device_id: devices.device_id,
+            pci_bus_id: devices.pci_bus_id,
         }
     }
 }
@@ -0,0 +1,263 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, LazyLock,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use device_query::{DeviceQuery, DeviceState};
+use log::{error, info, warn};
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tokio::{select, sync::watch, time::interval};
+
+use crate::{
+    commands::{start_gpu_mining, stop_gpu_mining},
+    configs::{
+        config_mining::{ConfigMining, ConfigMiningContent},
+        trait_config::ConfigImpl,
+    },
+    events_emitter::EventsEmitter,
+    mining::{gpu::manager::GpuManager, pause_manager::MiningPauseManager},
+    tasks_tracker::TasksTrackers,
+    UniverseAppState,
+};
+
+static LOG_TARGET: &str = "tari::mining::idle_monitor";
+static INSTANCE: LazyLock<IdleMiningMonitor> = LazyLock::new(IdleMiningMonitor::new);
+
+const INPUT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const EVALUATION_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct IdleMiningStatus {
+    // Input can't be read e.g. without accessibility permission on macOS
+    pub is_available: bool,
+    pub is_enabled: bool,
+    pub is_idle: bool,
+    // Mode the miners are using right now
+    pub mining_mode: String,
+}
+
+/// Switches running miners to the idle mining mode after a period without keyboard or mouse input
+/// and back to the selected mode on the first input. The selected mode itself is never changed
+pub struct IdleMiningMonitor {
+    is_available: AtomicBool,
+    is_idle: AtomicBool,
+}
+
+impl IdleMiningMonitor {
+    fn new() -> Self {
+        Self {
+            is_available: AtomicBool::new(false),
+            is_idle: AtomicBool::new(false),
+        }
+    }
+
+    pub fn current() -> &'static IdleMiningMonitor {
+        &INSTANCE
+    }
+
+    /// Mode whose usage percentages the miners should be started with
+    pub fn active_mining_mode(&self, config: &ConfigMiningContent) -> String {
+        let use_idle_mode = self.is_idle.load(Ordering::SeqCst)
+            && *config.idle_mining_enabled()
+            && config
+                .mining_modes()
+                .contains_key(config.idle_mining_mode());
+        if use_idle_mode {
+            config.idle_mining_mode().clone()
+        } else {
+            config.selected_mining_mode().clone()
+        }
+    }
+
+    pub async fn spawn_idle_detection_loop(app_handle: AppHandle) {
+        info!(target: LOG_TARGET, "Spawning idle mining monitor");
+        let mut shutdown_signal = TasksTrackers::current().common.get_signal().await;
+        if shutdown_signal.is_triggered() {
+            info!(target: LOG_TARGET, "Shutdown signal already triggered, exiting idle mining monitor");
+            return;
+        }
+
+        TasksTrackers::current()
+            .common
+            .get_task_tracker()
+            .await
+            .spawn(async move {
+                let last_input_rx = tokio::task::spawn_blocking(spawn_input_watcher)
+                    .await
+                    .ok()
+                    .flatten();
+                let Some(mut last_input_rx) = last_input_rx else {
+                    warn!(target: LOG_TARGET, "Could not read keyboard and mouse state, idle mining is unavailable");
+                    Self::emit_update(&ConfigMining::content().await).await;
+                    return;
+                };
+                INSTANCE.is_available.store(true, Ordering::SeqCst);
+                Self::emit_update(&ConfigMining::content().await).await;
+
+                let mut evaluation_interval = interval(EVALUATION_INTERVAL);
+                loop {
+                    select! {
+                        _ = evaluation_interval.tick() => {}
+                        result = last_input_rx.changed() => {
+                            if result.is_err() {
+                                warn!(target: LOG_TARGET, "Input watcher stopped, idle mining is unavailable");
+                                INSTANCE.is_available.store(false, Ordering::SeqCst);
+                                break;
+                            }
+                        }
+                        _ = shutdown_signal.wait() => {
+                            info!(target: LOG_TARGET, "Shutdown signal received, stopping idle mining monitor");
+                            break;
+                        }
+                    }
+                    let idle_for = last_input_rx.borrow().elapsed();
+                    Self::evaluate(&app_handle, idle_for).await;
+                }
+            });
+    }
+
+    async fn evaluate(app_handle: &AppHandle, idle_for: Duration) {
+        let config = ConfigMining::content().await;
+        let should_be_idle = *config.idle_mining_enabled()
+            && idle_for >= Duration::from_secs(*config.idle_mining_timeout_secs());
+        if should_be_idle == INSTANCE.is_idle.load(Ordering::SeqCst) {
+            return;
+        }
+        // Switching modes would resume paused miners, the switch happens after resuming
+        if MiningPauseManager::current().is_paused().await {
+            return;
+        }
+
+        INSTANCE.is_idle.store(should_be_idle, Ordering::SeqCst);
+        let mining_mode = INSTANCE.active_mining_mode(&config);
+        if should_be_idle {
+            info!(target: LOG_TARGET, "No input for {idle_for:?}, switching miners to {mining_mode} mode");
+        } else {
+            info!(target: LOG_TARGET, "Input detected, switching miners back to {mining_mode} mode");
+        }
+        if let Err(e) = Self::apply_mining_mode(app_handle, &config, &mining_mode).await {
+            error!(target: LOG_TARGET, "Failed to switch miners to {mining_mode} mode: {e}");
+        }
+        Self::emit_update(&config).await;
+    }
+
+    /// Reapplies the active mode after idle mining settings were changed while idle
+    pub async fn handle_settings_changed(&self, app_handle: &AppHandle) {
+        let config = ConfigMining::content().await;
+        if self.is_idle.load(Ordering::SeqCst) {
+            if !*config.idle_mining_enabled() {
+                self.is_idle.store(false, Ordering::SeqCst);
+            }
+            let mining_mode = self.active_mining_mode(&config);
+            if let Err(e) = Self::apply_mining_mode(app_handle, &config, &mining_mode).await {
+                error!(target: LOG_TARGET, "Failed to switch miners to {mining_mode} mode: {e}");
+            }
+        }
+        Self::emit_update(&config).await;
+    }
+
+    async fn apply_mining_mode(
+        app_handle: &AppHandle,
+        config: &ConfigMiningContent,
+        mining_mode: &str,
+    ) -> Result<(), anyhow::Error> {
+        let state = app_handle.state::<UniverseAppState>();
+        let log_dir = app_handle.path().app_log_dir()?;
+
+        let cpu_miner = state.cpu_miner.read().await;
+        if cpu_miner.is_running().await {
+            cpu_miner
+                .set_cpu_usage_percentage(config.get_cpu_usage_percentage(mining_mode), &log_dir)
+                .await?;
+        }
+        drop(cpu_miner);
+
+        // Gpu miners pick up the usage only when started
+        if GpuManager::read().await.is_mining() {
+            stop_gpu_mining(app_handle.clone())
+                .await
+                .map_err(anyhow::Error::msg)?;
+            start_gpu_mining(state, app_handle.clone())
+                .await
+                .map_err(anyhow::Error::msg)?;
+        }
+        Ok(())
+    }
+
+    async fn emit_update(config: &ConfigMiningContent) {
+        EventsEmitter::emit_idle_mining_update(IdleMiningStatus {
+            is_available: INSTANCE.is_available.load(Ordering::SeqCst),
+            is_enabled: *config.idle_mining_enabled(),
+            is_idle: INSTANCE.is_idle.load(Ordering::SeqCst),
+            mining_mode: INSTANCE.active_mining_mode(config),
+        })
+        .await;
+    }
+}
+
+/// Polls keyboard and mouse on a dedicated thread, device state can't be moved between threads on every platform
+/// The receiver holds the time of the last input, the thread exits once it's dropped
+fn spawn_input_watcher() -> Option<watch::Receiver<Instant>> {
+    let (last_input_tx, last_input_rx) = watch::channel(Instant::now());
+    let (is_ready_tx, is_ready_rx) = mpsc::channel();
+
+    let spawn_result = thread::Builder::new()
+        .name("idle-input-watcher".to_string())
+        .spawn(move || {
+            let Some(device_state) = DeviceState::checked_new() else {
+                let _unused = is_ready_tx.send(false);
+                return;
+            };
+            let _unused = is_ready_tx.send(true);
+
+            let mut last_mouse_coords = device_state.get_mouse().coords;
+            while !last_input_tx.is_closed() {
+                thread::sleep(INPUT_POLL_INTERVAL);
+                let mouse = device_state.get_mouse();
+                let has_input = mouse.coords != last_mouse_coords
+                    || mouse.button_pressed.iter().any(|is_pressed| *is_pressed)
+                    || !device_state.get_keys().is_empty();
+                if has_input {
+                    last_input_tx.send_replace(Instant::now());
+                }
+                last_mouse_coords = mouse.coords;
+            }
+        });
+    if let Err(e) = spawn_result {
+        error!(target: LOG_TARGET, "Failed to spawn input watcher thread: {e}");
+        return None;
+    }
+
+    is_ready_rx
+        .recv()
+        .ok()
+        .filter(|is_ready| *is_ready)
+        .map(|_| last_input_rx)
+}
@@ -22,4 +22,8 @@
 
 pub mod cpu;
 pub mod gpu;
+pub mod idle_monitor;
+pub mod pause_manager;
 pub mod pools;
+pub mod profit_switcher;
+pub mod scheduler;
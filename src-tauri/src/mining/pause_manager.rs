@@ -0,0 +1,183 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{collections::HashSet, sync::LazyLock};
+
+use log::{info, warn};
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+
+use crate::{
+    events::MiningPauseUpdatePayload, events_emitter::EventsEmitter,
+    mining::gpu::manager::GpuManager, process_killer::is_process_suspend_supported,
+    UniverseAppState,
+};
+
+static LOG_TARGET: &str = "tari::mining::pause_manager";
+static INSTANCE: LazyLock<MiningPauseManager> = LazyLock::new(MiningPauseManager::new);
+
+/// Why mining is paused, it's resumed only after every reason is gone
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum MiningPauseReason {
+    User,
+}
+
+/// Pauses running miners in place instead of stopping them
+/// Miner processes and their pool connections stay alive, so pausing and resuming takes milliseconds
+pub struct MiningPauseManager {
+    reasons: Mutex<HashSet<MiningPauseReason>>,
+}
+
+impl MiningPauseManager {
+    fn new() -> Self {
+        Self {
+            reasons: Mutex::new(HashSet::new()),
+        }
+    }
+
+    pub fn current() -> &'static MiningPauseManager {
+        &INSTANCE
+    }
+
+    pub async fn pause(
+        &self,
+        app_handle: &AppHandle,
+        reason: MiningPauseReason,
+    ) -> Result<(), anyhow::Error> {
+        let mut reasons = self.reasons.lock().await;
+        let is_new_reason = reasons.insert(reason);
+        info!(target: LOG_TARGET, "Pausing mining | reason: {reason:?} | active reasons: {reasons:?}");
+
+        let result = Self::pause_miners(app_handle).await;
+        if result.is_err() && is_new_reason {
+            // Mining is paused all together or not at all, a reason without paused miners would drift from their state
+            reasons.remove(&reason);
+            if reasons.is_empty() {
+                let state = app_handle.state::<UniverseAppState>();
+                let _unused = state.cpu_miner.read().await.resume().await.inspect_err(|e| {
+                    warn!(target: LOG_TARGET, "Failed to resume cpu miner after failed pause: {e}");
+                });
+            }
+        }
+        drop(reasons);
+
+        Self::emit_update(app_handle).await;
+        result
+    }
+
+    /// Gpu miners can't pause on their own, they are left mining where processes can't be suspended
+    async fn pause_miners(app_handle: &AppHandle) -> Result<(), anyhow::Error> {
+        let state = app_handle.state::<UniverseAppState>();
+        let cpu_miner = state.cpu_miner.read().await;
+        if cpu_miner.is_running().await {
+            cpu_miner.pause().await?;
+        }
+        drop(cpu_miner);
+
+        if is_process_suspend_supported() {
+            GpuManager::read().await.pause_mining()?;
+        } else {
+            warn!(target: LOG_TARGET, "Gpu miners can't be paused on this platform, they keep mining");
+        }
+        Ok(())
+    }
+
+    pub async fn resume(
+        &self,
+        app_handle: &AppHandle,
+        reason: MiningPauseReason,
+    ) -> Result<(), anyhow::Error> {
+        let mut reasons = self.reasons.lock().await;
+        reasons.remove(&reason);
+        if !reasons.is_empty() {
+            info!(target: LOG_TARGET, "Mining stays paused | resumed reason: {reason:?} | active reasons: {reasons:?}");
+            return Ok(());
+        }
+        info!(target: LOG_TARGET, "Resuming mining | reason: {reason:?}");
+
+        let state = app_handle.state::<UniverseAppState>();
+        if let Err(e) = state.cpu_miner.read().await.resume().await {
+            // The cpu miner is still paused, the reason is kept so resuming can be retried
+            reasons.insert(reason);
+            drop(reasons);
+            Self::emit_update(app_handle).await;
+            return Err(e);
+        }
+        let gpu_result = GpuManager::read().await.resume_mining();
+        drop(reasons);
+
+        Self::emit_update(app_handle).await;
+        gpu_result
+    }
+
+    /// Restarted miners come up unpaused, they are paused again while any reason is active
+    pub async fn handle_mining_started(&self, app_handle: &AppHandle) {
+        let reasons = self.reasons.lock().await;
+        if reasons.is_empty() {
+            return;
+        }
+        info!(target: LOG_TARGET, "Mining started while paused, pausing it again | active reasons: {reasons:?}");
+        if let Err(e) = Self::pause_miners(app_handle).await {
+            warn!(target: LOG_TARGET, "Failed to pause started miners: {e}");
+        }
+        drop(reasons);
+        Self::emit_update(app_handle).await;
+    }
+
+    /// Miners start unpaused, reasons left behind after all of them stopped would keep a later pause from resuming
+    pub async fn handle_mining_stopped(&self, app_handle: &AppHandle) {
+        let mut reasons = self.reasons.lock().await;
+        if reasons.is_empty() {
+            return;
+        }
+        let state = app_handle.state::<UniverseAppState>();
+        if state.cpu_miner.read().await.is_running().await || GpuManager::read().await.is_mining() {
+            return;
+        }
+        warn!(target: LOG_TARGET, "Mining stopped while paused, clearing pause reasons: {reasons:?}");
+        reasons.clear();
+        drop(reasons);
+        Self::emit_update(app_handle).await;
+    }
+
+    pub async fn is_paused(&self) -> bool {
+        !self.reasons.lock().await.is_empty()
+    }
+
+    pub async fn get_status(&self, app_handle: &AppHandle) -> MiningPauseUpdatePayload {
+        let reasons = self.reasons.lock().await.iter().copied().collect();
+        let state = app_handle.state::<UniverseAppState>();
+        let is_cpu_paused = state.cpu_miner.read().await.is_paused().await;
+        let is_gpu_paused = GpuManager::read().await.is_paused();
+        MiningPauseUpdatePayload {
+            is_cpu_paused,
+            is_gpu_paused,
+            reasons,
+        }
+    }
+
+    async fn emit_update(app_handle: &AppHandle) {
+        let payload = Self::current().get_status(app_handle).await;
+        EventsEmitter::emit_mining_pause_update(payload).await;
+    }
+}
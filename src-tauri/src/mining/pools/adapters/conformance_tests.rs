@@ -0,0 +1,372 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Conformance suite every pool stats adapter has to pass
+//! Recorded pool responses are replayed by a local stand-in server so `request_pool_status`
+//! is exercised end to end without reaching the real pools
+
+#![allow(clippy::unwrap_used)]
+
+use std::{collections::HashMap, net::SocketAddr, sync::Arc};
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::get,
+    Router,
+};
+use tokio::net::TcpListener;
+
+use crate::{
+    configs::pools::GenericPoolStatsPaths,
+    mining::pools::{
+        adapters::{
+            generic_pool::GenericPoolAdapter, kryptex_pool::KryptexPoolAdapter,
+            lucky_pool::LuckyPoolAdapter, support_xmr_pool::SupportXmrPoolAdapter, PoolApiAdapter,
+        },
+        PoolStatus,
+    },
+};
+
+const ADAPTER_NAME: &str = "ConformancePool";
+
+#[derive(Debug)]
+enum Expected {
+    Status(PoolStatus),
+    Error,
+}
+
+/// Recorded pool response and what the adapter is expected to make of it
+/// The case name is used as the address the stand-in server serves the response under
+struct FixtureCase {
+    name: &'static str,
+    http_status: StatusCode,
+    body: &'static str,
+    expected: Expected,
+}
+
+impl FixtureCase {
+    fn ok(name: &'static str, body: &'static str, expected: Expected) -> Self {
+        Self {
+            name,
+            http_status: StatusCode::OK,
+            body,
+            expected,
+        }
+    }
+
+    fn with_http_status(mut self, http_status: StatusCode) -> Self {
+        self.http_status = http_status;
+        self
+    }
+}
+
+/// Responses every pool can return regardless of its API shape
+/// 5xx responses are not included as they are retried with a backoff by the http client
+fn common_cases() -> Vec<FixtureCase> {
+    vec![
+        FixtureCase::ok(
+            "common_html_error_page",
+            include_str!("fixtures/common/html_error_page.html"),
+            Expected::Error,
+        ),
+        FixtureCase::ok(
+            "common_truncated_body",
+            include_str!("fixtures/common/truncated.json"),
+            Expected::Error,
+        ),
+        FixtureCase::ok("common_empty_body", "", Expected::Error),
+        FixtureCase::ok(
+            "common_not_found",
+            include_str!("fixtures/common/not_found.json"),
+            Expected::Error,
+        )
+        .with_http_status(StatusCode::NOT_FOUND),
+        FixtureCase::ok("common_bad_request", "", Expected::Error)
+            .with_http_status(StatusCode::BAD_REQUEST),
+    ]
+}
+
+type RecordedResponses = Arc<HashMap<String, (StatusCode, &'static str)>>;
+
+/// Local stand-in for a pool stats API replaying recorded responses
+struct PoolStandInServer {
+    address: SocketAddr,
+}
+
+impl PoolStandInServer {
+    async fn start(cases: &[FixtureCase]) -> Self {
+        let responses: RecordedResponses = Arc::new(
+            cases
+                .iter()
+                .map(|case| (case.name.to_string(), (case.http_status, case.body)))
+                .collect(),
+        );
+        let router = Router::new()
+            .route("/stats/:address", get(Self::replay_response))
+            .with_state(responses);
+
+        let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0)))
+            .await
+            .unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        Self { address }
+    }
+
+    async fn replay_response(
+        State(responses): State<RecordedResponses>,
+        Path(address): Path<String>,
+    ) -> (StatusCode, &'static str) {
+        responses
+            .get(&address)
+            .copied()
+            .unwrap_or((StatusCode::NOT_FOUND, "No recorded response"))
+    }
+
+    fn stats_url(&self) -> String {
+        format!("http://{}/stats/%TARI_ADDRESS%", self.address)
+    }
+}
+
+fn check_result(
+    case: &FixtureCase,
+    source: &str,
+    result: &Result<PoolStatus, anyhow::Error>,
+) -> Option<String> {
+    match (&case.expected, result) {
+        (Expected::Status(expected), Ok(status)) if expected == status => None,
+        (Expected::Error, Err(_)) => None,
+        (expected, result) => Some(format!(
+            "[{}] {source}: expected {expected:?}, got {result:?}",
+            case.name
+        )),
+    }
+}
+
+/// Run the adapter against the adapter specific and common cases
+/// Every case is requested through the stand-in server and successful responses are also converted directly
+/// so both paths are guaranteed to agree
+async fn assert_adapter_conformance<A, F>(create_adapter: F, adapter_cases: Vec<FixtureCase>)
+where
+    A: PoolApiAdapter,
+    F: Fn(String, String) -> A,
+{
+    let mut cases = adapter_cases;
+    cases.extend(common_cases());
+
+    let server = PoolStandInServer::start(&cases).await;
+    let adapter = create_adapter(ADAPTER_NAME.to_string(), server.stats_url());
+    assert_eq!(adapter.name(), ADAPTER_NAME);
+
+    let mut failures = Vec::new();
+    for case in &cases {
+        let requested = adapter.request_pool_status(case.name.to_string()).await;
+        failures.extend(check_result(case, "request_pool_status", &requested));
+
+        if case.http_status.is_success() {
+            let converted = adapter.convert_api_data(case.body);
+            failures.extend(check_result(case, "convert_api_data", &converted));
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "Adapter failed conformance cases:\n{}",
+        failures.join("\n")
+    );
+}
+
+#[tokio::test]
+async fn lucky_pool_adapter_conformance() {
+    let cases = vec![
+        FixtureCase::ok(
+            "stats",
+            include_str!("fixtures/lucky_pool/stats.json"),
+            Expected::Status(PoolStatus {
+                accepted_shares: 1284,
+                unpaid: 15_250_000.0,
+                balance: 250_000_000.0,
+                min_payout: 50_000_000,
+            }),
+        ),
+        FixtureCase::ok(
+            "address_not_found",
+            include_str!("fixtures/lucky_pool/address_not_found.json"),
+            Expected::Status(PoolStatus::default()),
+        ),
+        FixtureCase::ok(
+            "empty_miner",
+            include_str!("fixtures/lucky_pool/empty_miner.json"),
+            Expected::Status(PoolStatus::default()),
+        ),
+        FixtureCase::ok(
+            "huge_numbers",
+            include_str!("fixtures/lucky_pool/huge_numbers.json"),
+            Expected::Status(PoolStatus {
+                accepted_shares: u64::MAX,
+                unpaid: u64::MAX as f64 * 2.0,
+                balance: u64::MAX as f64,
+                min_payout: u64::MAX,
+            }),
+        ),
+        FixtureCase::ok(
+            "error_body",
+            include_str!("fixtures/lucky_pool/error_body.json"),
+            Expected::Error,
+        ),
+    ];
+
+    assert_adapter_conformance(LuckyPoolAdapter::new, cases).await;
+}
+
+#[tokio::test]
+async fn support_xmr_pool_adapter_conformance() {
+    let cases = vec![
+        FixtureCase::ok(
+            "stats",
+            include_str!("fixtures/support_xmr_pool/stats.json"),
+            Expected::Status(PoolStatus {
+                accepted_shares: 412,
+                unpaid: 3_250_000.0,
+                balance: 183_250_000.0,
+                min_payout: 0,
+            }),
+        ),
+        FixtureCase::ok(
+            "empty_miner",
+            include_str!("fixtures/support_xmr_pool/empty_miner.json"),
+            Expected::Status(PoolStatus::default()),
+        ),
+        FixtureCase::ok(
+            "huge_numbers",
+            include_str!("fixtures/support_xmr_pool/huge_numbers.json"),
+            Expected::Status(PoolStatus {
+                accepted_shares: u64::MAX,
+                unpaid: u64::MAX as f64,
+                balance: u64::MAX as f64 * 2.0,
+                min_payout: 0,
+            }),
+        ),
+        FixtureCase::ok(
+            "error_body",
+            include_str!("fixtures/support_xmr_pool/error_body.json"),
+            Expected::Error,
+        ),
+    ];
+
+    assert_adapter_conformance(SupportXmrPoolAdapter::new, cases).await;
+}
+
+#[tokio::test]
+async fn kryptex_pool_adapter_conformance() {
+    let cases = vec![
+        FixtureCase::ok(
+            "stats",
+            include_str!("fixtures/kryptex_pool/stats.json"),
+            Expected::Status(PoolStatus {
+                accepted_shares: 0,
+                unpaid: 3_250_000.0,
+                balance: 185_250_000.0,
+                min_payout: 50,
+            }),
+        ),
+        FixtureCase::ok(
+            "empty_miner",
+            include_str!("fixtures/kryptex_pool/empty_miner.json"),
+            Expected::Status(PoolStatus {
+                min_payout: 50,
+                ..PoolStatus::default()
+            }),
+        ),
+        FixtureCase::ok(
+            "huge_numbers",
+            include_str!("fixtures/kryptex_pool/huge_numbers.json"),
+            Expected::Status(PoolStatus {
+                accepted_shares: 0,
+                unpaid: 18_446_744_073_709.5 * 1_000_000.0,
+                balance: 18_446_744_073_709.5 * 1_000_000.0,
+                min_payout: u64::MAX,
+            }),
+        ),
+        FixtureCase::ok(
+            "error_body",
+            include_str!("fixtures/kryptex_pool/error_body.json"),
+            Expected::Error,
+        )
+        .with_http_status(StatusCode::NOT_FOUND),
+    ];
+
+    assert_adapter_conformance(KryptexPoolAdapter::new, cases).await;
+}
+
+#[tokio::test]
+async fn generic_pool_adapter_conformance() {
+    let stats_paths = GenericPoolStatsPaths {
+        accepted_shares: Some("/miner/shares/valid".to_string()),
+        unpaid: Some("/miner/balance/unpaid".to_string()),
+        balance: Some("/miner/balance/total".to_string()),
+        min_payout: Some("/pool/minPayout".to_string()),
+    };
+    let cases = vec![
+        FixtureCase::ok(
+            "stats",
+            include_str!("fixtures/generic_pool/stats.json"),
+            Expected::Status(PoolStatus {
+                accepted_shares: 977,
+                unpaid: 4_200_000.0,
+                balance: 125_000_000.0,
+                min_payout: 25_000_000,
+            }),
+        ),
+        FixtureCase::ok(
+            "empty_miner",
+            include_str!("fixtures/generic_pool/empty_miner.json"),
+            Expected::Status(PoolStatus {
+                min_payout: 25_000_000,
+                ..PoolStatus::default()
+            }),
+        ),
+        FixtureCase::ok(
+            "huge_numbers",
+            include_str!("fixtures/generic_pool/huge_numbers.json"),
+            Expected::Status(PoolStatus {
+                accepted_shares: u64::MAX,
+                unpaid: 1e300,
+                balance: u64::MAX as f64,
+                min_payout: u64::MAX,
+            }),
+        ),
+        FixtureCase::ok(
+            "error_body",
+            include_str!("fixtures/generic_pool/error_body.json"),
+            Expected::Error,
+        ),
+    ];
+
+    assert_adapter_conformance(
+        |name, stats_url| GenericPoolAdapter::new(name, stats_url, stats_paths.clone()),
+        cases,
+    )
+    .await;
+}
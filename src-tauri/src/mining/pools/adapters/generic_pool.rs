@@ -0,0 +1,108 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{
+    configs::pools::GenericPoolStatsPaths,
+    mining::pools::{adapters::PoolApiAdapter, PoolStatus},
+    requests::clients::http_client::HttpClient,
+};
+use anyhow::anyhow;
+use log::info;
+use serde_json::Value;
+
+static LOG_TARGET: &str = "universe::mining::pools::adapters::generic_pool";
+
+/// Adapter for user-defined pools
+/// Values are read from the stats response using JSON pointer paths configured by the user
+#[derive(Clone, Debug)]
+pub struct GenericPoolAdapter {
+    name: String,
+    stats_url: String,
+    stats_paths: GenericPoolStatsPaths,
+}
+
+impl GenericPoolAdapter {
+    pub fn new(name: String, stats_url: String, stats_paths: GenericPoolStatsPaths) -> Self {
+        Self {
+            name,
+            stats_url,
+            stats_paths,
+        }
+    }
+
+    // Pools tend to return numbers either as JSON numbers or as strings so we accept both
+    fn read_number(data: &Value, path: &Option<String>) -> Result<f64, anyhow::Error> {
+        let Some(path) = path.as_ref().filter(|path| !path.is_empty()) else {
+            return Ok(0.0);
+        };
+        let value = data
+            .pointer(path)
+            .ok_or_else(|| anyhow!("Path {path} not found in pool stats response"))?;
+        match value {
+            Value::Number(number) => number
+                .as_f64()
+                .ok_or_else(|| anyhow!("Value at {path} is not a valid number: {number}")),
+            Value::String(text) => text
+                .trim()
+                .parse::<f64>()
+                .map_err(|e| anyhow!("Value at {path} is not a valid number: {text} | error: {e}")),
+            Value::Null => Ok(0.0),
+            other => Err(anyhow!("Value at {path} is not a number: {other}")),
+        }
+    }
+}
+
+impl PoolApiAdapter for GenericPoolAdapter {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn convert_api_data(&self, data: &str) -> Result<PoolStatus, anyhow::Error> {
+        let response: Value = serde_json::from_str(data)?;
+        let pool_status = PoolStatus {
+            accepted_shares: Self::read_number(&response, &self.stats_paths.accepted_shares)?
+                as u64,
+            unpaid: Self::read_number(&response, &self.stats_paths.unpaid)?,
+            balance: Self::read_number(&response, &self.stats_paths.balance)?,
+            min_payout: Self::read_number(&response, &self.stats_paths.min_payout)? as u64,
+        };
+        Ok(pool_status)
+    }
+
+    async fn request_pool_status(&self, address: String) -> Result<PoolStatus, anyhow::Error> {
+        if self.stats_url.is_empty() {
+            return Err(anyhow!(
+                "Stats URL is not configured for pool: {}",
+                self.name
+            ));
+        }
+        let url = self
+            .stats_url
+            .replace("%TARI_ADDRESS%", &address.to_string());
+        info!(target: LOG_TARGET, "Requesting {} pool status from: {url}", self.name);
+        let pool_status_response = HttpClient::with_retries(3).send_get_request(&url).await?;
+        let response_text = pool_status_response.text().await?;
+        let pool_status = self.convert_api_data(response_text.as_str())?;
+        Ok(pool_status)
+    }
+}
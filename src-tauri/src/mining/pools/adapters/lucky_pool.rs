@@ -157,8 +157,9 @@ impl PoolApiAdapter for LuckyPoolAdapter {
         let converted_data: LuckyPoolStatusResponseBody = serde_json::from_str(data)?;
         let pool_status = PoolStatus {
             accepted_shares: converted_data.stats.accepted_shares.get_number(),
-            unpaid: (converted_data.stats.unlocked.get_number()
-                + converted_data.stats.locked.get_number()) as f64,
+            // Summed as floats as both values can be close to u64::MAX
+            unpaid: converted_data.stats.unlocked.get_number() as f64
+                + converted_data.stats.locked.get_number() as f64,
             balance: converted_data.stats.paid.get_number() as f64,
             min_payout: converted_data.stats.payment_threshold.get_number(),
         };
@@ -22,12 +22,15 @@
 
 use crate::mining::pools::{
     adapters::{
-        kryptex_pool::KryptexPoolAdapter, lucky_pool::LuckyPoolAdapter,
-        support_xmr_pool::SupportXmrPoolAdapter,
+        generic_pool::GenericPoolAdapter, kryptex_pool::KryptexPoolAdapter,
+        lucky_pool::LuckyPoolAdapter, support_xmr_pool::SupportXmrPoolAdapter,
     },
     PoolStatus,
 };
 
+#[cfg(test)]
+mod conformance_tests;
+pub mod generic_pool;
 pub mod kryptex_pool;
 pub mod lucky_pool;
 pub mod support_xmr_pool;
@@ -43,6 +46,7 @@ pub enum PoolApiAdapters {
     LuckyPool(LuckyPoolAdapter),
     SupportXmr(SupportXmrPoolAdapter),
     Kryptex(KryptexPoolAdapter),
+    Generic(GenericPoolAdapter),
 }
 
 impl PoolApiAdapter for PoolApiAdapters {
@@ -51,6 +55,7 @@ impl PoolApiAdapter for PoolApiAdapters {
             PoolApiAdapters::LuckyPool(adapter) => adapter.name(),
             PoolApiAdapters::SupportXmr(adapter) => adapter.name(),
             PoolApiAdapters::Kryptex(adapter) => adapter.name(),
+            PoolApiAdapters::Generic(adapter) => adapter.name(),
         }
     }
 
@@ -59,6 +64,7 @@ impl PoolApiAdapter for PoolApiAdapters {
             PoolApiAdapters::LuckyPool(adapter) => adapter.convert_api_data(data),
             PoolApiAdapters::SupportXmr(adapter) => adapter.convert_api_data(data),
             PoolApiAdapters::Kryptex(adapter) => adapter.convert_api_data(data),
+            PoolApiAdapters::Generic(adapter) => adapter.convert_api_data(data),
         }
     }
     async fn request_pool_status(&self, address: String) -> Result<PoolStatus, anyhow::Error> {
@@ -66,6 +72,7 @@ impl PoolApiAdapter for PoolApiAdapters {
             PoolApiAdapters::LuckyPool(adapter) => adapter.request_pool_status(address).await,
             PoolApiAdapters::SupportXmr(adapter) => adapter.request_pool_status(address).await,
             PoolApiAdapters::Kryptex(adapter) => adapter.request_pool_status(address).await,
+            PoolApiAdapters::Generic(adapter) => adapter.request_pool_status(address).await,
         }
     }
 }
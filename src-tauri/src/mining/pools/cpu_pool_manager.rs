@@ -20,56 +20,77 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{collections::HashMap, sync::LazyLock};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        LazyLock,
+    },
+};
 
+use log::{info, warn};
 use tokio::{
     spawn,
     sync::{RwLock, RwLockWriteGuard},
+    time::interval,
 };
 
 use crate::{
     configs::{
-        config_pools::ConfigPoolsContent,
+        config_pools::{ConfigPools, ConfigPoolsContent},
         pools::{cpu_pools::CpuPool, BasePoolData},
+        trait_config::ConfigImpl,
     },
+    events::PoolFailoverPayload,
     events_emitter::EventsEmitter,
     mining::pools::{
         adapters::{
-            kryptex_pool::KryptexPoolAdapter, lucky_pool::LuckyPoolAdapter,
-            support_xmr_pool::SupportXmrPoolAdapter, PoolApiAdapters,
+            generic_pool::GenericPoolAdapter, kryptex_pool::KryptexPoolAdapter,
+            lucky_pool::LuckyPoolAdapter, support_xmr_pool::SupportXmrPoolAdapter, PoolApiAdapters,
         },
+        earnings_history::{PoolEarningsHistory, PoolEarningsMiner},
+        pool_failover::{PoolFailover, PoolSwitchDecision, POOL_HEALTH_CHECK_INTERVAL},
         pools_manager::PoolManager,
-        PoolManagerInterfaceTrait, PoolStatus,
+        PoolManagerInterfaceTrait, PoolStatsRequest, PoolStatus,
     },
+    setup::setup_manager::SetupManager,
     tasks_tracker::TasksTrackers,
 };
 
-#[allow(dead_code)]
 static LOG_TARGET: &str = "tari::mining::pools::cpu_pool_manager";
 static INSTANCE: LazyLock<CpuPoolManager> = LazyLock::new(CpuPoolManager::new);
 
 pub struct CpuPoolManager {
     pool_status_manager: RwLock<PoolManager>,
+    pool_failover: RwLock<PoolFailover<CpuPool>>,
+    is_failover_watcher_running: AtomicBool,
 }
 
 impl CpuPoolManager {
     pub fn new() -> Self {
-        let cpu_pool = CpuPool::default();
-        let cpu_pool_data = cpu_pool.default_content();
+        let cpu_pool_data = CpuPool::default_pool_data();
 
         let pool_adapter = Self::resolve_pool_adapter(cpu_pool_data);
         let pool_manager = PoolManager::new(
             pool_adapter,
             TasksTrackers::current().cpu_mining_phase.clone(),
             Self::construct_callback_for_pool_status_update(),
+            Self::construct_callback_for_pool_stats_request(),
         );
         Self {
             pool_status_manager: RwLock::new(pool_manager),
+            pool_failover: RwLock::new(PoolFailover::default()),
+            is_failover_watcher_running: AtomicBool::new(false),
         }
     }
 
     pub async fn initialize_from_pool_config(config_content: &ConfigPoolsContent) {
         let cpu_pool_content = config_content.current_cpu_pool().clone();
+        INSTANCE
+            .pool_failover
+            .write()
+            .await
+            .reset(cpu_pool_content.pool_type.clone());
         let pool_adapter = Self::resolve_pool_adapter(cpu_pool_content);
 
         if *config_content.cpu_pool_enabled() {
@@ -87,6 +108,123 @@ impl CpuPoolManager {
                 .load_pool_adapter(pool_adapter)
                 .await;
         }
+
+        Self::spawn_failover_watcher().await;
+    }
+
+    /// Pool xmrig should connect to
+    /// It's the selected pool unless the failover switched to one of the fallback pools
+    pub async fn active_pool_data(config_content: &ConfigPoolsContent) -> BasePoolData<CpuPool> {
+        INSTANCE
+            .pool_failover
+            .read()
+            .await
+            .active_pool()
+            .and_then(|pool| config_content.cpu_pools().get(pool).cloned())
+            .unwrap_or_else(|| config_content.current_cpu_pool())
+    }
+
+    /// Periodically probes pools from the failover list while mining and switches between them when needed
+    /// Runs in the common tasks tracker as restarting the CPU mining phase should not stop it
+    async fn spawn_failover_watcher() {
+        if INSTANCE
+            .is_failover_watcher_running
+            .swap(true, Ordering::SeqCst)
+        {
+            return;
+        }
+
+        let mut shutdown_signal = TasksTrackers::current().common.get_signal().await;
+        TasksTrackers::current()
+            .common
+            .get_task_tracker()
+            .await
+            .spawn(async move {
+                let mut health_check_interval = interval(POOL_HEALTH_CHECK_INTERVAL);
+                health_check_interval
+                    .set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+                loop {
+                    tokio::select! {
+                        _ = shutdown_signal.wait() => {
+                            info!(target: LOG_TARGET, "CPU pool failover watcher received shutdown signal");
+                            break;
+                        }
+                        _ = health_check_interval.tick() => {
+                            Self::check_pools_health().await;
+                        }
+                    }
+                }
+                INSTANCE
+                    .is_failover_watcher_running
+                    .store(false, Ordering::SeqCst);
+            });
+    }
+
+    async fn check_pools_health() {
+        let config_content = ConfigPools::content().await;
+        if !*config_content.cpu_pool_enabled()
+            || !INSTANCE.pool_failover.read().await.is_mining_active()
+        {
+            return;
+        }
+
+        let decision = if *config_content.pool_failover_enabled() {
+            let candidates = config_content.cpu_failover_candidates();
+            let pools = candidates
+                .iter()
+                .filter_map(|pool| {
+                    config_content
+                        .cpu_pools()
+                        .get(pool)
+                        .map(|pool_data| (pool.clone(), pool_data.pool_url.clone()))
+                })
+                .collect();
+            Self::probe_failover_pools(pools).await;
+
+            INSTANCE
+                .pool_failover
+                .write()
+                .await
+                .evaluate(&candidates, *config_content.switch_back_to_primary_pool())
+        } else {
+            INSTANCE.pool_failover.read().await.evaluate_disabled()
+        };
+
+        if let Some(decision) = decision {
+            Self::switch_active_pool(decision, &config_content).await;
+        }
+    }
+
+    async fn switch_active_pool(
+        decision: PoolSwitchDecision<CpuPool>,
+        config_content: &ConfigPoolsContent,
+    ) {
+        info!(target: LOG_TARGET, "Switching CPU pool from '{}' to '{}' | reason: {:?}", decision.from, decision.to, decision.reason);
+        INSTANCE.pool_failover.write().await.apply_switch(&decision);
+
+        let active_pool_data = Self::active_pool_data(config_content).await;
+        INSTANCE
+            .pool_status_manager
+            .write()
+            .await
+            .handle_pool_change(Self::resolve_pool_adapter(active_pool_data))
+            .await;
+
+        EventsEmitter::emit_cpu_pool_failover(PoolFailoverPayload {
+            from_pool: decision.from.key_string(),
+            to_pool: decision.to.key_string(),
+            primary_pool: config_content.current_cpu_pool().pool_type.key_string(),
+            reason: decision.reason,
+            pools_health: Self::get_pools_health().await,
+        })
+        .await;
+
+        if let Err(error) = SetupManager::get_instance()
+            .restart_cpu_mining_after_pool_switch()
+            .await
+        {
+            warn!(target: LOG_TARGET, "Failed to restart CPU mining after pool switch: {error}");
+        }
     }
 }
 
@@ -95,6 +233,10 @@ impl PoolManagerInterfaceTrait<CpuPool> for CpuPoolManager {
         INSTANCE.pool_status_manager.write().await
     }
 
+    async fn get_write_failover() -> RwLockWriteGuard<'static, PoolFailover<CpuPool>> {
+        INSTANCE.pool_failover.write().await
+    }
+
     fn construct_callback_for_pool_status_update(
     ) -> impl Fn(HashMap<String, PoolStatus>) + Send + Sync + 'static {
         move |pool_statuses: HashMap<String, PoolStatus>| {
@@ -104,6 +246,36 @@ impl PoolManagerInterfaceTrait<CpuPool> for CpuPoolManager {
         }
     }
 
+    fn construct_callback_for_pool_stats_request(
+    ) -> impl Fn(PoolStatsRequest) + Send + Sync + 'static {
+        move |request: PoolStatsRequest| {
+            spawn(async move {
+                let is_mining = {
+                    let mut failover = Self::get_write_failover().await;
+                    if let Ok(pool) = CpuPool::from_string(&request.pool_name) {
+                        failover.record_stats_request(
+                            &pool,
+                            request.is_current,
+                            request.pool_status.is_some(),
+                        );
+                    }
+                    failover.is_mining_active()
+                };
+                if let Some(pool_status) = request.pool_status {
+                    // Only the pool and address currently mined to are accounted in the estimate
+                    PoolEarningsHistory::record_sample(
+                        PoolEarningsMiner::Cpu,
+                        request.pool_name,
+                        request.address,
+                        pool_status,
+                        is_mining && request.is_current,
+                    )
+                    .await;
+                }
+            });
+        }
+    }
+
     fn resolve_pool_adapter(pool: BasePoolData<CpuPool>) -> PoolApiAdapters {
         match pool.pool_type {
             CpuPool::LuckyPoolRANDOMX => PoolApiAdapters::LuckyPool(LuckyPoolAdapter::new(
@@ -117,6 +289,13 @@ impl PoolManagerInterfaceTrait<CpuPool> for CpuPoolManager {
                 pool.pool_type.key_string(),
                 pool.stats_url,
             )),
+            CpuPool::Custom { .. } => PoolApiAdapters::Generic(GenericPoolAdapter::new(
+                pool.pool_type.key_string(),
+                pool.stats_url,
+                pool.custom
+                    .map(|custom| custom.stats_paths)
+                    .unwrap_or_default(),
+            )),
         }
     }
 }
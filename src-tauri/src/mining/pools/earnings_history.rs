@@ -0,0 +1,522 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{LazyLock, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::anyhow;
+use log::{info, warn};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tokio::task::spawn_blocking;
+
+use crate::mining::pools::PoolStatus;
+
+static LOG_TARGET: &str = "tari::mining::pools::earnings_history";
+static INSTANCE: LazyLock<PoolEarningsHistory> = LazyLock::new(PoolEarningsHistory::new);
+
+const DATABASE_FILE_NAME: &str = "pool_earnings_history.sqlite";
+const SECONDS_PER_DAY: i64 = 86_400;
+const SECONDS_PER_WEEK: i64 = 7 * SECONDS_PER_DAY;
+/// Unix epoch started on Thursday, weekly periods are shifted so they start on Monday
+const WEEK_START_OFFSET: i64 = 4 * SECONDS_PER_DAY;
+/// Samples older than this are removed when the history is opened
+const RETENTION_PERIOD: i64 = 365 * SECONDS_PER_DAY;
+/// Longest gap between two samples the estimate is accumulated over
+/// Anything longer means the app was closed or offline and nothing was mined in between
+const MAX_ESTIMATE_SAMPLE_GAP: i64 = 10 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PoolEarningsMiner {
+    Cpu,
+    Gpu,
+}
+
+impl PoolEarningsMiner {
+    fn as_str(self) -> &'static str {
+        match self {
+            PoolEarningsMiner::Cpu => "Cpu",
+            PoolEarningsMiner::Gpu => "Gpu",
+        }
+    }
+
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "Cpu" => Some(PoolEarningsMiner::Cpu),
+            "Gpu" => Some(PoolEarningsMiner::Gpu),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PoolEarningsPeriod {
+    Daily,
+    Weekly,
+}
+
+impl PoolEarningsPeriod {
+    /// Length and start offset of the period in seconds
+    fn bucket(self) -> (i64, i64) {
+        match self {
+            PoolEarningsPeriod::Daily => (SECONDS_PER_DAY, 0),
+            PoolEarningsPeriod::Weekly => (SECONDS_PER_WEEK, WEEK_START_OFFSET),
+        }
+    }
+}
+
+/// Earnings of a single pool and address aggregated over a day or week (UTC)
+/// All amounts are in micro minotari
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PoolEarningsAggregate {
+    pub miner: PoolEarningsMiner,
+    pub pool_name: String,
+    pub address: String,
+    /// Unix timestamp of the period start
+    pub period_start: i64,
+    pub accepted_shares: u64,
+    /// Growth of the unpaid balance reported by the pool
+    pub earned: f64,
+    /// Sum of the detected payouts
+    pub paid: f64,
+    pub payouts_count: u64,
+    /// What `estimate_earning` predicted for the time spent mining on the pool
+    pub estimated: f64,
+    pub last_unpaid: f64,
+    pub last_balance: f64,
+}
+
+pub struct PoolEarningsHistory {
+    connection: Mutex<Option<Connection>>,
+    estimated_daily_earnings: Mutex<HashMap<PoolEarningsMiner, u64>>,
+}
+
+impl PoolEarningsHistory {
+    fn new() -> Self {
+        Self {
+            connection: Mutex::new(None),
+            estimated_daily_earnings: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Open the history database in the app local data directory
+    /// Samples are not recorded until this is called
+    pub async fn initialize(app_handle: AppHandle) {
+        let database_path = match app_handle.path().app_local_data_dir() {
+            Ok(dir) => dir.join(DATABASE_FILE_NAME),
+            Err(e) => {
+                warn!(target: LOG_TARGET, "Failed to resolve pool earnings history path: {e}");
+                return;
+            }
+        };
+
+        match spawn_blocking(move || Self::open_database(database_path)).await {
+            Ok(Ok(connection)) => {
+                if let Ok(mut lock) = INSTANCE.connection.lock() {
+                    *lock = Some(connection);
+                }
+                info!(target: LOG_TARGET, "Pool earnings history initialized");
+            }
+            Ok(Err(e)) => {
+                warn!(target: LOG_TARGET, "Failed to open pool earnings history: {e}");
+            }
+            Err(e) => {
+                warn!(target: LOG_TARGET, "Failed to open pool earnings history: {e}");
+            }
+        }
+    }
+
+    /// Keep the latest daily earnings estimate of the miner so it can be stored with the pool samples
+    /// ### Arguments
+    /// * `miner` - Miner the estimate is for
+    /// * `estimated_daily_earnings` - Output of `estimate_earning` in micro minotari per day
+    pub fn set_estimated_daily_earnings(miner: PoolEarningsMiner, estimated_daily_earnings: u64) {
+        if let Ok(mut estimates) = INSTANCE.estimated_daily_earnings.lock() {
+            estimates.insert(miner, estimated_daily_earnings);
+        }
+    }
+
+    /// Store the pool status fetched for the address and detect payouts since the previous sample
+    /// ### Arguments
+    /// * `miner` - Miner that is connected to the pool
+    /// * `pool_name` - Name of the pool adapter the status was fetched with
+    /// * `address` - Tari address the status was fetched for
+    /// * `status` - Status reported by the pool
+    /// * `is_mining` - Whether the miner was mining since the previous sample, otherwise nothing is estimated
+    pub async fn record_sample(
+        miner: PoolEarningsMiner,
+        pool_name: String,
+        address: String,
+        status: PoolStatus,
+        is_mining: bool,
+    ) {
+        let estimated_daily_earnings = if is_mining {
+            INSTANCE
+                .estimated_daily_earnings
+                .lock()
+                .map(|estimates| estimates.get(&miner).copied().unwrap_or_default())
+                .unwrap_or_default()
+        } else {
+            0
+        };
+
+        let result = spawn_blocking(move || {
+            let lock = INSTANCE
+                .connection
+                .lock()
+                .map_err(|e| anyhow!("Pool earnings history lock poisoned: {e}"))?;
+            match lock.as_ref() {
+                Some(connection) => insert_sample(
+                    connection,
+                    miner,
+                    &pool_name,
+                    &address,
+                    &status,
+                    current_timestamp(),
+                    estimated_daily_earnings,
+                ),
+                None => Ok(()),
+            }
+        })
+        .await;
+
+        match result {
+            Ok(Err(e)) => warn!(target: LOG_TARGET, "Failed to record pool earnings sample: {e}"),
+            Err(e) => warn!(target: LOG_TARGET, "Failed to record pool earnings sample: {e}"),
+            Ok(Ok(())) => {}
+        }
+    }
+
+    /// Earnings of every pool and address aggregated per period, oldest period first
+    pub async fn get_aggregates(
+        period: PoolEarningsPeriod,
+    ) -> Result<Vec<PoolEarningsAggregate>, anyhow::Error> {
+        spawn_blocking(move || {
+            let lock = INSTANCE
+                .connection
+                .lock()
+                .map_err(|e| anyhow!("Pool earnings history lock poisoned: {e}"))?;
+            match lock.as_ref() {
+                Some(connection) => query_aggregates(connection, period),
+                None => Err(anyhow!("Pool earnings history is not initialized")),
+            }
+        })
+        .await?
+    }
+
+    fn open_database(path: PathBuf) -> Result<Connection, anyhow::Error> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let connection = Connection::open(path)?;
+        create_schema(&connection)?;
+        connection.execute(
+            "DELETE FROM pool_earnings_samples WHERE recorded_at < ?1",
+            params![current_timestamp() - RETENTION_PERIOD],
+        )?;
+        Ok(connection)
+    }
+}
+
+fn current_timestamp() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| i64::try_from(duration.as_secs()).unwrap_or(i64::MAX))
+        .unwrap_or_default()
+}
+
+fn create_schema(connection: &Connection) -> Result<(), anyhow::Error> {
+    connection.execute_batch(
+        "CREATE TABLE IF NOT EXISTS pool_earnings_samples (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            miner TEXT NOT NULL,
+            pool_name TEXT NOT NULL,
+            address TEXT NOT NULL,
+            recorded_at INTEGER NOT NULL,
+            accepted_shares INTEGER NOT NULL,
+            unpaid REAL NOT NULL,
+            balance REAL NOT NULL,
+            accepted_shares_delta INTEGER NOT NULL,
+            earned REAL NOT NULL,
+            paid REAL NOT NULL,
+            estimated REAL NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS pool_earnings_samples_pool_idx
+            ON pool_earnings_samples (miner, pool_name, address, recorded_at);",
+    )?;
+    Ok(())
+}
+
+/// Insert a sample together with its deltas to the previous sample of the same pool and address
+/// A drop of the unpaid balance is treated as a payout of the difference
+#[allow(clippy::cast_possible_wrap)]
+fn insert_sample(
+    connection: &Connection,
+    miner: PoolEarningsMiner,
+    pool_name: &str,
+    address: &str,
+    status: &PoolStatus,
+    recorded_at: i64,
+    estimated_daily_earnings: u64,
+) -> Result<(), anyhow::Error> {
+    let previous_sample = connection
+        .query_row(
+            "SELECT recorded_at, accepted_shares, unpaid FROM pool_earnings_samples
+            WHERE miner = ?1 AND pool_name = ?2 AND address = ?3
+            ORDER BY recorded_at DESC, id DESC LIMIT 1",
+            params![miner.as_str(), pool_name, address],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, f64>(2)?,
+                ))
+            },
+        )
+        .optional()?;
+
+    let accepted_shares = status.accepted_shares as i64;
+    let (accepted_shares_delta, earned, paid, estimated) = match previous_sample {
+        Some((previous_recorded_at, previous_accepted_shares, previous_unpaid)) => {
+            let unpaid_delta = status.unpaid - previous_unpaid;
+            let elapsed = (recorded_at - previous_recorded_at).clamp(0, MAX_ESTIMATE_SAMPLE_GAP);
+            (
+                (accepted_shares - previous_accepted_shares).max(0),
+                unpaid_delta.max(0.0),
+                (-unpaid_delta).max(0.0),
+                estimated_daily_earnings as f64 * elapsed as f64 / SECONDS_PER_DAY as f64,
+            )
+        }
+        // First sample is only a baseline for the following ones
+        None => (0, 0.0, 0.0, 0.0),
+    };
+
+    if paid > 0.0 {
+        info!(target: LOG_TARGET, "Detected payout of {paid} from pool {pool_name}");
+    }
+
+    connection.execute(
+        "INSERT INTO pool_earnings_samples (
+            miner, pool_name, address, recorded_at, accepted_shares, unpaid, balance,
+            accepted_shares_delta, earned, paid, estimated
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+        params![
+            miner.as_str(),
+            pool_name,
+            address,
+            recorded_at,
+            accepted_shares,
+            status.unpaid,
+            status.balance,
+            accepted_shares_delta,
+            earned,
+            paid,
+            estimated
+        ],
+    )?;
+    Ok(())
+}
+
+fn query_aggregates(
+    connection: &Connection,
+    period: PoolEarningsPeriod,
+) -> Result<Vec<PoolEarningsAggregate>, anyhow::Error> {
+    let (period_length, period_offset) = period.bucket();
+    // SQLite returns bare columns from the row holding MAX() so last_unpaid/last_balance come from the latest sample
+    let mut statement = connection.prepare(
+        "SELECT miner, pool_name, address, (recorded_at - ?2) / ?1 * ?1 + ?2 AS period_start,
+            SUM(accepted_shares_delta), SUM(earned), SUM(paid), SUM(paid > 0), SUM(estimated),
+            MAX(recorded_at), unpaid, balance
+        FROM pool_earnings_samples
+        GROUP BY miner, pool_name, address, period_start
+        ORDER BY period_start, miner, pool_name, address",
+    )?;
+
+    let rows = statement.query_map(params![period_length, period_offset], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            PoolEarningsAggregate {
+                miner: PoolEarningsMiner::Cpu,
+                pool_name: row.get(1)?,
+                address: row.get(2)?,
+                period_start: row.get(3)?,
+                accepted_shares: row.get::<_, i64>(4)?.max(0) as u64,
+                earned: row.get(5)?,
+                paid: row.get(6)?,
+                payouts_count: row.get::<_, i64>(7)?.max(0) as u64,
+                estimated: row.get(8)?,
+                last_unpaid: row.get(10)?,
+                last_balance: row.get(11)?,
+            },
+        ))
+    })?;
+
+    let mut aggregates = Vec::new();
+    for row in rows {
+        let (miner, aggregate) = row?;
+        match PoolEarningsMiner::from_str(&miner) {
+            Some(miner) => aggregates.push(PoolEarningsAggregate { miner, ..aggregate }),
+            None => warn!(target: LOG_TARGET, "Skipping pool earnings of unknown miner: {miner}"),
+        }
+    }
+    Ok(aggregates)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    const POOL: &str = "LuckyPoolRANDOMX";
+    const ADDRESS: &str = "address";
+    // Monday, 2024-01-01 00:00:00 UTC
+    const MONDAY: i64 = 1_704_067_200;
+
+    fn status(accepted_shares: u64, unpaid: f64) -> PoolStatus {
+        PoolStatus {
+            accepted_shares,
+            unpaid,
+            balance: unpaid,
+            min_payout: 0,
+        }
+    }
+
+    fn connection() -> Connection {
+        let connection = Connection::open_in_memory().unwrap();
+        create_schema(&connection).unwrap();
+        connection
+    }
+
+    fn insert(connection: &Connection, status: PoolStatus, recorded_at: i64, estimate: u64) {
+        insert_sample(
+            connection,
+            PoolEarningsMiner::Cpu,
+            POOL,
+            ADDRESS,
+            &status,
+            recorded_at,
+            estimate,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn first_sample_is_a_baseline() {
+        let connection = connection();
+        insert(&connection, status(100, 5_000.0), MONDAY, 86_400);
+
+        let aggregates = query_aggregates(&connection, PoolEarningsPeriod::Daily).unwrap();
+        assert_eq!(aggregates.len(), 1);
+        assert_eq!(aggregates[0].earned, 0.0);
+        assert_eq!(aggregates[0].accepted_shares, 0);
+        assert_eq!(aggregates[0].estimated, 0.0);
+        assert_eq!(aggregates[0].last_unpaid, 5_000.0);
+    }
+
+    #[test]
+    fn unpaid_drop_is_detected_as_payout() {
+        let connection = connection();
+        insert(&connection, status(10, 1_000.0), MONDAY, 0);
+        insert(&connection, status(20, 3_000.0), MONDAY + 60, 0);
+        insert(&connection, status(30, 500.0), MONDAY + 120, 0);
+        insert(&connection, status(40, 1_500.0), MONDAY + 180, 0);
+
+        let aggregates = query_aggregates(&connection, PoolEarningsPeriod::Daily).unwrap();
+        assert_eq!(aggregates.len(), 1);
+        assert_eq!(aggregates[0].earned, 3_000.0);
+        assert_eq!(aggregates[0].paid, 2_500.0);
+        assert_eq!(aggregates[0].payouts_count, 1);
+        assert_eq!(aggregates[0].accepted_shares, 30);
+        assert_eq!(aggregates[0].last_unpaid, 1_500.0);
+    }
+
+    #[test]
+    fn estimate_is_accumulated_over_capped_gaps() {
+        let connection = connection();
+        // 86400 per day is 1 per second
+        insert(&connection, status(0, 0.0), MONDAY, 86_400);
+        insert(&connection, status(0, 0.0), MONDAY + 60, 86_400);
+        // App was closed for an hour, only the max gap counts
+        insert(&connection, status(0, 0.0), MONDAY + 60 + 3_600, 86_400);
+
+        let aggregates = query_aggregates(&connection, PoolEarningsPeriod::Daily).unwrap();
+        assert_eq!(
+            aggregates[0].estimated,
+            (60 + MAX_ESTIMATE_SAMPLE_GAP) as f64
+        );
+    }
+
+    #[test]
+    fn samples_are_grouped_per_day_and_week() {
+        let connection = connection();
+        for day in 0..8 {
+            insert(
+                &connection,
+                status(0, (day * 100) as f64),
+                MONDAY + day * SECONDS_PER_DAY,
+                0,
+            );
+        }
+
+        let daily = query_aggregates(&connection, PoolEarningsPeriod::Daily).unwrap();
+        assert_eq!(daily.len(), 8);
+        assert_eq!(daily[1].period_start, MONDAY + SECONDS_PER_DAY);
+        assert_eq!(daily[1].earned, 100.0);
+
+        let weekly = query_aggregates(&connection, PoolEarningsPeriod::Weekly).unwrap();
+        assert_eq!(weekly.len(), 2);
+        assert_eq!(weekly[0].period_start, MONDAY);
+        assert_eq!(weekly[0].earned, 600.0);
+        assert_eq!(weekly[1].period_start, MONDAY + SECONDS_PER_WEEK);
+        assert_eq!(weekly[1].earned, 100.0);
+    }
+
+    #[test]
+    fn pools_and_addresses_are_tracked_separately() {
+        let connection = connection();
+        insert(&connection, status(0, 1_000.0), MONDAY, 0);
+        insert_sample(
+            &connection,
+            PoolEarningsMiner::Gpu,
+            POOL,
+            ADDRESS,
+            &status(0, 200.0),
+            MONDAY + 60,
+            0,
+        )
+        .unwrap();
+        insert(&connection, status(0, 1_500.0), MONDAY + 120, 0);
+
+        let aggregates = query_aggregates(&connection, PoolEarningsPeriod::Daily).unwrap();
+        assert_eq!(aggregates.len(), 2);
+        assert_eq!(aggregates[0].miner, PoolEarningsMiner::Cpu);
+        assert_eq!(aggregates[0].earned, 500.0);
+        assert_eq!(aggregates[0].paid, 0.0);
+        assert_eq!(aggregates[1].miner, PoolEarningsMiner::Gpu);
+        assert_eq!(aggregates[1].earned, 0.0);
+    }
+}
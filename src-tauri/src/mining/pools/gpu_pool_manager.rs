@@ -20,10 +20,20 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{collections::HashMap, sync::LazyLock};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        LazyLock,
+    },
+};
 
-use log::info;
-use tokio::{spawn, sync::RwLock};
+use log::{info, warn};
+use tokio::{
+    spawn,
+    sync::{RwLock, RwLockWriteGuard},
+    time::interval,
+};
 
 use crate::{
     configs::{
@@ -31,16 +41,20 @@ use crate::{
         pools::{gpu_pools::GpuPool, BasePoolData},
         trait_config::ConfigImpl,
     },
+    events::PoolFailoverPayload,
     events_emitter::EventsEmitter,
     mining::{
         gpu::consts::GpuMinerType,
         pools::{
             adapters::{
-                kryptex_pool::KryptexPoolAdapter, lucky_pool::LuckyPoolAdapter,
-                support_xmr_pool::SupportXmrPoolAdapter, PoolApiAdapters,
+                generic_pool::GenericPoolAdapter, kryptex_pool::KryptexPoolAdapter,
+                lucky_pool::LuckyPoolAdapter, support_xmr_pool::SupportXmrPoolAdapter,
+                PoolApiAdapters,
             },
+            earnings_history::{PoolEarningsHistory, PoolEarningsMiner},
+            pool_failover::{PoolFailover, PoolSwitchDecision, POOL_HEALTH_CHECK_INTERVAL},
             pools_manager::PoolManager,
-            PoolManagerInterfaceTrait, PoolStatus,
+            PoolManagerInterfaceTrait, PoolStatsRequest, PoolStatus,
         },
     },
     setup::setup_manager::SetupManager,
@@ -52,25 +66,37 @@ static INSTANCE: LazyLock<GpuPoolManager> = LazyLock::new(GpuPoolManager::new);
 
 pub struct GpuPoolManager {
     pool_status_manager: RwLock<PoolManager>,
+    pool_failover: RwLock<PoolFailover<GpuPool>>,
+    is_failover_watcher_running: AtomicBool,
+    // Miner mining to the tracked pool, miners running on their assigned devices use their own pools
+    selected_miner: RwLock<Option<GpuMinerType>>,
 }
 
 impl GpuPoolManager {
     pub fn new() -> Self {
-        let gpu_pool = GpuPool::default();
-        let gpu_pool_content = gpu_pool.default_content();
+        let gpu_pool_content = GpuPool::default_pool_data();
 
         let pool_adapter = Self::resolve_pool_adapter(gpu_pool_content);
         let pool_manager = PoolManager::new(
             pool_adapter,
             TasksTrackers::current().gpu_mining_phase.clone(),
             Self::construct_callback_for_pool_status_update(),
+            Self::construct_callback_for_pool_stats_request(),
         );
         Self {
             pool_status_manager: RwLock::new(pool_manager),
+            pool_failover: RwLock::new(PoolFailover::default()),
+            is_failover_watcher_running: AtomicBool::new(false),
+            selected_miner: RwLock::new(None),
         }
     }
     pub async fn initialize_from_pool_config(config_content: &ConfigPoolsContent) {
         let gpu_pool_content = config_content.current_gpu_pool().clone();
+        INSTANCE
+            .pool_failover
+            .write()
+            .await
+            .reset(gpu_pool_content.pool_type.clone());
         let pool_adapter = Self::resolve_pool_adapter(gpu_pool_content);
 
         if *config_content.gpu_pool_enabled() {
@@ -88,6 +114,134 @@ impl GpuPoolManager {
                 .load_pool_adapter(pool_adapter)
                 .await;
         }
+
+        Self::spawn_failover_watcher().await;
+    }
+
+    /// Pool the GPU miner should connect to
+    /// It's the selected pool unless the failover switched to one of the fallback pools
+    pub async fn active_pool_data(config_content: &ConfigPoolsContent) -> BasePoolData<GpuPool> {
+        INSTANCE
+            .pool_failover
+            .read()
+            .await
+            .active_pool()
+            .and_then(|pool| config_content.gpu_pools().get(pool).cloned())
+            .unwrap_or_else(|| config_content.current_gpu_pool())
+    }
+
+    /// Periodically probes pools from the failover list while mining and switches between them when needed
+    /// Runs in the common tasks tracker as restarting the GPU mining phase should not stop it
+    async fn spawn_failover_watcher() {
+        if INSTANCE
+            .is_failover_watcher_running
+            .swap(true, Ordering::SeqCst)
+        {
+            return;
+        }
+
+        let mut shutdown_signal = TasksTrackers::current().common.get_signal().await;
+        TasksTrackers::current()
+            .common
+            .get_task_tracker()
+            .await
+            .spawn(async move {
+                let mut health_check_interval = interval(POOL_HEALTH_CHECK_INTERVAL);
+                health_check_interval
+                    .set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+                loop {
+                    tokio::select! {
+                        _ = shutdown_signal.wait() => {
+                            info!(target: LOG_TARGET, "GPU pool failover watcher received shutdown signal");
+                            break;
+                        }
+                        _ = health_check_interval.tick() => {
+                            Self::check_pools_health().await;
+                        }
+                    }
+                }
+                INSTANCE
+                    .is_failover_watcher_running
+                    .store(false, Ordering::SeqCst);
+            });
+    }
+
+    async fn check_pools_health() {
+        let config_content = ConfigPools::content().await;
+        if !*config_content.gpu_pool_enabled()
+            || !INSTANCE.pool_failover.read().await.is_mining_active()
+        {
+            return;
+        }
+
+        let decision = if *config_content.pool_failover_enabled() {
+            let candidates = config_content.gpu_failover_candidates();
+            let pools = candidates
+                .iter()
+                .filter_map(|pool| {
+                    config_content
+                        .gpu_pools()
+                        .get(pool)
+                        .map(|pool_data| (pool.clone(), pool_data.pool_url.clone()))
+                })
+                .collect();
+            Self::probe_failover_pools(pools).await;
+
+            INSTANCE
+                .pool_failover
+                .write()
+                .await
+                .evaluate(&candidates, *config_content.switch_back_to_primary_pool())
+        } else {
+            INSTANCE.pool_failover.read().await.evaluate_disabled()
+        };
+
+        if let Some(decision) = decision {
+            Self::switch_active_pool(decision, &config_content).await;
+        }
+    }
+
+    async fn switch_active_pool(
+        decision: PoolSwitchDecision<GpuPool>,
+        config_content: &ConfigPoolsContent,
+    ) {
+        info!(target: LOG_TARGET, "Switching GPU pool from '{}' to '{}' | reason: {:?}", decision.from, decision.to, decision.reason);
+        INSTANCE.pool_failover.write().await.apply_switch(&decision);
+
+        let active_pool_data = Self::active_pool_data(config_content).await;
+        INSTANCE
+            .pool_status_manager
+            .write()
+            .await
+            .handle_pool_change(Self::resolve_pool_adapter(active_pool_data))
+            .await;
+
+        EventsEmitter::emit_gpu_pool_failover(PoolFailoverPayload {
+            from_pool: decision.from.key_string(),
+            to_pool: decision.to.key_string(),
+            primary_pool: config_content.current_gpu_pool().pool_type.key_string(),
+            reason: decision.reason,
+            pools_health: Self::get_pools_health().await,
+        })
+        .await;
+
+        if let Err(error) = SetupManager::get_instance()
+            .restart_gpu_mining_after_pool_switch()
+            .await
+        {
+            warn!(target: LOG_TARGET, "Failed to restart GPU mining after pool switch: {error}");
+        }
+    }
+
+    /// Shares are only accounted for the selected miner as other miners don't mine to the tracked pool
+    pub async fn handle_gpu_miner_shares_update(
+        miner: &GpuMinerType,
+        accepted_shares: u64,
+        rejected_shares: u64,
+    ) {
+        if INSTANCE.selected_miner.read().await.as_ref() == Some(miner) {
+            Self::handle_miner_shares_update(accepted_shares, rejected_shares).await;
+        }
     }
 
     /// Handle the case when user switches or fallbacks the GPU miner type (e.g., from Lolminer to Graxil)
@@ -101,6 +255,7 @@ impl GpuPoolManager {
     /// ### Arguments
     /// * `miner` - The new GPU miner type
     pub async fn handle_miner_switch(miner: GpuMinerType) {
+        *INSTANCE.selected_miner.write().await = Some(miner.clone());
         let current_pool_content = ConfigPools::content().await.current_gpu_pool().clone();
 
         if !miner.is_pool_mining_supported() {
@@ -117,7 +272,7 @@ impl GpuPoolManager {
             EventsEmitter::emit_pools_config_loaded(&ConfigPools::content().await.clone()).await;
         }
 
-        if miner.is_pool_supported(&current_pool_content.pool_type) {
+        if miner.is_pool_supported(&current_pool_content) {
             info!(target: LOG_TARGET, "Current selected GPU pool '{}' supports the new miner type '{miner:?}', no pool switch needed", current_pool_content.pool_name);
         } else {
             info!(target: LOG_TARGET, "Current selected GPU pool '{}' does not support the new miner type '{miner:?}', switching to default pool for that miner", current_pool_content.pool_name);
@@ -133,6 +288,11 @@ impl GpuPoolManager {
 
                 let default_pool_content = ConfigPools::content().await.current_gpu_pool().clone();
 
+                INSTANCE
+                    .pool_failover
+                    .write()
+                    .await
+                    .reset(default_pool_content.pool_type.clone());
                 INSTANCE
                     .pool_status_manager
                     .write()
@@ -145,10 +305,14 @@ impl GpuPoolManager {
 }
 
 impl PoolManagerInterfaceTrait<GpuPool> for GpuPoolManager {
-    async fn get_write_manager() -> tokio::sync::RwLockWriteGuard<'static, PoolManager> {
+    async fn get_write_manager() -> RwLockWriteGuard<'static, PoolManager> {
         INSTANCE.pool_status_manager.write().await
     }
 
+    async fn get_write_failover() -> RwLockWriteGuard<'static, PoolFailover<GpuPool>> {
+        INSTANCE.pool_failover.write().await
+    }
+
     fn construct_callback_for_pool_status_update(
     ) -> impl Fn(HashMap<String, PoolStatus>) + Send + Sync + 'static {
         move |pool_statuses: HashMap<String, PoolStatus>| {
@@ -158,6 +322,36 @@ impl PoolManagerInterfaceTrait<GpuPool> for GpuPoolManager {
         }
     }
 
+    fn construct_callback_for_pool_stats_request(
+    ) -> impl Fn(PoolStatsRequest) + Send + Sync + 'static {
+        move |request: PoolStatsRequest| {
+            spawn(async move {
+                let is_mining = {
+                    let mut failover = Self::get_write_failover().await;
+                    if let Ok(pool) = GpuPool::from_string(&request.pool_name) {
+                        failover.record_stats_request(
+                            &pool,
+                            request.is_current,
+                            request.pool_status.is_some(),
+                        );
+                    }
+                    failover.is_mining_active()
+                };
+                if let Some(pool_status) = request.pool_status {
+                    // Only the pool and address currently mined to are accounted in the estimate
+                    PoolEarningsHistory::record_sample(
+                        PoolEarningsMiner::Gpu,
+                        request.pool_name,
+                        request.address,
+                        pool_status,
+                        is_mining && request.is_current,
+                    )
+                    .await;
+                }
+            });
+        }
+    }
+
     fn resolve_pool_adapter(pool: BasePoolData<GpuPool>) -> PoolApiAdapters {
         match pool.pool_type {
             GpuPool::LuckyPoolC29 => PoolApiAdapters::LuckyPool(LuckyPoolAdapter::new(
@@ -179,6 +373,13 @@ impl PoolManagerInterfaceTrait<GpuPool> for GpuPoolManager {
             GpuPool::SupportXTMPoolSHA3X => PoolApiAdapters::SupportXmr(
                 SupportXmrPoolAdapter::new(pool.pool_type.key_string(), pool.stats_url),
             ),
+            GpuPool::Custom { .. } => PoolApiAdapters::Generic(GenericPoolAdapter::new(
+                pool.pool_type.key_string(),
+                pool.stats_url,
+                pool.custom
+                    .map(|custom| custom.stats_paths)
+                    .unwrap_or_default(),
+            )),
         }
     }
 }
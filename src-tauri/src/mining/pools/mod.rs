@@ -20,23 +20,34 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt::Display, hash::Hash};
 
 use serde::Serialize;
 use tari_common_types::tari_address::TariAddress;
 use tokio::sync::RwLockWriteGuard;
 
 use crate::{
-    configs::pools::BasePoolData,
-    mining::pools::{adapters::PoolApiAdapters, pools_manager::PoolManager},
+    configs::{
+        config_pools::ConfigPools, config_wallet::ConfigWallet, pools::BasePoolData,
+        trait_config::ConfigImpl,
+    },
+    mining::pools::{
+        adapters::PoolApiAdapters,
+        cpu_pool_manager::CpuPoolManager,
+        gpu_pool_manager::GpuPoolManager,
+        pool_failover::{probe_pools_latency, PoolFailover, PoolHealth},
+        pools_manager::PoolManager,
+    },
 };
 
 mod adapters;
 pub mod cpu_pool_manager;
+pub mod earnings_history;
 pub mod gpu_pool_manager;
+pub mod pool_failover;
 pub mod pools_manager;
 
-#[derive(Clone, Debug, Serialize, Default)]
+#[derive(Clone, Debug, Serialize, Default, PartialEq)]
 pub(crate) struct PoolStatus {
     pub accepted_shares: u64,
     pub unpaid: f64,
@@ -44,11 +55,58 @@ pub(crate) struct PoolStatus {
     pub min_payout: u64,
 }
 
-pub trait PoolManagerInterfaceTrait<T> {
+/// Result of a single request to the pool stats endpoint
+#[derive(Clone, Debug)]
+pub(crate) struct PoolStatsRequest {
+    pub pool_name: String,
+    pub address: String,
+    /// Whether the request was made for the pool and address currently mined to
+    pub is_current: bool,
+    /// Status returned by the pool, `None` when the request failed
+    pub pool_status: Option<PoolStatus>,
+}
+
+/// Last known status of an address on a pool
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct PoolAddressStatus {
+    pub pool_name: String,
+    pub address: String,
+    pub is_current_pool: bool,
+    pub is_current_address: bool,
+    pub status: PoolStatus,
+}
+
+/// Addresses, besides the current mining address, pool stats should be tracked for
+/// These are the previously used mining addresses and the external address book entries
+async fn resolve_tracked_mining_addresses() -> Vec<String> {
+    let mut addresses = ConfigPools::content().await.used_mining_addresses().clone();
+    for record in ConfigWallet::content()
+        .await
+        .external_tari_addresses_book()
+        .values()
+    {
+        let address = record.address.to_base58();
+        if !addresses.contains(&address) {
+            addresses.push(address);
+        }
+    }
+    addresses
+}
+
+/// Has to be called whenever the used mining addresses or the address book change
+pub async fn refresh_tracked_mining_addresses() {
+    let tracked_addresses = resolve_tracked_mining_addresses().await;
+    CpuPoolManager::handle_tracked_addresses_change(tracked_addresses.clone()).await;
+    GpuPoolManager::handle_tracked_addresses_change(tracked_addresses).await;
+}
+
+pub trait PoolManagerInterfaceTrait<T: Clone + Eq + Hash + Display> {
     // =============== Getters ===============
 
     async fn get_write_manager() -> RwLockWriteGuard<'static, PoolManager>;
 
+    async fn get_write_failover() -> RwLockWriteGuard<'static, PoolFailover<T>>;
+
     // =============== To be implemented by the specific pool manager (CPU/GPU) ===============
 
     /// Callback to be called when pool status is updated
@@ -59,6 +117,14 @@ pub trait PoolManagerInterfaceTrait<T> {
     fn construct_callback_for_pool_status_update(
     ) -> impl Fn(HashMap<String, PoolStatus>) + Send + Sync + 'static;
 
+    /// Callback to be called after each request to the pool stats endpoint
+    /// Results are used as part of the pool health score and stored in the earnings history
+    /// This function is called in a separate task, so it should be non-blocking
+    /// ### Arguments
+    /// * `request` - Pool, address and the result of the request
+    fn construct_callback_for_pool_stats_request(
+    ) -> impl Fn(PoolStatsRequest) + Send + Sync + 'static;
+
     /// Resolve the appropriate pool adapter based on the selected pool configuration
     /// ### Arguments
     /// * `pool` - The selected pool configuration
@@ -75,6 +141,10 @@ pub trait PoolManagerInterfaceTrait<T> {
     /// ### Arguments
     /// * `pool` - The new selected CPU pool configuration
     async fn handle_new_selected_pool(pool: BasePoolData<T>) {
+        // User picked the pool so any fallback pool selected by the failover is no longer relevant
+        Self::get_write_failover()
+            .await
+            .reset(pool.pool_type.clone());
         let new_pool_adapter = Self::resolve_pool_adapter(pool);
 
         Self::get_write_manager()
@@ -94,20 +164,77 @@ pub trait PoolManagerInterfaceTrait<T> {
             .await;
     }
 
+    /// Handle the case when the set of addresses we want pool stats for changes
+    /// This should be called along with `handle_wallet_address_change`
+    /// ### Arguments
+    /// * `addresses` - Previously used and address book addresses in base58 format
+    async fn handle_tracked_addresses_change(addresses: Vec<String>) {
+        Self::get_write_manager()
+            .await
+            .handle_tracked_addresses_change(addresses)
+            .await;
+    }
+
     /// Handle the case when user starts or stops mining as listener for pool status behave differently based on it
     /// This should be called whenever mining is started or stopped
     /// ### Arguments
     /// * `is_mining` - A boolean indicating whether mining is active or not
     async fn handle_mining_status_change(is_mining: bool) {
+        Self::get_write_failover()
+            .await
+            .set_mining_active(is_mining);
         Self::get_write_manager()
             .await
             .toggle_mining_active(is_mining)
             .await;
     }
 
+    /// Handle share counters reported by the miner for the pool it is connected to
+    /// Used to calculate the rejected share ratio of the pool health score
+    /// ### Arguments
+    /// * `accepted_shares` - Amount of accepted shares since the miner connected to the pool
+    /// * `rejected_shares` - Amount of rejected shares since the miner connected to the pool
+    async fn handle_miner_shares_update(accepted_shares: u64, rejected_shares: u64) {
+        Self::get_write_failover()
+            .await
+            .record_miner_shares(accepted_shares, rejected_shares);
+    }
+
+    /// Probe pools from the failover list and update their health
+    /// Probing is done without holding the lock as unreachable pools take a while to time out
+    /// ### Arguments
+    /// * `pools` - Pools to probe with their stratum urls
+    async fn probe_failover_pools(pools: Vec<(T, String)>) {
+        let results = probe_pools_latency(pools).await;
+        let mut failover = Self::get_write_failover().await;
+        for (pool, result) in results {
+            failover.health_mut(&pool).record_connect_result(&result);
+        }
+    }
+
+    /// Current health of all pools probed during this session
+    async fn get_pools_health() -> HashMap<String, PoolHealth> {
+        Self::get_write_failover()
+            .await
+            .health()
+            .iter()
+            .map(|(pool, health)| (pool.to_string(), health.clone()))
+            .collect()
+    }
+
+    /// Last known status of the current, previously used and address book addresses on pools used during this session
+    async fn get_address_pool_statuses() -> Vec<PoolAddressStatus> {
+        Self::get_write_manager()
+            .await
+            .get_address_pool_statuses()
+            .await
+    }
+
     /// Start a periodic task to fetch and update pool statuses
     /// This should be called when mining starts, it has handled to not start multiple tasks
     async fn start_stats_watcher() {
+        // Pools are probed by the failover only while mining
+        Self::get_write_failover().await.set_mining_active(true);
         Self::get_write_manager()
             .await
             .spawn_periodic_pool_status_update_task()
@@ -0,0 +1,509 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+    time::Duration,
+};
+
+use futures::future::join_all;
+use serde::Serialize;
+use tokio::{net::TcpStream, time::Instant};
+
+/// How often pools from the failover list are probed while mining
+pub const POOL_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+const TCP_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Latency at or below which the connection part of the score is maxed out
+const GOOD_LATENCY: Duration = Duration::from_millis(150);
+/// Latency at or above which the connection part of the score is halved, slow pools still beat unreachable ones
+const BAD_LATENCY: Duration = Duration::from_millis(2000);
+/// Consecutive failed connects after which the pool is treated as down
+const MAX_CONSECUTIVE_CONNECT_FAILURES: u32 = 3;
+/// Amount of stats endpoint results used for the success rate
+const STATS_REQUESTS_WINDOW: usize = 10;
+/// Minimum amount of shares reported by the miner before the rejected ratio is taken into account
+const MIN_SHARES_FOR_REJECTED_RATIO: u64 = 10;
+const MAX_REJECTED_RATIO: f64 = 0.5;
+/// Score below which the active pool is considered unhealthy
+const UNHEALTHY_SCORE: f64 = 0.5;
+/// Score the primary pool has to keep before switching back to it
+/// Higher than `UNHEALTHY_SCORE` so we don't flap between pools
+const RECOVERED_SCORE: f64 = 0.75;
+/// Consecutive healthy checks of the primary pool required before switching back to it
+const REQUIRED_HEALTHY_CHECKS_FOR_SWITCH_BACK: u32 = 10;
+/// Minimal time spent on a fallback pool before switching back to the primary one
+const MIN_TIME_BEFORE_SWITCH_BACK: Duration = Duration::from_secs(5 * 60);
+
+// Weights of the score components, they sum up to 1.0
+const CONNECTION_WEIGHT: f64 = 0.5;
+const STATS_WEIGHT: f64 = 0.2;
+const SHARES_WEIGHT: f64 = 0.3;
+
+#[derive(Clone, Debug, Serialize, Default)]
+pub struct PoolHealth {
+    pub latency_ms: Option<u64>,
+    pub consecutive_connect_failures: u32,
+    pub consecutive_healthy_checks: u32,
+    pub stats_success_rate: Option<f64>,
+    pub accepted_shares: u64,
+    pub rejected_shares: u64,
+    pub score: f64,
+    #[serde(skip)]
+    stats_requests: VecDeque<bool>,
+}
+
+impl PoolHealth {
+    pub fn record_connect_result(&mut self, result: &Result<Duration, anyhow::Error>) {
+        match result {
+            Ok(latency) => {
+                self.latency_ms = Some(u64::try_from(latency.as_millis()).unwrap_or(u64::MAX));
+                self.consecutive_connect_failures = 0;
+            }
+            Err(_) => {
+                self.consecutive_connect_failures =
+                    self.consecutive_connect_failures.saturating_add(1);
+            }
+        }
+        self.refresh_score();
+
+        if self.can_take_over() {
+            self.consecutive_healthy_checks = self.consecutive_healthy_checks.saturating_add(1);
+        } else {
+            self.consecutive_healthy_checks = 0;
+        }
+    }
+
+    pub fn record_stats_request(&mut self, success: bool) {
+        if self.stats_requests.len() == STATS_REQUESTS_WINDOW {
+            self.stats_requests.pop_front();
+        }
+        self.stats_requests.push_back(success);
+        #[allow(clippy::cast_precision_loss)]
+        let success_rate = self
+            .stats_requests
+            .iter()
+            .filter(|success| **success)
+            .count() as f64
+            / self.stats_requests.len() as f64;
+        self.stats_success_rate = Some(success_rate);
+        self.refresh_score();
+    }
+
+    /// Miners report share counters for the current connection, so the latest values replace the previous ones
+    pub fn record_miner_shares(&mut self, accepted_shares: u64, rejected_shares: u64) {
+        self.accepted_shares = accepted_shares;
+        self.rejected_shares = rejected_shares;
+        self.refresh_score();
+    }
+
+    /// Share counters are meaningless once the miner is no longer connected to the pool
+    pub fn clear_miner_shares(&mut self) {
+        self.record_miner_shares(0, 0);
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn rejected_ratio(&self) -> Option<f64> {
+        let total_shares = self.accepted_shares.saturating_add(self.rejected_shares);
+        if total_shares < MIN_SHARES_FOR_REJECTED_RATIO {
+            return None;
+        }
+        Some(self.rejected_shares as f64 / total_shares as f64)
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn connection_score(&self) -> f64 {
+        if self.consecutive_connect_failures >= MAX_CONSECUTIVE_CONNECT_FAILURES {
+            return 0.0;
+        }
+        let latency_score = match self.latency_ms {
+            Some(latency_ms) => {
+                let good = GOOD_LATENCY.as_millis() as f64;
+                let bad = BAD_LATENCY.as_millis() as f64;
+                1.0 - ((latency_ms as f64 - good) / (bad - good)).clamp(0.0, 1.0) * 0.5
+            }
+            // Not probed yet, we don't want to punish the pool for it
+            None => 1.0,
+        };
+        // Single failed connects happen, the pool is only degraded until it fails repeatedly
+        if self.consecutive_connect_failures > 0 {
+            latency_score * 0.5
+        } else {
+            latency_score
+        }
+    }
+
+    fn refresh_score(&mut self) {
+        let stats_score = self.stats_success_rate.unwrap_or(1.0);
+        let shares_score = 1.0 - self.rejected_ratio().unwrap_or(0.0);
+        self.score = CONNECTION_WEIGHT * self.connection_score()
+            + STATS_WEIGHT * stats_score
+            + SHARES_WEIGHT * shares_score;
+    }
+
+    /// The pool should not be mined on anymore
+    pub fn is_failing(&self) -> bool {
+        self.consecutive_connect_failures >= MAX_CONSECUTIVE_CONNECT_FAILURES
+            || self
+                .rejected_ratio()
+                .is_some_and(|ratio| ratio > MAX_REJECTED_RATIO)
+            || self.score < UNHEALTHY_SCORE
+    }
+
+    /// The pool was reachable on the last probe and can be switched to
+    pub fn can_take_over(&self) -> bool {
+        self.latency_ms.is_some() && self.consecutive_connect_failures == 0 && !self.is_failing()
+    }
+
+    /// The pool was stable long enough to switch back to it
+    pub fn has_recovered(&self) -> bool {
+        self.can_take_over()
+            && self.consecutive_healthy_checks >= REQUIRED_HEALTHY_CHECKS_FOR_SWITCH_BACK
+            && self.score >= RECOVERED_SCORE
+    }
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+pub enum PoolSwitchReason {
+    /// Active pool became unhealthy and the next healthy pool from the failover list was selected
+    PoolUnhealthy,
+    /// Primary pool recovered and switch back policy is enabled
+    PrimaryRecovered,
+    /// Failover was disabled by the user while mining on a fallback pool
+    FailoverDisabled,
+}
+
+#[derive(Clone, Debug)]
+pub struct PoolSwitchDecision<T> {
+    pub from: T,
+    pub to: T,
+    pub reason: PoolSwitchReason,
+}
+
+/// Tracks health of the pools from the failover list and decides when to switch between them
+/// Primary pool is the one selected by the user, the active pool is the one miners are connected to
+pub struct PoolFailover<T> {
+    primary_pool: Option<T>,
+    active_pool: Option<T>,
+    last_switch_at: Option<Instant>,
+    is_mining_active: bool,
+    health: HashMap<T, PoolHealth>,
+}
+
+impl<T: Clone + Eq + Hash> Default for PoolFailover<T> {
+    fn default() -> Self {
+        Self {
+            primary_pool: None,
+            active_pool: None,
+            last_switch_at: None,
+            is_mining_active: false,
+            health: HashMap::new(),
+        }
+    }
+}
+
+impl<T: Clone + Eq + Hash> PoolFailover<T> {
+    /// Forget about the fallback pool, e.g. when user selects a different pool
+    pub fn reset(&mut self, primary_pool: T) {
+        self.primary_pool = Some(primary_pool);
+        self.active_pool = None;
+        self.last_switch_at = None;
+    }
+
+    /// Fallback pool the miners are connected to, None when mining on the primary pool
+    pub fn active_pool(&self) -> Option<&T> {
+        self.active_pool.as_ref()
+    }
+
+    /// Pool the miners are connected to, either the fallback or the primary one
+    pub fn current_pool(&self) -> Option<&T> {
+        self.active_pool.as_ref().or(self.primary_pool.as_ref())
+    }
+
+    pub fn is_mining_active(&self) -> bool {
+        self.is_mining_active
+    }
+
+    pub fn set_mining_active(&mut self, is_mining_active: bool) {
+        self.is_mining_active = is_mining_active;
+    }
+
+    pub fn health(&self) -> &HashMap<T, PoolHealth> {
+        &self.health
+    }
+
+    pub fn health_mut(&mut self, pool: &T) -> &mut PoolHealth {
+        self.health.entry(pool.clone()).or_default()
+    }
+
+    /// Stats of previously used and address book addresses fail routinely, e.g. when the pool doesn't know them,
+    /// so only requests for the address currently mined to count towards the pool health
+    pub fn record_stats_request(&mut self, pool: &T, is_current: bool, success: bool) {
+        if is_current {
+            self.health_mut(pool).record_stats_request(success);
+        }
+    }
+
+    pub fn record_miner_shares(&mut self, accepted_shares: u64, rejected_shares: u64) {
+        if let Some(pool) = self.current_pool().cloned() {
+            self.health_mut(&pool)
+                .record_miner_shares(accepted_shares, rejected_shares);
+        }
+    }
+
+    /// ### Arguments
+    /// * `candidates` - Ordered list of pools, the first one is the primary pool selected by the user
+    /// * `switch_back_enabled` - Whether to switch back to the primary pool once it recovers
+    pub fn evaluate(
+        &mut self,
+        candidates: &[T],
+        switch_back_enabled: bool,
+    ) -> Option<PoolSwitchDecision<T>> {
+        let primary = candidates.first()?.clone();
+        if self.primary_pool.as_ref() != Some(&primary) {
+            self.reset(primary.clone());
+        }
+        // Fallback pool could have been removed from the list in the meantime
+        if self
+            .active_pool
+            .as_ref()
+            .is_some_and(|pool| !candidates.contains(pool))
+        {
+            return Some(PoolSwitchDecision {
+                from: self.active_pool.clone()?,
+                to: primary,
+                reason: PoolSwitchReason::PoolUnhealthy,
+            });
+        }
+        let active = self.current_pool()?.clone();
+
+        if self.health.get(&active).is_some_and(PoolHealth::is_failing) {
+            return candidates
+                .iter()
+                .find(|pool| {
+                    **pool != active
+                        && self
+                            .health
+                            .get(*pool)
+                            .is_some_and(PoolHealth::can_take_over)
+                })
+                .map(|pool| PoolSwitchDecision {
+                    from: active.clone(),
+                    to: pool.clone(),
+                    reason: PoolSwitchReason::PoolUnhealthy,
+                });
+        }
+
+        let can_switch_back = self
+            .last_switch_at
+            .is_none_or(|switched_at| switched_at.elapsed() >= MIN_TIME_BEFORE_SWITCH_BACK);
+        if switch_back_enabled
+            && active != primary
+            && can_switch_back
+            && self
+                .health
+                .get(&primary)
+                .is_some_and(PoolHealth::has_recovered)
+        {
+            return Some(PoolSwitchDecision {
+                from: active,
+                to: primary,
+                reason: PoolSwitchReason::PrimaryRecovered,
+            });
+        }
+
+        None
+    }
+
+    /// Switch back to the primary pool when failover gets disabled
+    pub fn evaluate_disabled(&self) -> Option<PoolSwitchDecision<T>> {
+        Some(PoolSwitchDecision {
+            from: self.active_pool.clone()?,
+            to: self.primary_pool.clone()?,
+            reason: PoolSwitchReason::FailoverDisabled,
+        })
+    }
+
+    pub fn apply_switch(&mut self, decision: &PoolSwitchDecision<T>) {
+        self.health_mut(&decision.from).clear_miner_shares();
+        if self.primary_pool.as_ref() == Some(&decision.to) {
+            self.active_pool = None;
+        } else {
+            self.active_pool = Some(decision.to.clone());
+        }
+        self.last_switch_at = Some(Instant::now());
+    }
+}
+
+/// Measures how long it takes to open a TCP connection to the pool stratum endpoint
+pub async fn probe_pool_latency(pool_url: &str) -> Result<Duration, anyhow::Error> {
+    let started_at = Instant::now();
+    tokio::time::timeout(TCP_CONNECT_TIMEOUT, TcpStream::connect(pool_url))
+        .await
+        .map_err(|_| anyhow::anyhow!("Timed out connecting to pool: {pool_url}"))?
+        .map_err(|e| anyhow::anyhow!("Failed to connect to pool: {pool_url} | error: {e}"))?;
+    Ok(started_at.elapsed())
+}
+
+/// Probes all pools at once so a single unreachable pool doesn't delay the others
+pub async fn probe_pools_latency<T>(
+    pools: Vec<(T, String)>,
+) -> Vec<(T, Result<Duration, anyhow::Error>)> {
+    join_all(pools.into_iter().map(|(pool, pool_url)| async move {
+        let result = probe_pool_latency(&pool_url).await;
+        (pool, result)
+    }))
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn healthy() -> PoolHealth {
+        let mut health = PoolHealth::default();
+        for _ in 0..REQUIRED_HEALTHY_CHECKS_FOR_SWITCH_BACK {
+            health.record_connect_result(&Ok(Duration::from_millis(50)));
+        }
+        health
+    }
+
+    fn unreachable() -> PoolHealth {
+        let mut health = PoolHealth::default();
+        for _ in 0..MAX_CONSECUTIVE_CONNECT_FAILURES {
+            health.record_connect_result(&Err(anyhow::anyhow!("connection refused")));
+        }
+        health
+    }
+
+    fn failover(health: Vec<(&'static str, PoolHealth)>) -> PoolFailover<&'static str> {
+        let mut failover = PoolFailover::default();
+        failover.reset("primary");
+        failover.health = health.into_iter().collect();
+        failover
+    }
+
+    #[test]
+    fn score_is_maxed_out_for_fast_pool() {
+        assert!((healthy().score - 1.0).abs() < f64::EPSILON);
+        assert!(healthy().has_recovered());
+    }
+
+    #[test]
+    fn single_connect_failure_does_not_fail_pool() {
+        let mut health = healthy();
+        health.record_connect_result(&Err(anyhow::anyhow!("timeout")));
+        assert!(!health.is_failing());
+        assert!(!health.can_take_over());
+        assert_eq!(health.consecutive_healthy_checks, 0);
+    }
+
+    #[test]
+    fn high_rejected_ratio_fails_pool() {
+        let mut health = healthy();
+        health.record_miner_shares(4, 6);
+        assert!(health.is_failing());
+        health.clear_miner_shares();
+        assert!(!health.is_failing());
+    }
+
+    #[test]
+    fn rejected_ratio_ignored_for_few_shares() {
+        let mut health = healthy();
+        health.record_miner_shares(0, 3);
+        assert!(!health.is_failing());
+    }
+
+    #[test]
+    fn failed_stats_request_of_tracked_address_keeps_health() {
+        let mut failover = failover(vec![("primary", healthy())]);
+        failover.record_stats_request(&"primary", false, false);
+        let health = failover.health().get("primary").unwrap();
+        assert_eq!(health.stats_success_rate, None);
+        assert!((health.score - 1.0).abs() < f64::EPSILON);
+
+        failover.record_stats_request(&"primary", true, false);
+        assert_eq!(
+            failover.health().get("primary").unwrap().stats_success_rate,
+            Some(0.0)
+        );
+    }
+
+    #[test]
+    fn fails_over_to_first_healthy_candidate() {
+        let mut failover = failover(vec![
+            ("primary", unreachable()),
+            ("second", unreachable()),
+            ("third", healthy()),
+        ]);
+        let decision = failover
+            .evaluate(&["primary", "second", "third"], true)
+            .unwrap();
+        assert_eq!(decision.to, "third");
+        assert_eq!(decision.reason, PoolSwitchReason::PoolUnhealthy);
+
+        failover.apply_switch(&decision);
+        assert_eq!(failover.active_pool(), Some(&"third"));
+    }
+
+    #[test]
+    fn stays_on_failing_pool_without_healthy_candidates() {
+        let mut failover = failover(vec![
+            ("primary", unreachable()),
+            ("second", PoolHealth::default()),
+        ]);
+        assert!(failover.evaluate(&["primary", "second"], true).is_none());
+    }
+
+    #[test]
+    fn switches_back_only_after_recovery_and_delay() {
+        let mut failover = failover(vec![("primary", unreachable()), ("second", healthy())]);
+        let decision = failover.evaluate(&["primary", "second"], true).unwrap();
+        failover.apply_switch(&decision);
+
+        failover.health.insert("primary", healthy());
+        // Switched too recently
+        assert!(failover.evaluate(&["primary", "second"], true).is_none());
+
+        failover.last_switch_at = Instant::now().checked_sub(MIN_TIME_BEFORE_SWITCH_BACK);
+        assert!(failover.evaluate(&["primary", "second"], false).is_none());
+        let decision = failover.evaluate(&["primary", "second"], true).unwrap();
+        assert_eq!(decision.to, "primary");
+        assert_eq!(decision.reason, PoolSwitchReason::PrimaryRecovered);
+
+        failover.apply_switch(&decision);
+        assert_eq!(failover.active_pool(), None);
+        assert_eq!(failover.current_pool(), Some(&"primary"));
+    }
+
+    #[test]
+    fn new_primary_resets_fallback() {
+        let mut failover = failover(vec![("primary", unreachable()), ("second", healthy())]);
+        let decision = failover.evaluate(&["primary", "second"], true).unwrap();
+        failover.apply_switch(&decision);
+
+        assert!(failover.evaluate(&["second", "primary"], true).is_none());
+        assert_eq!(failover.active_pool(), None);
+    }
+}
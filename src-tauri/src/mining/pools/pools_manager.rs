@@ -32,12 +32,17 @@ use tokio::{
 use crate::{
     mining::pools::{
         adapters::{PoolApiAdapter, PoolApiAdapters},
-        PoolStatus,
+        PoolAddressStatus, PoolStatsRequest, PoolStatus,
     },
     tasks_tracker::TaskTrackerUtil,
 };
 
 static LOG_TARGET: &str = "tari::mining::pools::pools_manager";
+/// Statuses of addresses other than the current one rarely change, so they are fetched less often
+const TRACKED_ADDRESSES_UPDATE_INTERVAL: Duration = Duration::from_secs(600);
+
+/// Pool statuses keyed by pool name and mining address
+type AddressPoolStatuses = HashMap<(String, String), PoolStatus>;
 
 #[derive(Clone)]
 struct TaskState {
@@ -45,8 +50,13 @@ struct TaskState {
     pub cached_mining_address: String,
     pub tracking_duration: Duration,
     pub pool_statuses: Arc<RwLock<HashMap<String, PoolStatus>>>,
+    pub tracked_addresses: Arc<RwLock<Vec<String>>>,
+    pub known_pool_adapters: Arc<RwLock<HashMap<String, PoolApiAdapters>>>,
+    pub address_pool_statuses: Arc<RwLock<AddressPoolStatuses>>,
+    pub last_tracked_addresses_update: Option<Instant>,
     pub is_mining_active: bool,
     pub pool_stats_event_callback: Arc<dyn Fn(HashMap<String, PoolStatus>) + Send + Sync + 'static>,
+    pub pool_stats_request_callback: Arc<dyn Fn(PoolStatsRequest) + Send + Sync + 'static>,
 }
 
 impl TaskState {
@@ -54,16 +64,25 @@ impl TaskState {
         pool_adapter: PoolApiAdapters,
         cached_mining_address: String,
         pool_statuses: Arc<RwLock<HashMap<String, PoolStatus>>>,
+        tracked_addresses: Arc<RwLock<Vec<String>>>,
+        known_pool_adapters: Arc<RwLock<HashMap<String, PoolApiAdapters>>>,
+        address_pool_statuses: Arc<RwLock<AddressPoolStatuses>>,
         is_mining_active: bool,
         pool_stats_event_callback: Arc<dyn Fn(HashMap<String, PoolStatus>) + Send + Sync + 'static>,
+        pool_stats_request_callback: Arc<dyn Fn(PoolStatsRequest) + Send + Sync + 'static>,
     ) -> Self {
         Self {
             pool_adapter,
             cached_mining_address,
             tracking_duration: Duration::ZERO,
             pool_statuses,
+            tracked_addresses,
+            known_pool_adapters,
+            address_pool_statuses,
+            last_tracked_addresses_update: None,
             is_mining_active,
             pool_stats_event_callback,
+            pool_stats_request_callback,
         }
     }
 }
@@ -83,11 +102,16 @@ pub enum PoolManagerThreadCommands {
 /// - 300-second intervals when mining is inactive  
 /// - 1-hour grace period after mining stops before task shuts down
 /// - Automatic updates when pool or mining address configuration changes
+/// - Tracking of previously used and address book addresses on every pool used during the session
 /// - Integration with TasksTrackers for proper shutdown handling
 pub struct PoolManager {
     pool_adapter: PoolApiAdapters,
     cached_mining_address: Option<String>,
     pool_stats: Arc<RwLock<HashMap<String, PoolStatus>>>,
+    // Addresses other than the current mining address we still want stats for
+    tracked_addresses: Arc<RwLock<Vec<String>>>,
+    known_pool_adapters: Arc<RwLock<HashMap<String, PoolApiAdapters>>>,
+    address_pool_stats: Arc<RwLock<AddressPoolStatuses>>,
     // Task tracking for periodic status updates
     is_task_running: bool,
     is_mining_active: bool,
@@ -95,6 +119,7 @@ pub struct PoolManager {
     // Communication channels
     task_sender: Option<mpsc::UnboundedSender<PoolManagerThreadCommands>>,
     pool_stats_event_callback: Arc<dyn Fn(HashMap<String, PoolStatus>) + Send + Sync + 'static>,
+    pool_stats_request_callback: Arc<dyn Fn(PoolStatsRequest) + Send + Sync + 'static>,
 }
 
 impl PoolManager {
@@ -102,22 +127,34 @@ impl PoolManager {
         pool_adapter: PoolApiAdapters,
         task_tracker: Arc<TaskTrackerUtil>,
         callback: impl Fn(HashMap<String, PoolStatus>) + Send + Sync + 'static,
+        stats_request_callback: impl Fn(PoolStatsRequest) + Send + Sync + 'static,
     ) -> Self {
         Self {
             pool_adapter,
             cached_mining_address: None,
             pool_stats: Arc::new(RwLock::new(HashMap::new())),
+            tracked_addresses: Arc::new(RwLock::new(Vec::new())),
+            known_pool_adapters: Arc::new(RwLock::new(HashMap::new())),
+            address_pool_stats: Arc::new(RwLock::new(HashMap::new())),
             is_task_running: false,
             is_mining_active: true,
             task_sender: None,
             task_tracker,
             pool_stats_event_callback: Arc::new(callback),
+            pool_stats_request_callback: Arc::new(stats_request_callback),
         }
     }
 
     pub async fn update_current_pool_status(&self) {
         if let Some(address) = &self.cached_mining_address {
-            let pool_status = self.pool_adapter.request_pool_status(address.clone()).await;
+            let pool_status = Self::request_address_pool_status(
+                &self.pool_adapter,
+                address,
+                true,
+                &self.pool_stats_request_callback,
+                &self.address_pool_stats,
+            )
+            .await;
             match pool_status {
                 Ok(status) => {
                     {
@@ -142,11 +179,13 @@ impl PoolManager {
     /// ### Note
     /// Added for cases when pool is disabled but we want to load the correct last adapter from config
     pub async fn load_pool_adapter(&mut self, adapter: PoolApiAdapters) {
+        self.remember_pool_adapter(&adapter).await;
         self.pool_adapter = adapter;
     }
 
     pub async fn handle_pool_change(&mut self, adapter: PoolApiAdapters) {
         info!(target: LOG_TARGET, "Updated pool configuration to: {adapter:?}");
+        self.remember_pool_adapter(&adapter).await;
         self.pool_adapter = adapter.clone();
 
         // No point in continuing the task after mining address changed when mining is not active there propably won't be any stats to fetch
@@ -197,6 +236,49 @@ impl PoolManager {
         }
     }
 
+    /// Replace the set of addresses tracked next to the current mining address
+    /// Stats of these addresses are fetched for every pool used during the session
+    /// ### Arguments
+    /// * `addresses` - Base58 Tari addresses, the current mining address is skipped if present
+    pub async fn handle_tracked_addresses_change(&mut self, addresses: Vec<String>) {
+        let mut tracked_addresses = Vec::new();
+        for address in addresses {
+            if self.cached_mining_address.as_ref() != Some(&address)
+                && !tracked_addresses.contains(&address)
+            {
+                tracked_addresses.push(address);
+            }
+        }
+        info!(target: LOG_TARGET, "Tracking pool stats of {} additional addresses", tracked_addresses.len());
+        *self.tracked_addresses.write().await = tracked_addresses;
+    }
+
+    /// Last known status of every tracked (pool, address) pair
+    pub async fn get_address_pool_statuses(&self) -> Vec<PoolAddressStatus> {
+        let mut statuses: Vec<PoolAddressStatus> = self
+            .address_pool_stats
+            .read()
+            .await
+            .iter()
+            .map(|((pool_name, address), status)| PoolAddressStatus {
+                pool_name: pool_name.clone(),
+                address: address.clone(),
+                is_current_pool: pool_name == self.pool_adapter.name(),
+                is_current_address: self.cached_mining_address.as_ref() == Some(address),
+                status: status.clone(),
+            })
+            .collect();
+        statuses.sort_by(|a, b| (&a.pool_name, &a.address).cmp(&(&b.pool_name, &b.address)));
+        statuses
+    }
+
+    async fn remember_pool_adapter(&self, adapter: &PoolApiAdapters) {
+        self.known_pool_adapters
+            .write()
+            .await
+            .insert(adapter.name().to_string(), adapter.clone());
+    }
+
     pub async fn toggle_mining_active(&mut self, is_active: bool) {
         // Update local state
         info!(target: LOG_TARGET, "Mining active status changed to: {is_active}");
@@ -245,8 +327,12 @@ impl PoolManager {
                 self.pool_adapter.clone(),
                 tari_address.clone(),
                 self.pool_stats.clone(),
+                self.tracked_addresses.clone(),
+                self.known_pool_adapters.clone(),
+                self.address_pool_stats.clone(),
                 self.is_mining_active,
                 self.pool_stats_event_callback.clone(),
+                self.pool_stats_request_callback.clone(),
             );
 
             let mut shutdown_signal = self.task_tracker.get_signal().await;
@@ -331,10 +417,14 @@ impl PoolManager {
 
     // Static version of periodic_update_logic for use in background task
     async fn periodic_update_logic_static(task_state: &mut TaskState) {
-        let pool_status = task_state
-            .pool_adapter
-            .request_pool_status(task_state.cached_mining_address.clone())
-            .await;
+        let pool_status = Self::request_address_pool_status(
+            &task_state.pool_adapter,
+            &task_state.cached_mining_address,
+            true,
+            &task_state.pool_stats_request_callback,
+            &task_state.address_pool_statuses,
+        )
+        .await;
         match pool_status {
             Ok(status) => {
                 {
@@ -348,5 +438,73 @@ impl PoolManager {
                 warn!(target: LOG_TARGET, "Failed to fetch pool status: {e}");
             }
         }
+
+        if task_state
+            .last_tracked_addresses_update
+            .is_none_or(|updated_at| updated_at.elapsed() >= TRACKED_ADDRESSES_UPDATE_INTERVAL)
+        {
+            task_state.last_tracked_addresses_update = Some(Instant::now());
+            Self::update_tracked_addresses_static(task_state).await;
+        }
+    }
+
+    /// Fetch stats of the tracked addresses on every pool used during the session
+    /// and of the current address on the pools used before
+    async fn update_tracked_addresses_static(task_state: &TaskState) {
+        let mut addresses = task_state.tracked_addresses.read().await.clone();
+        if !addresses.contains(&task_state.cached_mining_address) {
+            addresses.push(task_state.cached_mining_address.clone());
+        }
+        let adapters: Vec<PoolApiAdapters> = task_state
+            .known_pool_adapters
+            .read()
+            .await
+            .values()
+            .cloned()
+            .collect();
+
+        for adapter in &adapters {
+            for address in &addresses {
+                if adapter.name() == task_state.pool_adapter.name()
+                    && *address == task_state.cached_mining_address
+                {
+                    continue;
+                }
+                if let Err(e) = Self::request_address_pool_status(
+                    adapter,
+                    address,
+                    false,
+                    &task_state.pool_stats_request_callback,
+                    &task_state.address_pool_statuses,
+                )
+                .await
+                {
+                    debug!(target: LOG_TARGET, "Failed to fetch pool status of tracked address {address} on {}: {e}", adapter.name());
+                }
+            }
+        }
+    }
+
+    async fn request_address_pool_status(
+        adapter: &PoolApiAdapters,
+        address: &str,
+        is_current: bool,
+        pool_stats_request_callback: &Arc<dyn Fn(PoolStatsRequest) + Send + Sync + 'static>,
+        address_pool_statuses: &Arc<RwLock<AddressPoolStatuses>>,
+    ) -> Result<PoolStatus, anyhow::Error> {
+        let pool_status = adapter.request_pool_status(address.to_string()).await;
+        (pool_stats_request_callback)(PoolStatsRequest {
+            pool_name: adapter.name().to_string(),
+            address: address.to_string(),
+            is_current,
+            pool_status: pool_status.as_ref().ok().cloned(),
+        });
+        if let Ok(status) = &pool_status {
+            address_pool_statuses.write().await.insert(
+                (adapter.name().to_string(), address.to_string()),
+                status.clone(),
+            );
+        }
+        pool_status
     }
 }
@@ -0,0 +1,605 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Display,
+    sync::LazyLock,
+    time::{Duration, Instant, SystemTime},
+};
+
+use log::{debug, info, warn};
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tokio::{select, sync::Mutex, time::interval};
+
+use crate::{
+    configs::{
+        config_mining::{ConfigMining, ConfigMiningContent},
+        config_pools::ConfigPools,
+        trait_config::ConfigImpl,
+    },
+    events_emitter::EventsEmitter,
+    mining::gpu::{
+        consts::{GpuDeviceMinerRecommendation, GpuMinerType},
+        manager::GpuManager,
+    },
+    node::node_adapter::BaseNodeStatus,
+    setup::setup_manager::SetupManager,
+    tasks_tracker::TasksTrackers,
+    utils::math_utils::estimate_earning,
+    UniverseAppState,
+};
+
+static LOG_TARGET: &str = "tari::mining::profit_switcher";
+static INSTANCE: LazyLock<ProfitSwitcher> = LazyLock::new(ProfitSwitcher::new);
+
+const EVALUATION_INTERVAL: Duration = Duration::from_secs(60);
+const MAX_LOGGED_DECISIONS: usize = 100;
+
+/// What the cpu mines, Tari RandomX goes through the cpu pool and Monero RandomX is merge mined through mmproxy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum CpuMiningTarget {
+    TariRandomX,
+    MergeMinedMoneroRandomX,
+}
+
+impl CpuMiningTarget {
+    fn network_hashrate(self, node_status: &BaseNodeStatus) -> Option<u64> {
+        match self {
+            CpuMiningTarget::TariRandomX => Some(node_status.tari_randomx_network_hashrate),
+            CpuMiningTarget::MergeMinedMoneroRandomX => {
+                Some(node_status.monero_randomx_network_hashrate)
+            }
+        }
+        .filter(|network_hashrate| *network_hashrate > 0)
+    }
+}
+
+impl Display for CpuMiningTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CpuMiningTarget::TariRandomX => write!(f, "Tari RandomX"),
+            CpuMiningTarget::MergeMinedMoneroRandomX => write!(f, "Merge mined Monero RandomX"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub enum ProfitSwitchSubject {
+    Cpu,
+    GpuDevice { device_id: u32 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ProfitSwitchOutcome {
+    Switched,
+    // Better option doesn't earn enough more than the current one
+    BelowThreshold,
+    // Subject was switched not long ago
+    CooldownActive,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfitSwitchDecision {
+    pub timestamp: SystemTime,
+    pub subject: ProfitSwitchSubject,
+    pub current_option: String,
+    pub best_option: String,
+    pub current_estimated_earnings: u64,
+    pub best_estimated_earnings: u64,
+    pub outcome: ProfitSwitchOutcome,
+}
+
+#[derive(Default)]
+struct ProfitSwitcherState {
+    decisions: VecDeque<ProfitSwitchDecision>,
+    last_switches: HashMap<ProfitSwitchSubject, Instant>,
+    // Last logged best option and outcome, the same evaluation is not logged again
+    last_outcomes: HashMap<ProfitSwitchSubject, (String, ProfitSwitchOutcome)>,
+}
+
+/// Periodically compares expected earnings of the algorithms the cpu and every gpu device can mine
+/// and switches to the best one when it earns more than the configured threshold
+/// Gpu devices are compared using their benchmarked hashrates, so the gpu benchmark has to be run first
+pub struct ProfitSwitcher {
+    state: Mutex<ProfitSwitcherState>,
+}
+
+impl ProfitSwitcher {
+    fn new() -> Self {
+        Self {
+            state: Mutex::new(ProfitSwitcherState::default()),
+        }
+    }
+
+    pub async fn get_decisions() -> Vec<ProfitSwitchDecision> {
+        INSTANCE
+            .state
+            .lock()
+            .await
+            .decisions
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    pub async fn spawn_evaluation_loop(app_handle: AppHandle) {
+        info!(target: LOG_TARGET, "Spawning profit switcher");
+        let mut shutdown_signal = TasksTrackers::current().common.get_signal().await;
+        if shutdown_signal.is_triggered() {
+            info!(target: LOG_TARGET, "Shutdown signal already triggered, exiting profit switcher");
+            return;
+        }
+
+        TasksTrackers::current()
+            .common
+            .get_task_tracker()
+            .await
+            .spawn(async move {
+                let mut evaluation_interval = interval(EVALUATION_INTERVAL);
+                loop {
+                    select! {
+                        _ = evaluation_interval.tick() => {
+                            if *ConfigMining::content().await.profit_switching_enabled() {
+                                Self::evaluate(&app_handle).await;
+                            }
+                        }
+                        _ = shutdown_signal.wait() => {
+                            info!(target: LOG_TARGET, "Shutdown signal received, stopping profit switcher");
+                            break;
+                        }
+                    }
+                }
+            });
+    }
+
+    async fn evaluate(app_handle: &AppHandle) {
+        let state = app_handle.state::<UniverseAppState>();
+        let node_status = *state.node_status_watch_rx.borrow();
+        if node_status.block_reward.as_u64() == 0 {
+            debug!(target: LOG_TARGET, "Node status is not available yet, skipping profit evaluation");
+            return;
+        }
+
+        let cpu_hash_rate = {
+            let cpu_status = state.cpu_miner_status_watch_rx.borrow();
+            if cpu_status.is_mining {
+                cpu_status.hash_rate
+            } else {
+                0.0
+            }
+        };
+        if cpu_hash_rate > 0.0 {
+            Self::evaluate_cpu(&node_status, cpu_hash_rate).await;
+        }
+
+        if GpuManager::read().await.is_mining() && *ConfigPools::content().await.gpu_pool_enabled()
+        {
+            Self::evaluate_gpu_devices(&node_status).await;
+        }
+    }
+
+    async fn evaluate_cpu(node_status: &BaseNodeStatus, hash_rate: f64) {
+        let options: Vec<(CpuMiningTarget, u64)> = [
+            CpuMiningTarget::TariRandomX,
+            CpuMiningTarget::MergeMinedMoneroRandomX,
+        ]
+        .into_iter()
+        .filter_map(|target| {
+            target
+                .network_hashrate(node_status)
+                .map(|network_hashrate| {
+                    (
+                        target,
+                        estimate_earning(network_hashrate, hash_rate, node_status.block_reward),
+                    )
+                })
+        })
+        .collect();
+
+        let current_target = if *ConfigPools::content().await.cpu_pool_enabled() {
+            CpuMiningTarget::TariRandomX
+        } else {
+            CpuMiningTarget::MergeMinedMoneroRandomX
+        };
+        let Some(&(_, current_earnings)) =
+            options.iter().find(|(target, _)| *target == current_target)
+        else {
+            return;
+        };
+        let Some(&(best_target, best_earnings)) =
+            options.iter().max_by_key(|(_, earnings)| *earnings)
+        else {
+            return;
+        };
+
+        let subject = ProfitSwitchSubject::Cpu;
+        let Some(mut outcome) = Self::resolve_outcome(
+            &subject,
+            current_earnings,
+            best_earnings,
+            &ConfigMining::content().await,
+        )
+        .await
+        else {
+            return;
+        };
+
+        if outcome == ProfitSwitchOutcome::Switched {
+            info!(target: LOG_TARGET, "Switching cpu mining from {current_target} to {best_target}");
+            let is_pool_enabled = best_target == CpuMiningTarget::TariRandomX;
+            if let Err(e) = SetupManager::get_instance()
+                .switch_cpu_pool_mining(is_pool_enabled)
+                .await
+            {
+                warn!(target: LOG_TARGET, "Failed to switch cpu mining to {best_target}: {e}");
+                outcome = ProfitSwitchOutcome::Failed;
+            }
+        }
+
+        Self::record_decision(ProfitSwitchDecision {
+            timestamp: SystemTime::now(),
+            subject,
+            current_option: current_target.to_string(),
+            best_option: best_target.to_string(),
+            current_estimated_earnings: current_earnings,
+            best_estimated_earnings: best_earnings,
+            outcome,
+        })
+        .await;
+    }
+
+    async fn evaluate_gpu_devices(node_status: &BaseNodeStatus) {
+        let config_mining = ConfigMining::content().await;
+        let selected_miner = config_mining.gpu_miner_type().clone();
+        let mut has_switched = false;
+
+        for device_id in config_mining.get_mining_gpu_devices() {
+            let options = Self::gpu_device_options(
+                config_mining.gpu_benchmark_results(),
+                device_id,
+                node_status,
+            );
+            let current_miner = config_mining
+                .gpu_devices_settings()
+                .get_assigned_miner(device_id)
+                .unwrap_or_else(|| selected_miner.clone());
+            let Some(((current, current_earnings), (best, best_earnings))) =
+                Self::current_and_best_gpu_options(&options, &current_miner)
+            else {
+                continue;
+            };
+
+            let subject = ProfitSwitchSubject::GpuDevice { device_id };
+            let Some(mut outcome) =
+                Self::resolve_outcome(&subject, *current_earnings, *best_earnings, &config_mining)
+                    .await
+            else {
+                continue;
+            };
+
+            if outcome == ProfitSwitchOutcome::Switched {
+                info!(target: LOG_TARGET, "Switching gpu device {device_id} from {:?} to {:?} mined by {}", current.algorithm, best.algorithm, best.miner);
+                // Devices without assignment are mined by the selected miner
+                let assigned_miner =
+                    Some(best.miner.clone()).filter(|miner| *miner != selected_miner);
+                match ConfigMining::update_field(
+                    ConfigMiningContent::assign_gpu_device_miner,
+                    (device_id, assigned_miner),
+                )
+                .await
+                {
+                    Ok(()) => has_switched = true,
+                    Err(e) => {
+                        warn!(target: LOG_TARGET, "Failed to assign gpu device {device_id} to {}: {e}", best.miner);
+                        outcome = ProfitSwitchOutcome::Failed;
+                    }
+                }
+            }
+
+            Self::record_decision(ProfitSwitchDecision {
+                timestamp: SystemTime::now(),
+                subject,
+                current_option: Self::gpu_option_label(current),
+                best_option: Self::gpu_option_label(best),
+                current_estimated_earnings: *current_earnings,
+                best_estimated_earnings: *best_earnings,
+                outcome,
+            })
+            .await;
+        }
+
+        if has_switched {
+            EventsEmitter::emit_update_gpu_devices_settings(
+                ConfigMining::content().await.gpu_devices_settings().clone(),
+            )
+            .await;
+            // Miners read their devices only on start
+            if let Err(e) = SetupManager::get_instance()
+                .restart_gpu_mining_after_pool_switch()
+                .await
+            {
+                warn!(target: LOG_TARGET, "Failed to restart gpu mining after profit switch: {e}");
+            }
+        }
+    }
+
+    /// Fastest benchmarked miner of every algorithm on the device with its current expected earnings
+    fn gpu_device_options(
+        benchmark_results: &[GpuDeviceMinerRecommendation],
+        device_id: u32,
+        node_status: &BaseNodeStatus,
+    ) -> Vec<(GpuDeviceMinerRecommendation, u64)> {
+        let mut fastest: Vec<&GpuDeviceMinerRecommendation> = vec![];
+        for measurement in benchmark_results
+            .iter()
+            .filter(|m| m.device_id == device_id && m.hash_rate > 0.0)
+        {
+            match fastest
+                .iter_mut()
+                .find(|current| current.algorithm == measurement.algorithm)
+            {
+                Some(current) if current.hash_rate < measurement.hash_rate => {
+                    *current = measurement
+                }
+                Some(_) => {}
+                None => fastest.push(measurement),
+            }
+        }
+
+        fastest
+            .into_iter()
+            .filter_map(|measurement| {
+                measurement
+                    .algorithm
+                    .network_hashrate(node_status)
+                    .map(|network_hashrate| {
+                        (
+                            measurement.clone(),
+                            estimate_earning(
+                                network_hashrate,
+                                measurement.hash_rate,
+                                node_status.block_reward,
+                            ),
+                        )
+                    })
+            })
+            .collect()
+    }
+
+    /// Option mined by the current miner and the most profitable one
+    fn current_and_best_gpu_options<'a>(
+        options: &'a [(GpuDeviceMinerRecommendation, u64)],
+        current_miner: &GpuMinerType,
+    ) -> Option<(
+        &'a (GpuDeviceMinerRecommendation, u64),
+        &'a (GpuDeviceMinerRecommendation, u64),
+    )> {
+        // Algorithms can only be compared when all of them have an estimate
+        if options.len() < 2 {
+            return None;
+        }
+        let current = options.iter().find(|(measurement, _)| {
+            current_miner
+                .supported_algorithms()
+                .contains(&measurement.algorithm)
+        })?;
+        let best = options.iter().max_by_key(|(_, earnings)| *earnings)?;
+        Some((current, best))
+    }
+
+    fn gpu_option_label(measurement: &GpuDeviceMinerRecommendation) -> String {
+        format!("{:?} ({})", measurement.algorithm, measurement.miner)
+    }
+
+    /// Returns `None` when the current option is already the best one
+    async fn resolve_outcome(
+        subject: &ProfitSwitchSubject,
+        current_earnings: u64,
+        best_earnings: u64,
+        config_mining: &ConfigMiningContent,
+    ) -> Option<ProfitSwitchOutcome> {
+        let since_last_switch = INSTANCE
+            .state
+            .lock()
+            .await
+            .last_switches
+            .get(subject)
+            .map(Instant::elapsed);
+        Self::compare_earnings(
+            current_earnings,
+            best_earnings,
+            *config_mining.profit_switching_threshold_percentage(),
+            since_last_switch,
+            Duration::from_secs(*config_mining.profit_switching_min_interval_secs()),
+        )
+    }
+
+    fn compare_earnings(
+        current_earnings: u64,
+        best_earnings: u64,
+        threshold_percentage: u32,
+        since_last_switch: Option<Duration>,
+        min_interval: Duration,
+    ) -> Option<ProfitSwitchOutcome> {
+        if best_earnings <= current_earnings {
+            return None;
+        }
+        let required_earnings = current_earnings
+            .saturating_mul(100 + u64::from(threshold_percentage))
+            .saturating_div(100);
+        if best_earnings <= required_earnings {
+            return Some(ProfitSwitchOutcome::BelowThreshold);
+        }
+        if since_last_switch.is_some_and(|elapsed| elapsed < min_interval) {
+            return Some(ProfitSwitchOutcome::CooldownActive);
+        }
+        Some(ProfitSwitchOutcome::Switched)
+    }
+
+    async fn record_decision(decision: ProfitSwitchDecision) {
+        {
+            let mut state = INSTANCE.state.lock().await;
+            if decision.outcome == ProfitSwitchOutcome::Switched {
+                state
+                    .last_switches
+                    .insert(decision.subject.clone(), Instant::now());
+            }
+            let evaluation = (decision.best_option.clone(), decision.outcome);
+            if state.last_outcomes.get(&decision.subject) == Some(&evaluation) {
+                return;
+            }
+            state
+                .last_outcomes
+                .insert(decision.subject.clone(), evaluation);
+            if state.decisions.len() >= MAX_LOGGED_DECISIONS {
+                state.decisions.pop_front();
+            }
+            state.decisions.push_back(decision.clone());
+        }
+        EventsEmitter::emit_profit_switch_decision(decision).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    use tari_transaction_components::tari_amount::MicroMinotari;
+
+    use crate::mining::gpu::consts::{EngineType, GpuMiningAlgorithm};
+
+    const MIN_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+    fn benchmark_result(
+        miner: GpuMinerType,
+        algorithm: GpuMiningAlgorithm,
+        hash_rate: f64,
+    ) -> GpuDeviceMinerRecommendation {
+        GpuDeviceMinerRecommendation {
+            device_id: 0,
+            engine: (miner == GpuMinerType::Glytex).then_some(EngineType::OpenCL),
+            miner,
+            algorithm,
+            hash_rate,
+            estimated_daily_earnings: None,
+        }
+    }
+
+    #[test]
+    fn switches_gpu_device_to_more_profitable_algorithm() {
+        let benchmark_results = vec![
+            benchmark_result(GpuMinerType::Graxil, GpuMiningAlgorithm::SHA3X, 1_000.0),
+            benchmark_result(GpuMinerType::Glytex, GpuMiningAlgorithm::SHA3X, 800.0),
+            benchmark_result(GpuMinerType::LolMiner, GpuMiningAlgorithm::C29, 10.0),
+        ];
+        // The device earns 10 times more on C29
+        let node_status = BaseNodeStatus {
+            sha_network_hashrate: 1_000_000,
+            c29_network_hashrate: 1_000,
+            block_reward: MicroMinotari(1_000_000),
+            ..Default::default()
+        };
+
+        let options = ProfitSwitcher::gpu_device_options(&benchmark_results, 0, &node_status);
+        assert_eq!(options.len(), 2);
+        let ((current, current_earnings), (best, best_earnings)) =
+            ProfitSwitcher::current_and_best_gpu_options(&options, &GpuMinerType::Graxil).unwrap();
+
+        assert_eq!(current.miner, GpuMinerType::Graxil);
+        assert_eq!(best.miner, GpuMinerType::LolMiner);
+        assert_eq!(best.algorithm, GpuMiningAlgorithm::C29);
+        assert_eq!(
+            ProfitSwitcher::compare_earnings(
+                *current_earnings,
+                *best_earnings,
+                10,
+                None,
+                MIN_INTERVAL
+            ),
+            Some(ProfitSwitchOutcome::Switched)
+        );
+    }
+
+    #[test]
+    fn keeps_gpu_device_when_network_hashrate_is_unknown() {
+        let benchmark_results = vec![
+            benchmark_result(GpuMinerType::Graxil, GpuMiningAlgorithm::SHA3X, 1_000.0),
+            benchmark_result(GpuMinerType::LolMiner, GpuMiningAlgorithm::C29, 10.0),
+        ];
+        let node_status = BaseNodeStatus {
+            sha_network_hashrate: 1_000_000,
+            block_reward: MicroMinotari(1_000_000),
+            ..Default::default()
+        };
+
+        let options = ProfitSwitcher::gpu_device_options(&benchmark_results, 0, &node_status);
+        assert!(
+            ProfitSwitcher::current_and_best_gpu_options(&options, &GpuMinerType::Graxil).is_none()
+        );
+    }
+
+    #[test]
+    fn does_not_switch_when_current_option_is_best() {
+        assert_eq!(
+            ProfitSwitcher::compare_earnings(100, 100, 10, None, MIN_INTERVAL),
+            None
+        );
+        assert_eq!(
+            ProfitSwitcher::compare_earnings(100, 90, 10, None, MIN_INTERVAL),
+            None
+        );
+    }
+
+    #[test]
+    fn requires_gain_above_threshold() {
+        assert_eq!(
+            ProfitSwitcher::compare_earnings(100, 110, 10, None, MIN_INTERVAL),
+            Some(ProfitSwitchOutcome::BelowThreshold)
+        );
+        assert_eq!(
+            ProfitSwitcher::compare_earnings(100, 111, 10, None, MIN_INTERVAL),
+            Some(ProfitSwitchOutcome::Switched)
+        );
+    }
+
+    #[test]
+    fn waits_for_min_interval_since_last_switch() {
+        assert_eq!(
+            ProfitSwitcher::compare_earnings(
+                100,
+                200,
+                10,
+                Some(Duration::from_secs(60)),
+                MIN_INTERVAL
+            ),
+            Some(ProfitSwitchOutcome::CooldownActive)
+        );
+        assert_eq!(
+            ProfitSwitcher::compare_earnings(100, 200, 10, Some(MIN_INTERVAL), MIN_INTERVAL),
+            Some(ProfitSwitchOutcome::Switched)
+        );
+    }
+}
@@ -0,0 +1,405 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{sync::LazyLock, time::Duration};
+
+use chrono::{Datelike, Days, Local, NaiveDateTime, TimeZone, Timelike};
+use log::{error, info};
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tokio::{select, sync::Mutex, time::interval};
+
+use crate::{
+    commands::{start_cpu_mining, start_gpu_mining, stop_cpu_mining, stop_gpu_mining},
+    configs::{
+        config_mining::{ConfigMining, ConfigMiningContent},
+        config_schedule::{
+            ConfigSchedule, ConfigScheduleContent, MiningScheduleAction, MiningScheduleRule,
+            ScheduleDay,
+        },
+        trait_config::ConfigImpl,
+    },
+    events_emitter::EventsEmitter,
+    mining::gpu::manager::GpuManager,
+    tasks_tracker::TasksTrackers,
+    UniverseAppState,
+};
+
+static LOG_TARGET: &str = "tari::mining::scheduler";
+static INSTANCE: LazyLock<MiningScheduler> = LazyLock::new(MiningScheduler::new);
+
+const EVALUATION_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MiningScheduleTransition {
+    // Unix timestamp in seconds
+    pub timestamp: i64,
+    pub action: MiningScheduleAction,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct MiningScheduleStatus {
+    pub is_enabled: bool,
+    pub current_action: Option<MiningScheduleAction>,
+    pub active_rule: Option<String>,
+    pub next_transition: Option<MiningScheduleTransition>,
+    // Whether the current action was just applied, the frontend has to sync its mining state then
+    pub was_applied: bool,
+}
+
+/// Switches mining mode and starts or stops mining following the weekly schedule
+/// Actions are applied on the first evaluation and then only when the schedule moves to another action
+/// or is changed, so mining can be toggled by hand in between. Failed actions are retried on the next evaluation
+pub struct MiningScheduler {
+    last_action: Mutex<Option<MiningScheduleAction>>,
+}
+
+impl MiningScheduler {
+    fn new() -> Self {
+        Self {
+            last_action: Mutex::new(None),
+        }
+    }
+
+    pub async fn spawn_evaluation_loop(app_handle: AppHandle) {
+        info!(target: LOG_TARGET, "Spawning mining scheduler");
+        let mut shutdown_signal = TasksTrackers::current().common.get_signal().await;
+        if shutdown_signal.is_triggered() {
+            info!(target: LOG_TARGET, "Shutdown signal already triggered, exiting mining scheduler");
+            return;
+        }
+
+        TasksTrackers::current()
+            .common
+            .get_task_tracker()
+            .await
+            .spawn(async move {
+                let mut evaluation_interval = interval(EVALUATION_INTERVAL);
+                loop {
+                    select! {
+                        _ = evaluation_interval.tick() => {
+                            Self::evaluate(&app_handle, false).await;
+                        }
+                        _ = shutdown_signal.wait() => {
+                            info!(target: LOG_TARGET, "Shutdown signal received, stopping mining scheduler");
+                            break;
+                        }
+                    }
+                }
+            });
+    }
+
+    /// Evaluates the schedule right away, `force_apply` applies the current action even without a transition
+    pub async fn evaluate(app_handle: &AppHandle, force_apply: bool) {
+        let schedule = ConfigSchedule::content().await;
+        let mut last_action = INSTANCE.last_action.lock().await;
+        if !*schedule.is_enabled() {
+            if last_action.take().is_some() {
+                EventsEmitter::emit_mining_schedule_update(MiningScheduleStatus::default()).await;
+            }
+            return;
+        }
+
+        let now = Local::now().naive_local();
+        let (action, active_rule) = resolve_action(&schedule, now);
+        let was_applied = should_apply_action(force_apply, last_action.as_ref(), &action);
+
+        if was_applied {
+            info!(target: LOG_TARGET, "Applying scheduled action: {action:?} | rule: {:?}", active_rule.map(|rule| &rule.name));
+            // Kept locked while applying so a forced evaluation doesn't run along with a tick
+            let result = Self::apply_action(app_handle, &action).await;
+            remember_action(&mut last_action, &action, &result);
+            drop(last_action);
+            if let Err(e) = result {
+                error!(target: LOG_TARGET, "Failed to apply scheduled action {action:?}: {e}");
+            }
+
+            let next_transition = next_transition(&schedule, now).and_then(|(at, action)| {
+                Local
+                    .from_local_datetime(&at)
+                    .earliest()
+                    .map(|at| MiningScheduleTransition {
+                        timestamp: at.timestamp(),
+                        action,
+                    })
+            });
+            EventsEmitter::emit_mining_schedule_update(MiningScheduleStatus {
+                is_enabled: true,
+                current_action: Some(action.clone()),
+                active_rule: active_rule.map(|rule| rule.name.clone()),
+                next_transition,
+                was_applied,
+            })
+            .await;
+        }
+    }
+
+    async fn apply_action(
+        app_handle: &AppHandle,
+        action: &MiningScheduleAction,
+    ) -> Result<(), anyhow::Error> {
+        let state = app_handle.state::<UniverseAppState>();
+        match action {
+            MiningScheduleAction::Mine { mode } => {
+                if ConfigMining::content().await.selected_mining_mode() != mode {
+                    // Miners pick up the mode's usage only when started
+                    stop_cpu_mining(state.clone(), app_handle.clone())
+                        .await
+                        .map_err(anyhow::Error::msg)?;
+                    stop_gpu_mining(app_handle.clone())
+                        .await
+                        .map_err(anyhow::Error::msg)?;
+                    ConfigMining::update_field(
+                        ConfigMiningContent::set_selected_mining_mode,
+                        mode.clone(),
+                    )
+                    .await?;
+                    EventsEmitter::emit_mining_config_loaded(&ConfigMining::content().await).await;
+                }
+                start_cpu_mining(state.clone(), app_handle.clone())
+                    .await
+                    .map_err(anyhow::Error::msg)?;
+                if !GpuManager::read().await.is_mining() {
+                    start_gpu_mining(state, app_handle.clone())
+                        .await
+                        .map_err(anyhow::Error::msg)?;
+                }
+            }
+            MiningScheduleAction::Off => {
+                stop_cpu_mining(state, app_handle.clone())
+                    .await
+                    .map_err(anyhow::Error::msg)?;
+                stop_gpu_mining(app_handle.clone())
+                    .await
+                    .map_err(anyhow::Error::msg)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The app may start inside a window with another action than mining was left in, so the first one is applied too
+fn should_apply_action(
+    force_apply: bool,
+    last_action: Option<&MiningScheduleAction>,
+    action: &MiningScheduleAction,
+) -> bool {
+    let is_first_evaluation = last_action.is_none();
+    let is_transition = last_action.is_some_and(|last| last != action);
+    force_apply || is_transition || is_first_evaluation
+}
+
+/// Failed actions are forgotten so the next evaluation retries them, e.g. when mining can't be started yet during setup
+fn remember_action(
+    last_action: &mut Option<MiningScheduleAction>,
+    action: &MiningScheduleAction,
+    result: &Result<(), anyhow::Error>,
+) {
+    *last_action = result.is_ok().then(|| action.clone());
+}
+
+fn rule_matches(rule: &MiningScheduleRule, at: NaiveDateTime) -> bool {
+    let minute = at.hour() * 60 + at.minute();
+    let start = u32::from(rule.start_minute);
+    let end = u32::from(rule.end_minute);
+    let is_today = rule.days.contains(&ScheduleDay::from(at.weekday()));
+    let is_yesterday = rule.days.contains(&ScheduleDay::from(at.weekday().pred()));
+
+    match start.cmp(&end) {
+        std::cmp::Ordering::Equal => is_today,
+        std::cmp::Ordering::Less => is_today && minute >= start && minute < end,
+        // Window past midnight started either today or yesterday
+        std::cmp::Ordering::Greater => {
+            (is_today && minute >= start) || (is_yesterday && minute < end)
+        }
+    }
+}
+
+/// First matching rule wins, the default action applies when none matches
+fn resolve_action(
+    schedule: &ConfigScheduleContent,
+    at: NaiveDateTime,
+) -> (MiningScheduleAction, Option<&MiningScheduleRule>) {
+    match schedule.rules().iter().find(|rule| rule_matches(rule, at)) {
+        Some(rule) => (rule.action.clone(), Some(rule)),
+        None => (schedule.default_action().clone(), None),
+    }
+}
+
+/// Actions change only where a rule starts or ends, so only these times within the next week are checked
+fn next_transition(
+    schedule: &ConfigScheduleContent,
+    at: NaiveDateTime,
+) -> Option<(NaiveDateTime, MiningScheduleAction)> {
+    let (current_action, _) = resolve_action(schedule, at);
+    let mut candidates: Vec<NaiveDateTime> = (0..=7)
+        .filter_map(|day_offset| at.date().checked_add_days(Days::new(day_offset)))
+        .flat_map(|date| {
+            schedule.rules().iter().flat_map(move |rule| {
+                [rule.start_minute, rule.end_minute]
+                    .into_iter()
+                    .filter_map(move |minute| {
+                        date.and_hms_opt(u32::from(minute / 60), u32::from(minute % 60), 0)
+                    })
+            })
+        })
+        .filter(|candidate| *candidate > at)
+        .collect();
+    candidates.sort();
+    candidates.dedup();
+
+    candidates.into_iter().find_map(|candidate| {
+        let (action, _) = resolve_action(schedule, candidate);
+        (action != current_action).then_some((candidate, action))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use chrono::NaiveDate;
+
+    const WEEKDAYS: [ScheduleDay; 5] = [
+        ScheduleDay::Monday,
+        ScheduleDay::Tuesday,
+        ScheduleDay::Wednesday,
+        ScheduleDay::Thursday,
+        ScheduleDay::Friday,
+    ];
+
+    fn mine(mode: &str) -> MiningScheduleAction {
+        MiningScheduleAction::Mine {
+            mode: mode.to_string(),
+        }
+    }
+
+    // Turbo 23:00-07:00, Eco otherwise, off 17:00-20:00 weekdays
+    fn time_of_use_schedule() -> ConfigScheduleContent {
+        let mut schedule = ConfigScheduleContent::default();
+        schedule.set_schedule((
+            true,
+            mine("Eco"),
+            vec![
+                MiningScheduleRule {
+                    name: "Peak".to_string(),
+                    days: WEEKDAYS.to_vec(),
+                    start_minute: 17 * 60,
+                    end_minute: 20 * 60,
+                    action: MiningScheduleAction::Off,
+                },
+                MiningScheduleRule {
+                    name: "Night".to_string(),
+                    days: [
+                        WEEKDAYS.as_slice(),
+                        &[ScheduleDay::Saturday, ScheduleDay::Sunday],
+                    ]
+                    .concat(),
+                    start_minute: 23 * 60,
+                    end_minute: 7 * 60,
+                    action: mine("Turbo"),
+                },
+            ],
+        ));
+        schedule
+    }
+
+    // 2024-01-01 is a Monday
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 1, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn resolves_rules_in_order_with_windows_past_midnight() {
+        let schedule = time_of_use_schedule();
+
+        assert_eq!(resolve_action(&schedule, at(1, 12, 0)).0, mine("Eco"));
+        assert_eq!(
+            resolve_action(&schedule, at(1, 17, 0)).0,
+            MiningScheduleAction::Off
+        );
+        assert_eq!(resolve_action(&schedule, at(1, 20, 0)).0, mine("Eco"));
+        assert_eq!(resolve_action(&schedule, at(2, 3, 0)).0, mine("Turbo"));
+        // Saturday has no peak window
+        assert_eq!(resolve_action(&schedule, at(6, 18, 0)).0, mine("Eco"));
+        // Sunday night window continues into Monday morning
+        assert_eq!(resolve_action(&schedule, at(8, 6, 59)).0, mine("Turbo"));
+    }
+
+    #[test]
+    fn finds_next_transition() {
+        let schedule = time_of_use_schedule();
+
+        assert_eq!(
+            next_transition(&schedule, at(1, 16, 0)),
+            Some((at(1, 17, 0), MiningScheduleAction::Off))
+        );
+        assert_eq!(
+            next_transition(&schedule, at(1, 17, 0)),
+            Some((at(1, 20, 0), mine("Eco")))
+        );
+        assert_eq!(
+            next_transition(&schedule, at(5, 23, 30)),
+            Some((at(6, 7, 0), mine("Eco")))
+        );
+    }
+
+    #[test]
+    fn applies_action_on_first_evaluation_and_transitions() {
+        let off = MiningScheduleAction::Off;
+
+        // App started inside an off window
+        assert!(should_apply_action(false, None, &off));
+        assert!(!should_apply_action(false, Some(&off), &off));
+        assert!(should_apply_action(false, Some(&mine("Eco")), &off));
+        assert!(should_apply_action(true, Some(&off), &off));
+    }
+
+    #[test]
+    fn retries_action_that_failed_to_apply() {
+        let eco = mine("Eco");
+        let mut last_action = None;
+
+        remember_action(
+            &mut last_action,
+            &eco,
+            &Err(anyhow::anyhow!("cpu mining phase not ready")),
+        );
+        assert_eq!(last_action, None);
+        assert!(should_apply_action(false, last_action.as_ref(), &eco));
+
+        remember_action(&mut last_action, &eco, &Ok(()));
+        assert_eq!(last_action, Some(eco.clone()));
+        assert!(!should_apply_action(false, last_action.as_ref(), &eco));
+    }
+
+    #[test]
+    fn has_no_transition_without_rules() {
+        let mut schedule = time_of_use_schedule();
+        schedule.set_rules(vec![]);
+
+        assert_eq!(next_transition(&schedule, at(1, 12, 0)), None);
+    }
+}
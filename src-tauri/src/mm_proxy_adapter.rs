@@ -20,12 +20,14 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use crate::process_adapter::{
     HealthStatus, ProcessAdapter, ProcessInstance, ProcessStartupSpec, StatusMonitor,
 };
+use crate::process_output_buffer::ProcessOutputRegistry;
+use crate::process_resource_limits::ProcessResourceLimits;
 use crate::utils::file_utils::convert_to_string;
 use crate::utils::logging_utils::setup_logging;
 use anyhow::{anyhow, Error};
@@ -164,7 +166,9 @@ impl ProcessAdapter for MergeMiningProxyAdapter {
                     data_dir,
                     pid_file_name: self.pid_file_name().to_string(),
                     name: self.name().to_string(),
+                    resource_limits: ProcessResourceLimits::default(),
                 },
+                output_buffer: ProcessOutputRegistry::buffer(self.name()),
             },
             MergeMiningProxyStatusMonitor {
                 json_rpc_port: config.port,
@@ -179,6 +183,10 @@ impl ProcessAdapter for MergeMiningProxyAdapter {
     fn pid_file_name(&self) -> &str {
         "mmproxy_pid"
     }
+
+    fn log_file_path(&self, log_folder: &Path) -> Option<PathBuf> {
+        Some(log_folder.join("proxy").join("log").join("proxy.log"))
+    }
 }
 
 #[derive(Clone)]
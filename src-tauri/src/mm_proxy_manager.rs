@@ -176,7 +176,6 @@ impl MmProxyManager {
         process_watcher.stop().await?;
         Ok(())
     }
-    #[allow(dead_code)]
     pub async fn is_running(&self) -> bool {
         let lock = self.watcher.read().await;
         lock.is_running()
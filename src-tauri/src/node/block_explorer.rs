@@ -0,0 +1,222 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::collections::BTreeSet;
+
+use anyhow::anyhow;
+use minotari_node_grpc_client::grpc::{BlockHeaderResponse, HistoricalBlock, MempoolStatsResponse};
+use serde::Serialize;
+
+// Heights requested at once, every block needs its own header lookup for the difficulty
+pub const MAX_EXPLORER_BLOCKS_PER_REQUEST: u64 = 100;
+const COINBASE_OUTPUT_TYPE: u32 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum PowAlgorithm {
+    MoneroRandomX,
+    Sha3x,
+    TariRandomX,
+    Cuckaroo29,
+    Unknown(u64),
+}
+
+impl From<u64> for PowAlgorithm {
+    fn from(pow_algo: u64) -> Self {
+        match pow_algo {
+            0 => PowAlgorithm::MoneroRandomX,
+            1 => PowAlgorithm::Sha3x,
+            2 => PowAlgorithm::TariRandomX,
+            3 => PowAlgorithm::Cuckaroo29,
+            other => PowAlgorithm::Unknown(other),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ExplorerBlock {
+    pub height: u64,
+    pub hash: String,
+    pub prev_hash: String,
+    pub timestamp: u64,
+    pub pow_algo: PowAlgorithm,
+    pub difficulty: u64,
+    pub reward: u64,
+    pub confirmations: u64,
+    /// Extra data miners put in their coinbases, usually the pool or miner name
+    pub coinbase_extras: Vec<String>,
+    pub num_coinbases: usize,
+    pub num_kernels: usize,
+    pub num_inputs: usize,
+    pub num_outputs: usize,
+}
+
+impl ExplorerBlock {
+    pub fn from_grpc(
+        historical_block: HistoricalBlock,
+        header_response: &BlockHeaderResponse,
+    ) -> Result<Self, anyhow::Error> {
+        let block = historical_block
+            .block
+            .ok_or_else(|| anyhow!("Block data missing"))?;
+        let header = block
+            .header
+            .ok_or_else(|| anyhow!("Block header missing"))?;
+        let body = block.body.unwrap_or_default();
+
+        let coinbase_features = body
+            .outputs
+            .iter()
+            .filter_map(|output| output.features.as_ref())
+            .filter(|features| features.output_type == COINBASE_OUTPUT_TYPE)
+            .collect::<Vec<_>>();
+        // Pools pay out with many coinbases sharing the same extra
+        let coinbase_extras = coinbase_features
+            .iter()
+            .filter(|features| !features.coinbase_extra.is_empty())
+            .map(|features| String::from_utf8_lossy(&features.coinbase_extra).into_owned())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+
+        Ok(Self {
+            height: header.height,
+            hash: hex::encode(&header.hash),
+            prev_hash: hex::encode(&header.prev_hash),
+            timestamp: header.timestamp,
+            pow_algo: header
+                .pow
+                .map_or(PowAlgorithm::Unknown(u64::MAX), |pow| pow.pow_algo.into()),
+            difficulty: header_response.difficulty,
+            reward: header_response.reward,
+            confirmations: historical_block.confirmations,
+            coinbase_extras,
+            num_coinbases: coinbase_features.len(),
+            num_kernels: body.kernels.len(),
+            num_inputs: body.inputs.len(),
+            num_outputs: body.outputs.len(),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct MempoolStats {
+    pub unconfirmed_txs: u64,
+    pub reorg_txs: u64,
+    pub unconfirmed_weight: u64,
+}
+
+impl From<MempoolStatsResponse> for MempoolStats {
+    fn from(response: MempoolStatsResponse) -> Self {
+        Self {
+            unconfirmed_txs: response.unconfirmed_txs,
+            reorg_txs: response.reorg_txs,
+            unconfirmed_weight: response.unconfirmed_weight,
+        }
+    }
+}
+
+pub fn validate_height_range(from_height: u64, to_height: u64) -> Result<(), anyhow::Error> {
+    if from_height > to_height {
+        return Err(anyhow!("Invalid height range {from_height}..={to_height}"));
+    }
+    if to_height - from_height >= MAX_EXPLORER_BLOCKS_PER_REQUEST {
+        return Err(anyhow!(
+            "At most {MAX_EXPLORER_BLOCKS_PER_REQUEST} blocks can be requested at once"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use minotari_node_grpc_client::grpc::{
+        AggregateBody, Block, BlockHeader, OutputFeatures, ProofOfWork, TransactionKernel,
+        TransactionOutput,
+    };
+
+    use super::*;
+
+    fn coinbase(extra: &[u8]) -> TransactionOutput {
+        TransactionOutput {
+            features: Some(OutputFeatures {
+                output_type: COINBASE_OUTPUT_TYPE,
+                coinbase_extra: extra.to_vec(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn converts_historical_block() {
+        let historical_block = HistoricalBlock {
+            confirmations: 3,
+            block: Some(Block {
+                header: Some(BlockHeader {
+                    height: 42,
+                    hash: vec![0xab; 32],
+                    timestamp: 1_700_000_000,
+                    pow: Some(ProofOfWork {
+                        pow_algo: 1,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                body: Some(AggregateBody {
+                    outputs: vec![
+                        coinbase(b"pool"),
+                        coinbase(b"pool"),
+                        coinbase(b""),
+                        TransactionOutput::default(),
+                    ],
+                    kernels: vec![TransactionKernel::default(); 2],
+                    ..Default::default()
+                }),
+            }),
+        };
+        let header_response = BlockHeaderResponse {
+            difficulty: 1000,
+            reward: 5,
+            ..Default::default()
+        };
+
+        let block = ExplorerBlock::from_grpc(historical_block, &header_response).unwrap();
+        assert_eq!(block.height, 42);
+        assert_eq!(block.hash, "ab".repeat(32));
+        assert_eq!(block.pow_algo, PowAlgorithm::Sha3x);
+        assert_eq!(block.difficulty, 1000);
+        assert_eq!(block.coinbase_extras, vec!["pool".to_string()]);
+        assert_eq!(block.num_coinbases, 3);
+        assert_eq!(block.num_kernels, 2);
+        assert_eq!(block.num_outputs, 4);
+    }
+
+    #[test]
+    fn limits_height_range() {
+        assert!(validate_height_range(10, 10).is_ok());
+        assert!(validate_height_range(0, MAX_EXPLORER_BLOCKS_PER_REQUEST - 1).is_ok());
+        assert!(validate_height_range(0, MAX_EXPLORER_BLOCKS_PER_REQUEST).is_err());
+        assert!(validate_height_range(11, 10).is_err());
+    }
+}
@@ -25,8 +25,11 @@ use crate::node::node_adapter::{
     BaseNodeStatus, NodeAdapter, NodeAdapterService, NodeStatusMonitor,
 };
 use crate::node::node_manager::NodeType;
+use crate::node::node_storage::{node_network_dir, prepare_node_storage, NodeStorageProfile};
 use crate::port_allocator::PortAllocator;
 use crate::process_adapter::{ProcessAdapter, ProcessInstance, ProcessStartupSpec};
+use crate::process_output_buffer::ProcessOutputRegistry;
+use crate::process_resource_limits::ProcessResourceLimits;
 use crate::utils::file_utils::convert_to_string;
 use crate::utils::logging_utils::setup_logging;
 use async_trait::async_trait;
@@ -77,11 +80,12 @@ pub(crate) struct LocalNodeAdapter {
     status_broadcast: watch::Sender<BaseNodeStatus>,
     pub(crate) use_tor: bool,
     pub(crate) tcp_listener_port: u16,
-    pub(crate) use_pruned_mode: bool,
+    pub(crate) storage_profile: NodeStorageProfile,
     pub(crate) tor_control_port: Option<u16>,
     required_initial_peers: u32,
     pub(crate) ab_test_group: ABTestSelector,
     pub(crate) http_api_port: u16,
+    pub(crate) resource_limits: ProcessResourceLimits,
 }
 
 impl LocalNodeAdapter {
@@ -94,12 +98,13 @@ impl LocalNodeAdapter {
             grpc_address: Some(("127.0.0.1".to_string(), grpc_port)),
             status_broadcast,
             tcp_listener_port,
-            use_pruned_mode: false,
+            storage_profile: NodeStorageProfile::default(),
             required_initial_peers: 3,
             use_tor: false,
             tor_control_port: None,
             ab_test_group: ABTestSelector::GroupA,
             http_api_port,
+            resource_limits: ProcessResourceLimits::default(),
         }
     }
 
@@ -164,6 +169,14 @@ impl NodeAdapter for LocalNodeAdapter {
     fn set_ab_group(&mut self, ab_test_group: ABTestSelector) {
         self.ab_test_group = ab_test_group;
     }
+
+    fn set_resource_limits(&mut self, resource_limits: ProcessResourceLimits) {
+        self.resource_limits = resource_limits;
+    }
+
+    fn set_storage_profile(&mut self, storage_profile: NodeStorageProfile) {
+        self.storage_profile = storage_profile;
+    }
 }
 
 impl ProcessAdapter for LocalNodeAdapter {
@@ -183,7 +196,7 @@ impl ProcessAdapter for LocalNodeAdapter {
 
         info!(target: LOG_TARGET, "Starting minotari node");
         let working_dir: PathBuf = data_dir.join("node");
-        let network_dir = working_dir.join(Network::get_current().to_string().to_lowercase());
+        let network_dir = node_network_dir(&data_dir);
         fs::create_dir_all(&network_dir)?;
         let migration_file = network_dir.join("migrations.json");
         let mut migration_info = MinotariNodeMigrationInfo::load_or_create(&migration_file)?;
@@ -215,6 +228,7 @@ impl ProcessAdapter for LocalNodeAdapter {
             migration_info.version = 2;
         }
         migration_info.save(&migration_file)?;
+        prepare_node_storage(&network_dir, &self.storage_profile)?;
 
         // Remove peerdb on every restart as requested by Protocol team
         let peer_db_dir = network_dir.join("peer_db");
@@ -262,7 +276,7 @@ impl ProcessAdapter for LocalNodeAdapter {
                 self.required_initial_peers
             ),
             "-p".to_string(),
-            "base_node.grpc_server_allow_methods=\"list_connected_peers, get_blocks\"".to_string(),
+            "base_node.grpc_server_allow_methods=\"list_connected_peers, get_blocks, get_header_by_hash, get_mempool_stats\"".to_string(),
             "-p".to_string(),
             "base_node.p2p.allow_test_addresses=true".to_string(),
             "-p".to_string(),
@@ -273,10 +287,11 @@ impl ProcessAdapter for LocalNodeAdapter {
                 self.http_api_port
             ),
         ];
-        if self.use_pruned_mode {
-            args.push("-p".to_string());
-            args.push("base_node.storage.pruning_horizon=100".to_string());
-        }
+        args.push("-p".to_string());
+        args.push(format!(
+            "base_node.storage.pruning_horizon={}",
+            self.storage_profile.pruning_horizon()
+        ));
         // Uncomment to test winning blocks
         // if cfg!(debug_assertions) {
         // args.push("--network".to_string());
@@ -388,7 +403,9 @@ impl ProcessAdapter for LocalNodeAdapter {
                     data_dir: data_dir.clone(),
                     pid_file_name: self.pid_file_name().to_string(),
                     name: self.name().to_string(),
+                    resource_limits: self.resource_limits.clone(),
                 },
+                output_buffer: ProcessOutputRegistry::buffer(self.name()),
             },
             NodeStatusMonitor::new(
                 NodeType::Local,
@@ -410,4 +427,13 @@ impl ProcessAdapter for LocalNodeAdapter {
     fn pid_file_name(&self) -> &str {
         "node_pid"
     }
+
+    fn log_file_path(&self, log_folder: &Path) -> Option<PathBuf> {
+        Some(
+            log_folder
+                .join("base_node")
+                .join("log")
+                .join("base_layer.log"),
+        )
+    }
 }
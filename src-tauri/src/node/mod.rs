@@ -20,8 +20,13 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+pub mod block_explorer;
+pub mod external_node;
 pub mod local_node_adapter;
 pub mod node_adapter;
 pub mod node_manager;
+pub mod node_snapshot;
+pub mod node_storage;
 pub mod remote_node_adapter;
+pub mod remote_node_selector;
 pub mod utils;
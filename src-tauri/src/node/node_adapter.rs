@@ -21,13 +21,17 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use crate::ab_test_selector::ABTestSelector;
+use crate::node::block_explorer::{ExplorerBlock, MempoolStats};
+use crate::node::external_node::{AuthenticatedBaseNodeClient, GrpcConnectionOptions};
 use crate::node::node_manager::NodeType;
+use crate::node::node_storage::NodeStorageProfile;
 use crate::node::utils::SyncProgressInfo;
 use crate::process_adapter::{HandleUnhealthyResult, HealthStatus, StatusMonitor};
+use crate::process_resource_limits::ProcessResourceLimits;
 use anyhow::{anyhow, Error};
 use async_trait::async_trait;
 use minotari_node_grpc_client::grpc::{
-    BlockHeader, Empty, GetBlocksRequest, GetNetworkStateRequest,
+    BlockHeader, Empty, GetBlocksRequest, GetHeaderByHashRequest, GetNetworkStateRequest,
 };
 use std::path::PathBuf;
 use std::time::{Duration, SystemTime};
@@ -36,7 +40,6 @@ use tokio::fs;
 
 use chrono::{NaiveDateTime, TimeZone, Utc};
 use log::{error, info, warn};
-use minotari_node_grpc_client::BaseNodeGrpcClient;
 use serde::Serialize;
 use std::collections::HashMap;
 use std::fmt::Write as _;
@@ -65,12 +68,15 @@ pub trait NodeAdapter {
     fn use_tor(&mut self, use_tor: bool);
     fn set_tor_control_port(&mut self, tor_control_port: Option<u16>);
     fn set_ab_group(&mut self, ab_group: ABTestSelector);
+    fn set_resource_limits(&mut self, resource_limits: ProcessResourceLimits);
+    fn set_storage_profile(&mut self, storage_profile: NodeStorageProfile);
 }
 
 #[derive(Debug, Clone)]
 pub(crate) struct NodeAdapterService {
     connection_address: String,
     required_sync_peers: u32,
+    connection_options: GrpcConnectionOptions,
 }
 
 impl NodeAdapterService {
@@ -78,11 +84,24 @@ impl NodeAdapterService {
         Self {
             connection_address,
             required_sync_peers,
+            connection_options: GrpcConnectionOptions::default(),
         }
     }
 
+    pub fn with_connection_options(mut self, connection_options: GrpcConnectionOptions) -> Self {
+        self.connection_options = connection_options;
+        self
+    }
+
+    async fn connect(&self) -> Result<AuthenticatedBaseNodeClient, Error> {
+        self.connection_options
+            .connect(&self.connection_address)
+            .await
+    }
+
     pub async fn get_network_state(&self) -> Result<BaseNodeStatus, NodeStatusMonitorError> {
-        let mut client = BaseNodeGrpcClient::connect(self.connection_address.clone())
+        let mut client = self
+            .connect()
             .await
             .map_err(|_| NodeStatusMonitorError::NodeNotStarted)?;
 
@@ -101,6 +120,7 @@ impl NodeAdapterService {
         Ok(BaseNodeStatus {
             sha_network_hashrate: res.sha3x_estimated_hash_rate,
             tari_randomx_network_hashrate: res.tari_randomx_estimated_hash_rate,
+            c29_network_hashrate: res.cuckaroo_estimated_hash_rate,
             monero_randomx_network_hashrate: res.monero_randomx_estimated_hash_rate,
             block_reward: MicroMinotari(res.reward),
             block_height,
@@ -118,7 +138,7 @@ impl NodeAdapterService {
         &self,
         heights: Vec<u64>,
     ) -> Result<Vec<(u64, String)>, Error> {
-        let mut client = BaseNodeGrpcClient::connect(self.connection_address.clone()).await?;
+        let mut client = self.connect().await?;
 
         let mut res = client
             .get_blocks(GetBlocksRequest { heights })
@@ -143,8 +163,75 @@ impl NodeAdapterService {
         Ok(blocks)
     }
 
+    pub async fn get_explorer_blocks(
+        &self,
+        heights: Vec<u64>,
+    ) -> Result<Vec<ExplorerBlock>, Error> {
+        let mut client = self.connect().await?;
+        let mut res = client
+            .get_blocks(GetBlocksRequest { heights })
+            .await?
+            .into_inner();
+        let mut historical_blocks = Vec::new();
+        while let Some(historical_block) = res.message().await? {
+            historical_blocks.push(historical_block);
+        }
+
+        let mut blocks = Vec::with_capacity(historical_blocks.len());
+        for historical_block in historical_blocks {
+            let hash = historical_block
+                .block
+                .as_ref()
+                .and_then(|block| block.header.as_ref())
+                .map(|header| header.hash.clone())
+                .ok_or_else(|| anyhow!("Block header missing"))?;
+            // Difficulty and reward are only part of the header response
+            let header_response = client
+                .get_header_by_hash(GetHeaderByHashRequest { hash })
+                .await?
+                .into_inner();
+            blocks.push(ExplorerBlock::from_grpc(
+                historical_block,
+                &header_response,
+            )?);
+        }
+        blocks.sort_by_key(|block| block.height);
+        Ok(blocks)
+    }
+
+    pub async fn get_explorer_block_by_hash(&self, hash: &str) -> Result<ExplorerBlock, Error> {
+        let hash = hex::decode(hash).map_err(|_| anyhow!("Invalid block hash: {hash}"))?;
+        let mut client = self.connect().await?;
+        let header_response = client
+            .get_header_by_hash(GetHeaderByHashRequest { hash })
+            .await?
+            .into_inner();
+        let height = header_response
+            .header
+            .as_ref()
+            .map(|header| header.height)
+            .ok_or_else(|| anyhow!("Block header missing"))?;
+
+        let historical_block = client
+            .get_blocks(GetBlocksRequest {
+                heights: vec![height],
+            })
+            .await?
+            .into_inner()
+            .message()
+            .await?
+            .ok_or_else(|| anyhow!("Block {height} not found"))?;
+        ExplorerBlock::from_grpc(historical_block, &header_response)
+    }
+
+    pub async fn get_mempool_stats(&self) -> Result<MempoolStats, Error> {
+        let mut client = self.connect().await?;
+        let stats = client.get_mempool_stats(Empty {}).await?.into_inner();
+        Ok(stats.into())
+    }
+
     pub async fn get_identity(&self) -> Result<NodeIdentity, Error> {
-        let mut client = BaseNodeGrpcClient::connect(self.connection_address.clone()).await?;
+        let mut client = self.connect().await?;
         let id = client.identify(Empty {}).await?;
         let res = id.into_inner();
 
@@ -161,7 +248,8 @@ impl NodeAdapterService {
         progress_percentage_tx: &watch::Sender<f64>,
         shutdown_signal: ShutdownSignal,
     ) -> Result<u64, NodeStatusMonitorError> {
-        let mut client = BaseNodeGrpcClient::connect(self.connection_address.clone())
+        let mut client = self
+            .connect()
             .await
             .map_err(|_e| NodeStatusMonitorError::NodeNotStarted)?;
 
@@ -205,7 +293,7 @@ impl NodeAdapterService {
     }
 
     pub async fn list_connected_peers(&self) -> Result<Vec<String>, anyhow::Error> {
-        let mut client = BaseNodeGrpcClient::connect(self.connection_address.clone()).await?;
+        let mut client = self.connect().await?;
         let peers_list = client
             .list_connected_peers(Empty {})
             .await
@@ -379,7 +467,7 @@ impl StatusMonitor for NodeStatusMonitor {
         &self,
         _duration_since_last_healthy_status: Duration,
     ) -> Result<HandleUnhealthyResult, anyhow::Error> {
-        if self.node_type == NodeType::Remote {
+        if matches!(self.node_type, NodeType::Remote | NodeType::External) {
             // Do not clear local node files for remote nodes
             return Ok(HandleUnhealthyResult::Continue);
         }
@@ -587,6 +675,7 @@ pub(crate) struct BaseNodeStatus {
     pub sha_network_hashrate: u64,
     pub monero_randomx_network_hashrate: u64,
     pub tari_randomx_network_hashrate: u64,
+    pub c29_network_hashrate: u64,
     pub block_reward: MicroMinotari,
     pub block_height: u64,
     pub block_time: u64,
@@ -601,6 +690,7 @@ impl Default for BaseNodeStatus {
             sha_network_hashrate: 0,
             monero_randomx_network_hashrate: 0,
             tari_randomx_network_hashrate: 0,
+            c29_network_hashrate: 0,
             block_reward: MicroMinotari(0),
             block_height: 0,
             block_time: 0,
@@ -39,9 +39,13 @@ use tokio_util::task::TaskTracker;
 
 use crate::configs::config_core::ConfigCore;
 use crate::configs::trait_config::ConfigImpl;
+use crate::node::block_explorer::{validate_height_range, ExplorerBlock, MempoolStats};
+use crate::node::external_node::ExternalNodeConfig;
 use crate::node::node_adapter::{
     NodeAdapter, NodeAdapterService, NodeIdentity, NodeStatusMonitorError, ReadinessStatus,
 };
+use crate::node::node_storage::spawn_low_disk_watchdog;
+use crate::node::remote_node_selector::RemoteNodeSelector;
 use crate::process_adapter::ProcessAdapter;
 use crate::process_stats_collector::ProcessStatsCollectorBuilder;
 use crate::process_watcher::ProcessWatcher;
@@ -70,6 +74,8 @@ pub enum NodeType {
     Remote,
     RemoteUntilLocal,
     LocalAfterRemote,
+    // Self hosted node configured in `ConfigCore::external_node`
+    External,
 }
 
 impl NodeType {
@@ -80,7 +86,13 @@ impl NodeType {
         )
     }
     pub fn is_remote(&self) -> bool {
-        matches!(self, NodeType::Remote | NodeType::RemoteUntilLocal)
+        matches!(
+            self,
+            NodeType::Remote | NodeType::RemoteUntilLocal | NodeType::External
+        )
+    }
+    pub fn is_external(&self) -> bool {
+        matches!(self, NodeType::External)
     }
 }
 
@@ -95,6 +107,7 @@ pub struct NodeManager {
     remote_node_watch_rx: watch::Receiver<BaseNodeStatus>,
     local_node_db_cleared: Arc<AtomicBool>,
     orphan_chain_detected: Arc<AtomicBool>,
+    paused_for_low_disk: Arc<AtomicBool>,
 }
 
 impl NodeManager {
@@ -123,7 +136,9 @@ impl NodeManager {
 
         let current_adapter: Box<dyn NodeAdapter + Send + Sync> = match node_type {
             NodeType::Local | NodeType::LocalAfterRemote => Box::new(local_node_adapter),
-            NodeType::Remote | NodeType::RemoteUntilLocal => Box::new(remote_node_adapter),
+            NodeType::Remote | NodeType::RemoteUntilLocal | NodeType::External => {
+                Box::new(remote_node_adapter)
+            }
         };
 
         Self {
@@ -136,6 +151,7 @@ impl NodeManager {
             remote_node_watch_rx,
             local_node_db_cleared: Arc::new(AtomicBool::new(false)),
             orphan_chain_detected: Arc::new(AtomicBool::new(false)),
+            paused_for_low_disk: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -151,6 +167,8 @@ impl NodeManager {
     ) -> Result<(), NodeManagerError> {
         let shutdown_signal = TasksTrackers::current().node_phase.get_signal().await;
         let task_tracker = TasksTrackers::current().node_phase.get_task_tracker().await;
+        // Nodes are started unpaused, a low disk pause from before the phase restart doesn't apply to them
+        self.set_paused_for_low_disk(false);
 
         if self.is_local().await {
             self.configure_adapter(
@@ -170,12 +188,21 @@ impl NodeManager {
                 task_tracker.clone(),
             )
             .await?;
+            spawn_low_disk_watchdog(
+                self.clone(),
+                base_path.clone(),
+                shutdown_signal.clone(),
+                &task_tracker,
+            );
         }
         if self.is_remote().await {
+            let is_external = self.is_external().await;
+            self.configure_remote_connection(is_external).await?;
             self.configure_adapter(
                 self.remote_node_watcher.clone(),
                 self.is_remote_current().await,
-                remote_grpc_address,
+                // The external node address is set with its connection options
+                remote_grpc_address.filter(|_| !is_external),
                 use_tor,
                 None, // no control port needed
             )
@@ -186,9 +213,17 @@ impl NodeManager {
                 config_path,
                 log_path,
                 shutdown_signal.clone(),
-                task_tracker,
+                task_tracker.clone(),
             )
             .await?;
+            // A self hosted node has nothing to fail over to
+            if !is_external {
+                RemoteNodeSelector::spawn_failover_monitor(
+                    self.clone(),
+                    shutdown_signal.clone(),
+                    &task_tracker,
+                );
+            }
         }
 
         let node_type = self.get_node_type().await;
@@ -223,8 +258,14 @@ impl NodeManager {
         if let Some(node_watcher) = node_watcher.as_mut() {
             node_watcher.adapter.use_tor(use_tor);
             node_watcher.adapter.set_tor_control_port(tor_control_port);
-            let ab_group = *ConfigCore::content().await.ab_group();
-            node_watcher.adapter.set_ab_group(ab_group);
+            let config_core = ConfigCore::content().await;
+            node_watcher.adapter.set_ab_group(*config_core.ab_group());
+            node_watcher
+                .adapter
+                .set_resource_limits(config_core.node_resource_limits().clone());
+            node_watcher
+                .adapter
+                .set_storage_profile(*config_core.node_storage_profile());
 
             if let Some(remote_grpc_address) = remote_grpc_address {
                 node_watcher.adapter.set_grpc_address(remote_grpc_address)?;
@@ -238,6 +279,24 @@ impl NodeManager {
         Ok(())
     }
 
+    async fn configure_remote_connection(&self, is_external: bool) -> Result<(), anyhow::Error> {
+        let external_node = if is_external {
+            Some(ExternalNodeConfig::load_connection_options().await?)
+        } else {
+            None
+        };
+        let mut node_watcher = self.remote_node_watcher.write().await;
+        if let Some(node_watcher) = node_watcher.as_mut() {
+            match external_node {
+                Some((endpoint, connection_options)) => node_watcher
+                    .adapter
+                    .use_external_node(&endpoint, connection_options),
+                None => node_watcher.adapter.use_public_node(),
+            }
+        }
+        Ok(())
+    }
+
     pub async fn set_node_type(&self, new_node_type: NodeType) {
         let mut node_type = self.node_type.write().await;
         *node_type = new_node_type;
@@ -459,11 +518,70 @@ impl NodeManager {
             .load(std::sync::atomic::Ordering::SeqCst)
     }
 
+    pub fn is_paused_for_low_disk(&self) -> bool {
+        self.paused_for_low_disk
+            .load(std::sync::atomic::Ordering::SeqCst)
+    }
+
+    pub fn set_paused_for_low_disk(&self, is_paused: bool) {
+        self.paused_for_low_disk
+            .store(is_paused, std::sync::atomic::Ordering::SeqCst);
+    }
+
+    pub async fn suspend_local_node(&self) -> Result<(), anyhow::Error> {
+        let node_watcher = self.local_node_watcher.read().await;
+        match node_watcher.as_ref() {
+            Some(node_watcher) => node_watcher.suspend(),
+            None => Err(anyhow::anyhow!("Local node watcher not found")),
+        }
+    }
+
+    pub async fn resume_local_node(&self) -> Result<(), anyhow::Error> {
+        let node_watcher = self.local_node_watcher.read().await;
+        match node_watcher.as_ref() {
+            Some(node_watcher) => node_watcher.resume(),
+            None => Err(anyhow::anyhow!("Local node watcher not found")),
+        }
+    }
+
+    pub async fn stop_local_node(&self) -> Result<(), anyhow::Error> {
+        let mut node_watcher = self.local_node_watcher.write().await;
+        if let Some(node_watcher) = node_watcher.as_mut() {
+            node_watcher.stop().await?;
+        }
+        Ok(())
+    }
+
     pub async fn list_connected_peers(&self) -> Result<Vec<String>, anyhow::Error> {
         let current_service = self.get_current_service().await?;
         current_service.list_connected_peers().await
     }
 
+    pub async fn get_explorer_blocks(
+        &self,
+        from_height: u64,
+        to_height: u64,
+    ) -> Result<Vec<ExplorerBlock>, anyhow::Error> {
+        validate_height_range(from_height, to_height)?;
+        let current_service = self.get_current_service().await?;
+        current_service
+            .get_explorer_blocks((from_height..=to_height).collect())
+            .await
+    }
+
+    pub async fn get_explorer_block_by_hash(
+        &self,
+        hash: &str,
+    ) -> Result<ExplorerBlock, anyhow::Error> {
+        let current_service = self.get_current_service().await?;
+        current_service.get_explorer_block_by_hash(hash).await
+    }
+
+    pub async fn get_mempool_stats(&self) -> Result<MempoolStats, anyhow::Error> {
+        let current_service = self.get_current_service().await?;
+        current_service.get_mempool_stats().await
+    }
+
     // Self Checks
     pub async fn is_local(&self) -> bool {
         let node_type = self.get_node_type().await;
@@ -483,6 +601,26 @@ impl NodeManager {
         let node_type = self.get_node_type().await;
         node_type.is_remote()
     }
+
+    pub async fn is_external(&self) -> bool {
+        let node_type = self.get_node_type().await;
+        node_type.is_external()
+    }
+
+    /// Mmproxy and solo gpu miners get the plain grpc address, external nodes requiring tls or authentication can't serve them
+    pub async fn ensure_plain_grpc_access(&self) -> Result<(), anyhow::Error> {
+        if self.is_external().await
+            && ConfigCore::content()
+                .await
+                .external_node()
+                .requires_secure_connection()?
+        {
+            return Err(anyhow::anyhow!(
+                "Merge mining and solo gpu mining can't use an external node requiring tls or authentication"
+            ));
+        }
+        Ok(())
+    }
 }
 
 // Helpers
@@ -518,7 +656,7 @@ where
 {
     let mut watcher_guard = node_watcher.write().await;
     if let Some(watcher_ref) = watcher_guard.as_mut() {
-        watcher_ref.stop_on_exit_codes = STOP_ON_ERROR_CODES.to_vec();
+        watcher_ref.restart_policy.stop_on_exit_codes = STOP_ON_ERROR_CODES.to_vec();
         watcher_ref
             .start(
                 base_path,
@@ -0,0 +1,484 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    fs::{self, File},
+    io::{BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, Context};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tari_common::configuration::Network;
+
+use crate::{
+    configs::{
+        config_core::{ConfigCore, ConfigCoreContent},
+        trait_config::ConfigImpl,
+    },
+    node::{
+        node_manager::NodeManager,
+        node_storage::{
+            load_storage_profile, node_network_dir, save_storage_profile, NodeStorageProfile,
+        },
+    },
+};
+
+const LOG_TARGET: &str = "tari::universe::node_snapshot";
+const MANIFEST_FILE: &str = "manifest.json";
+const DATABASE_FILE: &str = "data.mdb";
+const MANIFEST_VERSION: u32 = 1;
+const COPY_BUFFER_SIZE: usize = 1024 * 1024;
+
+// Requested with cli arguments, run by the node phase once the configs are loaded
+static PENDING_IMPORT: Mutex<Option<PathBuf>> = Mutex::new(None);
+static PENDING_EXPORT: Mutex<Option<PathBuf>> = Mutex::new(None);
+static PENDING_DATABASE_IMPORT: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    pub version: u32,
+    pub network: String,
+    pub block_height: u64,
+    pub tip_hash: String,
+    pub storage_profile: NodeStorageProfile,
+    pub created_at: u64,
+    pub size_bytes: u64,
+    pub sha256: String,
+}
+
+impl SnapshotManifest {
+    fn validate(&self) -> Result<(), anyhow::Error> {
+        if self.version != MANIFEST_VERSION {
+            return Err(anyhow!("Unsupported snapshot version: {}", self.version));
+        }
+        let network = Network::get_current_or_user_setting_or_default();
+        if self.network != network.as_key_str() {
+            return Err(anyhow!(
+                "Snapshot is for {} but the app runs on {}",
+                self.network,
+                network.as_key_str()
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn node_db_dir(data_dir: &Path) -> PathBuf {
+    node_network_dir(data_dir)
+        .join("data")
+        .join("base_node")
+        .join("db")
+}
+
+/// The database was written between reading the tip and stopping the node, the manifest would not match it
+#[derive(Debug, thiserror::Error)]
+#[error("Node database changed while the node was stopped for the snapshot, the export has to be retried")]
+pub struct TipMovedError;
+
+pub fn request_import(snapshot_path: PathBuf) {
+    if let Ok(mut pending) = PENDING_IMPORT.lock() {
+        *pending = Some(snapshot_path);
+    }
+}
+
+pub fn take_pending_import() -> Option<PathBuf> {
+    PENDING_IMPORT
+        .lock()
+        .ok()
+        .and_then(|mut pending| pending.take())
+}
+
+pub fn request_database_import(database_file: PathBuf) {
+    if let Ok(mut pending) = PENDING_DATABASE_IMPORT.lock() {
+        *pending = Some(database_file);
+    }
+}
+
+pub fn take_pending_database_import() -> Option<PathBuf> {
+    PENDING_DATABASE_IMPORT
+        .lock()
+        .ok()
+        .and_then(|mut pending| pending.take())
+}
+
+pub fn request_export(destination_dir: PathBuf) {
+    if let Ok(mut pending) = PENDING_EXPORT.lock() {
+        *pending = Some(destination_dir);
+    }
+}
+
+pub fn take_pending_export() -> Option<PathBuf> {
+    PENDING_EXPORT
+        .lock()
+        .ok()
+        .and_then(|mut pending| pending.take())
+}
+
+/// Stops the local node so the database is not written while copied, the caller has to restart the node phase.
+/// Fails with `TipMovedError` when a block was written after the tip was read
+pub async fn export_snapshot(
+    node_manager: &NodeManager,
+    data_dir: &Path,
+    destination_dir: &Path,
+) -> Result<SnapshotManifest, anyhow::Error> {
+    if !node_manager.is_local_current().await {
+        return Err(anyhow!(
+            "Snapshots can only be exported while the local node is used"
+        ));
+    }
+    let database_file = node_db_dir(data_dir).join(DATABASE_FILE);
+    // Any write after this point, e.g. a new block, could move the tip past the one read below
+    let modified_before = fs::metadata(&database_file)?.modified()?;
+    let node_service = node_manager.get_current_service().await?;
+    let status = node_service.get_network_state().await?;
+    let tip_hash = node_service
+        .get_historical_blocks(vec![status.block_height])
+        .await?
+        .first()
+        .map(|(_, hash)| hash.clone())
+        .ok_or_else(|| anyhow!("Tip block {} not found", status.block_height))?;
+
+    node_manager.stop_local_node().await?;
+    if fs::metadata(&database_file)?.modified()? != modified_before {
+        return Err(TipMovedError.into());
+    }
+
+    let network = Network::get_current_or_user_setting_or_default();
+    let storage_profile = load_storage_profile(&node_network_dir(data_dir))?.unwrap_or_default();
+    let snapshot_dir = destination_dir.join(format!(
+        "tari-node-{}-{}",
+        network.as_key_str(),
+        status.block_height
+    ));
+    let manifest = SnapshotManifest {
+        version: MANIFEST_VERSION,
+        network: network.as_key_str().to_string(),
+        block_height: status.block_height,
+        tip_hash,
+        storage_profile,
+        created_at: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        size_bytes: 0,
+        sha256: String::new(),
+    };
+    let manifest = tokio::task::spawn_blocking(move || {
+        write_snapshot(&database_file, &snapshot_dir, manifest)
+    })
+    .await??;
+    info!(target: LOG_TARGET, "Exported node snapshot at height {}", manifest.block_height);
+    Ok(manifest)
+}
+
+fn write_snapshot(
+    database_file: &Path,
+    snapshot_dir: &Path,
+    mut manifest: SnapshotManifest,
+) -> Result<SnapshotManifest, anyhow::Error> {
+    if snapshot_dir.exists() {
+        return Err(anyhow!("Snapshot already exists at {snapshot_dir:?}"));
+    }
+    fs::create_dir_all(snapshot_dir)?;
+    let result = (|| {
+        let (size_bytes, sha256) =
+            copy_with_sha256(database_file, &snapshot_dir.join(DATABASE_FILE))?;
+        manifest.size_bytes = size_bytes;
+        manifest.sha256 = sha256;
+        // Written last, a snapshot without a manifest is incomplete
+        fs::write(
+            snapshot_dir.join(MANIFEST_FILE),
+            serde_json::to_string_pretty(&manifest)?,
+        )?;
+        Ok(manifest)
+    })();
+    if result.is_err() {
+        let _unused = fs::remove_dir_all(snapshot_dir);
+    }
+    result
+}
+
+/// Accepts the snapshot directory or its manifest, the node must not be running
+pub fn import_snapshot(
+    data_dir: &Path,
+    snapshot_path: &Path,
+) -> Result<SnapshotManifest, anyhow::Error> {
+    let snapshot_dir = if snapshot_path.is_file() {
+        snapshot_path
+            .parent()
+            .ok_or_else(|| anyhow!("Invalid snapshot path: {snapshot_path:?}"))?
+    } else {
+        snapshot_path
+    };
+    let manifest_file = snapshot_dir.join(MANIFEST_FILE);
+    let manifest: SnapshotManifest = serde_json::from_str(
+        &fs::read_to_string(&manifest_file)
+            .with_context(|| format!("Snapshot manifest not found at {manifest_file:?}"))?,
+    )?;
+    manifest.validate()?;
+
+    swap_in_database(data_dir, &snapshot_dir.join(DATABASE_FILE), Some(&manifest))?;
+    info!(target: LOG_TARGET, "Imported node snapshot at height {}", manifest.block_height);
+    Ok(manifest)
+}
+
+/// Imports a bare database file as done by the deprecated import-backup argument, it is not verified
+pub fn import_database(data_dir: &Path, database_file: &Path) -> Result<(), anyhow::Error> {
+    if !database_file.is_file() {
+        return Err(anyhow!("Node database not found at {database_file:?}"));
+    }
+    swap_in_database(data_dir, database_file, None)?;
+    info!(target: LOG_TARGET, "Imported node database from {database_file:?}");
+    Ok(())
+}
+
+/// Copies the database next to the current one before swapping them, the previous database is restored on failure.
+/// Without a manifest the copy is not verified and the storage profile is kept
+fn swap_in_database(
+    data_dir: &Path,
+    database_file: &Path,
+    manifest: Option<&SnapshotManifest>,
+) -> Result<(), anyhow::Error> {
+    let db_dir = node_db_dir(data_dir);
+    let db_parent = db_dir
+        .parent()
+        .ok_or_else(|| anyhow!("Invalid node database path: {db_dir:?}"))?;
+    fs::create_dir_all(db_parent)?;
+    let staging_dir = db_parent.join("db.importing");
+    let backup_dir = db_parent.join("db.previous");
+    if staging_dir.exists() {
+        fs::remove_dir_all(&staging_dir)?;
+    }
+    fs::create_dir_all(&staging_dir)?;
+
+    // Hashing the copy checks the snapshot and the copy in one read
+    let copied = copy_with_sha256(database_file, &staging_dir.join(DATABASE_FILE)).and_then(
+        |(size_bytes, sha256)| match manifest {
+            Some(manifest) if size_bytes != manifest.size_bytes || sha256 != manifest.sha256 => {
+                Err(anyhow!("Snapshot does not match its manifest checksum"))
+            }
+            _ => Ok(()),
+        },
+    );
+    if let Err(e) = copied {
+        let _unused = fs::remove_dir_all(&staging_dir);
+        return Err(e);
+    }
+
+    let network_dir = node_network_dir(data_dir);
+    let has_previous_db = db_dir.exists();
+    let backed_up = (|| {
+        let previous_profile = load_storage_profile(&network_dir)?;
+        if backup_dir.exists() {
+            fs::remove_dir_all(&backup_dir)?;
+        }
+        if has_previous_db {
+            fs::rename(&db_dir, &backup_dir)?;
+        }
+        Ok::<_, anyhow::Error>(previous_profile)
+    })();
+    let previous_profile = match backed_up {
+        Ok(previous_profile) => previous_profile,
+        Err(e) => {
+            let _unused = fs::remove_dir_all(&staging_dir);
+            return Err(e);
+        }
+    };
+    let swapped = fs::rename(&staging_dir, &db_dir)
+        .map_err(anyhow::Error::from)
+        .and_then(|()| match manifest {
+            Some(manifest) => save_storage_profile(&network_dir, &manifest.storage_profile),
+            None => Ok(()),
+        });
+    if let Err(e) = swapped {
+        warn!(target: LOG_TARGET, "Failed to import node database, restoring the previous one: {e}");
+        let _unused = fs::remove_dir_all(&db_dir);
+        let _unused = fs::remove_dir_all(&staging_dir);
+        if has_previous_db {
+            fs::rename(&backup_dir, &db_dir)?;
+        }
+        if let Some(previous_profile) = previous_profile {
+            save_storage_profile(&network_dir, &previous_profile)?;
+        }
+        return Err(e);
+    }
+    if has_previous_db {
+        let _unused = fs::remove_dir_all(&backup_dir).inspect_err(|e| {
+            warn!(target: LOG_TARGET, "Failed to remove the previous node database: {e}");
+        });
+    }
+    Ok(())
+}
+
+/// Keeps the config in line with the imported database, so it is not removed as a profile switch
+pub async fn use_snapshot_storage_profile(
+    manifest: &SnapshotManifest,
+) -> Result<(), anyhow::Error> {
+    let min_free_disk_space_bytes = *ConfigCore::content().await.node_min_free_disk_space_bytes();
+    ConfigCore::update_field(
+        ConfigCoreContent::set_node_storage,
+        (manifest.storage_profile, min_free_disk_space_bytes),
+    )
+    .await
+}
+
+fn copy_with_sha256(source: &Path, destination: &Path) -> Result<(u64, String), anyhow::Error> {
+    let mut reader = BufReader::with_capacity(
+        COPY_BUFFER_SIZE,
+        File::open(source).with_context(|| format!("Failed to open {source:?}"))?,
+    );
+    let mut writer = BufWriter::with_capacity(COPY_BUFFER_SIZE, File::create(destination)?);
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; COPY_BUFFER_SIZE];
+    let mut size_bytes = 0u64;
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        let chunk = buffer.get(..read).unwrap_or_default();
+        hasher.update(chunk);
+        writer.write_all(chunk)?;
+        size_bytes += read as u64;
+    }
+    writer
+        .into_inner()
+        .map_err(|e| anyhow!("Failed to flush {destination:?}: {e}"))?
+        .sync_all()?;
+    Ok((size_bytes, hex::encode(hasher.finalize())))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "tari_universe_snapshot_test_{name}_{}",
+            std::process::id()
+        ));
+        let _unused = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_test_snapshot(root: &Path, content: &[u8]) -> PathBuf {
+        let database_file = root.join("source.mdb");
+        fs::write(&database_file, content).unwrap();
+        let snapshot_dir = root.join("snapshot");
+        let manifest = SnapshotManifest {
+            version: MANIFEST_VERSION,
+            network: Network::get_current_or_user_setting_or_default()
+                .as_key_str()
+                .to_string(),
+            block_height: 10,
+            tip_hash: "00".repeat(32),
+            storage_profile: NodeStorageProfile::Pruned {
+                pruning_horizon: 1000,
+            },
+            created_at: 0,
+            size_bytes: 0,
+            sha256: String::new(),
+        };
+        let manifest = write_snapshot(&database_file, &snapshot_dir, manifest).unwrap();
+        assert_eq!(manifest.size_bytes, content.len() as u64);
+        assert_eq!(manifest.sha256, hex::encode(Sha256::digest(content)));
+        snapshot_dir
+    }
+
+    #[test]
+    fn imports_verified_snapshot() {
+        let root = test_dir("import");
+        let data_dir = root.join("app");
+        let db_dir = node_db_dir(&data_dir);
+        fs::create_dir_all(&db_dir).unwrap();
+        fs::write(db_dir.join(DATABASE_FILE), b"old").unwrap();
+
+        let snapshot_dir = write_test_snapshot(&root, b"snapshot");
+        let manifest = import_snapshot(&data_dir, &snapshot_dir.join(MANIFEST_FILE)).unwrap();
+
+        assert_eq!(fs::read(db_dir.join(DATABASE_FILE)).unwrap(), b"snapshot");
+        assert!(!db_dir.with_file_name("db.previous").exists());
+        assert_eq!(
+            load_storage_profile(&node_network_dir(&data_dir)).unwrap(),
+            Some(manifest.storage_profile)
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn keeps_database_when_snapshot_is_corrupt() {
+        let root = test_dir("corrupt");
+        let data_dir = root.join("app");
+        let db_dir = node_db_dir(&data_dir);
+        fs::create_dir_all(&db_dir).unwrap();
+        fs::write(db_dir.join(DATABASE_FILE), b"old").unwrap();
+
+        let snapshot_dir = write_test_snapshot(&root, b"snapshot");
+        fs::write(snapshot_dir.join(DATABASE_FILE), b"tampered").unwrap();
+
+        assert!(import_snapshot(&data_dir, &snapshot_dir).is_err());
+        assert_eq!(fs::read(db_dir.join(DATABASE_FILE)).unwrap(), b"old");
+        assert!(!db_dir.with_file_name("db.importing").exists());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn imports_bare_database_keeping_storage_profile() {
+        let root = test_dir("bare");
+        let data_dir = root.join("app");
+        let db_dir = node_db_dir(&data_dir);
+        fs::create_dir_all(&db_dir).unwrap();
+        fs::write(db_dir.join(DATABASE_FILE), b"old").unwrap();
+        let database_file = root.join(DATABASE_FILE);
+        fs::write(&database_file, b"backup").unwrap();
+
+        import_database(&data_dir, &database_file).unwrap();
+
+        assert_eq!(fs::read(db_dir.join(DATABASE_FILE)).unwrap(), b"backup");
+        assert!(!db_dir.with_file_name("db.importing").exists());
+        assert!(!db_dir.with_file_name("db.previous").exists());
+        assert_eq!(
+            load_storage_profile(&node_network_dir(&data_dir)).unwrap(),
+            Some(NodeStorageProfile::Archival)
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn rejects_other_network() {
+        let root = test_dir("network");
+        let snapshot_dir = write_test_snapshot(&root, b"snapshot");
+        let manifest_file = snapshot_dir.join(MANIFEST_FILE);
+        let mut manifest: SnapshotManifest =
+            serde_json::from_str(&fs::read_to_string(&manifest_file).unwrap()).unwrap();
+        manifest.network = "othernet".to_string();
+        fs::write(&manifest_file, serde_json::to_string(&manifest).unwrap()).unwrap();
+
+        assert!(import_snapshot(&root.join("app"), &snapshot_dir).is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
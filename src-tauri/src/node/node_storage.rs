@@ -0,0 +1,366 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::anyhow;
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use sysinfo::Disks;
+use tari_common::configuration::Network;
+use tari_shutdown::ShutdownSignal;
+use tokio_util::task::TaskTracker;
+
+use crate::{
+    configs::{config_core::ConfigCore, trait_config::ConfigImpl},
+    events_emitter::EventsEmitter,
+    node::node_manager::NodeManager,
+    setup::setup_manager::{SetupManager, SetupPhase},
+    tasks_tracker::TasksTrackers,
+};
+
+const LOG_TARGET: &str = "tari::universe::node_storage";
+const STORAGE_PROFILE_FILE: &str = "storage_profile.json";
+const WATCHDOG_INTERVAL: Duration = Duration::from_secs(60);
+// Free space needed on top of the minimum before the node is resumed, so it doesn't flap
+const RESUME_MARGIN_BYTES: u64 = 1024 * 1024 * 1024;
+pub const MIN_PRUNING_HORIZON: u64 = 100;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NodeStorageProfile {
+    /// Keeps full blocks only for the last `pruning_horizon` blocks
+    Pruned { pruning_horizon: u64 },
+    #[default]
+    Archival,
+}
+
+impl NodeStorageProfile {
+    pub fn validate(&self) -> Result<(), anyhow::Error> {
+        if let NodeStorageProfile::Pruned { pruning_horizon } = self {
+            if *pruning_horizon < MIN_PRUNING_HORIZON {
+                return Err(anyhow!(
+                    "Pruning horizon has to be at least {MIN_PRUNING_HORIZON} blocks"
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Value of `base_node.storage.pruning_horizon`, 0 keeps every block
+    pub fn pruning_horizon(&self) -> u64 {
+        match self {
+            NodeStorageProfile::Pruned { pruning_horizon } => *pruning_horizon,
+            NodeStorageProfile::Archival => 0,
+        }
+    }
+
+    /// Pruned blocks can't be restored, the node prunes an archival database on its own
+    pub fn requires_resync_from(&self, previous: &NodeStorageProfile) -> bool {
+        matches!(
+            (previous, self),
+            (
+                NodeStorageProfile::Pruned { .. },
+                NodeStorageProfile::Archival
+            )
+        )
+    }
+}
+
+pub fn node_network_dir(data_dir: &Path) -> PathBuf {
+    data_dir
+        .join("node")
+        .join(Network::get_current().to_string().to_lowercase())
+}
+
+/// Profile the node database was built with, databases from before profiles existed are archival
+pub fn load_storage_profile(
+    network_dir: &Path,
+) -> Result<Option<NodeStorageProfile>, anyhow::Error> {
+    let profile_file = network_dir.join(STORAGE_PROFILE_FILE);
+    if profile_file.exists() {
+        let content = fs::read_to_string(profile_file)?;
+        return Ok(Some(serde_json::from_str(&content)?));
+    }
+    if network_dir.join("data").exists() {
+        return Ok(Some(NodeStorageProfile::Archival));
+    }
+    Ok(None)
+}
+
+/// Removes a database which can't be used with the new profile, called before the node starts
+pub fn prepare_node_storage(
+    network_dir: &Path,
+    profile: &NodeStorageProfile,
+) -> Result<(), anyhow::Error> {
+    if let Some(previous) = load_storage_profile(network_dir)? {
+        let node_db_dir = network_dir.join("data");
+        if previous != *profile {
+            info!(target: LOG_TARGET, "Switching node storage from {previous:?} to {profile:?}");
+        }
+        if profile.requires_resync_from(&previous) && node_db_dir.exists() {
+            info!(target: LOG_TARGET, "Removing pruned node database at {node_db_dir:?}");
+            fs::remove_dir_all(node_db_dir)?;
+        }
+    }
+    save_storage_profile(network_dir, profile)
+}
+
+pub fn save_storage_profile(
+    network_dir: &Path,
+    profile: &NodeStorageProfile,
+) -> Result<(), anyhow::Error> {
+    fs::write(
+        network_dir.join(STORAGE_PROFILE_FILE),
+        serde_json::to_string(profile)?,
+    )?;
+    Ok(())
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct NodeDiskUsage {
+    pub storage_profile: NodeStorageProfile,
+    pub node_data_bytes: u64,
+    // Unknown when the disk holding the data directory is not found
+    pub available_bytes: Option<u64>,
+    pub total_bytes: Option<u64>,
+    pub min_free_bytes: u64,
+    pub is_paused_for_low_disk: bool,
+}
+
+pub async fn get_node_disk_usage(
+    node_manager: &NodeManager,
+    data_dir: &Path,
+) -> Result<NodeDiskUsage, anyhow::Error> {
+    let config_core = ConfigCore::content().await;
+    let network_dir = node_network_dir(data_dir);
+    let (node_data_bytes, disk_space) = tokio::task::spawn_blocking(move || {
+        (
+            directory_size(&network_dir),
+            disk_space_for_path(&network_dir),
+        )
+    })
+    .await?;
+
+    Ok(NodeDiskUsage {
+        storage_profile: *config_core.node_storage_profile(),
+        node_data_bytes,
+        available_bytes: disk_space.map(|(available, _)| available),
+        total_bytes: disk_space.map(|(_, total)| total),
+        min_free_bytes: *config_core.node_min_free_disk_space_bytes(),
+        is_paused_for_low_disk: node_manager.is_paused_for_low_disk(),
+    })
+}
+
+fn directory_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.metadata() {
+            Ok(metadata) if metadata.is_dir() => directory_size(&entry.path()),
+            Ok(metadata) => metadata.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+/// Available and total bytes of the disk with the most specific mount point containing `path`
+fn disk_space_for_path(path: &Path) -> Option<(u64, u64)> {
+    let path = path
+        .ancestors()
+        .find_map(|ancestor| ancestor.canonicalize().ok())?;
+    let disks = Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().components().count())
+        .map(|disk| (disk.available_space(), disk.total_space()))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum WatchdogAction {
+    Pause,
+    Resume,
+}
+
+fn watchdog_action(
+    available_bytes: u64,
+    min_free_bytes: u64,
+    is_paused: bool,
+) -> Option<WatchdogAction> {
+    if min_free_bytes == 0 {
+        return is_paused.then_some(WatchdogAction::Resume);
+    }
+    if !is_paused && available_bytes < min_free_bytes {
+        return Some(WatchdogAction::Pause);
+    }
+    if is_paused && available_bytes >= min_free_bytes.saturating_add(RESUME_MARGIN_BYTES) {
+        return Some(WatchdogAction::Resume);
+    }
+    None
+}
+
+/// Pauses the local node before its database fills the disk and resumes it once space is freed
+pub fn spawn_low_disk_watchdog(
+    node_manager: NodeManager,
+    data_dir: PathBuf,
+    mut shutdown_signal: ShutdownSignal,
+    task_tracker: &TaskTracker,
+) {
+    task_tracker.spawn(async move {
+        let mut interval = tokio::time::interval(WATCHDOG_INTERVAL);
+        // Set when the node could not be suspended and was stopped instead
+        let mut is_stopped = false;
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    let usage = match get_node_disk_usage(&node_manager, &data_dir).await {
+                        Ok(usage) => usage,
+                        Err(e) => {
+                            warn!(target: LOG_TARGET, "Failed to read node disk usage: {e}");
+                            continue;
+                        }
+                    };
+                    let Some(available_bytes) = usage.available_bytes else {
+                        continue;
+                    };
+                    let action = watchdog_action(
+                        available_bytes,
+                        usage.min_free_bytes,
+                        usage.is_paused_for_low_disk,
+                    );
+                    match action {
+                        Some(WatchdogAction::Pause) => {
+                            warn!(target: LOG_TARGET, "Only {available_bytes} bytes left on disk, pausing the local node");
+                            if let Err(e) = node_manager.suspend_local_node().await {
+                                warn!(target: LOG_TARGET, "Could not suspend the local node, stopping it instead: {e}");
+                                if let Err(e) = node_manager.stop_local_node().await {
+                                    error!(target: LOG_TARGET, "Failed to stop the local node: {e}");
+                                    continue;
+                                }
+                                is_stopped = true;
+                            }
+                            node_manager.set_paused_for_low_disk(true);
+                        }
+                        Some(WatchdogAction::Resume) => {
+                            info!(target: LOG_TARGET, "{available_bytes} bytes available on disk, resuming the local node");
+                            node_manager.set_paused_for_low_disk(false);
+                            if is_stopped {
+                                // Restarting the node phase cancels this task
+                                TasksTrackers::current().common.get_task_tracker().await.spawn(async {
+                                    SetupManager::get_instance()
+                                        .restart_phases(vec![SetupPhase::Node, SetupPhase::Wallet, SetupPhase::CpuMining])
+                                        .await;
+                                });
+                            } else if let Err(e) = node_manager.resume_local_node().await {
+                                error!(target: LOG_TARGET, "Failed to resume the local node: {e}");
+                            }
+                        }
+                        None => {}
+                    }
+                    EventsEmitter::emit_node_disk_usage_update(NodeDiskUsage {
+                        is_paused_for_low_disk: node_manager.is_paused_for_low_disk(),
+                        ..usage
+                    }).await;
+                }
+                _ = shutdown_signal.wait() => {
+                    break;
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    const GIB: u64 = 1024 * 1024 * 1024;
+
+    #[test]
+    fn only_pruned_to_archival_requires_resync() {
+        let pruned = NodeStorageProfile::Pruned {
+            pruning_horizon: 1000,
+        };
+        assert!(NodeStorageProfile::Archival.requires_resync_from(&pruned));
+        assert!(!pruned.requires_resync_from(&NodeStorageProfile::Archival));
+        assert!(!NodeStorageProfile::Pruned {
+            pruning_horizon: 100
+        }
+        .requires_resync_from(&pruned));
+        assert!(NodeStorageProfile::Pruned {
+            pruning_horizon: 10
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn pauses_below_minimum_and_resumes_above_margin() {
+        assert_eq!(
+            watchdog_action(GIB, 2 * GIB, false),
+            Some(WatchdogAction::Pause)
+        );
+        assert_eq!(watchdog_action(3 * GIB, 2 * GIB, false), None);
+        assert_eq!(watchdog_action(2 * GIB, 2 * GIB, true), None);
+        assert_eq!(
+            watchdog_action(3 * GIB, 2 * GIB, true),
+            Some(WatchdogAction::Resume)
+        );
+        // Disabled watchdog releases a paused node
+        assert_eq!(watchdog_action(0, 0, false), None);
+        assert_eq!(watchdog_action(0, 0, true), Some(WatchdogAction::Resume));
+    }
+
+    #[test]
+    fn resyncs_when_switching_from_pruned_to_archival() {
+        let network_dir = std::env::temp_dir().join(format!(
+            "tari_universe_node_storage_test_{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(network_dir.join("data")).unwrap();
+        assert_eq!(
+            load_storage_profile(&network_dir).unwrap(),
+            Some(NodeStorageProfile::Archival)
+        );
+
+        let pruned = NodeStorageProfile::Pruned {
+            pruning_horizon: 1000,
+        };
+        prepare_node_storage(&network_dir, &pruned).unwrap();
+        assert!(network_dir.join("data").exists());
+        assert_eq!(load_storage_profile(&network_dir).unwrap(), Some(pruned));
+
+        prepare_node_storage(&network_dir, &NodeStorageProfile::Archival).unwrap();
+        assert!(!network_dir.join("data").exists());
+
+        fs::remove_dir_all(network_dir).unwrap();
+    }
+}
@@ -30,10 +30,14 @@ use tonic::async_trait;
 use crate::{
     ab_test_selector::ABTestSelector,
     node::{
+        external_node::{ExternalNodeEndpoint, GrpcConnectionOptions},
         node_adapter::{NodeAdapter, NodeAdapterService, NodeStatusMonitor},
         node_manager::NodeType,
+        node_storage::NodeStorageProfile,
     },
     process_adapter::{ProcessAdapter, ProcessInstanceTrait},
+    process_resource_limits::ProcessResourceLimits,
+    process_restart_policy::RestartPolicy,
     BaseNodeStatus,
 };
 use anyhow::Error;
@@ -50,6 +54,9 @@ pub(crate) struct RemoteNodeAdapter {
     pub(crate) use_tor: bool,
     ab_group: ABTestSelector,
     status_broadcast: watch::Sender<BaseNodeStatus>,
+    // Either `Remote` for the public nodes or `External` for a self hosted one
+    node_type: NodeType,
+    connection_options: GrpcConnectionOptions,
 }
 
 impl RemoteNodeAdapter {
@@ -59,6 +66,8 @@ impl RemoteNodeAdapter {
             status_broadcast,
             use_tor: false,
             ab_group: ABTestSelector::GroupA,
+            node_type: NodeType::Remote,
+            connection_options: GrpcConnectionOptions::default(),
         }
     }
 
@@ -73,33 +82,61 @@ impl RemoteNodeAdapter {
             } else {
                 format!("http://{}:{}", grpc_address.0, grpc_address.1)
             };
-            Some(NodeAdapterService::new(address, 1))
+            Some(
+                NodeAdapterService::new(address, 1)
+                    .with_connection_options(self.connection_options.clone()),
+            )
         } else {
             None
         }
     }
 
-    // Expected format currently: https://grpc.<network>.tari.com:443
-    pub fn set_grpc_address(&mut self, grpc_address: String) -> Result<(), anyhow::Error> {
-        let has_scheme = grpc_address.starts_with("http");
-        let is_https = grpc_address.starts_with("https");
-        if !has_scheme {
-            let parts = grpc_address.split(':').collect::<Vec<&str>>();
-            let port = parts[1].parse::<u16>()?;
-            let scheme = if port == 443 { "https://" } else { "http://" };
-            self.grpc_address = Some((
-                format!("{}{}", scheme, parts[0]),
-                if is_https { 443 } else { 80 },
-            ));
-            return Ok(());
-        }
+    pub fn use_external_node(
+        &mut self,
+        endpoint: &ExternalNodeEndpoint,
+        connection_options: GrpcConnectionOptions,
+    ) {
+        self.grpc_address = Some((endpoint.base_url(), endpoint.port));
+        self.node_type = NodeType::External;
+        self.connection_options = connection_options;
+    }
+
+    pub fn use_public_node(&mut self) {
+        self.node_type = NodeType::Remote;
+        self.connection_options = GrpcConnectionOptions::default();
+    }
 
-        let parts = grpc_address.split(':').collect::<Vec<&str>>();
-        self.grpc_address = Some((format!("{}:{}", parts[0], parts[1]), parts[2].parse()?));
+    pub fn set_grpc_address(&mut self, grpc_address: String) -> Result<(), anyhow::Error> {
+        self.grpc_address = Some(parse_grpc_address(&grpc_address)?);
         Ok(())
     }
 }
 
+// Expected format currently: https://grpc.<network>.tari.com:443
+// Addresses without a scheme use https for port 443 and http otherwise
+pub fn parse_grpc_address(grpc_address: &str) -> Result<(String, u16), anyhow::Error> {
+    let parts = grpc_address.split(':').collect::<Vec<&str>>();
+    if !grpc_address.starts_with("http") {
+        let [host, port] = parts[..] else {
+            return Err(anyhow::anyhow!("Invalid grpc address: {grpc_address}"));
+        };
+        let port = port.parse::<u16>()?;
+        let scheme = if port == 443 { "https://" } else { "http://" };
+        return Ok((format!("{scheme}{host}"), port));
+    }
+
+    let [scheme, host, port] = parts[..] else {
+        return Err(anyhow::anyhow!("Invalid grpc address: {grpc_address}"));
+    };
+    Ok((format!("{scheme}:{host}"), port.parse()?))
+}
+
+/// Address the grpc client connects to
+pub fn grpc_connection_url(grpc_address: &str) -> Result<String, anyhow::Error> {
+    let (host, port) = parse_grpc_address(grpc_address)?;
+    Ok(format!("{host}:{port}"))
+}
+
 #[async_trait]
 impl NodeAdapter for RemoteNodeAdapter {
     fn get_grpc_address(&self) -> Option<(String, u16)> {
@@ -139,6 +176,14 @@ impl NodeAdapter for RemoteNodeAdapter {
         log::info!(target: LOG_TARGET, "RemoteNodeAdapter doesn't use tor_control_port");
     }
 
+    fn set_resource_limits(&mut self, _resource_limits: ProcessResourceLimits) {
+        log::info!(target: LOG_TARGET, "RemoteNodeAdapter doesn't start a process to limit");
+    }
+
+    fn set_storage_profile(&mut self, _storage_profile: NodeStorageProfile) {
+        log::info!(target: LOG_TARGET, "RemoteNodeAdapter doesn't store the blockchain");
+    }
+
     async fn get_connection_details(&self) -> Result<(RistrettoPublicKey, String), anyhow::Error> {
         let node_service = self.get_service();
         if let Some(node_service) = node_service {
@@ -184,21 +229,16 @@ impl ProcessAdapter for RemoteNodeAdapter {
         _is_first_start: bool,
     ) -> Result<(Self::ProcessInstance, Self::StatusMonitor), anyhow::Error> {
         let inner_shutdown = Shutdown::new();
-        let grpc_address = self
-            .get_grpc_address()
+        let node_service = self
+            .get_service()
             .ok_or_else(|| anyhow::anyhow!("GRPC address not set"))?;
-        let address = if grpc_address.0.starts_with("http") {
-            format!("{}:{}", grpc_address.0, grpc_address.1)
-        } else {
-            format!("http://{}:{}", grpc_address.0, grpc_address.1)
-        };
         Ok((
             NullProcessInstance {
                 shutdown: inner_shutdown,
             },
             NodeStatusMonitor::new(
-                NodeType::Remote,
-                NodeAdapterService::new(address, 1),
+                self.node_type.clone(),
+                node_service,
                 self.status_broadcast.clone(),
                 Arc::new(AtomicU64::new(0)),
                 None, // Used only by Local Node
@@ -213,6 +253,11 @@ impl ProcessAdapter for RemoteNodeAdapter {
     fn pid_file_name(&self) -> &str {
         "remote_node_pid"
     }
+
+    // There is no process to crash, failures come from the remote service being unreachable
+    fn restart_policy(&self) -> RestartPolicy {
+        RestartPolicy::without_crash_loop_detection()
+    }
 }
 
 pub struct NullProcessInstance {
@@ -0,0 +1,375 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{sync::LazyLock, time::Duration};
+
+use futures::future::join_all;
+use log::{info, warn};
+use serde::Serialize;
+use tari_shutdown::ShutdownSignal;
+use tokio::{sync::RwLock, time::Instant};
+use tokio_util::task::TaskTracker;
+
+use crate::{
+    configs::{config_core::ConfigCore, trait_config::ConfigImpl},
+    node::{
+        node_adapter::NodeAdapterService, node_manager::NodeManager,
+        remote_node_adapter::grpc_connection_url,
+    },
+    setup::setup_manager::{SetupManager, SetupPhase},
+    tasks_tracker::TasksTrackers,
+};
+
+const LOG_TARGET: &str = "tari::universe::remote_node_selector";
+
+/// How often remote nodes are probed while one of them is in use
+pub const REMOTE_NODE_PROBE_INTERVAL: Duration = Duration::from_secs(60);
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+/// Consecutive bad probes of the selected node before failing over, so a single slow answer doesn't switch nodes
+const REQUIRED_FAILED_PROBES_FOR_FAILOVER: u32 = 2;
+
+static INSTANCE: LazyLock<RwLock<RemoteNodeSelector>> =
+    LazyLock::new(|| RwLock::new(RemoteNodeSelector::default()));
+
+#[derive(Clone, Debug, Serialize)]
+pub struct RemoteNodeProbe {
+    pub address: String,
+    pub latency_ms: Option<u64>,
+    pub block_height: Option<u64>,
+    pub is_synced: bool,
+    pub error: Option<String>,
+    pub probed_at: i64,
+    /// Probes in a row which failed or lagged behind the best tip
+    pub consecutive_failures: u32,
+    pub is_selected: bool,
+}
+
+impl RemoteNodeProbe {
+    fn is_responsive(&self) -> bool {
+        self.error.is_none() && self.is_synced && self.block_height.is_some()
+    }
+
+    fn is_usable(&self, best_tip: u64, max_blocks_behind: u64) -> bool {
+        self.is_responsive()
+            && self
+                .block_height
+                .is_some_and(|height| height.saturating_add(max_blocks_behind) >= best_tip)
+    }
+}
+
+/// Picks the remote base node used in remote mode and fails over to another one when it stops keeping up
+#[derive(Default)]
+pub struct RemoteNodeSelector {
+    probes: Vec<RemoteNodeProbe>,
+    selected_address: Option<String>,
+}
+
+impl RemoteNodeSelector {
+    pub fn current() -> &'static RwLock<Self> {
+        &INSTANCE
+    }
+
+    /// Primary address first, followed by the additional ones
+    pub async fn configured_addresses() -> Vec<String> {
+        let config = ConfigCore::content().await;
+        let mut addresses = vec![config.remote_base_node_address().clone()];
+        for address in config.remote_base_node_addresses() {
+            if !addresses.contains(address) {
+                addresses.push(address.clone());
+            }
+        }
+        addresses
+    }
+
+    pub fn probes(&self) -> Vec<RemoteNodeProbe> {
+        self.probes.clone()
+    }
+
+    /// Keeps the node selected before a restart, otherwise probes all of them and takes the fastest one at the tip
+    pub async fn select_initial() -> String {
+        let addresses = Self::configured_addresses().await;
+        let max_blocks_behind = *ConfigCore::content()
+            .await
+            .remote_node_failover_blocks_behind();
+        {
+            let selector = Self::current().read().await;
+            if let Some(selected) = selector
+                .selected_address
+                .as_ref()
+                .filter(|selected| addresses.contains(selected))
+            {
+                return selected.clone();
+            }
+        }
+
+        let primary = addresses[0].clone();
+        if addresses.len() == 1 {
+            Self::current().write().await.selected_address = Some(primary.clone());
+            return primary;
+        }
+
+        let results = probe_remote_nodes(addresses).await;
+        let mut selector = Self::current().write().await;
+        selector.record_probes(results, max_blocks_behind);
+        let selected = best_node(&selector.probes, max_blocks_behind)
+            .map(|probe| probe.address.clone())
+            .unwrap_or(primary);
+        info!(target: LOG_TARGET, "Selected remote node: {selected}");
+        selector.select(&selected);
+        selected
+    }
+
+    /// Probes all configured nodes and returns the node to fail over to, if the selected one stopped keeping up
+    pub async fn probe_and_evaluate() -> Option<String> {
+        let addresses = Self::configured_addresses().await;
+        let max_blocks_behind = *ConfigCore::content()
+            .await
+            .remote_node_failover_blocks_behind();
+        let results = probe_remote_nodes(addresses).await;
+
+        let mut selector = Self::current().write().await;
+        selector.record_probes(results, max_blocks_behind);
+        let selected = selector.selected_address.clone()?;
+        let target = failover_target(&selector.probes, &selected, max_blocks_behind)?;
+        warn!(target: LOG_TARGET, "Remote node {selected} is unavailable or behind, failing over to {target}");
+        selector.select(&target);
+        Some(target)
+    }
+
+    fn select(&mut self, address: &str) {
+        self.selected_address = Some(address.to_string());
+        for probe in &mut self.probes {
+            probe.is_selected = probe.address == address;
+        }
+    }
+
+    fn record_probes(&mut self, mut results: Vec<RemoteNodeProbe>, max_blocks_behind: u64) {
+        let best_tip = best_tip(&results).unwrap_or(0);
+        for probe in &mut results {
+            let previous_failures = self
+                .probes
+                .iter()
+                .find(|previous| previous.address == probe.address)
+                .map_or(0, |previous| previous.consecutive_failures);
+            probe.consecutive_failures = if probe.is_usable(best_tip, max_blocks_behind) {
+                0
+            } else {
+                previous_failures.saturating_add(1)
+            };
+            probe.is_selected = self.selected_address.as_ref() == Some(&probe.address);
+        }
+        self.probes = results;
+    }
+
+    /// Probes the nodes periodically while the remote node is in use and restarts the phases depending on it after a failover
+    pub fn spawn_failover_monitor(
+        node_manager: NodeManager,
+        mut shutdown_signal: ShutdownSignal,
+        task_tracker: &TaskTracker,
+    ) {
+        task_tracker.spawn(async move {
+            let mut interval = tokio::time::interval(REMOTE_NODE_PROBE_INTERVAL);
+            // The first tick completes immediately, the initial selection already probed the nodes
+            interval.tick().await;
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        if !node_manager.is_remote_current().await {
+                            info!(target: LOG_TARGET, "Remote node no longer in use, stopping remote node probing");
+                            break;
+                        }
+                        if Self::probe_and_evaluate().await.is_some() {
+                            // The restart shuts down the node phase this task belongs to
+                            TasksTrackers::current().common.get_task_tracker().await.spawn(async {
+                                SetupManager::get_instance()
+                                    .restart_phases(vec![
+                                        SetupPhase::Node,
+                                        SetupPhase::Wallet,
+                                        SetupPhase::CpuMining,
+                                        SetupPhase::GpuMining,
+                                    ])
+                                    .await;
+                            });
+                            break;
+                        }
+                    }
+                    _ = shutdown_signal.wait() => {
+                        break;
+                    }
+                }
+            }
+        });
+    }
+}
+
+fn best_tip(probes: &[RemoteNodeProbe]) -> Option<u64> {
+    probes
+        .iter()
+        .filter(|probe| probe.is_responsive())
+        .filter_map(|probe| probe.block_height)
+        .max()
+}
+
+/// Lowest latency node among the ones at the best tip
+fn best_node(probes: &[RemoteNodeProbe], max_blocks_behind: u64) -> Option<&RemoteNodeProbe> {
+    let best_tip = best_tip(probes)?;
+    probes
+        .iter()
+        .filter(|probe| probe.is_usable(best_tip, max_blocks_behind))
+        .min_by_key(|probe| probe.latency_ms.unwrap_or(u64::MAX))
+}
+
+fn failover_target(
+    probes: &[RemoteNodeProbe],
+    selected_address: &str,
+    max_blocks_behind: u64,
+) -> Option<String> {
+    let selected = probes
+        .iter()
+        .find(|probe| probe.address == selected_address)?;
+    if selected.consecutive_failures < REQUIRED_FAILED_PROBES_FOR_FAILOVER {
+        return None;
+    }
+    best_node(probes, max_blocks_behind)
+        .filter(|probe| probe.address != selected_address)
+        .map(|probe| probe.address.clone())
+}
+
+pub async fn probe_remote_node(address: String) -> RemoteNodeProbe {
+    let started_at = Instant::now();
+    let result = match grpc_connection_url(&address) {
+        Ok(url) => tokio::time::timeout(
+            PROBE_TIMEOUT,
+            NodeAdapterService::new(url, 1).get_network_state(),
+        )
+        .await
+        .map_err(|_| anyhow::anyhow!("Timed out"))
+        .and_then(|result| result.map_err(anyhow::Error::from)),
+        Err(e) => Err(e),
+    };
+    let mut probe = RemoteNodeProbe {
+        address,
+        latency_ms: None,
+        block_height: None,
+        is_synced: false,
+        error: None,
+        probed_at: chrono::Utc::now().timestamp_millis(),
+        consecutive_failures: 0,
+        is_selected: false,
+    };
+    match result {
+        Ok(status) => {
+            probe.latency_ms =
+                Some(u64::try_from(started_at.elapsed().as_millis()).unwrap_or(u64::MAX));
+            probe.block_height = Some(status.block_height);
+            probe.is_synced = status.is_synced;
+        }
+        Err(e) => probe.error = Some(e.to_string()),
+    }
+    probe
+}
+
+/// Probes all nodes at once so a single unreachable node doesn't delay the others
+pub async fn probe_remote_nodes(addresses: Vec<String>) -> Vec<RemoteNodeProbe> {
+    join_all(addresses.into_iter().map(probe_remote_node)).await
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn probe(address: &str, latency_ms: u64, block_height: u64) -> RemoteNodeProbe {
+        RemoteNodeProbe {
+            address: address.to_string(),
+            latency_ms: Some(latency_ms),
+            block_height: Some(block_height),
+            is_synced: true,
+            error: None,
+            probed_at: 0,
+            consecutive_failures: 0,
+            is_selected: false,
+        }
+    }
+
+    fn unreachable(address: &str) -> RemoteNodeProbe {
+        RemoteNodeProbe {
+            latency_ms: None,
+            block_height: None,
+            error: Some("connection refused".to_string()),
+            ..probe(address, 0, 0)
+        }
+    }
+
+    #[test]
+    fn best_node_is_fastest_at_tip() {
+        let probes = vec![
+            probe("slow", 300, 1000),
+            probe("fast", 20, 1000),
+            probe("lagging", 5, 990),
+            unreachable("down"),
+        ];
+        assert_eq!(best_node(&probes, 3).unwrap().address, "fast");
+        assert_eq!(
+            best_node(&[unreachable("down")], 3).map(|p| &p.address),
+            None
+        );
+    }
+
+    #[test]
+    fn fails_over_after_consecutive_bad_probes() {
+        let mut selector = RemoteNodeSelector::default();
+        selector.select("primary");
+
+        selector.record_probes(
+            vec![probe("primary", 50, 990), probe("backup", 80, 1000)],
+            3,
+        );
+        assert_eq!(failover_target(&selector.probes, "primary", 3), None);
+        assert!(selector.probes[0].is_selected);
+
+        selector.record_probes(vec![unreachable("primary"), probe("backup", 80, 1001)], 3);
+        assert_eq!(selector.probes[0].consecutive_failures, 2);
+        assert_eq!(
+            failover_target(&selector.probes, "primary", 3),
+            Some("backup".to_string())
+        );
+    }
+
+    #[test]
+    fn recovered_node_resets_failures() {
+        let mut selector = RemoteNodeSelector::default();
+        selector.select("primary");
+        selector.record_probes(vec![unreachable("primary"), probe("backup", 80, 1000)], 3);
+        selector.record_probes(
+            vec![probe("primary", 50, 1001), probe("backup", 80, 1001)],
+            3,
+        );
+        assert_eq!(selector.probes[0].consecutive_failures, 0);
+        assert_eq!(failover_target(&selector.probes, "primary", 3), None);
+        // No failover when every node is down
+        selector.record_probes(vec![unreachable("primary"), unreachable("backup")], 3);
+        selector.record_probes(vec![unreachable("primary"), unreachable("backup")], 3);
+        assert_eq!(failover_target(&selector.probes, "primary", 3), None);
+    }
+}
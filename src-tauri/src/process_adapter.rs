@@ -39,6 +39,9 @@ use tokio_util::task::TaskTracker;
 
 use crate::download_utils::set_permissions;
 use crate::process_killer::kill_process;
+use crate::process_output_buffer::{ProcessOutputBuffer, ProcessOutputStream};
+use crate::process_resource_limits::ProcessResourceLimits;
+use crate::process_restart_policy::RestartPolicy;
 use crate::process_utils::{launch_child_process, write_pid_file};
 
 const LOG_TARGET: &str = "tari::universe::process_adapter";
@@ -77,6 +80,15 @@ pub(crate) trait ProcessAdapter {
 
     fn pid_file_name(&self) -> &str;
 
+    /// Log file written by the process, its tail is attached to crash reports
+    fn log_file_path(&self, _log_folder: &Path) -> Option<PathBuf> {
+        None
+    }
+
+    fn restart_policy(&self) -> RestartPolicy {
+        RestartPolicy::default()
+    }
+
     #[allow(dead_code)]
     fn pid_file_exisits(&self, base_folder: PathBuf) -> bool {
         std::path::Path::new(&base_folder)
@@ -173,6 +185,10 @@ pub(crate) trait ProcessInstanceTrait: Sync + Send + 'static {
         &mut self,
         task_tracker: TaskTracker,
     ) -> Result<(i32, Vec<String>, Vec<String>), anyhow::Error>;
+    /// Most recent stdout and stderr lines of the process
+    fn output_tail(&self, _lines: usize) -> Vec<String> {
+        vec![]
+    }
 }
 
 #[derive(Clone)]
@@ -183,12 +199,14 @@ pub(crate) struct ProcessStartupSpec {
     pub pid_file_name: String,
     pub data_dir: PathBuf,
     pub name: String,
+    pub resource_limits: ProcessResourceLimits,
 }
 
 pub(crate) struct ProcessInstance {
     pub shutdown: Shutdown,
     pub handle: Option<JoinHandle<Result<i32, anyhow::Error>>>,
     pub startup_spec: ProcessStartupSpec,
+    pub output_buffer: ProcessOutputBuffer,
 }
 
 #[async_trait]
@@ -207,6 +225,8 @@ impl ProcessInstanceTrait for ProcessInstance {
         }
         info!(target: LOG_TARGET, "Starting {} process with args: {}", self.startup_spec.name, self.startup_spec.args.join(" "));
         let spec = self.startup_spec.clone();
+        let output_buffer = self.output_buffer.clone();
+        let output_task_tracker = task_tracker.clone();
         // Reset the shutdown each time.
         self.shutdown = Shutdown::new();
         let shutdown_signal = self.shutdown.to_signal();
@@ -224,13 +244,22 @@ impl ProcessInstanceTrait for ProcessInstance {
             }
             // start
             info!(target: LOG_TARGET, "Launching process for: {}", spec.name);
+            // Kept until the process exits, its memory cgroup is removed when dropped
+            let resource_limits = spec.resource_limits.prepare(&spec.name);
             let mut child = launch_child_process(
                 &spec.file_path,
                 spec.data_dir.as_path(),
                 spec.envs.as_ref(),
                 &spec.args,
-                false
+                true,
+                resource_limits.as_ref(),
             )?;
+            if let Some(stdout) = child.stdout.take() {
+                output_buffer.capture(stdout, ProcessOutputStream::Stdout, &output_task_tracker);
+            }
+            if let Some(stderr) = child.stderr.take() {
+                output_buffer.capture(stderr, ProcessOutputStream::Stderr, &output_task_tracker);
+            }
 
             if let Some(id) = child.id() {
                 let pid_file_res = write_pid_file(&spec, id);
@@ -290,12 +319,14 @@ impl ProcessInstanceTrait for ProcessInstance {
             return Ok((0, vec![], vec![]));
         };
 
+        let resource_limits = spec.resource_limits.prepare(&spec.name);
         let child = launch_child_process(
             &spec.file_path,
             spec.data_dir.as_path(),
             spec.envs.as_ref(),
             &spec.args,
             true,
+            resource_limits.as_ref(),
         )?;
 
         if let Some(id) = child.id() {
@@ -315,6 +346,13 @@ impl ProcessInstanceTrait for ProcessInstance {
             .map(|line| line.to_string())
             .collect();
 
+        for line in &stdout_lines {
+            self.output_buffer.push(ProcessOutputStream::Stdout, line);
+        }
+        for line in &stderr_lines {
+            self.output_buffer.push(ProcessOutputStream::Stderr, line);
+        }
+
         info!(target: LOG_TARGET, "Stopping {} process with exit code: {}", spec.name, exit_code);
 
         if let Err(error) = fs::remove_file(spec.data_dir.join(spec.pid_file_name)) {
@@ -344,6 +382,10 @@ impl ProcessInstanceTrait for ProcessInstance {
             None => Err(anyhow!("No process handle available")),
         }
     }
+
+    fn output_tail(&self, lines: usize) -> Vec<String> {
+        self.output_buffer.tail_formatted(lines)
+    }
 }
 
 impl Drop for ProcessInstance {
@@ -50,3 +50,45 @@ pub async fn kill_process(pid: i32) -> Result<(), anyhow::Error> {
     }
     Ok(())
 }
+
+/// Whether `suspend_process` and `resume_process` work on the current platform
+pub fn is_process_suspend_supported() -> bool {
+    cfg!(not(target_os = "windows"))
+}
+
+/// Freezes the process in place, it keeps its memory and open connections
+pub fn suspend_process(pid: i32) -> Result<(), anyhow::Error> {
+    #[cfg(target_os = "windows")]
+    {
+        Err(anyhow::anyhow!(
+            "Suspending process {pid} is not supported on Windows"
+        ))
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        use nix::sys::signal::{self, Signal};
+        use nix::unistd::Pid;
+
+        signal::kill(Pid::from_raw(pid), Signal::SIGSTOP)?;
+        Ok(())
+    }
+}
+
+pub fn resume_process(pid: i32) -> Result<(), anyhow::Error> {
+    #[cfg(target_os = "windows")]
+    {
+        Err(anyhow::anyhow!(
+            "Resuming process {pid} is not supported on Windows"
+        ))
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        use nix::sys::signal::{self, Signal};
+        use nix::unistd::Pid;
+
+        signal::kill(Pid::from_raw(pid), Signal::SIGCONT)?;
+        Ok(())
+    }
+}
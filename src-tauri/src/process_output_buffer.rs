@@ -0,0 +1,280 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, LazyLock, Mutex, PoisonError, RwLock};
+
+use log::warn;
+use serde::Serialize;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, BufReader};
+use tokio_util::task::TaskTracker;
+
+const LOG_TARGET: &str = "tari::universe::process_output_buffer";
+const DEFAULT_CAPACITY: usize = 1000;
+// Longer lines are split, so a process writing without newlines can't grow a line without limit
+const MAX_LINE_BYTES: u64 = 8 * 1024;
+
+static INSTANCE: LazyLock<ProcessOutputRegistry> = LazyLock::new(ProcessOutputRegistry::new);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ProcessOutputStream {
+    Stdout,
+    Stderr,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessOutputLine {
+    pub stream: ProcessOutputStream,
+    pub timestamp: i64,
+    pub line: String,
+}
+
+impl ProcessOutputLine {
+    pub fn format(&self) -> String {
+        let stream = match self.stream {
+            ProcessOutputStream::Stdout => "out",
+            ProcessOutputStream::Stderr => "err",
+        };
+        let time = chrono::DateTime::from_timestamp_millis(self.timestamp)
+            .map(|time| time.format("%Y-%m-%d %H:%M:%S%.3f").to_string())
+            .unwrap_or_default();
+        format!("{time} [{stream}] {}", self.line)
+    }
+}
+
+/// Ring buffer of the most recent output lines of a child process
+#[derive(Debug, Clone)]
+pub struct ProcessOutputBuffer {
+    lines: Arc<Mutex<VecDeque<ProcessOutputLine>>>,
+    capacity: usize,
+}
+
+impl ProcessOutputBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            lines: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    pub fn push(&self, stream: ProcessOutputStream, line: &str) {
+        let line = ProcessOutputLine {
+            stream,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            line: strip_ansi_escapes(line.trim_end()),
+        };
+        let mut lines = self.lines.lock().unwrap_or_else(PoisonError::into_inner);
+        if lines.len() >= self.capacity {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+
+    /// Last `count` lines, oldest first
+    pub fn tail(&self, count: usize) -> Vec<ProcessOutputLine> {
+        let lines = self.lines.lock().unwrap_or_else(PoisonError::into_inner);
+        let skip = lines.len().saturating_sub(count);
+        lines.iter().skip(skip).cloned().collect()
+    }
+
+    pub fn tail_formatted(&self, count: usize) -> Vec<String> {
+        self.tail(count)
+            .iter()
+            .map(ProcessOutputLine::format)
+            .collect()
+    }
+
+    /// Reads the pipe until it is closed. Lines are decoded lossily,
+    /// stopping at invalid utf-8 would leave the pipe full and block the child.
+    /// Lines longer than `MAX_LINE_BYTES` are split
+    pub fn capture<R: AsyncRead + Unpin + Send + 'static>(
+        &self,
+        reader: R,
+        stream: ProcessOutputStream,
+        task_tracker: &TaskTracker,
+    ) {
+        let buffer = self.clone();
+        task_tracker.spawn(async move {
+            let mut reader = BufReader::new(reader);
+            let mut line = Vec::new();
+            loop {
+                line.clear();
+                match (&mut reader)
+                    .take(MAX_LINE_BYTES)
+                    .read_until(b'\n', &mut line)
+                    .await
+                {
+                    Ok(0) => break,
+                    Ok(_) => buffer.push(stream, &String::from_utf8_lossy(&line)),
+                    Err(e) => {
+                        warn!(target: LOG_TARGET, "Failed to read process output: {e}");
+                        break;
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Output buffers of all managed processes by process name, they outlive process restarts
+pub struct ProcessOutputRegistry {
+    buffers: RwLock<HashMap<String, ProcessOutputBuffer>>,
+}
+
+impl ProcessOutputRegistry {
+    fn new() -> Self {
+        Self {
+            buffers: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn current() -> &'static ProcessOutputRegistry {
+        &INSTANCE
+    }
+
+    pub fn buffer(name: &str) -> ProcessOutputBuffer {
+        let registry = Self::current();
+        if let Some(buffer) = registry.get(name) {
+            return buffer;
+        }
+        registry
+            .buffers
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(name.to_string())
+            .or_insert_with(|| ProcessOutputBuffer::new(DEFAULT_CAPACITY))
+            .clone()
+    }
+
+    pub fn get(&self, name: &str) -> Option<ProcessOutputBuffer> {
+        self.buffers
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(name)
+            .cloned()
+    }
+
+    pub fn process_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .buffers
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .keys()
+            .cloned()
+            .collect();
+        names.sort();
+        names
+    }
+}
+
+/// Removes terminal color sequences, e.g. xmrig colors its output
+fn strip_ansi_escapes(line: &str) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            if chars.clone().next() == Some('[') {
+                chars.next();
+                // Parameters and intermediate bytes end with a final byte in the @ to ~ range
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn lines(buffer: &ProcessOutputBuffer) -> Vec<String> {
+        buffer
+            .tail(usize::MAX)
+            .into_iter()
+            .map(|l| l.line)
+            .collect()
+    }
+
+    #[test]
+    fn keeps_most_recent_lines() {
+        let buffer = ProcessOutputBuffer::new(3);
+        for index in 0..5 {
+            buffer.push(ProcessOutputStream::Stdout, &format!("line {index}\n"));
+        }
+        assert_eq!(lines(&buffer), vec!["line 2", "line 3", "line 4"]);
+        let tail = buffer.tail(2);
+        assert_eq!(tail.len(), 2);
+        assert_eq!(tail[1].line, "line 4");
+    }
+
+    #[test]
+    fn strips_color_sequences() {
+        assert_eq!(
+            strip_ansi_escapes("\u{1b}[1;32m * \u{1b}[0mHUGE PAGES \u{1b}[1;32mpermission granted"),
+            " * HUGE PAGES permission granted"
+        );
+        assert_eq!(strip_ansi_escapes("plain line"), "plain line");
+    }
+
+    #[tokio::test]
+    async fn captures_pipe_output_with_invalid_utf8() {
+        let buffer = ProcessOutputBuffer::new(10);
+        let task_tracker = TaskTracker::new();
+        let output: &[u8] = b"first\nsec\xffond\r\nlast without newline";
+        buffer.capture(output, ProcessOutputStream::Stderr, &task_tracker);
+        task_tracker.close();
+        task_tracker.wait().await;
+
+        assert_eq!(
+            lines(&buffer),
+            vec!["first", "sec\u{fffd}ond", "last without newline"]
+        );
+        assert!(buffer
+            .tail(10)
+            .iter()
+            .all(|line| line.stream == ProcessOutputStream::Stderr));
+    }
+
+    #[tokio::test]
+    async fn splits_lines_without_newline() {
+        let buffer = ProcessOutputBuffer::new(10);
+        let task_tracker = TaskTracker::new();
+        let output = vec![b'a'; 20 * 1024];
+        buffer.capture(
+            std::io::Cursor::new(output),
+            ProcessOutputStream::Stdout,
+            &task_tracker,
+        );
+        task_tracker.close();
+        task_tracker.wait().await;
+
+        let line_lengths: Vec<usize> = lines(&buffer).iter().map(String::len).collect();
+        assert_eq!(line_lengths, vec![8 * 1024, 8 * 1024, 4 * 1024]);
+    }
+}
@@ -0,0 +1,405 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+#[cfg(target_os = "linux")]
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[cfg(not(target_os = "linux"))]
+use log::debug;
+#[cfg(target_os = "linux")]
+use log::info;
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+const LOG_TARGET: &str = "tari::universe::process_resource_limits";
+
+pub const MAX_NICE_LEVEL: i32 = 19;
+pub const MAX_IO_PRIORITY_LEVEL: u8 = 7;
+// Affinity masks of the kernel cpu_set_t cover this many cores
+const MAX_CPU_INDEX: usize = 1024;
+
+#[cfg(target_os = "linux")]
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+#[cfg(target_os = "linux")]
+const CGROUP_NAME_PREFIX: &str = "tari-universe-";
+#[cfg(target_os = "linux")]
+const IOPRIO_WHO_PROCESS: i32 = 1;
+#[cfg(target_os = "linux")]
+const IOPRIO_CLASS_SHIFT: i32 = 13;
+
+/// Disk scheduling class of a process, same as `ionice`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IoPriority {
+    /// 0 (highest) - 7 (lowest)
+    BestEffort(u8),
+    /// Only gets disk time when no other process needs it
+    Idle,
+}
+
+impl IoPriority {
+    #[cfg(target_os = "linux")]
+    fn ioprio_value(self) -> i32 {
+        match self {
+            IoPriority::BestEffort(level) => (2 << IOPRIO_CLASS_SHIFT) | i32::from(level),
+            IoPriority::Idle => 3 << IOPRIO_CLASS_SHIFT,
+        }
+    }
+}
+
+/// Optional limits applied to a child process when it is started, only supported on linux
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProcessResourceLimits {
+    /// 0 (default) - 19 (lowest priority)
+    pub nice: Option<i32>,
+    pub io_priority: Option<IoPriority>,
+    /// Cores the process may run on, all cores when empty
+    pub cpu_affinity: Vec<usize>,
+    /// Enforced with a cgroup v2 memory controller, or a data segment rlimit when cgroups can't be used
+    pub memory_limit_bytes: Option<u64>,
+}
+
+impl ProcessResourceLimits {
+    pub fn is_empty(&self) -> bool {
+        self.nice.is_none()
+            && self.io_priority.is_none()
+            && self.cpu_affinity.is_empty()
+            && self.memory_limit_bytes.is_none()
+    }
+
+    pub fn validate(&self) -> Result<(), anyhow::Error> {
+        if let Some(nice) = self.nice {
+            // Raising the priority above the app's own requires elevated privileges
+            if !(0..=MAX_NICE_LEVEL).contains(&nice) {
+                return Err(anyhow::anyhow!(
+                    "Nice level must be between 0 and {MAX_NICE_LEVEL}, got {nice}"
+                ));
+            }
+        }
+        if let Some(IoPriority::BestEffort(level)) = self.io_priority {
+            if level > MAX_IO_PRIORITY_LEVEL {
+                return Err(anyhow::anyhow!(
+                    "IO priority level must be between 0 and {MAX_IO_PRIORITY_LEVEL}, got {level}"
+                ));
+            }
+        }
+        if let Some(cpu) = self.cpu_affinity.iter().find(|cpu| **cpu >= MAX_CPU_INDEX) {
+            return Err(anyhow::anyhow!("CPU index {cpu} is out of range"));
+        }
+        if self.memory_limit_bytes == Some(0) {
+            return Err(anyhow::anyhow!("Memory limit must be greater than 0"));
+        }
+        Ok(())
+    }
+
+    /// Resolves what can be checked before the process is spawned: cores the app itself may not use are dropped
+    /// and a memory cgroup is created when cgroups v2 is delegated to the user
+    pub fn prepare(&self, process_name: &str) -> Option<PreparedResourceLimits> {
+        if self.is_empty() {
+            return None;
+        }
+        if let Err(e) = self.validate() {
+            warn!(target: LOG_TARGET, "Ignoring resource limits of {process_name}: {e}");
+            return None;
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            let cpu_affinity = allowed_cpu_affinity(&self.cpu_affinity);
+            if cpu_affinity.is_empty() && !self.cpu_affinity.is_empty() {
+                warn!(target: LOG_TARGET, "None of the cores {:?} are available, {process_name} is not pinned", self.cpu_affinity);
+            }
+
+            let memory_cgroup = self.memory_limit_bytes.and_then(|limit_bytes| {
+                match create_memory_cgroup(Path::new(CGROUP_ROOT), process_name, limit_bytes) {
+                    Ok(cgroup) => Some(cgroup),
+                    Err(e) => {
+                        info!(target: LOG_TARGET, "Could not create memory cgroup for {process_name}, falling back to rlimit: {e}");
+                        None
+                    }
+                }
+            });
+            info!(target: LOG_TARGET, "Resource limits of {process_name}: {self:?}, memory cgroup: {memory_cgroup:?}");
+
+            Some(PreparedResourceLimits {
+                process_name: process_name.to_string(),
+                nice: self.nice,
+                io_priority: self.io_priority,
+                cpu_affinity,
+                memory_limit_bytes: self.memory_limit_bytes,
+                memory_cgroup,
+            })
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            debug!(target: LOG_TARGET, "Resource limits are only supported on linux, ignoring them for {process_name}");
+            None
+        }
+    }
+}
+
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub struct PreparedResourceLimits {
+    process_name: String,
+    nice: Option<i32>,
+    io_priority: Option<IoPriority>,
+    cpu_affinity: Vec<usize>,
+    memory_limit_bytes: Option<u64>,
+    #[cfg(target_os = "linux")]
+    memory_cgroup: Option<PathBuf>,
+}
+
+impl PreparedResourceLimits {
+    /// Applies priorities, affinity and the rlimit fallback in the child right before exec,
+    /// so threads started by the process inherit them
+    #[cfg(target_os = "linux")]
+    pub fn configure(&self, command: &mut tokio::process::Command) {
+        use nix::{
+            libc,
+            sched::{sched_setaffinity, CpuSet},
+            sys::resource::{setrlimit, Resource},
+            unistd::Pid,
+        };
+
+        let nice = self.nice;
+        let io_priority = self.io_priority.map(IoPriority::ioprio_value);
+        let cpu_set = if self.cpu_affinity.is_empty() {
+            None
+        } else {
+            let mut cpu_set = CpuSet::new();
+            for cpu in &self.cpu_affinity {
+                if let Err(e) = cpu_set.set(*cpu) {
+                    warn!(target: LOG_TARGET, "Can't pin {} to core {cpu}: {e}", self.process_name);
+                }
+            }
+            Some(cpu_set)
+        };
+        let data_limit = if self.memory_cgroup.is_none() {
+            self.memory_limit_bytes
+        } else {
+            None
+        };
+
+        // SAFETY: the closure runs in the forked child before exec, it only issues syscalls and doesn't allocate
+        unsafe {
+            command.pre_exec(move || {
+                // Priorities are best effort, they must not keep the process from starting
+                if let Some(nice) = nice {
+                    libc::setpriority(libc::PRIO_PROCESS, 0, nice);
+                }
+                if let Some(io_priority) = io_priority {
+                    libc::syscall(libc::SYS_ioprio_set, IOPRIO_WHO_PROCESS, 0, io_priority);
+                }
+                if let Some(cpu_set) = &cpu_set {
+                    sched_setaffinity(Pid::from_raw(0), cpu_set)?;
+                }
+                if let Some(data_limit) = data_limit {
+                    setrlimit(Resource::RLIMIT_DATA, data_limit, data_limit)?;
+                }
+                Ok(())
+            });
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn configure(&self, _command: &mut tokio::process::Command) {}
+
+    /// Moves the started process into its memory cgroup
+    #[cfg(target_os = "linux")]
+    pub fn attach(&self, pid: u32) {
+        if let Some(cgroup) = &self.memory_cgroup {
+            if let Err(e) = fs::write(cgroup.join("cgroup.procs"), pid.to_string()) {
+                warn!(target: LOG_TARGET, "Could not move {} into {cgroup:?}, its memory is not limited: {e}", self.process_name);
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn attach(&self, _pid: u32) {}
+}
+
+/// The memory cgroup can only be removed once the process in it is gone, so it is kept until then
+#[cfg(target_os = "linux")]
+impl Drop for PreparedResourceLimits {
+    fn drop(&mut self) {
+        if let Some(cgroup) = &self.memory_cgroup {
+            if let Err(e) = fs::remove_dir(cgroup) {
+                warn!(target: LOG_TARGET, "Could not remove {cgroup:?} of {}: {e}", self.process_name);
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn allowed_cpu_affinity(requested: &[usize]) -> Vec<usize> {
+    use nix::{sched::sched_getaffinity, unistd::Pid};
+
+    match sched_getaffinity(Pid::from_raw(0)) {
+        Ok(allowed) => requested
+            .iter()
+            .copied()
+            .filter(|cpu| allowed.is_set(*cpu).unwrap_or(false))
+            .collect(),
+        Err(_) => requested.to_vec(),
+    }
+}
+
+/// Cgroup of the current process relative to the cgroup root, read from `/proc/self/cgroup`
+#[cfg(target_os = "linux")]
+fn parse_own_cgroup(proc_self_cgroup: &str) -> Option<PathBuf> {
+    proc_self_cgroup
+        .lines()
+        .find_map(|line| line.strip_prefix("0::"))
+        .map(|path| PathBuf::from(path.trim().trim_start_matches('/')))
+}
+
+#[cfg(target_os = "linux")]
+fn create_memory_cgroup(
+    cgroup_root: &Path,
+    process_name: &str,
+    limit_bytes: u64,
+) -> Result<PathBuf, anyhow::Error> {
+    let own_cgroup = parse_own_cgroup(&fs::read_to_string("/proc/self/cgroup")?)
+        .ok_or_else(|| anyhow::anyhow!("cgroups v2 is not mounted"))?;
+    create_memory_cgroup_next_to(cgroup_root, &own_cgroup, process_name, limit_bytes)
+}
+
+/// Processes can't be moved into a cgroup which has processes of its own and distributes controllers,
+/// so the group is created next to the app's cgroup, in the subtree systemd delegates to the user
+#[cfg(target_os = "linux")]
+fn create_memory_cgroup_next_to(
+    cgroup_root: &Path,
+    own_cgroup: &Path,
+    process_name: &str,
+    limit_bytes: u64,
+) -> Result<PathBuf, anyhow::Error> {
+    let parent = own_cgroup
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .ok_or_else(|| anyhow::anyhow!("The app runs in the root cgroup"))?;
+    let parent = cgroup_root.join(parent);
+
+    let subtree_control_path = parent.join("cgroup.subtree_control");
+    let subtree_control = fs::read_to_string(&subtree_control_path)?;
+    if !subtree_control
+        .split_whitespace()
+        .any(|controller| controller == "memory")
+    {
+        fs::write(&subtree_control_path, "+memory")?;
+    }
+
+    let cgroup = parent.join(format!("{CGROUP_NAME_PREFIX}{process_name}"));
+    fs::create_dir_all(&cgroup)?;
+    fs::write(cgroup.join("memory.max"), limit_bytes.to_string())?;
+    Ok(cgroup)
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn validates_limits() {
+        assert!(ProcessResourceLimits::default().is_empty());
+        let limits = ProcessResourceLimits {
+            nice: Some(10),
+            io_priority: Some(IoPriority::BestEffort(7)),
+            cpu_affinity: vec![0, 1],
+            memory_limit_bytes: Some(1024 * 1024 * 1024),
+        };
+        assert!(limits.validate().is_ok());
+
+        for invalid in [
+            ProcessResourceLimits {
+                nice: Some(-5),
+                ..Default::default()
+            },
+            ProcessResourceLimits {
+                io_priority: Some(IoPriority::BestEffort(8)),
+                ..Default::default()
+            },
+            ProcessResourceLimits {
+                cpu_affinity: vec![MAX_CPU_INDEX],
+                ..Default::default()
+            },
+            ProcessResourceLimits {
+                memory_limit_bytes: Some(0),
+                ..Default::default()
+            },
+        ] {
+            assert!(invalid.validate().is_err(), "{invalid:?}");
+        }
+        assert_eq!(IoPriority::BestEffort(7).ioprio_value(), 0x4007);
+        assert_eq!(IoPriority::Idle.ioprio_value(), 0x6000);
+    }
+
+    #[test]
+    fn parses_own_cgroup() {
+        let content = "0::/user.slice/user-1000.slice/user@1000.service/app.slice/tari.scope\n";
+        assert_eq!(
+            parse_own_cgroup(content),
+            Some(PathBuf::from(
+                "user.slice/user-1000.slice/user@1000.service/app.slice/tari.scope"
+            ))
+        );
+        // cgroups v1 only
+        assert_eq!(
+            parse_own_cgroup("12:memory:/user.slice\n1:name=systemd:/\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn creates_memory_cgroup_next_to_own_cgroup() {
+        let root =
+            std::env::temp_dir().join(format!("tari-universe-cgroup-test-{}", std::process::id()));
+        let parent = root.join("user.slice/app.slice");
+        fs::create_dir_all(parent.join("tari.scope")).unwrap();
+        fs::write(parent.join("cgroup.subtree_control"), "cpu io\n").unwrap();
+
+        let cgroup = create_memory_cgroup_next_to(
+            &root,
+            Path::new("user.slice/app.slice/tari.scope"),
+            "wallet",
+            512,
+        )
+        .unwrap();
+
+        assert_eq!(cgroup, parent.join("tari-universe-wallet"));
+        assert_eq!(
+            fs::read_to_string(cgroup.join("memory.max")).unwrap(),
+            "512"
+        );
+        assert_eq!(
+            fs::read_to_string(parent.join("cgroup.subtree_control")).unwrap(),
+            "+memory"
+        );
+        assert!(
+            create_memory_cgroup_next_to(&root, Path::new("tari.scope"), "wallet", 512).is_err()
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,257 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use std::time::Duration;
+
+use rand::Rng;
+use tokio::time::Instant;
+
+// Only the end of the log is read, log files can grow to several megabytes before they are rolled
+const LOG_TAIL_MAX_BYTES: u64 = 64 * 1024;
+
+/// Decides how a process watcher restarts a failed process and when it gives up
+#[derive(Debug, Clone)]
+pub struct RestartPolicy {
+    /// Restarts allowed within `restart_window` before the process is considered to be crash looping,
+    /// `None` keeps restarting forever
+    pub max_restarts: Option<u32>,
+    pub restart_window: Duration,
+    /// Delay before the first restart, multiplied by `backoff_multiplier` for every consecutive restart
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub backoff_multiplier: u32,
+    /// Random delay added to the backoff, so processes failing together don't restart in lockstep
+    pub max_jitter: Duration,
+    /// Exit codes which stop the watcher instead of restarting the process
+    pub stop_on_exit_codes: Vec<i32>,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            max_restarts: Some(10),
+            restart_window: Duration::from_secs(15 * 60),
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            backoff_multiplier: 2,
+            max_jitter: Duration::from_millis(500),
+            stop_on_exit_codes: Vec::new(),
+        }
+    }
+}
+
+impl RestartPolicy {
+    /// For processes whose failures depend on external services, e.g. a remote node
+    pub fn without_crash_loop_detection() -> Self {
+        Self {
+            max_restarts: None,
+            ..Default::default()
+        }
+    }
+
+    pub fn should_stop_on_exit_code(&self, exit_code: i32) -> bool {
+        self.stop_on_exit_codes.contains(&exit_code)
+    }
+
+    /// Backoff before the next restart after `consecutive_restarts` restarts, without jitter
+    pub fn backoff(&self, consecutive_restarts: u32) -> Duration {
+        let multiplier = self.backoff_multiplier.saturating_pow(consecutive_restarts);
+        self.initial_backoff
+            .saturating_mul(multiplier)
+            .min(self.max_backoff)
+    }
+
+    fn jitter(&self) -> Duration {
+        let max_jitter_ms = u64::try_from(self.max_jitter.as_millis()).unwrap_or(u64::MAX);
+        if max_jitter_ms == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_millis(rand::thread_rng().gen_range(0..=max_jitter_ms))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RestartDecision {
+    Restart { delay: Duration },
+    CrashLoop { restarts: usize },
+}
+
+/// Restart history of a single watched process
+#[derive(Debug, Default)]
+pub struct RestartTracker {
+    restarts: VecDeque<Instant>,
+    consecutive_restarts: u32,
+}
+
+impl RestartTracker {
+    /// A healthy process resets the backoff, restarts still count towards the crash loop window
+    pub fn record_healthy(&mut self) {
+        self.consecutive_restarts = 0;
+    }
+
+    pub fn next_restart(&mut self, policy: &RestartPolicy, now: Instant) -> RestartDecision {
+        while self
+            .restarts
+            .front()
+            .is_some_and(|restart| now.duration_since(*restart) > policy.restart_window)
+        {
+            self.restarts.pop_front();
+        }
+
+        if let Some(max_restarts) = policy.max_restarts {
+            if self.restarts.len() >= max_restarts as usize {
+                return RestartDecision::CrashLoop {
+                    restarts: self.restarts.len(),
+                };
+            }
+        }
+
+        let delay = policy.backoff(self.consecutive_restarts) + policy.jitter();
+        self.restarts.push_back(now);
+        self.consecutive_restarts = self.consecutive_restarts.saturating_add(1);
+        RestartDecision::Restart { delay }
+    }
+}
+
+/// Last `max_lines` lines of a log file
+pub fn read_log_tail(path: &Path, max_lines: usize) -> Result<Vec<String>, std::io::Error> {
+    let mut file = File::open(path)?;
+    let start = file.metadata()?.len().saturating_sub(LOG_TAIL_MAX_BYTES);
+    file.seek(SeekFrom::Start(start))?;
+    let mut buffer = Vec::new();
+    file.read_to_end(&mut buffer)?;
+
+    let content = String::from_utf8_lossy(&buffer);
+    let mut lines: Vec<&str> = content.lines().collect();
+    // When reading starts in the middle of the file the first line is cut
+    if start > 0 && !lines.is_empty() {
+        lines.remove(0);
+    }
+    let skip = lines.len().saturating_sub(max_lines);
+    Ok(lines.into_iter().skip(skip).map(str::to_string).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn policy(max_restarts: Option<u32>) -> RestartPolicy {
+        RestartPolicy {
+            max_restarts,
+            restart_window: Duration::from_secs(60),
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(10),
+            backoff_multiplier: 2,
+            max_jitter: Duration::ZERO,
+            stop_on_exit_codes: vec![],
+        }
+    }
+
+    fn restart_delay(decision: RestartDecision) -> Duration {
+        match decision {
+            RestartDecision::Restart { delay } => delay,
+            RestartDecision::CrashLoop { .. } => panic!("Unexpected crash loop"),
+        }
+    }
+
+    #[test]
+    fn backoff_grows_exponentially_up_to_max() {
+        let policy = policy(None);
+        let mut tracker = RestartTracker::default();
+        let now = Instant::now();
+        let delays: Vec<u64> = (0..6)
+            .map(|_| restart_delay(tracker.next_restart(&policy, now)).as_secs())
+            .collect();
+        assert_eq!(delays, vec![1, 2, 4, 8, 10, 10]);
+
+        tracker.record_healthy();
+        assert_eq!(
+            restart_delay(tracker.next_restart(&policy, now)),
+            Duration::from_secs(1)
+        );
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn jitter_stays_within_limit() {
+        let policy = RestartPolicy {
+            max_jitter: Duration::from_millis(100),
+            ..policy(None)
+        };
+        for _ in 0..20 {
+            let delay =
+                restart_delay(RestartTracker::default().next_restart(&policy, Instant::now()));
+            assert!(delay >= Duration::from_secs(1));
+            assert!(delay <= Duration::from_millis(1100));
+        }
+    }
+
+    #[test]
+    fn detects_crash_loop_within_window() {
+        let policy = policy(Some(3));
+        let mut tracker = RestartTracker::default();
+        let start = Instant::now();
+        for _ in 0..3 {
+            restart_delay(tracker.next_restart(&policy, start));
+        }
+        assert_eq!(
+            tracker.next_restart(&policy, start + Duration::from_secs(30)),
+            RestartDecision::CrashLoop { restarts: 3 }
+        );
+        // Restarts older than the window are forgotten
+        restart_delay(tracker.next_restart(&policy, start + Duration::from_secs(61)));
+    }
+
+    #[test]
+    fn never_detects_crash_loop_without_limit() {
+        let policy = policy(None);
+        let mut tracker = RestartTracker::default();
+        for _ in 0..100 {
+            restart_delay(tracker.next_restart(&policy, Instant::now()));
+        }
+    }
+
+    #[test]
+    fn reads_last_lines_of_log() {
+        let path = std::env::temp_dir().join(format!("tari-log-tail-{}.log", std::process::id()));
+        let long_line = "x".repeat(1024);
+        let mut content: Vec<String> = (0..100).map(|_| long_line.clone()).collect();
+        content.extend((0..5).map(|index| format!("line {index}")));
+        std::fs::write(&path, content.join("\n")).unwrap();
+
+        let tail = read_log_tail(&path, 3).unwrap();
+        let all = read_log_tail(&path, 1000).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(tail, vec!["line 2", "line 3", "line 4"]);
+        // Only the last 64KiB are read and the cut first line is skipped
+        assert!(all.len() < content.len());
+        assert!(all
+            .iter()
+            .all(|line| line.len() == 1024 || line.starts_with("line")));
+    }
+}
@@ -23,6 +23,7 @@
 use std::{fs, future::Future, io::Write, path::Path, pin::Pin, time::Duration};
 
 use crate::process_adapter::ProcessStartupSpec;
+use crate::process_resource_limits::PreparedResourceLimits;
 
 pub fn launch_child_process(
     file_path: &Path,
@@ -30,6 +31,7 @@ pub fn launch_child_process(
     envs: Option<&std::collections::HashMap<String, String>>,
     args: &[String],
     allow_output: bool,
+    resource_limits: Option<&PreparedResourceLimits>,
 ) -> Result<tokio::process::Child, anyhow::Error> {
     let stdout = if allow_output {
         std::process::Stdio::piped()
@@ -41,31 +43,29 @@ pub fn launch_child_process(
     } else {
         std::process::Stdio::null()
     };
-    #[cfg(not(target_os = "windows"))]
-    {
-        Ok(tokio::process::Command::new(file_path)
-            .args(args)
-            .current_dir(current_dir)
-            .envs(envs.cloned().unwrap_or_default())
-            .stdout(stdout)
-            .stderr(stderr)
-            .kill_on_drop(true)
-            .spawn()?)
-    }
+    let mut command = tokio::process::Command::new(file_path);
+    command
+        .args(args)
+        .current_dir(current_dir)
+        .envs(envs.cloned().unwrap_or_default())
+        .stdout(stdout)
+        .stderr(stderr)
+        .kill_on_drop(true);
     #[cfg(target_os = "windows")]
     {
         use crate::consts::PROCESS_CREATION_NO_WINDOW;
 
-        Ok(tokio::process::Command::new(file_path)
-            .args(args)
-            .current_dir(current_dir)
-            .envs(envs.cloned().unwrap_or_default())
-            .stdout(stdout)
-            .stderr(stderr)
-            .kill_on_drop(true)
-            .creation_flags(PROCESS_CREATION_NO_WINDOW)
-            .spawn()?)
+        command.creation_flags(PROCESS_CREATION_NO_WINDOW);
+    }
+    if let Some(resource_limits) = resource_limits {
+        resource_limits.configure(&mut command);
+    }
+
+    let child = command.spawn()?;
+    if let (Some(resource_limits), Some(pid)) = (resource_limits, child.id()) {
+        resource_limits.attach(pid);
     }
+    Ok(child)
 }
 
 // pub async fn launch_and_get_outputs(
@@ -21,12 +21,18 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use crate::binaries::{Binaries, BinaryResolver};
+use crate::events::CriticalProblemPayload;
+use crate::events_emitter::EventsEmitter;
 use crate::process_adapter::{HandleUnhealthyResult, ProcessInstanceTrait};
 use crate::process_adapter::{HealthStatus, ProcessAdapter, StatusMonitor};
+use crate::process_killer::{resume_process, suspend_process};
+use crate::process_restart_policy::{
+    read_log_tail, RestartDecision, RestartPolicy, RestartTracker,
+};
 use futures_util::future::FusedFuture;
 use log::{error, info, warn};
 use std::path::{Path, PathBuf};
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use tari_shutdown::{Shutdown, ShutdownSignal};
@@ -39,6 +45,8 @@ use tokio::time::{Instant, MissedTickBehavior};
 use tokio_util::task::TaskTracker;
 
 const LOG_TARGET: &str = "tari::universe::process_watcher";
+const CRASH_LOOP_LOG_TAIL_LINES: usize = 30;
+const UNHEALTHY_OUTPUT_TAIL_LINES: usize = 20;
 
 #[derive(Debug, Clone, Default)]
 pub(crate) struct ProcessWatcherStats {
@@ -60,13 +68,17 @@ pub struct ProcessWatcher<TAdapter: ProcessAdapter> {
     pub health_timeout: tokio::time::Duration,
     pub expected_startup_time: tokio::time::Duration,
     pub(crate) status_monitor: Option<TAdapter::StatusMonitor>,
-    pub stop_on_exit_codes: Vec<i32>,
+    pub restart_policy: RestartPolicy,
     stats_broadcast: watch::Sender<ProcessWatcherStats>,
     is_first_start: Arc<AtomicBool>,
+    // Health checks are skipped while paused, a paused miner doesn't respond or reports no hashrate
+    is_paused: Arc<AtomicBool>,
+    pid_file_path: Option<PathBuf>,
 }
 
 impl<TAdapter: ProcessAdapter> ProcessWatcher<TAdapter> {
     pub fn new(adapter: TAdapter, stats_broadcast: watch::Sender<ProcessWatcherStats>) -> Self {
+        let restart_policy = adapter.restart_policy();
         Self {
             adapter,
             watcher_task: None,
@@ -75,9 +87,11 @@ impl<TAdapter: ProcessAdapter> ProcessWatcher<TAdapter> {
             health_timeout: tokio::time::Duration::from_secs(4),
             expected_startup_time: tokio::time::Duration::from_secs(20),
             status_monitor: None,
-            stop_on_exit_codes: Vec::new(),
+            restart_policy,
             stats_broadcast,
             is_first_start: Arc::new(AtomicBool::new(true)),
+            is_paused: Arc::new(AtomicBool::new(false)),
+            pid_file_path: None,
         }
     }
 }
@@ -124,6 +138,9 @@ impl<TAdapter: ProcessAdapter> ProcessWatcher<TAdapter> {
         let health_timeout = self.health_timeout;
 
         info!(target: LOG_TARGET, "Using {binary_path:?} for {name}");
+        self.is_paused.store(false, Ordering::SeqCst);
+        self.pid_file_path = Some(base_path.join(self.adapter.pid_file_name()));
+        let log_file_path = self.adapter.log_file_path(&log_path);
         let first_start = self
             .is_first_start
             .load(std::sync::atomic::Ordering::SeqCst);
@@ -140,8 +157,9 @@ impl<TAdapter: ProcessAdapter> ProcessWatcher<TAdapter> {
         let expected_startup_time = self.expected_startup_time;
         let mut global_shutdown_signal: ShutdownSignal = global_shutdown_signal.clone();
         let task_tracker = task_tracker.clone();
-        let stop_on_exit_codes = self.stop_on_exit_codes.clone();
+        let restart_policy = self.restart_policy.clone();
         let stats_broadcast = self.stats_broadcast.clone();
+        let is_paused = self.is_paused.clone();
         self.watcher_task = Some(task_tracker.clone().spawn(async move {
             child.start(task_tracker.clone()).await?;
             let mut uptime = Instant::now();
@@ -160,14 +178,18 @@ impl<TAdapter: ProcessAdapter> ProcessWatcher<TAdapter> {
             watch_timer.set_missed_tick_behavior(MissedTickBehavior::Delay);
             let mut warning_count = 0;
             let mut duration_since_last_healthy_status = Duration::from_secs(0);
+            let mut restart_tracker = RestartTracker::default();
             // read events such as stdout
             loop {
                 let unhealthy_timer = Instant::now();
                 select! {
                       _ = watch_timer.tick() => {
+                        if is_paused.load(Ordering::SeqCst) {
+                            continue;
+                        }
                         let status_monitor3 = status_monitor2.clone();
 
-                        if let Some(exit_code) = do_health_check(
+                        match do_health_check(
                             &mut child,
                             status_monitor3,
                             name.clone(),
@@ -180,10 +202,17 @@ impl<TAdapter: ProcessAdapter> ProcessWatcher<TAdapter> {
                             task_tracker.clone(),
                             inner_shutdown.clone(),
                             &mut warning_count,
-                            &stop_on_exit_codes,
+                            &restart_policy,
+                            &mut restart_tracker,
                             &mut stats
                         ).await? {
-                            return Ok(exit_code);
+                            HealthCheckOutcome::Continue => {}
+                            HealthCheckOutcome::Exit(exit_code) => return Ok(exit_code),
+                            HealthCheckOutcome::CrashLoop { exit_code, restarts } => {
+                                let output_tail = child.output_tail(CRASH_LOOP_LOG_TAIL_LINES);
+                                report_crash_loop(&name, restarts, &restart_policy, log_file_path.as_deref(), output_tail, &task_tracker);
+                                return Ok(exit_code);
+                            }
                         }
                     },
                     _ = inner_shutdown.wait() => {
@@ -224,8 +253,54 @@ impl<TAdapter: ProcessAdapter> ProcessWatcher<TAdapter> {
         Ok(())
     }
 
+    pub fn is_paused(&self) -> bool {
+        self.is_paused.load(Ordering::SeqCst)
+    }
+
+    /// Pauses health checks only, for processes which can pause their work on their own
+    pub fn set_paused(&self, is_paused: bool) {
+        self.is_paused.store(is_paused, Ordering::SeqCst);
+    }
+
+    /// Pauses the process with a signal, for processes without their own pause control
+    pub fn suspend(&self) -> Result<(), anyhow::Error> {
+        let pid = self.read_pid()?;
+        self.set_paused(true);
+        if let Err(e) = suspend_process(pid) {
+            self.set_paused(false);
+            return Err(e);
+        }
+        info!(target: LOG_TARGET, "Suspended {} process with pid {pid}", self.adapter.name());
+        Ok(())
+    }
+
+    pub fn resume(&self) -> Result<(), anyhow::Error> {
+        let pid = self.read_pid()?;
+        resume_process(pid)?;
+        self.set_paused(false);
+        info!(target: LOG_TARGET, "Resumed {} process with pid {pid}", self.adapter.name());
+        Ok(())
+    }
+
+    fn read_pid(&self) -> Result<i32, anyhow::Error> {
+        if !self.is_running() {
+            return Err(anyhow::anyhow!("{} is not running", self.adapter.name()));
+        }
+        let pid_file_path = self
+            .pid_file_path
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("{} pid file is unknown", self.adapter.name()))?;
+        let pid = std::fs::read_to_string(pid_file_path)?;
+        Ok(pid.trim().parse::<i32>()?)
+    }
+
     pub async fn stop(&mut self) -> Result<i32, anyhow::Error> {
         info!(target: LOG_TARGET, "Stopping process watcher for {}", self.adapter.name());
+        // A suspended process would not handle the termination signal
+        if self.is_paused() {
+            let _unused = self.resume();
+        }
+        self.set_paused(false);
         self.internal_shutdown.trigger();
         if let Some(task) = self.watcher_task.take() {
             let exit_code = task.await??;
@@ -236,6 +311,84 @@ impl<TAdapter: ProcessAdapter> ProcessWatcher<TAdapter> {
     }
 }
 
+enum HealthCheckOutcome {
+    Continue,
+    Exit(i32),
+    CrashLoop { exit_code: i32, restarts: usize },
+}
+
+fn report_crash_loop(
+    name: &str,
+    restarts: usize,
+    restart_policy: &RestartPolicy,
+    log_file_path: Option<&Path>,
+    output_tail: Vec<String>,
+    task_tracker: &TaskTracker,
+) {
+    error!(target: LOG_TARGET, "{name} was restarted {restarts} times within {:?}, stopping the watcher", restart_policy.restart_window);
+    let log_tail = log_file_path.and_then(|path| {
+        read_log_tail(path, CRASH_LOOP_LOG_TAIL_LINES)
+            .inspect_err(
+                |e| warn!(target: LOG_TARGET, "Could not read {name} log file {path:?}: {e}"),
+            )
+            .ok()
+    });
+    // Processes without a log file of their own only have the captured output
+    let log_tail = log_tail
+        .filter(|lines| !lines.is_empty())
+        .or(Some(output_tail).filter(|lines| !lines.is_empty()));
+    let payload = CriticalProblemPayload {
+        title: Some(format!("{name} keeps crashing")),
+        description: Some(format!(
+            "{name} was restarted {restarts} times within {} minutes and has been stopped.",
+            restart_policy.restart_window.as_secs() / 60
+        )),
+        error_message: log_tail.map(|lines| lines.join("\n")),
+    };
+    // Emitting waits for the frontend, it shouldn't hold the watcher task
+    task_tracker.spawn(EventsEmitter::emit_critical_problem(payload));
+}
+
+/// Stops the failed process and decides when it should be started again,
+/// returns the outcome for the watcher when it should not be restarted
+async fn stop_failed_process<TProcessInstance: ProcessInstanceTrait>(
+    child: &mut TProcessInstance,
+    name: &str,
+    restart_policy: &RestartPolicy,
+    restart_tracker: &mut RestartTracker,
+) -> Result<Duration, HealthCheckOutcome> {
+    let mut last_exit_code = 1;
+    match child.stop().await {
+        Ok(exit_code) => {
+            last_exit_code = exit_code;
+            if exit_code != 0 {
+                if restart_policy.should_stop_on_exit_code(exit_code) {
+                    return Err(HealthCheckOutcome::Exit(exit_code));
+                }
+                warn!(target: LOG_TARGET, "{name} exited with error code: {exit_code}, restarting because it is not a listed exit code to list for");
+            } else {
+                info!(target: LOG_TARGET, "{name} exited successfully");
+            }
+        }
+        Err(e) => {
+            error!(target: LOG_TARGET, "{name} exited with error: {e}");
+        }
+    }
+
+    let output_tail = child.output_tail(UNHEALTHY_OUTPUT_TAIL_LINES);
+    if !output_tail.is_empty() {
+        warn!(target: LOG_TARGET, "Last output of {name}:\n{}", output_tail.join("\n"));
+    }
+
+    match restart_tracker.next_restart(restart_policy, Instant::now()) {
+        RestartDecision::Restart { delay } => Ok(delay),
+        RestartDecision::CrashLoop { restarts } => Err(HealthCheckOutcome::CrashLoop {
+            exit_code: last_exit_code,
+            restarts,
+        }),
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn do_health_check<TStatusMonitor: StatusMonitor, TProcessInstance: ProcessInstanceTrait>(
     child: &mut TProcessInstance,
@@ -250,9 +403,10 @@ async fn do_health_check<TStatusMonitor: StatusMonitor, TProcessInstance: Proces
     task_tracker: TaskTracker,
     inner_shutdown: ShutdownSignal,
     warning_count: &mut u32,
-    stop_on_exit_codes: &[i32],
+    restart_policy: &RestartPolicy,
+    restart_tracker: &mut RestartTracker,
     stats: &mut ProcessWatcherStats,
-) -> Result<Option<i32>, anyhow::Error> {
+) -> Result<HealthCheckOutcome, anyhow::Error> {
     let mut is_healthy = false;
     let mut ping_failed = false;
 
@@ -310,28 +464,27 @@ async fn do_health_check<TStatusMonitor: StatusMonitor, TProcessInstance: Proces
         if uptime.elapsed() < expected_startup_time && !ping_failed {
             warn!(target: LOG_TARGET, "{name} is not healthy. Waiting for startup time to elapse");
         } else {
-            match child.stop().await {
-                Ok(exit_code) => {
-                    if exit_code != 0 {
-                        if stop_on_exit_codes.contains(&exit_code) {
-                            return Ok(Some(exit_code));
-                        }
-                        warn!(target: LOG_TARGET, "{name} exited with error code: {exit_code}, restarting because it is not a listed exit code to list for");
+            let delay =
+                match stop_failed_process(child, &name, restart_policy, restart_tracker).await {
+                    Ok(delay) => delay,
+                    Err(outcome) => return Ok(outcome),
+                };
 
-                        // return Ok(exit_code);
-                    } else {
-                        info!(target: LOG_TARGET, "{name} exited successfully");
-                    }
-                }
-                Err(e) => {
-                    error!(target: LOG_TARGET, "{name} exited with error: {e}");
-                    //   return Err(e);
+            // Restart dead app, the backoff can take a while so shutdown must not wait for it
+            let mut inner_shutdown3 = inner_shutdown.clone();
+            let mut app_shutdown3 = global_shutdown_signal.clone();
+            select! {
+                _ = sleep(delay) => {},
+                _ = inner_shutdown3.wait() => {
+                    info!(target: LOG_TARGET, "{name} won't be restarted, shutdown requested");
+                    return Ok(HealthCheckOutcome::Exit(0));
+                },
+                _ = app_shutdown3.wait() => {
+                    info!(target: LOG_TARGET, "{name} won't be restarted, app is shutting down");
+                    return Ok(HealthCheckOutcome::Exit(0));
                 }
             }
-
-            // Restart dead app
-            sleep(Duration::from_secs(1)).await;
-            warn!(target: LOG_TARGET, "Restarting {name} after health check failure");
+            warn!(target: LOG_TARGET, "Restarting {name} after health check failure, waited {delay:?}");
             *uptime = Instant::now();
             stats.num_restarts += 1;
             match status_monitor3
@@ -343,7 +496,7 @@ async fn do_health_check<TStatusMonitor: StatusMonitor, TProcessInstance: Proces
                 }
                 Ok(HandleUnhealthyResult::Stop) => {
                     info!(target: LOG_TARGET, "Stopping watcher after unhealthy state for {name}");
-                    return Ok(Some(1));
+                    return Ok(HealthCheckOutcome::Exit(1));
                 }
                 Err(e) => {
                     error!(target: LOG_TARGET, "Error handling unhealthy state for {name}: {e}");
@@ -365,9 +518,226 @@ async fn do_health_check<TStatusMonitor: StatusMonitor, TProcessInstance: Proces
     if is_healthy {
         // Reset the duration once we have a healthy status
         *duration_since_last_healthy_status = Duration::from_secs(0);
+        restart_tracker.record_healthy();
     }
 
     stats.current_uptime = uptime.elapsed();
 
-    Ok(None)
+    Ok(HealthCheckOutcome::Continue)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::atomic::AtomicU32;
+
+    /// Process which is never alive, every start crashes right away
+    struct CrashingProcess {
+        exit_code: i32,
+        is_alive: bool,
+        starts: Arc<AtomicU32>,
+    }
+
+    #[async_trait]
+    impl ProcessInstanceTrait for CrashingProcess {
+        fn ping(&self) -> bool {
+            self.is_alive
+        }
+        async fn start(&mut self, _task_tracker: TaskTracker) -> Result<(), anyhow::Error> {
+            self.starts.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+        async fn stop(&mut self) -> Result<i32, anyhow::Error> {
+            Ok(self.exit_code)
+        }
+        fn is_shutdown_triggered(&self) -> bool {
+            false
+        }
+        async fn wait(&mut self) -> Result<i32, anyhow::Error> {
+            Ok(self.exit_code)
+        }
+        async fn start_and_wait_for_output(
+            &mut self,
+            _task_tracker: TaskTracker,
+        ) -> Result<(i32, Vec<String>, Vec<String>), anyhow::Error> {
+            Ok((self.exit_code, vec![], vec![]))
+        }
+    }
+
+    #[derive(Clone)]
+    struct FixedStatusMonitor(HealthStatus);
+
+    #[async_trait]
+    impl StatusMonitor for FixedStatusMonitor {
+        async fn check_health(&self, _uptime: Duration, _timeout: Duration) -> HealthStatus {
+            self.0.clone()
+        }
+    }
+
+    fn restart_policy(max_restarts: Option<u32>, stop_on_exit_codes: Vec<i32>) -> RestartPolicy {
+        RestartPolicy {
+            max_restarts,
+            restart_window: Duration::from_secs(60),
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(5),
+            backoff_multiplier: 2,
+            max_jitter: Duration::ZERO,
+            stop_on_exit_codes,
+        }
+    }
+
+    struct HealthCheckRunner {
+        child: CrashingProcess,
+        status_monitor: FixedStatusMonitor,
+        restart_policy: RestartPolicy,
+        restart_tracker: RestartTracker,
+        stats: ProcessWatcherStats,
+        shutdown: Shutdown,
+        shutdown_signal: ShutdownSignal,
+    }
+
+    impl HealthCheckRunner {
+        fn new(
+            child: CrashingProcess,
+            status: HealthStatus,
+            restart_policy: RestartPolicy,
+        ) -> Self {
+            let shutdown = Shutdown::new();
+            Self {
+                child,
+                status_monitor: FixedStatusMonitor(status),
+                restart_policy,
+                restart_tracker: RestartTracker::default(),
+                stats: ProcessWatcherStats::default(),
+                shutdown_signal: shutdown.to_signal(),
+                shutdown,
+            }
+        }
+
+        async fn run(&mut self) -> HealthCheckOutcome {
+            let mut uptime = Instant::now();
+            let mut duration_since_last_healthy_status = Duration::ZERO;
+            let mut warning_count = 0;
+            do_health_check(
+                &mut self.child,
+                self.status_monitor.clone(),
+                "fake_process".to_string(),
+                &mut uptime,
+                &mut duration_since_last_healthy_status,
+                Instant::now(),
+                Duration::ZERO,
+                Duration::from_secs(1),
+                self.shutdown_signal.clone(),
+                TaskTracker::new(),
+                self.shutdown_signal.clone(),
+                &mut warning_count,
+                &self.restart_policy,
+                &mut self.restart_tracker,
+                &mut self.stats,
+            )
+            .await
+            .unwrap()
+        }
+    }
+
+    fn crashing_process(exit_code: i32) -> (CrashingProcess, Arc<AtomicU32>) {
+        let starts = Arc::new(AtomicU32::new(0));
+        let process = CrashingProcess {
+            exit_code,
+            is_alive: false,
+            starts: starts.clone(),
+        };
+        (process, starts)
+    }
+
+    #[tokio::test]
+    async fn restarts_crashed_process_until_crash_loop() {
+        let (process, starts) = crashing_process(101);
+        let mut runner = HealthCheckRunner::new(
+            process,
+            HealthStatus::Unhealthy,
+            restart_policy(Some(3), vec![]),
+        );
+
+        for _ in 0..3 {
+            assert!(matches!(runner.run().await, HealthCheckOutcome::Continue));
+        }
+        assert!(matches!(
+            runner.run().await,
+            HealthCheckOutcome::CrashLoop {
+                exit_code: 101,
+                restarts: 3
+            }
+        ));
+        assert_eq!(starts.load(Ordering::SeqCst), 3);
+        assert_eq!(runner.stats.num_restarts, 3);
+        assert_eq!(runner.stats.num_failures, 4);
+    }
+
+    #[tokio::test]
+    async fn stops_on_listed_exit_code() {
+        let (process, starts) = crashing_process(101);
+        let mut runner = HealthCheckRunner::new(
+            process,
+            HealthStatus::Unhealthy,
+            restart_policy(Some(3), vec![101]),
+        );
+
+        assert!(matches!(runner.run().await, HealthCheckOutcome::Exit(101)));
+        assert_eq!(starts.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn keeps_restarting_without_crash_loop_detection() {
+        let (process, starts) = crashing_process(1);
+        let mut runner = HealthCheckRunner::new(
+            process,
+            HealthStatus::Unhealthy,
+            restart_policy(None, vec![]),
+        );
+
+        for _ in 0..20 {
+            assert!(matches!(runner.run().await, HealthCheckOutcome::Continue));
+        }
+        assert_eq!(starts.load(Ordering::SeqCst), 20);
+    }
+
+    #[tokio::test]
+    async fn shutdown_interrupts_restart_backoff() {
+        let (process, starts) = crashing_process(1);
+        let mut restart_policy = restart_policy(None, vec![]);
+        restart_policy.initial_backoff = Duration::from_secs(60);
+        restart_policy.max_backoff = Duration::from_secs(60);
+        let mut runner = HealthCheckRunner::new(process, HealthStatus::Unhealthy, restart_policy);
+        let mut shutdown = std::mem::replace(&mut runner.shutdown, Shutdown::new());
+        tokio::spawn(async move {
+            sleep(Duration::from_millis(10)).await;
+            shutdown.trigger();
+        });
+
+        let outcome = tokio::time::timeout(Duration::from_secs(5), runner.run())
+            .await
+            .unwrap();
+        assert!(matches!(outcome, HealthCheckOutcome::Exit(0)));
+        assert_eq!(starts.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn healthy_process_is_not_restarted() {
+        let (mut process, starts) = crashing_process(1);
+        process.is_alive = true;
+        let mut runner = HealthCheckRunner::new(
+            process,
+            HealthStatus::Healthy,
+            restart_policy(Some(1), vec![]),
+        );
+
+        for _ in 0..5 {
+            assert!(matches!(runner.run().await, HealthCheckOutcome::Continue));
+        }
+        assert_eq!(starts.load(Ordering::SeqCst), 0);
+        assert_eq!(runner.stats.num_failures, 0);
+    }
 }
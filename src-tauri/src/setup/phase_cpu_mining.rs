@@ -211,6 +211,7 @@ impl SetupPhaseImpl for CpuMiningSetupPhase {
                     .await
                     .get_unique_string()
                     .await;
+                state.node_manager.ensure_plain_grpc_access().await?;
                 let base_node_grpc_address = state.node_manager.get_grpc_address().await?;
 
                 state
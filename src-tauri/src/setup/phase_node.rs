@@ -27,12 +27,16 @@ use crate::{
     configs::{config_core::ConfigCore, trait_config::ConfigImpl},
     events_emitter::EventsEmitter,
     events_manager::EventsManager,
-    node::node_manager::{NodeManagerError, STOP_ON_ERROR_CODES},
+    node::{
+        node_manager::{NodeManagerError, STOP_ON_ERROR_CODES},
+        node_snapshot,
+        remote_node_selector::RemoteNodeSelector,
+    },
     progress_trackers::{
         progress_plans::SetupStep,
         progress_stepper::{ProgressStepper, ProgressStepperBuilder},
     },
-    setup::setup_manager::SetupPhase,
+    setup::setup_manager::{SetupManager, SetupPhase},
     tasks_tracker::TasksTrackers,
     UniverseAppState,
 };
@@ -147,7 +151,13 @@ impl SetupPhaseImpl for NodeSetupPhase {
     async fn load_app_configuration() -> Result<Self::AppConfiguration, Error> {
         let config_core = ConfigCore::content().await;
         let use_tor = *config_core.use_tor();
-        let base_node_grpc_address = config_core.remote_base_node_address().clone();
+        // Probing the remote nodes is skipped when only the local or a self hosted node is used
+        let node_type = config_core.node_type();
+        let base_node_grpc_address = if node_type.is_remote() && !node_type.is_external() {
+            RemoteNodeSelector::select_initial().await
+        } else {
+            config_core.remote_base_node_address().clone()
+        };
 
         Ok(NodeSetupPhaseAppConfiguration {
             use_tor,
@@ -211,6 +221,34 @@ impl SetupPhaseImpl for NodeSetupPhase {
             })
             .await?;
 
+        // Requested with the import-snapshot cli argument, the node is not running yet
+        if let Some(snapshot_path) = node_snapshot::take_pending_import() {
+            let import_data_dir = data_dir.clone();
+            match tokio::task::spawn_blocking(move || {
+                node_snapshot::import_snapshot(&import_data_dir, &snapshot_path)
+            })
+            .await?
+            {
+                Ok(manifest) => {
+                    node_snapshot::use_snapshot_storage_profile(&manifest).await?;
+                }
+                Err(e) => {
+                    error!(target: LOG_TARGET, "Failed to import node snapshot: {e:?}");
+                }
+            }
+        }
+        // Requested with the deprecated import-backup cli argument
+        if let Some(database_file) = node_snapshot::take_pending_database_import() {
+            let import_data_dir = data_dir.clone();
+            if let Err(e) = tokio::task::spawn_blocking(move || {
+                node_snapshot::import_database(&import_data_dir, &database_file)
+            })
+            .await?
+            {
+                error!(target: LOG_TARGET, "Failed to import node database: {e:?}");
+            }
+        }
+
         progress_stepper.complete_step(SetupStep::StartingNode, || async {
             for _i in 0..2 {
                 let tor_control_port = state.tor_manager.get_control_port().await?;
@@ -345,6 +383,44 @@ impl SetupPhaseImpl for NodeSetupPhase {
                 });
         }
 
+        // Requested with the export-snapshot cli argument
+        if let Some(destination_dir) = node_snapshot::take_pending_export() {
+            let app_handle_clone: tauri::AppHandle = self.app_handle.clone();
+            let (data_dir, _, _) = self.get_app_dirs()?;
+            // Runs outside of the node phase as it restarts it
+            TasksTrackers::current()
+                .common
+                .get_task_tracker()
+                .await
+                .spawn(async move {
+                    let state = app_handle_clone.state::<UniverseAppState>();
+                    let mut node_status_watch_rx = (*state.node_status_watch_rx).clone();
+                    if node_status_watch_rx.wait_for(|status| status.is_synced).await.is_err() {
+                        return;
+                    }
+                    if !state.node_manager.is_local_current().await {
+                        warn!(target: LOG_TARGET, "Node snapshots can only be exported from the local node");
+                        return;
+                    }
+                    match node_snapshot::export_snapshot(&state.node_manager, &data_dir, &destination_dir).await {
+                        Ok(manifest) => {
+                            info!(target: LOG_TARGET, "Node snapshot exported to {destination_dir:?}: {manifest:?}");
+                        }
+                        // Retried once the node is running again
+                        Err(e) if e.is::<node_snapshot::TipMovedError>() => {
+                            warn!(target: LOG_TARGET, "{e}");
+                            node_snapshot::request_export(destination_dir);
+                        }
+                        Err(e) => {
+                            error!(target: LOG_TARGET, "Failed to export node snapshot: {e:?}");
+                        }
+                    }
+                    SetupManager::get_instance()
+                        .restart_phases(vec![SetupPhase::Node, SetupPhase::Wallet, SetupPhase::CpuMining])
+                        .await;
+                });
+        }
+
         let app_handle_clone: tauri::AppHandle = self.app_handle.clone();
         TasksTrackers::current()
             .node_phase
@@ -28,10 +28,11 @@ use super::listeners::{setup_listener, SetupFeature, SetupFeaturesList};
 use super::trait_setup_phase::SetupPhaseImpl;
 use super::utils::phase_builder::PhaseBuilder;
 use crate::app_in_memory_config::{MinerType, DEFAULT_EXCHANGE_ID};
-use crate::commands::start_cpu_mining;
+use crate::commands::{start_cpu_mining, start_gpu_mining};
 use crate::configs::config_core::ConfigCoreContent;
 use crate::configs::config_mining::ConfigMiningContent;
 use crate::configs::config_pools::{ConfigPools, ConfigPoolsContent};
+use crate::configs::config_schedule::ConfigSchedule;
 use crate::configs::config_ui::WalletUIMode;
 use crate::configs::config_wallet::ConfigWalletContent;
 use crate::events::CriticalProblemPayload;
@@ -39,6 +40,7 @@ use crate::internal_wallet::InternalWallet;
 use crate::mining::gpu::consts::GpuMinerType;
 use crate::mining::gpu::manager::GpuManager;
 use crate::mining::pools::cpu_pool_manager::CpuPoolManager;
+use crate::mining::pools::earnings_history::PoolEarningsHistory;
 use crate::mining::pools::gpu_pool_manager::GpuPoolManager;
 use crate::mining::pools::PoolManagerInterfaceTrait;
 use crate::progress_trackers::progress_plans::SetupStep;
@@ -305,6 +307,8 @@ impl SetupManager {
         ConfigMining::initialize(app_handle.clone()).await;
         ConfigUI::initialize(app_handle.clone()).await;
         ConfigPools::initialize(app_handle.clone()).await;
+        ConfigSchedule::initialize(app_handle.clone()).await;
+        PoolEarningsHistory::initialize(app_handle.clone()).await;
 
         let node_type = ConfigCore::content().await.node_type().clone();
         info!(target: LOG_TARGET, "Retrieved initial node type: {node_type:?}");
@@ -339,6 +343,7 @@ impl SetupManager {
         EventsEmitter::emit_mining_config_loaded(&ConfigMining::content().await).await;
         EventsEmitter::emit_ui_config_loaded(&ConfigUI::content().await).await;
         EventsEmitter::emit_pools_config_loaded(&ConfigPools::content().await).await;
+        EventsEmitter::emit_schedule_config_loaded(&ConfigSchedule::content().await).await;
 
         let is_on_exchange_specific_variant = ConfigCore::content()
             .await
@@ -834,6 +839,60 @@ impl SetupManager {
         Ok(())
     }
 
+    /// Used by the profit switcher to move CPU mining between Tari RandomX pool mining
+    /// and Monero merge mining through mmproxy
+    pub async fn switch_cpu_pool_mining(&self, is_pool_enabled: bool) -> Result<(), anyhow::Error> {
+        info!(target: LOG_TARGET, "Switching CPU pool mining to: {is_pool_enabled}");
+        let app_handle = self.app_handle().await;
+        let app_state = app_handle.state::<UniverseAppState>().clone();
+
+        if !is_pool_enabled {
+            CpuPoolManager::stop_stats_watcher().await;
+        }
+        ConfigPools::update_field(ConfigPoolsContent::set_cpu_pool_enabled, is_pool_enabled)
+            .await?;
+        EventsEmitter::emit_pools_config_loaded(&ConfigPools::content().await).await;
+
+        // Merge mining requires mmproxy which is started by the cpu mining phase
+        self.restart_phases(vec![SetupPhase::CpuMining]).await;
+
+        start_cpu_mining(app_state.clone(), app_handle.clone())
+            .await
+            .map_err(anyhow::Error::msg)?;
+
+        Ok(())
+    }
+
+    /// Used by the CPU pool failover after switching to another pool
+    /// xmrig reads the pool connection only on start so it has to be restarted
+    pub async fn restart_cpu_mining_after_pool_switch(&self) -> Result<(), anyhow::Error> {
+        info!(target: LOG_TARGET, "Restarting CPU mining after pool switch");
+        let app_handle = self.app_handle().await;
+        let app_state = app_handle.state::<UniverseAppState>().clone();
+
+        app_state.cpu_miner.write().await.stop().await?;
+        start_cpu_mining(app_state.clone(), app_handle.clone())
+            .await
+            .map_err(anyhow::Error::msg)?;
+
+        Ok(())
+    }
+
+    /// Used by the GPU pool failover after switching to another pool
+    /// GPU miners read the pool connection only on start so they have to be restarted
+    pub async fn restart_gpu_mining_after_pool_switch(&self) -> Result<(), anyhow::Error> {
+        info!(target: LOG_TARGET, "Restarting GPU mining after pool switch");
+        let app_handle = self.app_handle().await;
+        let app_state = app_handle.state::<UniverseAppState>().clone();
+
+        GpuManager::write().await.stop_mining().await?;
+        start_gpu_mining(app_state.clone(), app_handle.clone())
+            .await
+            .map_err(anyhow::Error::msg)?;
+
+        Ok(())
+    }
+
     pub async fn handle_switch_to_local_node(&self) {
         let app_handle = self.app_handle().await;
         info!(target: LOG_TARGET, "Handle Switching to Local Node in Setup Manager");
@@ -40,6 +40,7 @@ pub enum SystrayItemId {
     CpuHashrate,
     GpuHashrate,
     EstimatedEarning,
+    ThermalStatus,
     MinimizeToggle,
 }
 
@@ -49,6 +50,7 @@ impl SystrayItemId {
             SystrayItemId::CpuHashrate => "cpu_hashrate",
             SystrayItemId::GpuHashrate => "gpu_hashrate",
             SystrayItemId::EstimatedEarning => "estimated_earning",
+            SystrayItemId::ThermalStatus => "thermal_status",
             SystrayItemId::MinimizeToggle => "minimize_toggle",
         }
     }
@@ -60,6 +62,7 @@ impl SystrayItemId {
             SystrayItemId::EstimatedEarning => {
                 format!("Est. Earning: {}", format_currency(value, "XTM/day"))
             }
+            SystrayItemId::ThermalStatus => "Thermal guard: Off".to_string(),
             SystrayItemId::MinimizeToggle => "Minimize/Unminimize".to_string(),
         }
     }
@@ -126,6 +129,13 @@ impl SystemTrayManager {
             false,
             None::<&str>,
         )?;
+        let thermal_status = MenuItem::with_id(
+            &app,
+            SystrayItemId::ThermalStatus.to_str(),
+            SystrayItemId::ThermalStatus.get_title(0.0),
+            false,
+            None::<&str>,
+        )?;
         let minimize_toggle = MenuItem::with_id(
             &app,
             SystrayItemId::MinimizeToggle.to_str(),
@@ -146,6 +156,8 @@ impl SystemTrayManager {
                 &separator,
                 &estimated_earning,
                 &separator,
+                &thermal_status,
+                &separator,
                 &minimize_toggle,
             ],
         )?;
@@ -227,6 +239,25 @@ impl SystemTrayManager {
         self.update_tray(self.data.clone());
     }
 
+    pub fn update_tray_with_thermal_status(&mut self, text: String) {
+        let Some(menu) = &self.menu else {
+            error!(target: LOG_TARGET, "Menu not initialized");
+            return;
+        };
+        let id = SystrayItemId::ThermalStatus;
+        match menu
+            .get(id.to_str())
+            .and_then(|item| item.as_menuitem().cloned())
+        {
+            Some(menu_item) => {
+                if let Err(e) = menu_item.set_text(text) {
+                    error!(target: LOG_TARGET, "Failed to update menu field: {e}");
+                }
+            }
+            None => error!(target: LOG_TARGET, "Failed to get menu item for {id:?}"),
+        }
+    }
+
     pub fn update_tray(&mut self, data: SystemTrayData) {
         if let Some(tray) = &self.tray {
             if let Err(e) = tray.set_tooltip(self.get_tooltip_text(data.clone())) {
@@ -21,7 +21,7 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use anyhow::{anyhow, Error};
@@ -42,6 +42,8 @@ use crate::{
     process_adapter::{
         HealthStatus, ProcessAdapter, ProcessInstance, ProcessStartupSpec, StatusMonitor,
     },
+    process_output_buffer::ProcessOutputRegistry,
+    process_resource_limits::ProcessResourceLimits,
     utils::file_utils::convert_to_string,
 };
 
@@ -296,7 +298,9 @@ impl ProcessAdapter for TorAdapter {
                     data_dir: data_dir.clone(),
                     pid_file_name: self.pid_file_name().to_string(),
                     name: self.name().to_string(),
+                    resource_limits: ProcessResourceLimits::default(),
                 },
+                output_buffer: ProcessOutputRegistry::buffer(self.name()),
             },
             TorStatusMonitor {
                 control_port,
@@ -313,6 +317,10 @@ impl ProcessAdapter for TorAdapter {
     fn pid_file_name(&self) -> &str {
         "tor_pid"
     }
+
+    fn log_file_path(&self, log_folder: &Path) -> Option<PathBuf> {
+        Some(log_folder.join("tor.log"))
+    }
 }
 
 #[derive(Clone)]
@@ -39,6 +39,8 @@ use crate::process_adapter::{
     HealthStatus, ProcessAdapter, ProcessInstance, ProcessInstanceTrait, ProcessStartupSpec,
     StatusMonitor,
 };
+use crate::process_output_buffer::ProcessOutputRegistry;
+use crate::process_resource_limits::ProcessResourceLimits;
 use crate::tasks_tracker::TasksTrackers;
 use crate::utils::commands_builder::CommandBuilder;
 use crate::utils::logging_utils::setup_logging;
@@ -327,7 +329,9 @@ impl ProcessAdapter for SpendWallet {
                 pid_file_name: self.pid_file_name().to_string(),
                 data_dir,
                 name: self.name().to_string(),
+                resource_limits: ProcessResourceLimits::default(),
             },
+            output_buffer: ProcessOutputRegistry::buffer(self.name()),
         };
 
         Ok((instance, DummyStatusMonitor))
@@ -24,6 +24,8 @@ use crate::events_emitter::EventsEmitter;
 use crate::port_allocator::PortAllocator;
 use crate::process_adapter::{ProcessAdapter, ProcessInstance, ProcessStartupSpec};
 use crate::process_adapter_utils::setup_working_directory;
+use crate::process_output_buffer::ProcessOutputRegistry;
+use crate::process_resource_limits::ProcessResourceLimits;
 use crate::tasks_tracker::TasksTrackers;
 use crate::utils::file_utils::convert_to_string;
 use crate::utils::logging_utils::setup_logging;
@@ -83,6 +85,7 @@ pub struct WalletAdapter {
     pub(crate) state_broadcast: watch::Sender<Option<WalletState>>,
     pub(crate) wallet_birthday: Option<u16>,
     pub(crate) http_client_url: Option<String>,
+    pub(crate) resource_limits: ProcessResourceLimits,
 }
 
 impl WalletAdapter {
@@ -99,6 +102,7 @@ impl WalletAdapter {
             state_broadcast,
             wallet_birthday: None,
             http_client_url: None,
+            resource_limits: ProcessResourceLimits::default(),
         }
     }
 
@@ -455,7 +459,9 @@ impl ProcessAdapter for WalletAdapter {
                     data_dir,
                     pid_file_name: self.pid_file_name().to_string(),
                     name: self.name().to_string(),
+                    resource_limits: self.resource_limits.clone(),
                 },
+                output_buffer: ProcessOutputRegistry::buffer(self.name()),
             },
             WalletStatusMonitor::new(self.grpc_port, self.state_broadcast.clone()),
         ))
@@ -468,4 +474,8 @@ impl ProcessAdapter for WalletAdapter {
     fn pid_file_name(&self) -> &str {
         "wallet_pid"
     }
+
+    fn log_file_path(&self, log_folder: &Path) -> Option<PathBuf> {
+        Some(log_folder.join("wallet").join("log").join("base_layer.log"))
+    }
 }
@@ -20,6 +20,7 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use crate::configs::config_core::ConfigCore;
 use crate::configs::config_wallet::{ConfigWallet, ConfigWalletContent};
 use crate::configs::trait_config::ConfigImpl;
 use crate::events_emitter::EventsEmitter;
@@ -137,7 +138,9 @@ impl WalletManager {
 
         let tari_wallet_details = InternalWallet::tari_wallet_details().await;
         process_watcher.adapter.wallet_birthday = tari_wallet_details.map(|d| d.wallet_birthday);
-        process_watcher.stop_on_exit_codes = STOP_ON_ERROR_CODES.to_vec();
+        process_watcher.adapter.resource_limits =
+            ConfigCore::content().await.wallet_resource_limits().clone();
+        process_watcher.restart_policy.stop_on_exit_codes = STOP_ON_ERROR_CODES.to_vec();
 
         process_watcher
             .start(
@@ -0,0 +1,225 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// RandomX variant mined when connected to a pool, merge mining through mmproxy always uses `rx/0`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum XmrigRandomXAlgorithm {
+    #[default]
+    Rx0,
+    RxTari,
+}
+
+impl XmrigRandomXAlgorithm {
+    pub fn name(self) -> &'static str {
+        match self {
+            XmrigRandomXAlgorithm::Rx0 => "rx/0",
+            XmrigRandomXAlgorithm::RxTari => "rx/tari",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct XmrigOpenClConfig {
+    pub enabled: bool,
+    // Platform index or vendor name, the AMD platform like xmrig's own default when empty
+    pub platform: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct XmrigCudaConfig {
+    pub enabled: bool,
+    // Path to the xmrig-cuda plugin, xmrig looks next to its binary when empty
+    pub loader: Option<String>,
+}
+
+/// User facing xmrig settings persisted in `ConfigMining`
+/// Connection, http api and thread count are added when the config file is generated
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct XmrigConfig {
+    pub donate_level: u32,
+    pub huge_pages: bool,
+    pub huge_pages_jit: bool,
+    pub randomx_1gb_pages: bool,
+    // 0 (idle) - 5 (highest), os default when empty
+    pub cpu_priority: Option<u8>,
+    // Cores mining threads are pinned to, threads are not pinned when empty
+    pub cpu_affinity: Vec<u32>,
+    pub thread_intensity: u32,
+    pub pool_algorithm: XmrigRandomXAlgorithm,
+    pub opencl: XmrigOpenClConfig,
+    pub cuda: XmrigCudaConfig,
+}
+
+impl Default for XmrigConfig {
+    fn default() -> Self {
+        Self {
+            donate_level: 1,
+            huge_pages: true,
+            huge_pages_jit: false,
+            randomx_1gb_pages: false,
+            cpu_priority: None,
+            cpu_affinity: vec![],
+            thread_intensity: 1,
+            pool_algorithm: XmrigRandomXAlgorithm::default(),
+            opencl: XmrigOpenClConfig::default(),
+            cuda: XmrigCudaConfig::default(),
+        }
+    }
+}
+
+/// Values known only when xmrig is started
+pub struct XmrigRuntimeConfig {
+    pub pool: Value,
+    // Local daemon connections should retry as soon as possible
+    pub retry_pause_secs: u32,
+    pub http_api_port: u16,
+    pub http_api_token: String,
+    pub cpu_threads: Option<u32>,
+    pub log_file: Option<String>,
+}
+
+impl XmrigConfig {
+    /// Generates the content of xmrig `config.json`
+    /// The http api is not restricted so the config can be replaced while xmrig is running
+    pub fn generate(&self, runtime: XmrigRuntimeConfig) -> Value {
+        let mut cpu = json!({
+            "enabled": true,
+            "huge-pages": self.huge_pages,
+            "huge-pages-jit": self.huge_pages_jit,
+            "priority": self.cpu_priority.map(|priority| priority.min(5)),
+            "yield": true,
+        });
+        if let Some(threads_profile) = self.cpu_threads_profile(runtime.cpu_threads) {
+            cpu["rx"] = json!(threads_profile);
+            cpu["rx/tari"] = json!("rx");
+        }
+
+        json!({
+            "autosave": false,
+            "background": false,
+            "colors": false,
+            "watch": false,
+            "syslog": false,
+            "verbose": 1,
+            "print-time": 60,
+            "donate-level": self.donate_level,
+            // Switch for donating through proxies, not a percentage, 1 is xmrig's default
+            "donate-over-proxy": 1,
+            "log-file": runtime.log_file,
+            "retries": 5,
+            "retry-pause": runtime.retry_pause_secs,
+            "http": {
+                "enabled": true,
+                "host": "127.0.0.1",
+                "port": runtime.http_api_port,
+                "access-token": runtime.http_api_token,
+                "restricted": false,
+            },
+            "randomx": {
+                "init": -1,
+                "mode": "auto",
+                "1gb-pages": self.randomx_1gb_pages,
+            },
+            "cpu": cpu,
+            "opencl": {
+                "enabled": self.opencl.enabled,
+                "platform": self.opencl.platform.as_deref().unwrap_or("AMD"),
+            },
+            "cuda": {
+                "enabled": self.cuda.enabled,
+                "loader": self.cuda.loader,
+            },
+            "pools": [runtime.pool],
+        })
+    }
+
+    /// Threads as `[intensity, affinity]` pairs, `-1` leaves a thread unpinned
+    /// xmrig picks the threads itself when neither thread count nor affinity is set
+    fn cpu_threads_profile(&self, cpu_threads: Option<u32>) -> Option<Vec<(u32, i64)>> {
+        let threads_count = match cpu_threads {
+            Some(cpu_threads) => cpu_threads,
+            None if !self.cpu_affinity.is_empty() => {
+                u32::try_from(self.cpu_affinity.len()).unwrap_or(u32::MAX)
+            }
+            None => return None,
+        };
+        let intensity = self.thread_intensity.max(1);
+        let threads = (0..threads_count)
+            .map(|thread_index| {
+                let affinity = usize::try_from(thread_index)
+                    .ok()
+                    .and_then(|thread_index| self.cpu_affinity.get(thread_index))
+                    .map_or(-1, |core| i64::from(*core));
+                (intensity, affinity)
+            })
+            .collect();
+        Some(threads)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runtime(cpu_threads: Option<u32>) -> XmrigRuntimeConfig {
+        XmrigRuntimeConfig {
+            pool: json!({ "url": "127.0.0.1:18081", "coin": "monero" }),
+            retry_pause_secs: 1,
+            http_api_port: 18000,
+            http_api_token: "token".to_string(),
+            cpu_threads,
+            log_file: None,
+        }
+    }
+
+    #[test]
+    fn generates_thread_profile_with_affinity() {
+        let config = XmrigConfig {
+            cpu_affinity: vec![0, 2],
+            thread_intensity: 2,
+            ..Default::default()
+        };
+
+        let generated = config.generate(runtime(Some(3)));
+
+        assert_eq!(generated["cpu"]["rx"], json!([[2, 0], [2, 2], [2, -1]]));
+        assert_eq!(generated["cpu"]["rx/tari"], json!("rx"));
+        assert_eq!(generated["http"]["restricted"], json!(false));
+        assert_eq!(generated["pools"][0]["coin"], json!("monero"));
+    }
+
+    #[test]
+    fn leaves_threads_to_xmrig_without_count_and_affinity() {
+        let generated = XmrigConfig::default().generate(runtime(None));
+
+        assert!(generated["cpu"].get("rx").is_none());
+        assert_eq!(generated["donate-level"], json!(1));
+        assert_eq!(generated["donate-over-proxy"], json!(1));
+    }
+}
@@ -44,6 +44,50 @@ impl XmrigHttpApiClient {
             .await
     }
 
+    /// Replaces the config of the running xmrig, requires the http api to be unrestricted
+    pub async fn update_config(&self, config: &serde_json::Value) -> Result<(), anyhow::Error> {
+        let url = format!("{}/1/config", self.url);
+        let response = reqwest::Client::new()
+            .put(&url)
+            .header("Authorization", format!("Bearer {}", self.access_token))
+            .json(config)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "Failed to update xmrig config: {}",
+                response.status()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Pauses or resumes hashing, the pool connection is kept alive
+    async fn json_rpc(&self, method: &str) -> Result<(), anyhow::Error> {
+        let url = format!("{}/json_rpc", self.url);
+        let response = reqwest::Client::new()
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", self.access_token))
+            .json(&serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": method }))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "Xmrig json rpc {method} failed: {}",
+                response.status()
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn pause(&self) -> Result<(), anyhow::Error> {
+        self.json_rpc("pause").await
+    }
+
+    pub async fn resume(&self) -> Result<(), anyhow::Error> {
+        self.json_rpc("resume").await
+    }
+
     pub async fn summary(&self) -> Result<models::Summary, anyhow::Error> {
         for _i in 0..3 {
             let response = self.get("2/summary").await?;
@@ -64,4 +108,28 @@ impl XmrigHttpApiClient {
         }
         Err(anyhow::anyhow!("Failed to get xmrig summary"))
     }
+
+    /// Per-backend stats with per-thread hashrate, available since xmrig 6
+    pub async fn backends(&self) -> Result<Vec<models::Backend>, anyhow::Error> {
+        let response = self.get("2/backends").await?;
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "Failed to get xmrig backends: {}",
+                response.status()
+            ));
+        }
+        Ok(response.json().await?)
+    }
+
+    /// Legacy thread stats for xmrig versions without `2/backends`
+    pub async fn threads(&self) -> Result<models::Threads, anyhow::Error> {
+        let response = self.get("1/threads").await?;
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "Failed to get xmrig threads: {}",
+                response.status()
+            ));
+        }
+        Ok(response.json().await?)
+    }
 }
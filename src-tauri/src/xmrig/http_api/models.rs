@@ -27,12 +27,34 @@ pub(crate) struct Summary {
     pub(crate) connection: Connection,
 
     pub(crate) hashrate: Hashrate,
-    // hugepages: bool,
+    #[serde(default)]
+    pub(crate) algo: Option<String>,
+    #[serde(default)]
+    pub(crate) hugepages: Option<Hugepages>,
+    #[serde(default)]
+    pub(crate) results: Option<Results>,
+    #[serde(default)]
+    pub(crate) cpu: Option<CpuInfo>,
+    #[serde(default)]
+    pub(crate) paused: bool,
 }
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct Connection {
     pub(crate) uptime: u64,
+    #[serde(default)]
+    pub(crate) accepted: u64,
+    #[serde(default)]
+    pub(crate) rejected: u64,
+    #[serde(default)]
+    pub(crate) pool: Option<String>,
+    // Milliseconds, 0 until the first response from the pool
+    #[serde(default)]
+    pub(crate) ping: u64,
+    #[serde(default)]
+    pub(crate) failures: u64,
+    #[serde(default)]
+    pub(crate) diff: u64,
     // Sometimes doesn't exist
     // pub(crate) error_log: Vec<String>,
 }
@@ -40,4 +62,155 @@ pub struct Connection {
 #[derive(Deserialize, Debug, Clone)]
 pub struct Hashrate {
     pub(crate) total: Vec<Option<f64>>,
+    #[serde(default)]
+    pub(crate) highest: Option<f64>,
+    // Hashrate of every thread for the same windows as `total`
+    #[serde(default)]
+    pub(crate) threads: Vec<Vec<Option<f64>>>,
+}
+
+/// Older xmrig versions report only whether huge pages are used,
+/// newer ones report allocated and total pages
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(untagged)]
+pub enum Hugepages {
+    Enabled(bool),
+    Allocated([u64; 2]),
+}
+
+impl Hugepages {
+    /// Allocated and total pages, `None` when xmrig reports only a flag
+    pub fn pages(self) -> Option<(u64, u64)> {
+        match self {
+            Hugepages::Enabled(_) => None,
+            Hugepages::Allocated([allocated, total]) => Some((allocated, total)),
+        }
+    }
+
+    pub fn is_fully_allocated(self) -> bool {
+        match self {
+            Hugepages::Enabled(enabled) => enabled,
+            Hugepages::Allocated([allocated, total]) => total > 0 && allocated == total,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct Results {
+    pub(crate) shares_good: u64,
+    pub(crate) shares_total: u64,
+    pub(crate) avg_time_ms: u64,
+    pub(crate) hashes_total: u64,
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct CpuInfo {
+    pub(crate) brand: String,
+    pub(crate) arch: Option<String>,
+    pub(crate) cores: u32,
+    pub(crate) threads: u32,
+    pub(crate) nodes: u32,
+    pub(crate) aes: bool,
+    pub(crate) avx2: bool,
+    pub(crate) msr: Option<String>,
+    pub(crate) flags: Vec<String>,
+}
+
+/// Entry of `2/backends`
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct Backend {
+    #[serde(rename = "type")]
+    pub(crate) kind: String,
+    pub(crate) enabled: bool,
+    pub(crate) algo: Option<String>,
+    pub(crate) hugepages: Option<Hugepages>,
+    pub(crate) threads: Vec<BackendThread>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct BackendThread {
+    #[serde(default)]
+    pub(crate) intensity: u32,
+    // -1 when the thread is not pinned
+    #[serde(default = "unpinned_affinity")]
+    pub(crate) affinity: i64,
+    #[serde(default)]
+    pub(crate) hashrate: Vec<Option<f64>>,
+}
+
+fn unpinned_affinity() -> i64 {
+    -1
+}
+
+/// Response of the legacy `1/threads` endpoint, used when `2/backends` is not available
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct Threads {
+    pub(crate) hugepages: Option<Hugepages>,
+    pub(crate) threads: Vec<BackendThread>,
+}
+
+impl From<Threads> for Backend {
+    fn from(threads: Threads) -> Self {
+        Backend {
+            kind: "cpu".to_string(),
+            enabled: true,
+            hugepages: threads.hugepages,
+            threads: threads.threads,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn parses_summary_with_extended_fields() {
+        let summary: Summary = serde_json::from_str(
+            r#"{
+                "algo": "rx/0",
+                "paused": false,
+                "hugepages": [1168, 1168],
+                "connection": { "pool": "127.0.0.1:18081", "uptime": 120, "ping": 35, "failures": 1, "diff": 120001, "accepted": 10, "rejected": 2 },
+                "results": { "diff_current": 120001, "shares_good": 10, "shares_total": 12, "avg_time_ms": 12000, "hashes_total": 900000 },
+                "cpu": { "brand": "AMD Ryzen 9 5950X 16-Core Processor", "arch": "x86_64", "aes": true, "avx2": true, "cores": 16, "threads": 32, "packages": 1, "nodes": 1, "msr": "ryzen_19h", "flags": ["avx2", "sse4.1"] },
+                "hashrate": { "total": [5000.5, null, null], "highest": 5100.0, "threads": [[2500.2, null, null], [2500.3, null, null]] }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(summary.connection.ping, 35);
+        assert_eq!(summary.hugepages.unwrap().pages(), Some((1168, 1168)));
+        assert_eq!(summary.hashrate.threads.len(), 2);
+        assert_eq!(summary.cpu.unwrap().nodes, 1);
+        assert_eq!(summary.results.unwrap().shares_good, 10);
+    }
+
+    #[test]
+    fn parses_minimal_summary_and_backends() {
+        let summary: Summary = serde_json::from_str(
+            r#"{ "hugepages": true, "connection": { "uptime": 0 }, "hashrate": { "total": [null] } }"#,
+        )
+        .unwrap();
+        assert!(summary.hugepages.unwrap().is_fully_allocated());
+        assert!(summary.results.is_none());
+
+        let backends: Vec<Backend> = serde_json::from_str(
+            r#"[
+                { "type": "cpu", "enabled": true, "algo": "rx/0", "profile": "rx", "hugepages": [10, 12], "memory": 2359296,
+                  "hashrate": [100.0, null, null], "threads": [{ "intensity": 1, "affinity": 0, "av": 1, "hashrate": [50.0, null, null] }, { "intensity": 1, "hashrate": [50.0] }] },
+                { "type": "opencl", "enabled": false }
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(backends[0].threads[1].affinity, -1);
+        assert!(!backends[0].hugepages.unwrap().is_fully_allocated());
+        assert!(!backends[1].enabled);
+    }
 }
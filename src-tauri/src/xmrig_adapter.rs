@@ -22,8 +22,9 @@
 
 use anyhow::Error;
 use async_trait::async_trait;
-use log::{info, warn};
-use std::path::PathBuf;
+use log::{debug, info, warn};
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 use tari_shutdown::Shutdown;
@@ -34,9 +35,12 @@ use crate::process_adapter::{
     HandleUnhealthyResult, HealthStatus, ProcessAdapter, ProcessInstance, ProcessStartupSpec,
     StatusMonitor,
 };
+use crate::process_output_buffer::ProcessOutputRegistry;
+use crate::process_resource_limits::ProcessResourceLimits;
 use crate::setup::setup_manager::SetupManager;
 use crate::xmrig;
-use crate::xmrig::http_api::models::Summary;
+use crate::xmrig::config::{XmrigConfig, XmrigRandomXAlgorithm, XmrigRuntimeConfig};
+use crate::xmrig::http_api::models::{Backend, Summary};
 use crate::xmrig::http_api::XmrigHttpApiClient;
 
 const LOG_TARGET: &str = "tari::universe::xmrig_adapter";
@@ -55,39 +59,45 @@ pub enum XmrigNodeConnection {
 }
 
 impl XmrigNodeConnection {
-    pub fn generate_args(&self) -> Vec<String> {
+    pub fn generate_pool_config(&self, pool_algorithm: XmrigRandomXAlgorithm) -> Value {
         match self {
             XmrigNodeConnection::LocalMmproxy {
                 host_name,
                 port,
                 monero_address,
             } => {
-                vec![
-                    "--daemon".to_string(),
-                    format!("--url={}:{}", host_name, port),
-                    // "--daemon-poll-interval=10000".to_string(),
-                    "--coin=monero".to_string(),
-                    // We are using a local daemon, so retry as soon as possible
-                    "--retry-pause=1".to_string(),
-                    "--user".to_string(),
-                    format!("{}", monero_address),
-                ]
+                json!({
+                    "daemon": true,
+                    "url": format!("{}:{}", host_name, port),
+                    "coin": "monero",
+                    "user": monero_address,
+                })
             }
             XmrigNodeConnection::Pool {
                 host_name,
                 port,
-                tari_address: monero_address,
+                tari_address,
             } => {
-                vec![
-                    "--url".to_string(),
-                    format!("{}:{}", host_name, port),
-                    "--coin=monero".to_string(),
-                    "--user".to_string(),
-                    format!("{}", monero_address),
-                ]
+                let mut pool = json!({
+                    "url": format!("{}:{}", host_name, port),
+                    "user": tari_address,
+                });
+                match pool_algorithm {
+                    XmrigRandomXAlgorithm::Rx0 => pool["coin"] = json!("monero"),
+                    XmrigRandomXAlgorithm::RxTari => pool["algo"] = json!(pool_algorithm.name()),
+                }
+                pool
             }
         }
     }
+
+    /// We are using a local daemon, so retry as soon as possible
+    fn retry_pause_secs(&self) -> u32 {
+        match self {
+            XmrigNodeConnection::LocalMmproxy { .. } => 1,
+            XmrigNodeConnection::Pool { .. } => 5,
+        }
+    }
 }
 
 pub struct XmrigAdapter {
@@ -96,12 +106,18 @@ pub struct XmrigAdapter {
     pub http_api_token: String,
     pub http_api_port: u16,
     pub cpu_threads: Option<u32>,
+    pub xmrig_config: XmrigConfig,
+    pub resource_limits: ProcessResourceLimits,
     pub extra_options: Vec<String>,
     pub summary_broadcast: watch::Sender<Option<Summary>>,
+    pub backends_broadcast: watch::Sender<Option<Vec<Backend>>>,
 }
 
 impl XmrigAdapter {
-    pub fn new(summary_broadcast: watch::Sender<Option<Summary>>) -> Self {
+    pub fn new(
+        summary_broadcast: watch::Sender<Option<Summary>>,
+        backends_broadcast: watch::Sender<Option<Vec<Backend>>>,
+    ) -> Self {
         let http_api_port = PortAllocator::new().assign_port_with_fallback();
         let http_api_token = "pass".to_string();
         Self {
@@ -110,10 +126,47 @@ impl XmrigAdapter {
             http_api_token: http_api_token.clone(),
             http_api_port,
             cpu_threads: None,
+            xmrig_config: XmrigConfig::default(),
+            resource_limits: ProcessResourceLimits::default(),
             extra_options: Vec::new(),
             summary_broadcast,
+            backends_broadcast,
         }
     }
+
+    fn log_file_path(log_dir: &Path) -> PathBuf {
+        log_dir.join("xmrig").join("xmrig.log")
+    }
+
+    pub fn http_api_client(&self) -> XmrigHttpApiClient {
+        XmrigHttpApiClient::new(
+            format!("http://127.0.0.1:{}", self.http_api_port),
+            self.http_api_token.clone(),
+        )
+    }
+
+    /// Full xmrig config built from the persisted settings and the current connection
+    pub fn generate_config(&self, log_dir: &Path) -> Result<Value, anyhow::Error> {
+        let node_connection = self
+            .node_connection
+            .as_ref()
+            .ok_or(anyhow::anyhow!("Node connection not set"))?;
+        let log_file = Self::log_file_path(log_dir).to_str().map(String::from);
+        if log_file.is_none() {
+            warn!(target: LOG_TARGET, "Could not convert xmrig log file path to string");
+            warn!(target: LOG_TARGET, "Log file will not be set in xmrig config");
+        }
+
+        Ok(self.xmrig_config.generate(XmrigRuntimeConfig {
+            pool: node_connection.generate_pool_config(self.xmrig_config.pool_algorithm),
+            retry_pause_secs: node_connection.retry_pause_secs(),
+            http_api_port: self.http_api_port,
+            http_api_token: self.http_api_token.clone(),
+            // don't specify threads for ludicrous mode
+            cpu_threads: self.cpu_threads,
+            log_file,
+        }))
+    }
 }
 
 impl ProcessAdapter for XmrigAdapter {
@@ -123,51 +176,31 @@ impl ProcessAdapter for XmrigAdapter {
     fn spawn_inner(
         &self,
         data_dir: PathBuf,
-        _config_dir: PathBuf,
+        config_dir: PathBuf,
         log_dir: PathBuf,
         binary_version_path: PathBuf,
         _is_first_start: bool,
     ) -> Result<(ProcessInstance, Self::StatusMonitor), anyhow::Error> {
         let xmrig_shutdown = Shutdown::new();
-        let mut args = self
-            .node_connection
-            .as_ref()
-            .ok_or(anyhow::anyhow!("Node connection not set"))?
-            .generate_args();
-        let xmrig_log_file = log_dir.join("xmrig").join("xmrig.log");
-        std::fs::create_dir_all(
-            xmrig_log_file
-                .parent()
-                .expect("Could not get xmrig root log dir"),
-        )?;
-
+        let xmrig_log_file = Self::log_file_path(&log_dir);
         let xmrig_log_file_parent = xmrig_log_file
             .parent()
             .ok_or_else(|| anyhow::anyhow!("Could not get parent directory of xmrig log file"))?;
-
-        match xmrig_log_file.to_str() {
-            Some(log_file) => {
-                args.push(format!("--log-file={}", &log_file));
-            }
-            None => {
-                warn!(target: LOG_TARGET, "Could not convert xmrig log file path to string");
-                warn!(target: LOG_TARGET, "Logs argument will not be added to xmrig");
-            }
-        };
-
         std::fs::create_dir_all(xmrig_log_file_parent).unwrap_or_else(|error| {
             warn!(target: LOG_TARGET, "Could not create xmrig log file parent directory - {error}");
         });
 
-        args.push(format!("--http-port={}", self.http_api_port));
-        args.push(format!("--http-access-token={}", self.http_api_token));
-        args.push("--donate-level=1".to_string());
-
-        // don't specify threads for ludicrous mode
-        if let Some(cpu_threads) = self.cpu_threads {
-            args.push(format!("--threads={cpu_threads}"));
+        let xmrig_config_file = config_dir.join("xmrig").join("config.json");
+        if let Some(xmrig_config_dir) = xmrig_config_file.parent() {
+            std::fs::create_dir_all(xmrig_config_dir)?;
         }
-        args.push("--verbose".to_string());
+        let xmrig_config = self.generate_config(&log_dir)?;
+        std::fs::write(
+            &xmrig_config_file,
+            serde_json::to_string_pretty(&xmrig_config)?,
+        )?;
+
+        let mut args = vec![format!("--config={}", xmrig_config_file.display())];
         for extra_option in &self.extra_options {
             args.push(extra_option.clone());
         }
@@ -183,14 +216,14 @@ impl ProcessAdapter for XmrigAdapter {
                     data_dir,
                     pid_file_name: self.pid_file_name().to_string(),
                     name: self.name().to_string(),
+                    resource_limits: self.resource_limits.clone(),
                 },
+                output_buffer: ProcessOutputRegistry::buffer(self.name()),
             },
             XmrigStatusMonitor {
                 summary_broadcast: self.summary_broadcast.clone(),
-                client: XmrigHttpApiClient::new(
-                    format!("http://127.0.0.1:{}", self.http_api_port),
-                    self.http_api_token.clone(),
-                ),
+                backends_broadcast: self.backends_broadcast.clone(),
+                client: self.http_api_client(),
             },
         ))
     }
@@ -202,6 +235,10 @@ impl ProcessAdapter for XmrigAdapter {
     fn pid_file_name(&self) -> &str {
         "xmrig_pid"
     }
+
+    fn log_file_path(&self, log_folder: &Path) -> Option<PathBuf> {
+        Some(XmrigAdapter::log_file_path(log_folder))
+    }
 }
 
 // This is a flag to indicate if the fallback to solo mining has been triggered
@@ -212,6 +249,7 @@ static WAS_FALLBACK_TO_SOLO_MINING_TRIGGERED: AtomicBool = AtomicBool::new(false
 pub struct XmrigStatusMonitor {
     client: XmrigHttpApiClient,
     summary_broadcast: watch::Sender<Option<Summary>>,
+    backends_broadcast: watch::Sender<Option<Vec<Backend>>>,
 }
 
 #[async_trait]
@@ -248,6 +286,8 @@ impl StatusMonitor for XmrigStatusMonitor {
         match tokio::time::timeout(timeout_duration, self.summary()).await {
             Ok(summary_result) => match summary_result {
                 Ok(summary) => {
+                    // Backends are sent first so the summary listener always sees the matching thread stats
+                    let _result = self.backends_broadcast.send(self.backends().await);
                     let _result = self.summary_broadcast.send(Some(summary.clone()));
 
                     if summary.hashrate.total.iter().all(|x| x.eq(&Some(0.0))) {
@@ -259,12 +299,14 @@ impl StatusMonitor for XmrigStatusMonitor {
                 }
                 Err(e) => {
                     warn!(target: LOG_TARGET, "Failed to get xmrig summary: {e}");
+                    let _result = self.backends_broadcast.send(None);
                     let _result = self.summary_broadcast.send(None);
                     HealthStatus::Unhealthy
                 }
             },
             Err(_timeout_error) => {
                 warn!(target: LOG_TARGET, "Timeout while getting xmrig summary");
+                let _result = self.backends_broadcast.send(None);
                 let _result = self.summary_broadcast.send(None);
                 HealthStatus::Warning
             }
@@ -276,4 +318,18 @@ impl XmrigStatusMonitor {
     pub async fn summary(&self) -> Result<xmrig::http_api::models::Summary, Error> {
         self.client.summary().await
     }
+
+    /// Per-thread stats are best effort, older xmrig versions only expose `1/threads`
+    async fn backends(&self) -> Option<Vec<Backend>> {
+        match self.client.backends().await {
+            Ok(backends) => Some(backends),
+            Err(backends_error) => match self.client.threads().await {
+                Ok(threads) => Some(vec![threads.into()]),
+                Err(threads_error) => {
+                    debug!(target: LOG_TARGET, "Failed to get xmrig thread stats: {backends_error} | {threads_error}");
+                    None
+                }
+            },
+        }
+    }
 }
@@ -4,7 +4,12 @@ import { listen } from '@tauri-apps/api/event';
 import { BACKEND_STATE_UPDATE, BackendStateUpdateEvent } from '@app/types/backend-state.ts';
 
 import { handleNewBlockPayload, useBlockchainVisualisationStore } from '@app/store/useBlockchainVisualisationStore';
-import { setCpuMiningStatus, setGpuDevices, setGpuMiningStatus } from '@app/store/actions/miningMetricsStoreActions';
+import {
+    setCpuMiningStatus,
+    setGpuDevices,
+    setGpuDevicesMiningStatus,
+    setGpuMiningStatus,
+} from '@app/store/actions/miningMetricsStoreActions';
 import {
     handleAskForRestart,
     handleCloseSplashscreen,
@@ -16,7 +21,13 @@ import {
 } from '@app/store/actions/uiStoreActions';
 import {
     handleAvailableMinersChanged,
+    handleGpuBenchmarkProgress,
+    handleIdleMiningUpdate,
+    handleMiningPauseUpdate,
+    handleMiningScheduleUpdate,
+    handleProfitSwitchDecision,
     handleSelectedMinerChanged,
+    handleThermalGuardUpdate,
     setAvailableEngines,
 } from '@app/store/actions/miningStoreActions';
 import {
@@ -30,6 +41,7 @@ import {
 } from '@app/store/actions/appStateStoreActions';
 import {
     handleBaseNodeStatusUpdate,
+    handleNodeDiskUsageUpdate,
     setWalletBalance,
     updateWalletScanningProgress,
     useSecurityStore,
@@ -51,11 +63,17 @@ import {
     handleConfigWalletLoaded,
     handleWalletUIChanged,
     handleConfigPoolsLoaded,
+    handleConfigScheduleLoaded,
     handleGpuDevicesSettingsUpdated,
 } from '@app/store/actions/appConfigStoreActions';
 import { invoke } from '@tauri-apps/api/core';
 
-import { setCpuPoolStats, setGpuPoolStats } from '@app/store/actions/miningPoolsStoreActions';
+import {
+    handleCpuPoolFailover,
+    handleGpuPoolFailover,
+    setCpuPoolStats,
+    setGpuPoolStats,
+} from '@app/store/actions/miningPoolsStoreActions';
 import {
     handlePinLocked,
     handleSeedBackedUp,
@@ -106,6 +124,9 @@ const useTauriEventsListener = () => {
                         case 'GpuMiningUpdate':
                             setGpuMiningStatus(event.payload);
                             break;
+                        case 'GpuDevicesMiningUpdate':
+                            setGpuDevicesMiningStatus(event.payload);
+                            break;
                         case 'CpuMiningUpdate':
                             setCpuMiningStatus(event.payload);
                             break;
@@ -115,6 +136,12 @@ const useTauriEventsListener = () => {
                         case 'GpuPoolsStatsUpdate':
                             setGpuPoolStats(event.payload);
                             break;
+                        case 'CpuPoolFailover':
+                            handleCpuPoolFailover(event.payload);
+                            break;
+                        case 'GpuPoolFailover':
+                            handleGpuPoolFailover(event.payload);
+                            break;
                         case 'NewBlockHeight': {
                             const current = useBlockchainVisualisationStore.getState().latestBlockPayload?.block_height;
                             if (!current || current < event.payload.block_height) {
@@ -138,6 +165,9 @@ const useTauriEventsListener = () => {
                             console.info('ConfigPoolsLoaded', event.payload);
                             handleConfigPoolsLoaded(event.payload);
                             break;
+                        case 'ConfigScheduleLoaded':
+                            handleConfigScheduleLoaded(event.payload);
+                            break;
                         case 'CloseSplashscreen':
                             //TODO find better place for this
                             handleAppLoaded();
@@ -153,6 +183,27 @@ const useTauriEventsListener = () => {
                         case 'AvailableMiners':
                             handleAvailableMinersChanged(event.payload);
                             break;
+                        case 'GpuBenchmarkProgress':
+                            handleGpuBenchmarkProgress(event.payload);
+                            break;
+                        case 'ProfitSwitchDecision':
+                            handleProfitSwitchDecision(event.payload);
+                            break;
+                        case 'MiningPauseUpdate':
+                            handleMiningPauseUpdate(event.payload);
+                            break;
+                        case 'MiningScheduleUpdate':
+                            handleMiningScheduleUpdate(event.payload);
+                            break;
+                        case 'IdleMiningUpdate':
+                            handleIdleMiningUpdate(event.payload);
+                            break;
+                        case 'ThermalGuardUpdate':
+                            handleThermalGuardUpdate(event.payload);
+                            break;
+                        case 'NodeDiskUsageUpdate':
+                            handleNodeDiskUsageUpdate(event.payload);
+                            break;
                         case 'DetectedAvailableGpuEngines':
                             setAvailableEngines(event.payload.engines, event.payload.selected_engine);
                             break;
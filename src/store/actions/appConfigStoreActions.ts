@@ -7,6 +7,7 @@ import {
     useConfigCoreStore,
     useConfigMiningStore,
     useConfigPoolsStore,
+    useConfigScheduleStore,
     useConfigUIStore,
     useConfigWalletStore,
     useMiningMetricsStore,
@@ -22,19 +23,24 @@ import {
     ConfigCore,
     ConfigMining,
     ConfigPools,
+    ConfigSchedule,
     ConfigUI,
     ConfigWallet,
     CpuPools,
     FeedbackPrompts,
     GpuDeviceSettings,
     GpuPools,
+    MiningScheduleAction,
+    MiningScheduleRule,
     PromptType,
+    ThermalLimit,
+    XmrigConfig,
 } from '@app/types/configs.ts';
 import { NodeType, updateNodeType as updateNodeTypeForNodeStore } from '../useNodeStore.ts';
 import { setCurrentExchangeMinerId } from '../useExchangeStore.ts';
 import { fetchExchangeContent, refreshXCContent } from '@app/hooks/exchanges/fetchExchangeContent.ts';
 import { fetchExchangeList } from '@app/hooks/exchanges/fetchExchanges.ts';
-import { WalletUIMode } from '@app/types/events-payloads.ts';
+import { GpuMinerType, WalletUIMode } from '@app/types/events-payloads.ts';
 import { getSelectedCpuPool, getSelectedGpuPool } from '../selectors/appConfigStoreSelectors.ts';
 import { setFeedbackConfigItems } from '@app/store/stores/userFeedbackStore.ts';
 
@@ -82,6 +88,10 @@ export const handleConfigPoolsLoaded = (poolsConfig: ConfigPools) => {
     useConfigPoolsStore.setState((c) => ({ ...c, ...poolsConfig }));
 };
 
+export const handleConfigScheduleLoaded = (scheduleConfig: ConfigSchedule) => {
+    useConfigScheduleStore.setState((c) => ({ ...c, ...scheduleConfig }));
+};
+
 export const setAirdropTokensInConfig = (
     airdropTokensParam: Pick<AirdropTokens, 'refreshToken' | 'token'> | undefined,
     isSuccessFn?: (airdropTokens: { token: string; refresh_token: string } | undefined) => void
@@ -199,6 +209,105 @@ export const setGpuMiningEnabled = async (enabled: boolean) => {
         }
     }
 };
+export const setProfitSwitchingEnabled = async (enabled: boolean) => {
+    useConfigMiningStore.setState((c) => ({ ...c, profit_switching_enabled: enabled }));
+    invoke('set_profit_switching_enabled', { enabled }).catch((e) => {
+        console.error('Could not set profit switching', e);
+        setError('Could not change profit switching');
+        useConfigMiningStore.setState((c) => ({ ...c, profit_switching_enabled: !enabled }));
+    });
+};
+
+export const setProfitSwitchingSettings = async (thresholdPercentage: number, minIntervalSecs: number) => {
+    const { profit_switching_threshold_percentage, profit_switching_min_interval_secs } =
+        useConfigMiningStore.getState();
+    useConfigMiningStore.setState((c) => ({
+        ...c,
+        profit_switching_threshold_percentage: thresholdPercentage,
+        profit_switching_min_interval_secs: minIntervalSecs,
+    }));
+    invoke('set_profit_switching_settings', { thresholdPercentage, minIntervalSecs }).catch((e) => {
+        console.error('Could not set profit switching settings', e);
+        setError('Could not change profit switching settings');
+        useConfigMiningStore.setState((c) => ({
+            ...c,
+            profit_switching_threshold_percentage,
+            profit_switching_min_interval_secs,
+        }));
+    });
+};
+
+export const setIdleMining = async (enabled: boolean, miningMode: string, timeoutSecs: number) => {
+    const { idle_mining_enabled, idle_mining_mode, idle_mining_timeout_secs } = useConfigMiningStore.getState();
+    useConfigMiningStore.setState((c) => ({
+        ...c,
+        idle_mining_enabled: enabled,
+        idle_mining_mode: miningMode,
+        idle_mining_timeout_secs: timeoutSecs,
+    }));
+    invoke('set_idle_mining', { enabled, miningMode, timeoutSecs }).catch((e) => {
+        console.error('Could not set idle mining', e);
+        setError('Could not change idle mining settings');
+        useConfigMiningStore.setState((c) => ({
+            ...c,
+            idle_mining_enabled,
+            idle_mining_mode,
+            idle_mining_timeout_secs,
+        }));
+    });
+};
+
+export const setThermalGuardSettings = async (
+    enabled: boolean,
+    cpuLimit: ThermalLimit,
+    gpuLimit: ThermalLimit,
+    gpuDevicesLimits: Record<number, ThermalLimit>
+) => {
+    const { thermal_guard_enabled, cpu_thermal_limit, gpu_thermal_limit, gpu_devices_thermal_limits } =
+        useConfigMiningStore.getState();
+    useConfigMiningStore.setState((c) => ({
+        ...c,
+        thermal_guard_enabled: enabled,
+        cpu_thermal_limit: cpuLimit,
+        gpu_thermal_limit: gpuLimit,
+        gpu_devices_thermal_limits: gpuDevicesLimits,
+    }));
+    invoke('set_thermal_guard_settings', { enabled, cpuLimit, gpuLimit, gpuDevicesLimits }).catch((e) => {
+        console.error('Could not set thermal guard settings', e);
+        setError('Could not change thermal guard settings');
+        useConfigMiningStore.setState((c) => ({
+            ...c,
+            thermal_guard_enabled,
+            cpu_thermal_limit,
+            gpu_thermal_limit,
+            gpu_devices_thermal_limits,
+        }));
+    });
+};
+
+export const setMiningSchedule = async (
+    isEnabled: boolean,
+    defaultAction: MiningScheduleAction,
+    rules: MiningScheduleRule[]
+) => {
+    try {
+        await invoke('set_mining_schedule', { isEnabled, defaultAction, rules });
+    } catch (e) {
+        console.error('Could not set mining schedule', e);
+        setError(e as string);
+    }
+};
+
+export const setXmrigConfig = async (xmrigConfig: XmrigConfig) => {
+    const previousXmrigConfig = useConfigMiningStore.getState().xmrig_config;
+    useConfigMiningStore.setState((c) => ({ ...c, xmrig_config: xmrigConfig }));
+    invoke('set_xmrig_config', { xmrigConfig }).catch((e) => {
+        console.error('Could not set xmrig config', e);
+        setError('Could not change xmrig config');
+        useConfigMiningStore.setState((c) => ({ ...c, xmrig_config: previousXmrigConfig }));
+    });
+};
+
 export const setMineOnAppStart = async (mineOnAppStart: boolean) => {
     useConfigMiningStore.setState((c) => ({ ...c, mine_on_app_start: mineOnAppStart }));
     invoke('set_mine_on_app_start', { mineOnAppStart }).catch((e) => {
@@ -689,6 +798,25 @@ export const toggleDeviceExclusion = async (deviceIndex: number, excluded: boole
     }
 };
 
+export const assignGpuDeviceMiner = async (deviceIndex: number, miner: GpuMinerType | null) => {
+    try {
+        const wasGpuMiningInitiated = useMiningStore.getState().isGpuMiningInitiated;
+        const metricsState = useMiningMetricsStore.getState();
+        if (metricsState.gpu_mining_status.is_mining || wasGpuMiningInitiated) {
+            console.info('Stoping mining...');
+            await stopGpuMining();
+        }
+        await invoke('assign_gpu_device_miner', { deviceIndex, miner });
+        if (wasGpuMiningInitiated) {
+            console.info('Restarting mining...');
+            await startGpuMining();
+        }
+    } catch (e) {
+        console.error('Could not assign miner to gpu device: ', e);
+        setError(e as string);
+    }
+};
+
 export const handleFeedbackFields = (feedbackType: PromptType, feedback_sent: boolean) => {
     const current = useConfigUIStore.getState().feedback;
     const now = Date.now();
@@ -14,15 +14,21 @@ export {
     setApplicationLanguage,
     setAutoUpdate,
     setGpuMiningEnabled,
+    setIdleMining,
     setMineOnAppStart,
+    setMiningSchedule,
     setMoneroAddress,
     setMonerodConfig,
     setPreRelease,
+    setProfitSwitchingEnabled,
+    setProfitSwitchingSettings,
     setShouldAlwaysUseSystemLanguage,
     setShouldAutoLaunch,
     setShowExperimentalSettings,
+    setThermalGuardSettings,
     setUseTor,
     setVisualMode,
+    setXmrigConfig,
 } from './appConfigStoreActions.ts';
 
 export {
@@ -38,12 +44,23 @@ export {
     setReleaseNotes,
 } from './appStateStoreActions.ts';
 
-export { setCpuMiningStatus, setGpuDevices, setGpuMiningStatus } from './miningMetricsStoreActions.ts';
+export {
+    setCpuMiningStatus,
+    setGpuDevices,
+    setGpuDevicesMiningStatus,
+    setGpuMiningStatus,
+} from './miningMetricsStoreActions.ts';
 
 export {
+    cancelGpuBenchmark,
+    fetchMiningPauseStatus,
+    fetchProfitSwitchDecisions,
     getMiningNetwork,
+    pauseMining,
+    resumeMining,
     setCustomLevelsDialogOpen,
     setMiningControlsEnabled,
+    startGpuBenchmark,
     startMining,
     stopMining,
 } from './miningStoreActions.ts';
@@ -57,4 +74,4 @@ export {
 
 export { fetchTransactionsHistory, importSeedWords, setWalletBalance } from './walletStoreActions';
 
-export { handleBaseNodeStatusUpdate } from './nodeStoreActions.ts';
+export { handleBaseNodeStatusUpdate, handleNodeDiskUsageUpdate } from './nodeStoreActions.ts';
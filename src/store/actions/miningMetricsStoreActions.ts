@@ -1,4 +1,4 @@
-import { CpuMinerStatus, GpuDevice, GpuMinerStatus } from '@app/types/app-status.ts';
+import { CpuMinerStatus, GpuDevice, GpuDeviceMiningStatus, GpuMinerStatus } from '@app/types/app-status.ts';
 
 import { useMiningMetricsStore } from '../useMiningMetricsStore.ts';
 
@@ -8,6 +8,9 @@ export const setGpuDevices = (gpu_devices: GpuDevice[]) => {
 export const setGpuMiningStatus = (gpu_mining_status: GpuMinerStatus) => {
     useMiningMetricsStore.setState((c) => ({ ...c, gpu_mining_status }));
 };
+export const setGpuDevicesMiningStatus = (gpu_devices_mining_status: GpuDeviceMiningStatus[]) => {
+    useMiningMetricsStore.setState((c) => ({ ...c, gpu_devices_mining_status }));
+};
 export const setCpuMiningStatus = (cpu_mining_status: CpuMinerStatus) => {
     useMiningMetricsStore.setState((c) => ({ ...c, cpu_mining_status }));
 };
@@ -1,5 +1,6 @@
 import { PoolStats } from '@app/types/app-status';
-import { PoolType, RewardValues, useMiningPoolsStore } from '../useMiningPoolsStore';
+import { PoolFailoverPayload } from '@app/types/events-payloads';
+import { FailoverPool, PoolType, RewardValues, useMiningPoolsStore } from '../useMiningPoolsStore';
 import { deepEqual } from '@app/utils/objectDeepEqual.ts';
 import i18n from 'i18next';
 import { removeXTMCryptoDecimals } from '@app/utils';
@@ -56,8 +57,30 @@ export const clearCurrentSuccessValue = (type: PoolType) => {
     }
 };
 
+// Stats are reported for the pool miners are connected to, which differs from the selected one after a failover
+function resolveActivePool(selectedPool?: string, failoverPool?: FailoverPool) {
+    if (failoverPool && failoverPool.primaryPool === selectedPool) return failoverPool.activePool;
+    return selectedPool;
+}
+
+export const handleCpuPoolFailover = (payload: PoolFailoverPayload) => {
+    useMiningPoolsStore.setState((c) => ({
+        ...c,
+        cpuFailoverPool: { primaryPool: payload.primary_pool, activePool: payload.to_pool },
+    }));
+};
+export const handleGpuPoolFailover = (payload: PoolFailoverPayload) => {
+    useMiningPoolsStore.setState((c) => ({
+        ...c,
+        gpuFailoverPool: { primaryPool: payload.primary_pool, activePool: payload.to_pool },
+    }));
+};
+
 export const setCpuPoolStats = (cpuPoolStats: Record<string, PoolStats>) => {
-    const currentSelectedPool = useConfigPoolsStore.getState().current_cpu_pool;
+    const currentSelectedPool = resolveActivePool(
+        useConfigPoolsStore.getState().current_cpu_pool,
+        useMiningPoolsStore.getState().cpuFailoverPool
+    );
 
     if (!currentSelectedPool) return;
     if (!cpuPoolStats[currentSelectedPool]) return;
@@ -77,7 +100,10 @@ export const setCpuPoolStats = (cpuPoolStats: Record<string, PoolStats>) => {
     });
 };
 export const setGpuPoolStats = (gpuPoolStats: Record<string, PoolStats>) => {
-    const currentSelectedPool = useConfigPoolsStore.getState().current_gpu_pool;
+    const currentSelectedPool = resolveActivePool(
+        useConfigPoolsStore.getState().current_gpu_pool,
+        useMiningPoolsStore.getState().gpuFailoverPool
+    );
     if (!currentSelectedPool) return;
     if (!gpuPoolStats[currentSelectedPool]) return;
 
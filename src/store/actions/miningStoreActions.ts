@@ -8,7 +8,16 @@ import { useSetupStore } from '@app/store/useSetupStore.ts';
 import { useConfigMiningStore } from '../useAppConfigStore.ts';
 import { Network } from '@app/utils/network.ts';
 import { setupStoreSelectors } from '../selectors/setupStoreSelectors.ts';
-import { GpuMiner, GpuMinerType } from '@app/types/events-payloads.ts';
+import {
+    GpuBenchmarkProgressPayload,
+    GpuMiner,
+    GpuMinerType,
+    IdleMiningStatus,
+    MiningPauseUpdatePayload,
+    MiningScheduleStatus,
+    ProfitSwitchDecision,
+    ThermalGuardStatus,
+} from '@app/types/events-payloads.ts';
 
 export const restartMining = async () => {
     const isMining =
@@ -166,6 +175,106 @@ export const handleAvailableMinersChanged = (miners: Record<GpuMinerType, GpuMin
     useMiningStore.setState({ availableMiners: miners });
 };
 
+export const handleGpuBenchmarkProgress = (gpuBenchmarkProgress: GpuBenchmarkProgressPayload) => {
+    useMiningStore.setState({ gpuBenchmarkProgress });
+};
+
+export const startGpuBenchmark = async (durationSecs?: number) => {
+    try {
+        await invoke('start_gpu_benchmark', { durationSecs });
+    } catch (e) {
+        console.error('Could not start GPU benchmark: ', e);
+        setError(e as string);
+    }
+};
+
+export const cancelGpuBenchmark = async () => {
+    try {
+        await invoke('cancel_gpu_benchmark');
+    } catch (e) {
+        console.error('Could not cancel GPU benchmark: ', e);
+    }
+};
+
+// Keep in sync with MAX_LOGGED_DECISIONS in profit_switcher.rs
+const MAX_PROFIT_SWITCH_DECISIONS = 100;
+
+export const handleProfitSwitchDecision = (decision: ProfitSwitchDecision) => {
+    useMiningStore.setState((c) => ({
+        profitSwitchDecisions: [...c.profitSwitchDecisions, decision].slice(-MAX_PROFIT_SWITCH_DECISIONS),
+    }));
+};
+
+export const fetchProfitSwitchDecisions = async () => {
+    try {
+        const profitSwitchDecisions = await invoke('get_profit_switch_decisions');
+        useMiningStore.setState({ profitSwitchDecisions });
+    } catch (e) {
+        console.error('Could not get profit switch decisions: ', e);
+    }
+};
+
+export const handleMiningPauseUpdate = (pauseStatus: MiningPauseUpdatePayload) => {
+    useMiningStore.setState({ pauseStatus });
+};
+
+export const pauseMining = async () => {
+    console.info('Mining pausing...');
+    try {
+        await invoke('pause_mining');
+        console.info('Mining paused.');
+    } catch (e) {
+        console.error('Failed to pause mining: ', e);
+        setError(e as string);
+    }
+};
+
+export const resumeMining = async () => {
+    console.info('Mining resuming...');
+    try {
+        await invoke('resume_mining');
+        console.info('Mining resumed.');
+    } catch (e) {
+        console.error('Failed to resume mining: ', e);
+        setError(e as string);
+    }
+};
+
+export const handleMiningScheduleUpdate = (scheduleStatus: MiningScheduleStatus) => {
+    useMiningStore.setState({ scheduleStatus });
+    if (!scheduleStatus.was_applied || !scheduleStatus.current_action) return;
+
+    // The scheduler starts and stops miners on its own, keep the initiated flags in sync with what it did
+    if (scheduleStatus.current_action === 'Off') {
+        useMiningStore.setState({ isCpuMiningInitiated: false, isGpuMiningInitiated: false });
+        handleSessionMiningTime({ stopTimestamp: Date.now() });
+    } else {
+        const { cpu_mining_enabled, gpu_mining_enabled } = useConfigMiningStore.getState();
+        useMiningStore.setState({
+            isCpuMiningInitiated: cpu_mining_enabled,
+            isGpuMiningInitiated: gpu_mining_enabled,
+        });
+        handleSessionMiningTime({ startTimestamp: Date.now() });
+    }
+};
+
+export const handleIdleMiningUpdate = (idleMiningStatus: IdleMiningStatus) => {
+    useMiningStore.setState({ idleMiningStatus });
+};
+
+export const handleThermalGuardUpdate = (thermalGuardStatus: ThermalGuardStatus) => {
+    useMiningStore.setState({ thermalGuardStatus });
+};
+
+export const fetchMiningPauseStatus = async () => {
+    try {
+        const pauseStatus = await invoke('get_mining_pause_status');
+        useMiningStore.setState({ pauseStatus });
+    } catch (e) {
+        console.error('Could not get mining pause status: ', e);
+    }
+};
+
 export const switchSelectedMiner = async (newGpuMiner: GpuMinerType) => {
     const oldMiner = useMiningStore.getState().selectedMiner;
     useMiningStore.setState({ selectedMiner: newGpuMiner });
@@ -1,4 +1,5 @@
 import { BaseNodeStatus } from '@app/types/app-status.ts';
+import { NodeDiskUsage } from '@app/types/events-payloads.ts';
 
 import { setAnimationState } from '@tari-project/tari-tower';
 import { useNodeStore } from '../useNodeStore.ts';
@@ -22,3 +23,7 @@ export const handleBaseNodeStatusUpdate = (base_node_status: BaseNodeStatus) =>
         }
     }
 };
+
+export const handleNodeDiskUsageUpdate = (disk_usage: NodeDiskUsage) => {
+    useNodeStore.setState((c) => ({ ...c, disk_usage }));
+};
@@ -5,6 +5,7 @@ import {
     ConfigMining,
     ConfigMiningSelectors,
     ConfigPools,
+    ConfigSchedule,
     ConfigUI,
     ConfigWallet,
 } from '@app/types/configs';
@@ -76,6 +77,13 @@ const configPoolsInitialState: ConfigPools = {
     current_gpu_pool: undefined,
 };
 
+const configScheduleInitialState: ConfigSchedule = {
+    created_at: '',
+    is_enabled: false,
+    default_action: { Mine: { mode: 'Eco' } },
+    rules: [],
+};
+
 const configBEInMemoryInitialState: ConfigBackendInMemory = {
     airdrop_url: '',
     airdrop_api_url: '',
@@ -105,6 +113,10 @@ export const useConfigPoolsStore = create<ConfigPools>()(() => ({
     ...configPoolsInitialState,
 }));
 
+export const useConfigScheduleStore = create<ConfigSchedule>()(() => ({
+    ...configScheduleInitialState,
+}));
+
 export const useConfigBEInMemoryStore = create<ConfigBackendInMemory>()(() => ({
     ...configBEInMemoryInitialState,
 }));
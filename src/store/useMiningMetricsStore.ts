@@ -1,9 +1,10 @@
 import { create } from 'zustand';
-import { CpuMinerStatus, GpuMinerStatus, GpuDevice } from '@app/types/app-status';
+import { CpuMinerStatus, GpuMinerStatus, GpuDevice, GpuDeviceMiningStatus } from '@app/types/app-status';
 
 interface MiningMetricsStoreState {
     gpu_devices: GpuDevice[];
     gpu_mining_status: GpuMinerStatus;
+    gpu_devices_mining_status: GpuDeviceMiningStatus[];
     cpu_mining_status: CpuMinerStatus;
 }
 
@@ -15,6 +16,7 @@ const initialState: MiningMetricsStoreState = {
         estimated_earnings: 0,
         is_available: true,
     },
+    gpu_devices_mining_status: [],
     cpu_mining_status: {
         is_mining: false,
         hash_rate: 0,
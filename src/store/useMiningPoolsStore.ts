@@ -6,11 +6,17 @@ export interface RewardValues {
     rewardValue?: number | null;
     unpaidFMT?: string;
 }
+export interface FailoverPool {
+    primaryPool: string;
+    activePool: string;
+}
 interface MiningPoolsStoreState {
     cpuRewards?: RewardValues;
     cpuPoolStats?: PoolStats;
+    cpuFailoverPool?: FailoverPool;
     gpuRewards?: RewardValues;
     gpuPoolStats?: PoolStats;
+    gpuFailoverPool?: FailoverPool;
 }
 
 const initialRewards: RewardValues = {
@@ -1,6 +1,15 @@
 import { create } from 'zustand';
 import { Network } from '@app/utils/network';
-import { GpuMiner, GpuMinerType } from '@app/types/events-payloads';
+import {
+    GpuBenchmarkProgressPayload,
+    GpuMiner,
+    GpuMinerType,
+    IdleMiningStatus,
+    MiningPauseUpdatePayload,
+    MiningScheduleStatus,
+    ProfitSwitchDecision,
+    ThermalGuardStatus,
+} from '@app/types/events-payloads';
 
 export interface SessionMiningTime {
     startTimestamp?: number;
@@ -23,6 +32,12 @@ export interface MiningStoreState {
     availableEngines: string[];
     availableMiners?: Record<GpuMinerType, GpuMiner>;
     selectedMiner?: GpuMinerType;
+    gpuBenchmarkProgress?: GpuBenchmarkProgressPayload;
+    profitSwitchDecisions: ProfitSwitchDecision[];
+    pauseStatus?: MiningPauseUpdatePayload;
+    scheduleStatus?: MiningScheduleStatus;
+    idleMiningStatus?: IdleMiningStatus;
+    thermalGuardStatus?: ThermalGuardStatus;
     sessionMiningTime: SessionMiningTime;
 }
 
@@ -43,6 +58,12 @@ const initialState: MiningStoreState = {
     network: undefined,
     availableMiners: undefined,
     selectedMiner: undefined,
+    gpuBenchmarkProgress: undefined,
+    profitSwitchDecisions: [],
+    pauseStatus: undefined,
+    scheduleStatus: undefined,
+    idleMiningStatus: undefined,
+    thermalGuardStatus: undefined,
 };
 
 export const useMiningStore = create<MiningStoreState>()(() => ({
@@ -1,9 +1,9 @@
 import { create } from 'zustand';
-import { BackgroundNodeSyncUpdatePayload } from '@app/types/events-payloads';
+import { BackgroundNodeSyncUpdatePayload, NodeDiskUsage } from '@app/types/events-payloads';
 import { deepEqual } from '@app/utils/objectDeepEqual.ts';
 import { BaseNodeStatus } from '@app/types/app-status';
 
-export type NodeType = 'Local' | 'Remote' | 'RemoteUntilLocal' | 'LocalAfterRemote';
+export type NodeType = 'Local' | 'Remote' | 'RemoteUntilLocal' | 'LocalAfterRemote' | 'External';
 export interface NodeIdentity {
     public_key: string;
     public_addresses: string[];
@@ -17,6 +17,7 @@ interface NodeStoreState {
     tor_entry_guards: string[];
     isNodeConnected: boolean;
     base_node_status?: BaseNodeStatus;
+    disk_usage?: NodeDiskUsage;
 }
 
 const initialState: NodeStoreState = {
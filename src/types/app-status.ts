@@ -1,4 +1,5 @@
 import { AppModule } from '@app/store/types/setup';
+import { GpuMiningAlgorithm } from './events-payloads';
 
 export interface TorConfig {
     control_port: number;
@@ -59,6 +60,52 @@ export interface CpuMinerStatus {
     hash_rate: number;
     estimated_earnings: number;
     connection: CpuMinerConnectionStatus;
+    details?: CpuMinerDetails | null;
+}
+
+// Telemetry reported by xmrig, values it doesn't report are null
+export interface CpuMinerDetails {
+    algorithm: string | null;
+    is_paused: boolean;
+    pool: string | null;
+    pool_ping_ms: number | null;
+    difficulty: number;
+    accepted_shares: number;
+    rejected_shares: number;
+    connection_failures: number;
+    good_shares: number | null;
+    total_shares: number | null;
+    average_share_time_ms: number | null;
+    total_hashes: number | null;
+    highest_hash_rate: number | null;
+    hugepages: CpuMinerHugepagesStatus;
+    cpu: CpuMinerCpuInfo | null;
+    threads: CpuMinerThreadStatus[];
+}
+
+export interface CpuMinerHugepagesStatus {
+    is_enabled: boolean;
+    allocated: number | null;
+    total: number | null;
+}
+
+export interface CpuMinerCpuInfo {
+    brand: string;
+    architecture: string | null;
+    cores: number;
+    threads: number;
+    numa_nodes: number;
+    aes: boolean;
+    avx2: boolean;
+    msr: string | null;
+    features: string[];
+}
+
+export interface CpuMinerThreadStatus {
+    index: number;
+    affinity: number | null;
+    intensity: number | null;
+    hash_rate: number;
 }
 
 export interface PoolStats {
@@ -77,12 +124,34 @@ export interface GpuMinerStatus {
     hash_rate: number;
     estimated_earnings: number;
     is_available: boolean;
+    devices?: GpuDeviceMiningStatus[];
+    // Hashrates of different algorithms aren't comparable, they are reported per algorithm
+    algorithms?: GpuAlgorithmMiningStatus[];
+}
+
+export interface GpuAlgorithmMiningStatus {
+    algorithm: GpuMiningAlgorithm;
+    hash_rate: number;
+    estimated_earnings: number;
+}
+
+// Values not reported by the miner are null
+export interface GpuDeviceMiningStatus {
+    device_id: number;
+    name: string;
+    hash_rate: number;
+    accepted_shares: number | null;
+    rejected_shares: number | null;
+    temperature: number | null;
+    power_usage: number | null;
+    fan_speed: number | null;
 }
 
 export interface BaseNodeStatus {
     sha_network_hashrate: number;
     monero_randomx_network_hashrate: number;
     tari_randomx_network_hashrate: number;
+    c29_network_hashrate: number;
     block_reward: number;
     block_height: number;
     block_time: number;
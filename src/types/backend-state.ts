@@ -4,26 +4,43 @@ import {
     CriticalProblemPayload,
     DetectedAvailableGpuEngines,
     DetectedDevicesPayload,
+    GpuBenchmarkProgressPayload,
     GpuMiner,
     GpuMinerType,
+    IdleMiningStatus,
+    MiningPauseUpdatePayload,
+    MiningScheduleStatus,
     NewBlockHeightPayload,
+    NodeDiskUsage,
     NodeTypeUpdatePayload,
+    PoolFailoverPayload,
+    ProfitSwitchDecision,
     ProgressTrackerUpdatePayload,
     SetupPhase,
     ShowReleaseNotesPayload,
     TariAddressUpdatePayload,
+    ThermalGuardStatus,
     WalletUIMode,
 } from './events-payloads.ts';
 import {
     BaseNodeStatus,
     CpuMinerStatus,
+    GpuDeviceMiningStatus,
     GpuMinerStatus,
     NetworkStatus,
     PoolStats,
     SystemDependency,
     WalletBalance,
 } from './app-status.ts';
-import { ConfigCore, ConfigMining, ConfigPools, ConfigUI, ConfigWallet, GpuDeviceSettings } from './configs.ts';
+import {
+    ConfigCore,
+    ConfigMining,
+    ConfigPools,
+    ConfigSchedule,
+    ConfigUI,
+    ConfigWallet,
+    GpuDeviceSettings,
+} from './configs.ts';
 import { DisabledPhasesPayload } from '@app/store/actions/setupStoreActions.ts';
 import { AppModuleState } from '@app/store/types/setup.ts';
 
@@ -57,6 +74,38 @@ export type BackendStateUpdateEvent =
           event_type: 'GpuMiningUpdate';
           payload: GpuMinerStatus;
       }
+    | {
+          event_type: 'GpuDevicesMiningUpdate';
+          payload: GpuDeviceMiningStatus[];
+      }
+    | {
+          event_type: 'GpuBenchmarkProgress';
+          payload: GpuBenchmarkProgressPayload;
+      }
+    | {
+          event_type: 'ProfitSwitchDecision';
+          payload: ProfitSwitchDecision;
+      }
+    | {
+          event_type: 'MiningPauseUpdate';
+          payload: MiningPauseUpdatePayload;
+      }
+    | {
+          event_type: 'MiningScheduleUpdate';
+          payload: MiningScheduleStatus;
+      }
+    | {
+          event_type: 'IdleMiningUpdate';
+          payload: IdleMiningStatus;
+      }
+    | {
+          event_type: 'ThermalGuardUpdate';
+          payload: ThermalGuardStatus;
+      }
+    | {
+          event_type: 'NodeDiskUsageUpdate';
+          payload: NodeDiskUsage;
+      }
     | {
           event_type: 'NewBlockHeight';
           payload: NewBlockHeightPayload;
@@ -121,6 +170,10 @@ export type BackendStateUpdateEvent =
           event_type: 'ConfigPoolsLoaded';
           payload: ConfigPools;
       }
+    | {
+          event_type: 'ConfigScheduleLoaded';
+          payload: ConfigSchedule;
+      }
     | {
           event_type: 'RestartingPhases';
           payload: SetupPhase[];
@@ -153,6 +206,14 @@ export type BackendStateUpdateEvent =
           event_type: 'GpuPoolsStatsUpdate';
           payload: Record<string, PoolStats>;
       }
+    | {
+          event_type: 'CpuPoolFailover';
+          payload: PoolFailoverPayload;
+      }
+    | {
+          event_type: 'GpuPoolFailover';
+          payload: PoolFailoverPayload;
+      }
     | {
           event_type: 'ExchangeIdChanged';
           payload: string;
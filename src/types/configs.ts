@@ -1,5 +1,5 @@
 import { NodeType } from '@app/store/useNodeStore';
-import { WalletUIMode } from './events-payloads';
+import { GpuMinerType, GpuMiningAlgorithm, WalletUIMode } from './events-payloads';
 
 export interface ConfigCore {
     created_at: string;
@@ -19,8 +19,15 @@ export interface ConfigCore {
         refreshToken: string;
     };
     remote_base_node_address: string;
+    remote_base_node_addresses?: string[]; // Probed next to remote_base_node_address
+    remote_node_failover_blocks_behind?: number;
     node_type?: NodeType;
+    external_node?: ExternalNodeConfig; // Used when node_type is External
     exchange_id?: string;
+    node_resource_limits?: ProcessResourceLimits;
+    wallet_resource_limits?: ProcessResourceLimits;
+    node_storage_profile?: NodeStorageProfile;
+    node_min_free_disk_space_bytes?: number; // 0 disables the low disk space watchdog
 }
 export interface ConfigWallet {
     created_at: string;
@@ -67,6 +74,73 @@ export interface ConfigMining {
     cpu_mining_enabled: boolean;
     gpu_engine: string;
     is_gpu_mining_recommended: boolean;
+    gpu_benchmark_duration_secs?: number;
+    gpu_devices_recommendations?: Record<number, GpuDeviceMinerRecommendation>;
+    gpu_benchmark_completed_at?: {
+        secs_since_epoch?: number;
+        nanos_since_epoch?: number;
+    } | null;
+    gpu_benchmark_results?: GpuDeviceMinerRecommendation[];
+    profit_switching_enabled?: boolean;
+    profit_switching_threshold_percentage?: number;
+    profit_switching_min_interval_secs?: number;
+    xmrig_config?: XmrigConfig;
+    idle_mining_enabled?: boolean;
+    idle_mining_mode?: string;
+    idle_mining_timeout_secs?: number;
+    thermal_guard_enabled?: boolean;
+    cpu_thermal_limit?: ThermalLimit;
+    gpu_thermal_limit?: ThermalLimit;
+    gpu_devices_thermal_limits?: Record<number, ThermalLimit>;
+    cpu_miner_resource_limits?: ProcessResourceLimits;
+}
+
+export interface ThermalLimit {
+    max_temperature: number;
+    hysteresis: number;
+}
+
+export type NodeStorageProfile = { Pruned: { pruning_horizon: number } } | 'Archival';
+
+export interface ExternalNodeConfig {
+    grpc_address: string;
+    ca_certificate_path?: string | null;
+    client_certificate_path?: string | null;
+    client_key_path?: string | null;
+    tls_domain_name?: string | null;
+    username?: string | null; // Password is kept in the os keyring
+}
+
+export type IoPriority = { BestEffort: number } | 'Idle'; // BestEffort: 0 (highest) - 7 (lowest)
+
+// Only applied on linux
+export interface ProcessResourceLimits {
+    nice?: number | null; // 0 (default) - 19 (lowest priority)
+    io_priority?: IoPriority | null;
+    cpu_affinity: number[]; // Cores the process may run on, all cores when empty
+    memory_limit_bytes?: number | null;
+}
+
+export interface XmrigConfig {
+    donate_level: number;
+    huge_pages: boolean;
+    huge_pages_jit: boolean;
+    randomx_1gb_pages: boolean;
+    cpu_priority?: number | null; // 0 (idle) - 5 (highest)
+    cpu_affinity: number[]; // Cores mining threads are pinned to
+    thread_intensity: number;
+    pool_algorithm: 'Rx0' | 'RxTari';
+    opencl: { enabled: boolean; platform?: string | null };
+    cuda: { enabled: boolean; loader?: string | null };
+}
+
+export interface GpuDeviceMinerRecommendation {
+    device_id: number;
+    miner: GpuMinerType;
+    algorithm: GpuMiningAlgorithm;
+    engine?: string | null;
+    hash_rate: number;
+    estimated_daily_earnings?: number | null; // Missing when the network hashrate of the algorithm is unknown
 }
 
 export interface ConfigMiningSelectors {
@@ -76,6 +150,7 @@ export interface ConfigMiningSelectors {
 export interface GpuDeviceSettings {
     device_id: number;
     is_excluded: boolean;
+    assigned_miner?: GpuMinerType | null; // Miner mining on this device instead of the selected one
 }
 
 export enum MiningModeType {
@@ -102,11 +177,38 @@ export interface ConfigPools {
     gpu_pool_enabled: boolean; // Whether GPU pool mining is enabled | defaults to true
     current_gpu_pool?: GpuPools; // Name of the selected GPU pool => defaults to LuckyPool
     gpu_pools?: Record<GpuPools, BasePoolData>; // Available GPU pools
+    gpu_miner_pools?: Partial<Record<GpuMinerType, GpuPools>>; // Pools of miners running on their assigned devices
     // ======= Cpu Pool =======
     // When false we are solo mining with xmrig and mmproxy if true we are pool mining with xmrig
     cpu_pool_enabled: boolean; // Whether CPU pool mining is enabled | defaults to true
     current_cpu_pool?: CpuPools; // Name of the selected CPU pool => defaults to LuckyPool
     cpu_pools?: Record<CpuPools, BasePoolData>; // Available CPU pools
+    // ======= Pool failover =======
+    pool_failover_enabled: boolean; // Whether to switch to the next healthy pool when the selected one goes down | defaults to true
+    switch_back_to_primary_pool: boolean; // Whether to switch back to the selected pool once it recovers | defaults to true
+    cpu_pools_failover?: CpuPools[]; // Ordered list of CPU pools to fail over to
+    gpu_pools_failover?: Partial<Record<GpuMiningAlgorithm, GpuPools[]>>; // Ordered list of GPU pools to fail over to per algorithm
+    // ======= Pool stats =======
+    used_mining_addresses?: string[]; // Previously used mining addresses, most recent first
+}
+
+export type ScheduleDay = 'Monday' | 'Tuesday' | 'Wednesday' | 'Thursday' | 'Friday' | 'Saturday' | 'Sunday';
+export type MiningScheduleAction = 'Off' | { Mine: { mode: string } };
+
+// Weekly window in local time, a window ending before it starts runs past midnight
+export interface MiningScheduleRule {
+    name: string;
+    days: ScheduleDay[];
+    start_minute: number; // Minutes since midnight
+    end_minute: number;
+    action: MiningScheduleAction;
+}
+
+export interface ConfigSchedule {
+    created_at: string;
+    is_enabled: boolean;
+    default_action: MiningScheduleAction; // Applied when none of the rules matches
+    rules: MiningScheduleRule[]; // Earlier rules take precedence over later ones
 }
 
 export enum GpuPools {
@@ -127,14 +229,30 @@ export enum PoolOrigin {
     SupportXTM = 'SupportXTM',
     LuckyPool = 'LuckyPool',
     Kryptex = 'Kryptex',
+    Custom = 'Custom',
+}
+
+// JSON pointer paths used to read pool stats of custom pools, e.g. "/stats/unpaid"
+export interface GenericPoolStatsPaths {
+    accepted_shares?: string;
+    unpaid?: string;
+    balance?: string;
+    min_payout?: string;
+}
+
+export interface CustomPoolData {
+    algorithm?: GpuMiningAlgorithm; // Required for custom GPU pools
+    worker_name?: string;
+    stats_paths: GenericPoolStatsPaths;
 }
 
 export interface BasePoolData {
     pool_name: string;
-    pool_type: GpuPools | CpuPools;
+    pool_type: GpuPools | CpuPools | `Custom:${string}`;
     pool_origin: PoolOrigin;
     pool_url: string;
     stats_url: string;
+    custom?: CustomPoolData; // Only present for custom pools
 }
 
 export interface ConfigBackendInMemory {
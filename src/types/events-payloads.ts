@@ -1,5 +1,5 @@
 import { GpuDevice, TransactionInfo, WalletBalance } from './app-status';
-import { BasePoolData, ConfigPools, CpuPools, GpuPools } from './configs';
+import { BasePoolData, ConfigPools, CpuPools, GpuPools, MiningScheduleAction, NodeStorageProfile } from './configs';
 
 export enum SetupPhase {
     Core = 'Core',
@@ -116,6 +116,176 @@ export enum GpuMiningAlgorithm {
     C29 = 'C29',
 }
 
+export interface GpuBenchmarkProgressPayload {
+    is_running: boolean;
+    current_miner?: GpuMinerType;
+    current_engine?: string;
+    completed_runs: number;
+    total_runs: number;
+}
+
+export type ProfitSwitchSubject = 'Cpu' | { GpuDevice: { device_id: number } };
+export type ProfitSwitchOutcome = 'Switched' | 'BelowThreshold' | 'CooldownActive' | 'Failed';
+
+export interface ProfitSwitchDecision {
+    timestamp: {
+        secs_since_epoch: number;
+        nanos_since_epoch: number;
+    };
+    subject: ProfitSwitchSubject;
+    current_option: string;
+    best_option: string;
+    current_estimated_earnings: number;
+    best_estimated_earnings: number;
+    outcome: ProfitSwitchOutcome;
+}
+
+export type MiningPauseReason = 'User';
+
+export interface MiningPauseUpdatePayload {
+    is_cpu_paused: boolean;
+    is_gpu_paused: boolean;
+    reasons: MiningPauseReason[];
+}
+
+export interface MiningScheduleTransition {
+    timestamp: number; // Unix timestamp in seconds
+    action: MiningScheduleAction;
+}
+
+export interface MiningScheduleStatus {
+    is_enabled: boolean;
+    current_action?: MiningScheduleAction;
+    active_rule?: string;
+    next_transition?: MiningScheduleTransition;
+    was_applied: boolean;
+}
+
+export interface IdleMiningStatus {
+    is_available: boolean;
+    is_enabled: boolean;
+    is_idle: boolean;
+    mining_mode: string;
+}
+
+export type ThermalThrottleLevel = 'Normal' | 'Stopped' | { Throttled: { usage_percentage: number } };
+
+export interface DeviceThermalStatus {
+    device_id?: number;
+    temperature?: number;
+    max_temperature: number;
+    level: ThermalThrottleLevel;
+}
+
+export interface ThermalGuardStatus {
+    is_enabled: boolean;
+    cpu?: DeviceThermalStatus;
+    gpus: DeviceThermalStatus[];
+}
+
+export interface DeviceParameters {
+    usage_percentage: number;
+    current_temperature?: number | null; // Unknown when the device has no readable temperature sensor
+    max_temperature: number;
+    power_draw_watts?: number;
+    fan_speed_percentage?: number;
+    graphics_clock_mhz?: number;
+    memory_clock_mhz?: number;
+    memory_used_bytes?: number;
+    memory_total_bytes?: number;
+}
+
+export interface GpuDeviceTelemetry {
+    device_id: number;
+    pci_bus_id?: string;
+    vendor: string;
+    name: string;
+    status: { is_available: boolean; is_reader_implemented: boolean };
+    parameters?: DeviceParameters;
+    device_type: string;
+}
+
+export type ManagedProcessName =
+    | 'xmrig'
+    | 'graxil'
+    | 'glytex'
+    | 'lolminer'
+    | 'local_minotari_node'
+    | 'wallet'
+    | 'spend_wallet'
+    | 'minotari_merge_mining_proxy'
+    | 'tor';
+
+export interface RemoteNodeProbe {
+    address: string;
+    latency_ms?: number | null;
+    block_height?: number | null;
+    is_synced: boolean;
+    error?: string | null;
+    probed_at: number;
+    consecutive_failures: number;
+    is_selected: boolean;
+}
+
+export interface NodeDiskUsage {
+    storage_profile: NodeStorageProfile;
+    node_data_bytes: number;
+    available_bytes?: number | null;
+    total_bytes?: number | null;
+    min_free_bytes: number;
+    is_paused_for_low_disk: boolean;
+}
+
+export interface NodeSnapshotManifest {
+    version: number;
+    network: string;
+    block_height: number;
+    tip_hash: string;
+    storage_profile: NodeStorageProfile;
+    created_at: number; // Unix seconds
+    size_bytes: number;
+    sha256: string;
+}
+
+export type PowAlgorithm = 'MoneroRandomX' | 'Sha3x' | 'TariRandomX' | 'Cuckaroo29' | { Unknown: number };
+
+export interface ExplorerBlock {
+    height: number;
+    hash: string;
+    prev_hash: string;
+    timestamp: number; // Unix seconds
+    pow_algo: PowAlgorithm;
+    difficulty: number;
+    reward: number;
+    confirmations: number;
+    coinbase_extras: string[];
+    num_coinbases: number;
+    num_kernels: number;
+    num_inputs: number;
+    num_outputs: number;
+}
+
+export interface MempoolStats {
+    unconfirmed_txs: number;
+    reorg_txs: number;
+    unconfirmed_weight: number;
+}
+
+export interface ExternalNodeTestResult {
+    public_key: string;
+    public_addresses: string[];
+    genesis_matches_app_network?: boolean | null; // Unknown when the public node can't be reached
+    block_height: number;
+    is_synced: boolean;
+    latency_ms: number;
+}
+
+export interface ProcessOutputLine {
+    stream: 'Stdout' | 'Stderr';
+    timestamp: number;
+    line: string;
+}
+
 export interface GpuMiner {
     miner_type: GpuMinerType;
     features: GpuMinerFeature[];
@@ -123,3 +293,23 @@ export interface GpuMiner {
     is_healthy: boolean;
     last_error?: string;
 }
+
+export interface PoolHealth {
+    latency_ms?: number;
+    consecutive_connect_failures: number;
+    consecutive_healthy_checks: number;
+    stats_success_rate?: number;
+    accepted_shares: number;
+    rejected_shares: number;
+    score: number;
+}
+
+export type PoolSwitchReason = 'PoolUnhealthy' | 'PrimaryRecovered' | 'FailoverDisabled';
+
+export interface PoolFailoverPayload {
+    from_pool: string;
+    to_pool: string;
+    primary_pool: string;
+    reason: PoolSwitchReason;
+    pools_health: Record<string, PoolHealth>;
+}
@@ -9,9 +9,36 @@ import {
 import { Language } from '@app/i18initializer';
 import { PaperWalletDetails } from '@app/types/app-status.ts';
 import { displayMode } from '@app/store/types.ts';
-import { BasePoolData, ConfigBackendInMemory } from '@app/types/configs.ts';
+import {
+    BasePoolData,
+    ConfigBackendInMemory,
+    CustomPoolData,
+    ExternalNodeConfig,
+    MiningScheduleAction,
+    MiningScheduleRule,
+    NodeStorageProfile,
+    ProcessResourceLimits,
+    ThermalLimit,
+    XmrigConfig,
+} from '@app/types/configs.ts';
 import { ExchangeMiner } from './exchange';
 import { ActiveTapplet } from '@app/types/tapplets/tapplet.types';
+import {
+    ExplorerBlock,
+    ExternalNodeTestResult,
+    GpuMiningAlgorithm,
+    GpuDeviceTelemetry,
+    ManagedProcessName,
+    MempoolStats,
+    MiningPauseUpdatePayload,
+    NodeDiskUsage,
+    NodeSnapshotManifest,
+    PoolHealth,
+    ProcessOutputLine,
+    ProfitSwitchDecision,
+    RemoteNodeProbe,
+} from './events-payloads';
+import { PoolAddressStatus, PoolEarningsAggregate, PoolEarningsPeriod } from './mining/pools';
 
 declare module '@tauri-apps/api/core' {
     function invoke(
@@ -78,6 +105,78 @@ declare module '@tauri-apps/api/core' {
         param: 'toggle_device_exclusion',
         payload: { device_index: number; excluded: boolean }
     ): Promise<void>;
+    function invoke(
+        param: 'assign_gpu_device_miner',
+        payload: { deviceIndex: number; miner: GpuMinerType | null }
+    ): Promise<void>;
+    function invoke(param: 'start_gpu_benchmark', payload: { durationSecs?: number }): Promise<void>;
+    function invoke(param: 'cancel_gpu_benchmark'): Promise<void>;
+    function invoke(param: 'set_profit_switching_enabled', payload: { enabled: boolean }): Promise<void>;
+    function invoke(
+        param: 'set_profit_switching_settings',
+        payload: { thresholdPercentage: number; minIntervalSecs: number }
+    ): Promise<void>;
+    function invoke(param: 'get_profit_switch_decisions'): Promise<ProfitSwitchDecision[]>;
+    function invoke(param: 'pause_mining'): Promise<void>;
+    function invoke(param: 'resume_mining'): Promise<void>;
+    function invoke(param: 'get_mining_pause_status'): Promise<MiningPauseUpdatePayload>;
+    function invoke(
+        param: 'set_idle_mining',
+        payload: { enabled: boolean; miningMode: string; timeoutSecs: number }
+    ): Promise<void>;
+    function invoke(
+        param: 'set_thermal_guard_settings',
+        payload: {
+            enabled: boolean;
+            cpuLimit: ThermalLimit;
+            gpuLimit: ThermalLimit;
+            gpuDevicesLimits: Record<number, ThermalLimit>;
+        }
+    ): Promise<void>;
+    function invoke(param: 'get_gpu_devices_telemetry'): Promise<GpuDeviceTelemetry[]>;
+    function invoke(
+        param: 'get_process_output_tail',
+        payload: { processName: ManagedProcessName; lines?: number }
+    ): Promise<ProcessOutputLine[]>;
+    function invoke(param: 'get_remote_node_probes'): Promise<RemoteNodeProbe[]>;
+    function invoke(
+        param: 'set_remote_base_nodes',
+        payload: { addresses: string[]; failoverBlocksBehind: number }
+    ): Promise<void>;
+    function invoke(
+        param: 'test_external_node_connection',
+        payload: { config: ExternalNodeConfig; password?: string }
+    ): Promise<ExternalNodeTestResult>;
+    function invoke(
+        param: 'set_external_node',
+        payload: { config: ExternalNodeConfig; password?: string }
+    ): Promise<void>;
+    function invoke(param: 'get_node_disk_usage'): Promise<NodeDiskUsage>;
+    function invoke(param: 'export_node_snapshot', payload: { destinationDir: string }): Promise<NodeSnapshotManifest>;
+    function invoke(param: 'import_node_snapshot', payload: { snapshotPath: string }): Promise<NodeSnapshotManifest>;
+    function invoke(
+        param: 'get_explorer_blocks',
+        payload: { fromHeight: number; toHeight: number }
+    ): Promise<ExplorerBlock[]>;
+    function invoke(param: 'get_explorer_block_by_hash', payload: { hash: string }): Promise<ExplorerBlock>;
+    function invoke(param: 'get_mempool_stats'): Promise<MempoolStats>;
+    function invoke(
+        param: 'set_node_storage',
+        payload: { storageProfile: NodeStorageProfile; minFreeDiskSpaceBytes: number; allowResync: boolean }
+    ): Promise<void>;
+    function invoke(
+        param: 'set_process_resource_limits',
+        payload: {
+            nodeLimits: ProcessResourceLimits;
+            walletLimits: ProcessResourceLimits;
+            cpuMinerLimits: ProcessResourceLimits;
+        }
+    ): Promise<void>;
+    function invoke(
+        param: 'set_mining_schedule',
+        payload: { isEnabled: boolean; defaultAction: MiningScheduleAction; rules: MiningScheduleRule[] }
+    ): Promise<void>;
+    function invoke(param: 'set_xmrig_config', payload: { xmrigConfig: XmrigConfig }): Promise<void>;
     function invoke(
         param: 'set_show_experimental_settings',
         payload: { showExperimentalSettings: boolean }
@@ -136,6 +235,34 @@ declare module '@tauri-apps/api/core' {
     ): Promise<void>;
     function invoke(param: 'reset_gpu_pool_config', payload: { gpuPoolName: string }): Promise<void>;
     function invoke(param: 'reset_cpu_pool_config', payload: { cpuPoolName: string }): Promise<void>;
+    function invoke(
+        param: 'add_custom_cpu_pool' | 'add_custom_gpu_pool',
+        payload: { poolName: string; poolUrl: string; statsUrl: string; custom: CustomPoolData }
+    ): Promise<string>;
+    function invoke(
+        param: 'update_custom_cpu_pool' | 'update_custom_gpu_pool',
+        payload: { updatedConfig: BasePoolData }
+    ): Promise<void>;
+    function invoke(param: 'remove_custom_cpu_pool', payload: { cpuPoolName: string }): Promise<void>;
+    function invoke(param: 'remove_custom_gpu_pool', payload: { gpuPoolName: string }): Promise<void>;
+    function invoke(param: 'set_cpu_pools_failover', payload: { cpuPools: string[] }): Promise<void>;
+    function invoke(
+        param: 'set_gpu_pools_failover',
+        payload: { algorithm: GpuMiningAlgorithm; gpuPools: string[] }
+    ): Promise<void>;
+    function invoke(param: 'set_gpu_miner_pool', payload: { miner: GpuMinerType; gpuPool: string }): Promise<void>;
+    function invoke(param: 'toggle_pool_failover', payload: { enabled: boolean }): Promise<void>;
+    function invoke(param: 'toggle_switch_back_to_primary_pool', payload: { enabled: boolean }): Promise<void>;
+    function invoke(
+        param: 'get_pools_health'
+    ): Promise<{ cpu_pools: Record<string, PoolHealth>; gpu_pools: Record<string, PoolHealth> }>;
+    function invoke(
+        param: 'get_pool_earnings_history',
+        payload: { period: PoolEarningsPeriod }
+    ): Promise<PoolEarningsAggregate[]>;
+    function invoke(
+        param: 'get_pools_address_statuses'
+    ): Promise<{ cpu_pools: PoolAddressStatus[]; gpu_pools: PoolAddressStatus[] }>;
     function invoke(param: 'restart_phases', payload: { phases: SetupPhase[] }): Promise<void>;
     function invoke(param: 'list_connected_peers'): Promise<string[]>;
     function invoke(param: 'switch_gpu_miner', payload: { gpuMinerType: GpuMinerType }): Promise<void>;
@@ -0,0 +1,27 @@
+import { PoolStats } from '@app/types/app-status.ts';
+
+export type PoolEarningsMiner = 'Cpu' | 'Gpu';
+export type PoolEarningsPeriod = 'Daily' | 'Weekly';
+
+// All amounts are in micro minotari
+export interface PoolEarningsAggregate {
+    miner: PoolEarningsMiner;
+    pool_name: string;
+    address: string;
+    period_start: number; // unix timestamp (seconds, UTC)
+    accepted_shares: number;
+    earned: number;
+    paid: number;
+    payouts_count: number;
+    estimated: number;
+    last_unpaid: number;
+    last_balance: number;
+}
+
+export interface PoolAddressStatus {
+    pool_name: string;
+    address: string;
+    is_current_pool: boolean;
+    is_current_address: boolean;
+    status: PoolStats;
+}